@@ -0,0 +1,55 @@
+//! Benchmarks for uid/gid → name resolution during directory listings.
+//!
+//! Simulates a directory whose entries are owned by many distinct users and groups, and compares
+//! resolving names one entry at a time against prewarming the deduplicated set first.
+//!
+//! Run with: `cargo bench --bench owner_cache_benchmarks`
+//! Results are saved to `target/criterion/` with HTML reports.
+
+use cmdr_lib::owner_cache::{clear_owner_caches, get_group_name, get_owner_name, prewarm_owners};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+/// Number of entries in the simulated directory.
+const ENTRY_COUNT: u32 = 10_000;
+
+/// Returns (uid, gid) pairs for `ENTRY_COUNT` entries spread over `distinct_owners` owners.
+/// Uses ids starting at 500, which covers both real local users and unknown ids (the slow path).
+fn owner_ids(distinct_owners: u32) -> Vec<(u32, u32)> {
+    (0..ENTRY_COUNT)
+        .map(|i| (500 + i % distinct_owners, 20 + i % distinct_owners))
+        .collect()
+}
+
+fn bench_owner_resolution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("owner_resolution");
+
+    for distinct_owners in [1, 50, 500] {
+        let ids = owner_ids(distinct_owners);
+
+        group.bench_with_input(BenchmarkId::new("per_entry", distinct_owners), &ids, |b, ids| {
+            b.iter(|| {
+                clear_owner_caches();
+                for (uid, gid) in ids {
+                    get_owner_name(*uid);
+                    get_group_name(*gid);
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("prewarmed", distinct_owners), &ids, |b, ids| {
+            b.iter(|| {
+                clear_owner_caches();
+                prewarm_owners(ids.iter().map(|(uid, _)| *uid), ids.iter().map(|(_, gid)| *gid));
+                for (uid, gid) in ids {
+                    get_owner_name(*uid);
+                    get_group_name(*gid);
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_owner_resolution);
+criterion_main!(benches);
@@ -0,0 +1,128 @@
+//! Structured errors for Tauri commands, so the frontend can branch on the kind of error.
+//!
+//! Serialized like `{ "type": "listing_expired", "message": "Listing not found: …" }`. The message is
+//! meant for logs and as a fallback for display; the UI should pick its wording based on `type`.
+
+use crate::file_system::VolumeError;
+use serde::{Deserialize, Serialize};
+
+/// Error returned by commands.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommandError {
+    /// A file or directory doesn't exist
+    NotFound { message: String },
+    /// The OS refused access
+    PermissionDenied { message: String },
+    /// The listing ID is no longer in the cache (ended, or the app restarted). Start a new listing.
+    ListingExpired { message: String },
+    /// An argument doesn't make sense, like an index out of range or a path that isn't in the listing
+    InvalidArgument { message: String },
+    /// A move between two volumes, which can't be a rename. Copy with progress, then delete the source instead.
+    CrossVolumeMove { message: String },
+    /// Any other I/O or internal error
+    Io { message: String },
+}
+
+impl CommandError {
+    /// The listing with the given ID isn't in the cache.
+    pub fn listing_expired(listing_id: &str) -> Self {
+        Self::ListingExpired {
+            message: format!("Listing not found: {}", listing_id),
+        }
+    }
+
+    /// A lock guarding shared state was poisoned by a panic on another thread.
+    pub fn lock_failed(what: &str) -> Self {
+        Self::Io {
+            message: format!("Failed to acquire {} lock", what),
+        }
+    }
+
+    /// Converts an I/O error, prefixing its message with what was being done.
+    pub fn from_io(err: std::io::Error, context: &str) -> Self {
+        Self::from_io_kind(err.kind(), format!("{}: {}", context, err))
+    }
+
+    fn from_io_kind(kind: std::io::ErrorKind, message: String) -> Self {
+        match kind {
+            std::io::ErrorKind::NotFound => Self::NotFound { message },
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied { message },
+            std::io::ErrorKind::InvalidInput => Self::InvalidArgument { message },
+            _ => Self::Io { message },
+        }
+    }
+
+    /// The human-readable message.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound { message }
+            | Self::PermissionDenied { message }
+            | Self::ListingExpired { message }
+            | Self::InvalidArgument { message }
+            | Self::CrossVolumeMove { message }
+            | Self::Io { message } => message,
+        }
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<std::io::Error> for CommandError {
+    fn from(err: std::io::Error) -> Self {
+        Self::from_io_kind(err.kind(), err.to_string())
+    }
+}
+
+impl From<VolumeError> for CommandError {
+    fn from(err: VolumeError) -> Self {
+        let message = err.to_string();
+        match err {
+            VolumeError::NotFound(_) => Self::NotFound { message },
+            VolumeError::PermissionDenied(_) => Self::PermissionDenied { message },
+            _ => Self::Io { message },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_with_type_tag() {
+        let json = serde_json::to_value(CommandError::listing_expired("abc")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "listing_expired", "message": "Listing not found: abc" })
+        );
+    }
+
+    #[test]
+    fn test_io_errors_map_to_matching_variants() {
+        let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let denied = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "nope");
+        let other = std::io::Error::other("disk on fire");
+
+        assert!(matches!(CommandError::from(not_found), CommandError::NotFound { .. }));
+        assert!(matches!(
+            CommandError::from_io(denied, "Failed to list '/secret'"),
+            CommandError::PermissionDenied { message } if message == "Failed to list '/secret': nope"
+        ));
+        assert!(matches!(CommandError::from(other), CommandError::Io { .. }));
+    }
+
+    #[test]
+    fn test_display_is_the_message() {
+        let error = CommandError::InvalidArgument {
+            message: "Index out of range".to_string(),
+        };
+        assert_eq!(error.to_string(), "Index out of range");
+    }
+}
@@ -0,0 +1,16 @@
+//! Tauri commands for exporting and importing the app's configuration.
+
+use crate::config_transfer;
+
+/// Exports settings, known shares, and the sidebar config as a JSON string. Keychain passwords are never included.
+#[tauri::command]
+pub fn export_config(app: tauri::AppHandle) -> Result<String, String> {
+    config_transfer::export_config(&app)
+}
+
+/// Imports a JSON string from `export_config`. Nothing is applied if it's invalid.
+/// Emits `settings-changed` and `locations-changed` when done.
+#[tauri::command]
+pub fn import_config(app: tauri::AppHandle, json: String) -> Result<(), String> {
+    config_transfer::import_config(&app, &json)
+}
@@ -0,0 +1,11 @@
+//! Tauri commands for "Open With" app handlers.
+
+use crate::file_handlers::{self, FileHandler};
+use std::path::Path;
+
+/// Lists the applications that can open the given path, default app first.
+/// Returns an empty list if no app is registered for the file's type.
+#[tauri::command]
+pub fn get_handlers_for_path(path: String) -> Vec<FileHandler> {
+    file_handlers::get_handlers_for_path(Path::new(&path))
+}
@@ -1,13 +1,42 @@
 //! Tauri commands for file system operations.
 
+use crate::command_error::CommandError;
 use crate::file_system::{
-    FileEntry, ListingStartResult, ResortResult, SortColumn, SortOrder, find_file_index as ops_find_file_index,
-    get_file_at as ops_get_file_at, get_file_range as ops_get_file_range,
-    get_max_filename_width as ops_get_max_filename_width, get_total_count as ops_get_total_count,
-    list_directory_end as ops_list_directory_end,
-    list_directory_start_with_volume as ops_list_directory_start_with_volume, resort_listing as ops_resort_listing,
+    AliasResolution, ConflictPolicy, ContentMatch, CopyProgressEvent, DEFAULT_CONTENT_SEARCH_MAX_FILE_SIZE,
+    DeleteOutcome, DeleteResult, EXTENDED_METADATA_CHUNK_SIZE, ExtendedMetadata, FileEntry, FileRangeResult,
+    FilterMode, LinkKind, ListingOrigin, ListingStartResult, ListingStreamEvent, MAX_MODE, OwnerNames, PathInfo,
+    ResortResult, SortColumn, SortOrder, TransferOptions, TransferResult, WriteAccess,
+    add_to_watch_session as ops_add_to_watch_session, cancel_copy as ops_cancel_copy,
+    cancel_delete as ops_cancel_delete, cancel_directory_size as ops_cancel_directory_size,
+    cancel_find as ops_cancel_find, cancel_listing_stream as ops_cancel_listing_stream,
+    check_name_collision as ops_check_name_collision, check_space_for_paths as ops_check_space_for_paths,
+    compute_directory_size as ops_compute_directory_size, copy_file_with_progress as ops_copy_file_with_progress,
+    copy_with_progress as ops_copy_with_progress, delete_paths as ops_delete_paths,
+    delete_recursive as ops_delete_recursive, end_watch_session as ops_end_watch_session,
+    filter_listing as ops_filter_listing, find_file_index as ops_find_file_index, find_hardlinks as ops_find_hardlinks,
+    find_in_directory as ops_find_in_directory, get_extended_metadata_batch as ops_get_extended_metadata_batch,
+    get_extended_metadata_chunked as ops_get_extended_metadata_chunked, get_file_at as ops_get_file_at,
+    get_file_range as ops_get_file_range, get_file_range_or_recreate as ops_get_file_range_or_recreate,
+    get_listing_path as ops_get_listing_path, get_max_filename_width as ops_get_max_filename_width,
+    get_path_info as ops_get_path_info, get_path_metadata as ops_get_path_metadata,
+    get_total_count as ops_get_total_count, get_write_access as ops_get_write_access,
+    is_case_sensitive_volume as ops_is_case_sensitive_volume, is_remote_volume as ops_is_remote_volume,
+    is_writable as ops_is_writable, list_directory_end as ops_list_directory_end,
+    list_directory_start_streaming as ops_list_directory_start_streaming,
+    list_directory_start_with_volume as ops_list_directory_start_with_volume, register_delete_operation,
+    remove_from_watch_session as ops_remove_from_watch_session, resolve_alias as ops_resolve_alias,
+    resolve_conflict as ops_resolve_conflict, resolve_owners_batch as ops_resolve_owners_batch,
+    resort_listing as ops_resort_listing, search_file_contents as ops_search_file_contents,
+    set_debounce_ms as ops_set_debounce_ms, set_follow_symlinks_for_type as ops_set_follow_symlinks_for_type,
+    set_ignore_patterns as ops_set_ignore_patterns, set_manual_order as ops_set_manual_order,
+    start_folder_size_computation as ops_start_folder_size_computation, transfer_paths as ops_transfer_paths,
+    typeahead_find as ops_typeahead_find, unregister_delete_operation,
 };
-use std::path::PathBuf;
+use crate::owner_cache::resolve_owner_ids;
+use crate::settings::DirectorySort;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 
 /// Checks if a path exists.
 ///
@@ -23,6 +52,785 @@ pub fn path_exists(path: String) -> bool {
     path_buf.exists()
 }
 
+/// Gets the display form, canonical form, existence, and kind of a path in one call.
+///
+/// # Arguments
+/// * `path` - The path to inspect. Supports tilde expansion (~).
+///
+/// # Returns
+/// Path info. Canonicalization failures (broken symlink, missing permission) result in `canonical: null`.
+#[tauri::command]
+pub fn path_info(path: String) -> PathInfo {
+    let expanded_path = expand_tilde(&path);
+    ops_get_path_info(&PathBuf::from(expanded_path))
+}
+
+/// Gets the full metadata of a single path, without listing its parent.
+///
+/// # Arguments
+/// * `path` - The file or directory. Supports tilde expansion (~). Symlinks are described themselves.
+///
+/// # Returns
+/// The entry, with the extended fields (like `addedAt`) already loaded.
+#[tauri::command]
+pub fn get_path_metadata(path: String) -> Result<FileEntry, String> {
+    let path = PathBuf::from(expand_tilde(&path));
+    ops_get_path_metadata(&path).map_err(|e| format!("Failed to read '{}': {}", path.display(), e))
+}
+
+/// Checks whether a path is on a network volume (SMB, NFS, AFP, WebDAV).
+///
+/// # Arguments
+/// * `path` - The path to check. Supports tilde expansion (~).
+///
+/// # Returns
+/// True for network volumes. Lets the frontend use longer debounces, skip auto-sizing, or warn about slowness.
+#[tauri::command]
+pub fn is_remote_volume(path: String) -> bool {
+    let expanded_path = expand_tilde(&path);
+    ops_is_remote_volume(&PathBuf::from(expanded_path))
+}
+
+/// Finds other names of the same file (hardlinks sharing its device and inode) under a directory.
+///
+/// # Arguments
+/// * `root` - Directory to search recursively. Supports tilde expansion (~).
+/// * `path` - The file to find hardlinks of. Supports tilde expansion (~).
+///
+/// # Returns
+/// The other paths. Empty if the file has no other links, or they're all outside `root`.
+#[tauri::command]
+pub async fn find_hardlinks(root: String, path: String) -> Result<Vec<String>, String> {
+    let root = PathBuf::from(expand_tilde(&root));
+    let path = PathBuf::from(expand_tilde(&path));
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_find_hardlinks(&root, &path)
+            .map(|paths| paths.iter().map(|p| p.to_string_lossy().into_owned()).collect())
+            .map_err(|e| format!("Failed to find hardlinks of '{}': {}", path.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Failed to find hardlinks: {}", e))?
+}
+
+/// Copies a single file, emitting `copy-progress` events (`{ token, copied, total, bytesPerSec }`) along the way.
+///
+/// # Arguments
+/// * `from` - Source file. Supports tilde expansion (~).
+/// * `to` - Destination file path. Supports tilde expansion (~).
+/// * `token` - Optional, chosen by the caller to tell progress events apart. Sent as null if omitted.
+/// * `max_bytes_per_sec` - Optional speed limit, for example to avoid saturating a network link. Omit for full speed.
+/// * `conflict` - What to do if `to` exists. Defaults to failing. With `overwrite`, the existing file is only
+///   replaced once the copy is complete.
+/// * `ignore_space_check` - Skips the free space check, for when the user confirms to go ahead anyway.
+///
+/// # Returns
+/// The number of bytes copied. 0 if the file was skipped. Fails with "Not enough free space: …" before copying
+/// anything if the file doesn't fit.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)] // Tauri commands take their arguments one by one from the frontend
+pub async fn copy_file(
+    app: AppHandle,
+    from: String,
+    to: String,
+    token: Option<String>,
+    max_bytes_per_sec: Option<u64>,
+    conflict: Option<ConflictPolicy>,
+    ignore_space_check: Option<bool>,
+) -> Result<u64, String> {
+    let from = PathBuf::from(expand_tilde(&from));
+    let to = PathBuf::from(expand_tilde(&to));
+    let options = TransferOptions { max_bytes_per_sec };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (_, volume, relative_to) = crate::file_system::get_volume_manager()
+            .resolve(&to)
+            .ok_or_else(|| format!("No volume found for '{}'", to.display()))?;
+        let transferred = ops_resolve_conflict(volume.as_ref(), &relative_to, false, conflict.unwrap_or_default())
+            .map_err(|e| format!("Failed to copy '{}': {}", from.display(), e))?;
+        // Back to an absolute path, like the one passed in
+        let Some(destination) = transferred
+            .destination
+            .and_then(|d| d.file_name().map(|name| to.with_file_name(name)))
+        else {
+            return Ok(0);
+        };
+        let replace_existing = transferred.conflict == Some(ConflictPolicy::Overwrite);
+        check_space_before_copy(
+            std::slice::from_ref(&from),
+            parent_dir(&destination),
+            ignore_space_check,
+        )?;
+
+        ops_copy_file_with_progress(&from, &destination, &options, replace_existing, |progress| {
+            let event = CopyProgressEvent {
+                token: token.clone(),
+                progress: progress.clone(),
+            };
+            let _ = app.emit("copy-progress", event);
+        })
+        .map_err(|e| format!("Failed to copy '{}': {}", from.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Failed to copy: {}", e))?
+}
+
+/// Copies a file, or a directory with everything in it, emitting `copy-progress` events
+/// (`{ token, copied, total, bytesPerSec }`) along the way. For directories, progress counts all files together.
+///
+/// If cancelled with `cancel_copy` or failing halfway, the partial copy is removed.
+///
+/// # Arguments
+/// * `from` - Source file or directory. Supports tilde expansion (~).
+/// * `to` - Destination path, which must not exist. Supports tilde expansion (~).
+/// * `token` - Chosen by the caller, to tell progress events apart and to cancel with `cancel_copy`.
+/// * `max_bytes_per_sec` - Optional speed limit. Omit for full speed.
+/// * `ignore_space_check` - Skips the free space check, for when the user confirms to go ahead anyway.
+///
+/// # Returns
+/// The number of bytes copied. Fails with "Not enough free space: …" before copying anything if it doesn't fit.
+#[tauri::command]
+pub async fn copy_with_progress(
+    app: AppHandle,
+    from: String,
+    to: String,
+    token: String,
+    max_bytes_per_sec: Option<u64>,
+    ignore_space_check: Option<bool>,
+) -> Result<u64, String> {
+    let from = PathBuf::from(expand_tilde(&from));
+    let to = PathBuf::from(expand_tilde(&to));
+    let options = TransferOptions { max_bytes_per_sec };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        check_space_before_copy(std::slice::from_ref(&from), parent_dir(&to), ignore_space_check)?;
+        ops_copy_with_progress(&from, &to, &options, &token, |progress| {
+            let event = CopyProgressEvent {
+                token: Some(token.clone()),
+                progress: progress.clone(),
+            };
+            let _ = app.emit("copy-progress", event);
+        })
+        .map_err(|e| format!("Failed to copy '{}': {}", from.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Failed to copy: {}", e))?
+}
+
+/// Cancels a running `copy_with_progress`. Returns true if it was still running.
+///
+/// # Arguments
+/// * `token` - The token passed to `copy_with_progress`.
+#[tauri::command]
+pub fn cancel_copy(token: String) -> bool {
+    ops_cancel_copy(&token)
+}
+
+/// Deletes a file or a directory tree permanently, entry by entry.
+///
+/// Emits `delete-progress` events (`{ operationId, removed, total }`) at most every 100 ms, plus one at the end.
+/// Can be stopped with `cancel_delete`; entries deleted until then stay deleted.
+///
+/// # Arguments
+/// * `path` - What to delete. Supports tilde expansion (~). Symlinks are deleted, never followed.
+/// * `operation_id` - Frontend-generated ID, used for progress events and cancellation.
+///
+/// # Returns
+/// How many entries were removed out of the total, and whether the delete was cancelled.
+#[tauri::command]
+pub async fn delete_recursive(app: AppHandle, path: String, operation_id: String) -> Result<DeleteOutcome, String> {
+    let volume = crate::file_system::get_volume_manager()
+        .get("root")
+        .ok_or_else(|| "Root volume not found".to_string())?;
+    let path = PathBuf::from(expand_tilde(&path));
+    let cancelled = register_delete_operation(&operation_id);
+
+    let result = tauri::async_runtime::spawn_blocking({
+        let operation_id = operation_id.clone();
+        move || {
+            let mut last_emit: Option<Instant> = None;
+            ops_delete_recursive(volume.as_ref(), &path, &cancelled, |progress| {
+                let is_last = progress.removed == progress.total;
+                if is_last || last_emit.is_none_or(|t| t.elapsed() >= Duration::from_millis(100)) {
+                    let _ = app.emit(
+                        "delete-progress",
+                        serde_json::json!({
+                            "operationId": operation_id,
+                            "removed": progress.removed,
+                            "total": progress.total,
+                        }),
+                    );
+                    last_emit = Some(Instant::now());
+                }
+            })
+            .map_err(|e| e.to_string())
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to delete: {}", e));
+
+    unregister_delete_operation(&operation_id);
+    result?
+}
+
+/// Stops a running `delete_recursive` after the current entry.
+///
+/// # Returns
+/// True if the delete was still running.
+#[tauri::command]
+pub fn cancel_delete(operation_id: String) -> bool {
+    ops_cancel_delete(&operation_id)
+}
+
+/// Batches with more items than this report progress with `delete-paths-progress` events.
+const DELETE_PATHS_PROGRESS_MIN_ITEMS: usize = 20;
+
+/// Moves several files and directories to the Trash, or deletes them permanently with everything in them.
+///
+/// Each path is deleted on its own volume, and a path that fails doesn't stop the others. For batches of more than
+/// `DELETE_PATHS_PROGRESS_MIN_ITEMS`, emits a `delete-paths-progress` event (`{ path, success, done, total }`) after
+/// each item.
+///
+/// # Arguments
+/// * `paths` - What to delete. Supports tilde expansion (~).
+/// * `permanent` - Whether to delete permanently instead of trashing.
+///
+/// # Returns
+/// One result per path, in the same order, with the error for the ones that failed.
+#[tauri::command]
+pub async fn delete_paths(app: AppHandle, paths: Vec<String>, permanent: bool) -> Result<Vec<DeleteResult>, String> {
+    let paths: Vec<PathBuf> = paths.iter().map(|path| PathBuf::from(expand_tilde(path))).collect();
+    let total = paths.len();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_delete_paths(
+            crate::file_system::get_volume_manager(),
+            &paths,
+            permanent,
+            |result, done| {
+                if total > DELETE_PATHS_PROGRESS_MIN_ITEMS {
+                    let _ = app.emit(
+                        "delete-paths-progress",
+                        serde_json::json!({
+                            "path": result.path,
+                            "success": result.success,
+                            "done": done,
+                            "total": total,
+                        }),
+                    );
+                }
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to delete: {}", e))
+}
+
+/// Moves a file or directory to the Trash, or deletes it permanently.
+///
+/// # Arguments
+/// * `path` - What to delete. Supports tilde expansion (~).
+/// * `permanent` - Whether to delete permanently instead of trashing. Permanent deletes only remove files and empty
+///   directories, use `delete_recursive` for trees.
+///
+/// # Returns
+/// Where the entry ended up in the Trash, if known. Always None for permanent deletes.
+#[tauri::command]
+pub async fn delete_path(path: String, permanent: bool) -> Result<Option<String>, CommandError> {
+    let path = PathBuf::from(expand_tilde(&path));
+    let (_, volume, relative_path) =
+        crate::file_system::get_volume_manager()
+            .resolve(&path)
+            .ok_or_else(|| CommandError::NotFound {
+                message: format!("No volume found for {}", path.display()),
+            })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if permanent {
+            volume.delete(&relative_path)?;
+            Ok(None)
+        } else {
+            let location = volume.trash(&relative_path)?;
+            Ok(location.map(|location| location.to_string_lossy().to_string()))
+        }
+    })
+    .await
+    .map_err(|e| CommandError::Io {
+        message: format!("Failed to delete: {}", e),
+    })?
+}
+
+/// Checks whether the file system at a path treats names that only differ in case as different files.
+///
+/// # Arguments
+/// * `path` - The path to check. Supports tilde expansion (~). Missing paths are checked on their closest
+///   existing parent.
+///
+/// # Returns
+/// False on case-insensitive volumes (the APFS default), where "README" and "readme" are the same file.
+#[tauri::command]
+pub fn is_case_sensitive_volume(path: String) -> bool {
+    let expanded_path = expand_tilde(&path);
+    ops_is_case_sensitive_volume(&PathBuf::from(expanded_path))
+}
+
+/// Checks whether the current user can write to a path, for example to gray out paste in a read-only folder.
+///
+/// Asks the OS rather than reading permission bits, so ACLs and read-only mounts are accounted for.
+///
+/// # Arguments
+/// * `path` - The path to check. Supports tilde expansion (~).
+#[tauri::command]
+pub fn is_writable(path: String) -> bool {
+    let expanded_path = expand_tilde(&path);
+    ops_is_writable(&PathBuf::from(expanded_path))
+}
+
+/// Like `is_writable`, but also tells why a path isn't writable.
+///
+/// # Arguments
+/// * `path` - The path to check. Supports tilde expansion (~).
+///
+/// # Returns
+/// `writable`, `readOnlyMount`, or `noPermission` (also returned for missing paths).
+#[tauri::command]
+pub fn get_write_access(path: String) -> WriteAccess {
+    let expanded_path = expand_tilde(&path);
+    ops_get_write_access(&PathBuf::from(expanded_path))
+}
+
+/// Checks that a rename or copy won't silently replace a file whose name only differs in case.
+///
+/// # Arguments
+/// * `directory` - Target directory. Supports tilde expansion (~).
+/// * `new_name` - Name to create in the directory.
+/// * `source_name` - The current name for renames within the same directory, so that changing only the case of a
+///   name is allowed. Omit for copies.
+///
+/// # Returns
+/// An error like `"README" would replace "readme", …` on a case-only collision.
+#[tauri::command]
+pub fn check_name_collision(directory: String, new_name: String, source_name: Option<String>) -> Result<(), String> {
+    let volume = crate::file_system::get_volume_manager()
+        .get("root")
+        .ok_or_else(|| "Root volume not found".to_string())?;
+    let directory = PathBuf::from(expand_tilde(&directory));
+    ops_check_name_collision(volume.as_ref(), &directory, &new_name, source_name.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Creates a directory.
+///
+/// # Arguments
+/// * `path` - The directory to create. Supports tilde expansion (~).
+/// * `create_parents` - Whether to create missing parents too. Then an existing directory is not an error either.
+///
+/// # Returns
+/// The new directory's entry, so the UI can show it without re-listing.
+#[tauri::command]
+pub async fn make_directory(path: String, create_parents: bool) -> Result<FileEntry, CommandError> {
+    let path = PathBuf::from(expand_tilde(&path));
+    let (_, volume, relative_path) =
+        crate::file_system::get_volume_manager()
+            .resolve(&path)
+            .ok_or_else(|| CommandError::NotFound {
+                message: format!("No volume found for {}", path.display()),
+            })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if create_parents {
+            volume.create_directory_all(&relative_path)?;
+        } else {
+            volume.create_directory(&relative_path)?;
+        }
+        Ok(volume.get_metadata(&relative_path)?)
+    })
+    .await
+    .map_err(|e| CommandError::Io {
+        message: format!("Failed to create directory: {}", e),
+    })?
+}
+
+/// Sets the permission bits of a file or directory, like `chmod`.
+///
+/// # Arguments
+/// * `path` - The file or directory. Supports tilde expansion (~). Symlinks are followed.
+/// * `mode` - The new mode, like 0o644. Includes the setuid, setgid, and sticky bits, so at most 0o7777.
+///
+/// # Returns
+/// The updated entry, so the UI can show it without re-listing. An `invalid_argument` error if `mode` is above 0o7777.
+#[tauri::command]
+pub async fn set_file_permissions(path: String, mode: u32) -> Result<FileEntry, CommandError> {
+    if mode > MAX_MODE {
+        return Err(CommandError::InvalidArgument {
+            message: format!("Invalid mode {:o}, the most is {:o}", mode, MAX_MODE),
+        });
+    }
+    let path = PathBuf::from(expand_tilde(&path));
+    let (_, volume, relative_path) =
+        crate::file_system::get_volume_manager()
+            .resolve(&path)
+            .ok_or_else(|| CommandError::NotFound {
+                message: format!("No volume found for {}", path.display()),
+            })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        volume.set_permissions(&relative_path, mode)?;
+        Ok(volume.get_metadata(&relative_path)?)
+    })
+    .await
+    .map_err(|e| CommandError::Io {
+        message: format!("Failed to set permissions: {}", e),
+    })?
+}
+
+/// Changes the owner and group of a file or directory, like `chown`.
+///
+/// # Arguments
+/// * `path` - The file or directory. Supports tilde expansion (~). Symlinks are followed.
+/// * `owner_name` - New owner's user name, or a uid. None to keep the owner.
+/// * `group_name` - New group's name, or a gid. None to keep the group.
+///
+/// # Returns
+/// The updated entry. An `invalid_argument` error naming an unknown user or group, and `permission_denied` if the
+/// app may not give the file away, which is usual without admin rights.
+#[tauri::command]
+pub async fn set_file_owner(
+    path: String,
+    owner_name: Option<String>,
+    group_name: Option<String>,
+) -> Result<FileEntry, CommandError> {
+    let path = PathBuf::from(expand_tilde(&path));
+    let (_, volume, relative_path) =
+        crate::file_system::get_volume_manager()
+            .resolve(&path)
+            .ok_or_else(|| CommandError::NotFound {
+                message: format!("No volume found for {}", path.display()),
+            })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (uid, gid) = resolve_owner_ids(&uzers::UsersCache::new(), owner_name.as_deref(), group_name.as_deref())
+            .map_err(|message| CommandError::InvalidArgument { message })?;
+        volume.set_owner(&relative_path, uid, gid)?;
+        Ok(volume.get_metadata(&relative_path)?)
+    })
+    .await
+    .map_err(|e| CommandError::Io {
+        message: format!("Failed to change owner: {}", e),
+    })?
+}
+
+/// Lists the names of a file's extended attributes, like `com.apple.quarantine`.
+///
+/// # Arguments
+/// * `path` - The file or directory. Supports tilde expansion (~). Symlinks are followed.
+#[tauri::command]
+#[cfg(target_os = "macos")]
+pub async fn list_xattrs(path: String) -> Result<Vec<String>, String> {
+    let path = PathBuf::from(expand_tilde(&path));
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::file_system::list_xattrs(&path)
+            .map_err(|e| format!("Failed to list attributes of '{}': {}", path.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Failed to list attributes: {}", e))?
+}
+
+/// Reads an extended attribute of a file, for example to tell whether a download is quarantined.
+///
+/// # Arguments
+/// * `path` - The file or directory. Supports tilde expansion (~). Symlinks are followed.
+/// * `name` - The attribute, like "com.apple.quarantine".
+///
+/// # Returns
+/// The raw value, or None if the file doesn't have the attribute.
+#[tauri::command]
+#[cfg(target_os = "macos")]
+pub async fn get_xattr(path: String, name: String) -> Result<Option<Vec<u8>>, String> {
+    let path = PathBuf::from(expand_tilde(&path));
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::file_system::get_xattr(&path, &name)
+            .map_err(|e| format!("Failed to read attribute '{}' of '{}': {}", name, path.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Failed to read attribute: {}", e))?
+}
+
+/// Creates a symbolic or hard link.
+///
+/// # Arguments
+/// * `kind` - "symbolic" or "hard".
+/// * `link_path` - Where to create the link, which must not exist yet. Supports tilde expansion (~).
+/// * `target_path` - What the link points to. Supports tilde expansion (~). Symlinks store it as given, so it may
+///   be relative to the link's folder and doesn't have to exist. Hard links need an existing file on the same
+///   volume.
+///
+/// # Returns
+/// The new link's entry, so the UI can show it without re-listing. An `invalid_argument` error for a hard link to
+/// another volume.
+#[tauri::command]
+pub async fn create_link(kind: LinkKind, link_path: String, target_path: String) -> Result<FileEntry, CommandError> {
+    let link = PathBuf::from(expand_tilde(&link_path));
+    let target = PathBuf::from(expand_tilde(&target_path));
+
+    let volume_manager = crate::file_system::get_volume_manager();
+    let not_on_a_volume = |path: &PathBuf| CommandError::NotFound {
+        message: format!("No volume found for {}", path.display()),
+    };
+    let (link_volume_id, volume, relative_link) =
+        volume_manager.resolve(&link).ok_or_else(|| not_on_a_volume(&link))?;
+    let relative_target = match kind {
+        LinkKind::Symbolic => target,
+        LinkKind::Hard => {
+            let (target_volume_id, _, relative_target) = volume_manager
+                .resolve(&target)
+                .ok_or_else(|| not_on_a_volume(&target))?;
+            if target_volume_id != link_volume_id {
+                return Err(CommandError::InvalidArgument {
+                    message: format!("{} and {} are on different volumes", link.display(), target.display()),
+                });
+            }
+            relative_target
+        }
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        match kind {
+            LinkKind::Symbolic => volume.create_symlink(&relative_link, &relative_target)?,
+            LinkKind::Hard => volume.create_hardlink(&relative_link, &relative_target)?,
+        }
+        Ok(volume.get_metadata(&relative_link)?)
+    })
+    .await
+    .map_err(|e| CommandError::Io {
+        message: format!("Failed to create link: {}", e),
+    })?
+}
+
+/// Moves or renames a file or directory within a volume.
+///
+/// # Arguments
+/// * `from` - What to move. Supports tilde expansion (~).
+/// * `to` - The new path, which must not exist yet. Supports tilde expansion (~). On case-insensitive volumes, no
+///   other entry may have the same name in a different case either, but changing the case of `from`'s own name is
+///   fine.
+///
+/// # Returns
+/// The entry at its new path, so the UI can update it without re-listing. A `cross_volume_move` error if the paths
+/// are on different volumes: then copy with progress and delete the source instead. An `invalid_argument` error if
+/// `to` is inside `from`.
+#[tauri::command]
+pub async fn rename_path(from: String, to: String) -> Result<FileEntry, CommandError> {
+    let from = PathBuf::from(expand_tilde(&from));
+    let to = PathBuf::from(expand_tilde(&to));
+    if to.starts_with(&from) {
+        return Err(CommandError::InvalidArgument {
+            message: format!("Can't move {} inside itself", from.display()),
+        });
+    }
+
+    let volume_manager = crate::file_system::get_volume_manager();
+    let not_on_a_volume = |path: &PathBuf| CommandError::NotFound {
+        message: format!("No volume found for {}", path.display()),
+    };
+    let (from_volume_id, volume, relative_from) =
+        volume_manager.resolve(&from).ok_or_else(|| not_on_a_volume(&from))?;
+    let (to_volume_id, _, relative_to) = volume_manager.resolve(&to).ok_or_else(|| not_on_a_volume(&to))?;
+    if from_volume_id != to_volume_id {
+        return Err(CommandError::CrossVolumeMove {
+            message: format!("{} and {} are on different volumes", from.display(), to.display()),
+        });
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if let (Some(dir), Some(new_name)) = (relative_to.parent(), relative_to.file_name()) {
+            // Within the same directory, changing only the case of the name is fine
+            let source_name = relative_from
+                .file_name()
+                .filter(|_| relative_from.parent() == Some(dir))
+                .map(|name| name.to_string_lossy());
+            ops_check_name_collision(
+                volume.as_ref(),
+                dir,
+                &new_name.to_string_lossy(),
+                source_name.as_deref(),
+            )?;
+        }
+        volume.rename(&relative_from, &relative_to)?;
+        Ok(volume.get_metadata(&relative_to)?)
+    })
+    .await
+    .map_err(|e| CommandError::Io {
+        message: format!("Failed to rename: {}", e),
+    })?
+}
+
+/// Batches with more items than this report progress with `transfer-progress` events.
+const TRANSFER_PATHS_PROGRESS_MIN_ITEMS: usize = 20;
+
+/// Copies several files and directories into a directory, keeping their names.
+///
+/// Each path must be on the same volume as the destination, and a path that fails doesn't stop the others. For
+/// batches of more than `TRANSFER_PATHS_PROGRESS_MIN_ITEMS`, emits a `transfer-progress` event
+/// (`{ path, success, done, total }`) after each item.
+///
+/// # Arguments
+/// * `paths` - What to copy. Supports tilde expansion (~).
+/// * `destination` - Directory to copy into. Supports tilde expansion (~).
+/// * `conflict` - What to do for items whose name is taken in the destination. Defaults to failing them.
+/// * `ignore_space_check` - Skips the free space check, for when the user confirms to go ahead anyway.
+///
+/// # Returns
+/// One result per path, in the same order, with where it ended up and the conflict policy applied, if any.
+/// Fails with "Not enough free space: …" before copying anything if the paths don't fit together.
+#[tauri::command]
+pub async fn copy_paths(
+    app: AppHandle,
+    paths: Vec<String>,
+    destination: String,
+    conflict: Option<ConflictPolicy>,
+    ignore_space_check: Option<bool>,
+) -> Result<Vec<TransferResult>, String> {
+    transfer_paths(
+        app,
+        paths,
+        destination,
+        false,
+        conflict.unwrap_or_default(),
+        ignore_space_check,
+    )
+    .await
+}
+
+/// Moves several files and directories into a directory, keeping their names.
+///
+/// Works like `copy_paths`, including the `transfer-progress` events. There's no free space check: moves stay on
+/// one volume, so they take no extra space.
+///
+/// # Arguments
+/// * `paths` - What to move. Supports tilde expansion (~).
+/// * `destination` - Directory to move into. Supports tilde expansion (~).
+/// * `conflict` - What to do for items whose name is taken in the destination. Defaults to failing them.
+///
+/// # Returns
+/// One result per path, in the same order, with where it ended up and the conflict policy applied, if any.
+#[tauri::command]
+pub async fn move_paths(
+    app: AppHandle,
+    paths: Vec<String>,
+    destination: String,
+    conflict: Option<ConflictPolicy>,
+) -> Result<Vec<TransferResult>, String> {
+    transfer_paths(app, paths, destination, true, conflict.unwrap_or_default(), None).await
+}
+
+async fn transfer_paths(
+    app: AppHandle,
+    paths: Vec<String>,
+    destination: String,
+    is_move: bool,
+    conflict: ConflictPolicy,
+    ignore_space_check: Option<bool>,
+) -> Result<Vec<TransferResult>, String> {
+    let paths: Vec<PathBuf> = paths.iter().map(|path| PathBuf::from(expand_tilde(path))).collect();
+    let destination = PathBuf::from(expand_tilde(&destination));
+    let total = paths.len();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // Moves stay on one volume, so they need no space
+        if !is_move {
+            check_space_before_copy(&paths, &destination, ignore_space_check)?;
+        }
+        Ok(ops_transfer_paths(
+            crate::file_system::get_volume_manager(),
+            &paths,
+            &destination,
+            is_move,
+            conflict,
+            |result, done| {
+                if total > TRANSFER_PATHS_PROGRESS_MIN_ITEMS {
+                    let _ = app.emit(
+                        "transfer-progress",
+                        serde_json::json!({
+                            "path": result.path,
+                            "success": result.success,
+                            "done": done,
+                            "total": total,
+                        }),
+                    );
+                }
+            },
+        ))
+    })
+    .await
+    .map_err(|e| format!("Failed to {}: {}", if is_move { "move" } else { "copy" }, e))?
+}
+
+/// Fails with "Not enough free space: …" if the sources don't fit in `destination_dir`, unless the check is skipped.
+fn check_space_before_copy(
+    sources: &[PathBuf],
+    destination_dir: &Path,
+    ignore_space_check: Option<bool>,
+) -> Result<(), String> {
+    if ignore_space_check.unwrap_or(false) {
+        return Ok(());
+    }
+    ops_check_space_for_paths(
+        crate::file_system::get_volume_manager(),
+        sources,
+        destination_dir,
+        false,
+    )
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// The directory a copy to `path` is written into.
+fn parent_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(Path::new("/"))
+}
+
+/// Resolves a Finder alias to the path it points to.
+///
+/// # Arguments
+/// * `path` - Path of the alias file. Supports tilde expansion (~).
+///
+/// # Returns
+/// The target path, or `target: null` with a `reason` if the path isn't an alias or its target is missing.
+/// Always returns an empty result on non-macOS platforms.
+#[tauri::command]
+pub fn resolve_alias(path: String) -> AliasResolution {
+    let expanded_path = expand_tilde(&path);
+    ops_resolve_alias(&PathBuf::from(expanded_path))
+}
+
+/// Checks that the destination has enough free space before a copy or move.
+///
+/// # Arguments
+/// * `source_paths` - Files and directories to copy. Support tilde expansion (~). Directories count recursively.
+/// * `destination` - Target directory. Supports tilde expansion (~).
+/// * `ignore_space_check` - Skips the check, for when the user confirms to go ahead anyway. Sparse files and
+///   compression can make the estimate larger than the real usage.
+///
+/// # Returns
+/// The number of bytes needed, or an error like "Not enough free space: …" if they don't fit.
+#[tauri::command]
+pub fn check_copy_space(
+    source_paths: Vec<String>,
+    destination: String,
+    ignore_space_check: Option<bool>,
+) -> Result<u64, String> {
+    let source_paths: Vec<PathBuf> = source_paths.iter().map(|p| PathBuf::from(expand_tilde(p))).collect();
+    let destination = PathBuf::from(expand_tilde(&destination));
+
+    ops_check_space_for_paths(
+        crate::file_system::get_volume_manager(),
+        &source_paths,
+        &destination,
+        ignore_space_check.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
 // ============================================================================
 // On-demand virtual scrolling API
 // ============================================================================
@@ -37,17 +845,247 @@ pub fn path_exists(path: String) -> bool {
 /// * `include_hidden` - Whether to include hidden files in total count.
 /// * `sort_by` - Column to sort by (name, extension, size, modified, created).
 /// * `sort_order` - Ascending or descending.
+/// * `case_sensitive` - Whether to compare names as raw bytes, so uppercase comes first. Defaults to false.
+/// * `use_saved_sort` - Whether to use the sort last chosen for this directory, if there is one, instead of
+///   `sort_by` and `sort_order`. The result tells which sort was used. Defaults to false.
+/// * `resolve_owners` - Whether to look up owner and group names. If false, they're left as numeric IDs with
+///   `ownerResolved: false`, to fill in with `resolve_owners_batch` when needed. Defaults to true.
+///
+/// A directory the user may not read gives an empty listing with `accessDenied: true`, not an error.
+///
+/// If the "compute folder sizes" setting is on, also starts computing folder sizes in the background.
+/// Emits a `folder-size` event (`{ listingId, path, size }`) for each folder as its size becomes known.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)] // Tauri commands take their arguments one by one from the frontend
 pub fn list_directory_start(
+    app: AppHandle,
+    path: String,
+    include_hidden: bool,
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    case_sensitive: Option<bool>,
+    use_saved_sort: Option<bool>,
+    resolve_owners: Option<bool>,
+) -> Result<ListingStartResult, CommandError> {
+    let expanded_path = expand_tilde(&path);
+    let path_buf = PathBuf::from(&expanded_path);
+    let saved_sort = if use_saved_sort.unwrap_or(false) {
+        crate::settings::get_directory_sort(&path_buf)
+    } else {
+        None
+    };
+    let (sort_by, sort_order) = saved_sort.map_or((sort_by, sort_order), |saved| (saved.sort_by, saved.sort_order));
+    let result = ops_list_directory_start_with_volume(
+        "root",
+        &path_buf,
+        include_hidden,
+        sort_by,
+        sort_order,
+        case_sensitive.unwrap_or(false),
+        resolve_owners.unwrap_or(true),
+    )
+    .map_err(|e| CommandError::from_io(e, &format!("Failed to start directory listing '{}'", path)))?;
+
+    if crate::settings::load_settings(&app).compute_folder_sizes {
+        let listing_id = result.listing_id.clone();
+        ops_start_folder_size_computation(&result.listing_id, move |dir, size| {
+            let _ = app.emit(
+                "folder-size",
+                serde_json::json!({ "listingId": listing_id, "path": dir.to_string_lossy(), "size": size }),
+            );
+        })?;
+    }
+    Ok(result)
+}
+
+/// Starts a directory listing that streams its entries as they're read.
+///
+/// Returns the listing ID right away. Emits `listing-chunk` events with batches of entries while reading,
+/// then `listing-complete` with the total count once the sorted listing is cached. After that, use
+/// `get_file_range` and the other listing commands as usual. `list_directory_end` cancels an unfinished stream.
+///
+/// # Arguments
+/// * `path` - The directory path to list, on whichever volume contains it. Supports tilde expansion (~).
+/// * `include_hidden` - Whether to include hidden files in chunks and the total count.
+/// * `sort_by` - Column to sort by (name, extension, size, modified, created).
+/// * `sort_order` - Ascending or descending.
+/// * `case_sensitive` - Whether to compare names as raw bytes, so uppercase comes first. Defaults to false.
+#[tauri::command]
+pub fn list_directory_start_streaming(
+    app: AppHandle,
     path: String,
     include_hidden: bool,
     sort_by: SortColumn,
     sort_order: SortOrder,
-) -> Result<ListingStartResult, String> {
+    case_sensitive: Option<bool>,
+) -> Result<String, CommandError> {
     let expanded_path = expand_tilde(&path);
     let path_buf = PathBuf::from(&expanded_path);
-    ops_list_directory_start_with_volume("root", &path_buf, include_hidden, sort_by, sort_order)
-        .map_err(|e| format!("Failed to start directory listing '{}': {}", path, e))
+    let (volume_id, _, relative_path) =
+        crate::file_system::get_volume_manager()
+            .resolve(&path_buf)
+            .ok_or_else(|| CommandError::NotFound {
+                message: format!("No volume found for {}", path_buf.display()),
+            })?;
+    let case_sensitive = case_sensitive.unwrap_or(false);
+    ops_list_directory_start_streaming(
+        &volume_id,
+        &relative_path,
+        include_hidden,
+        sort_by,
+        sort_order,
+        case_sensitive,
+        move |event| {
+            let _ = match event {
+                ListingStreamEvent::Chunk(chunk) => app.emit("listing-chunk", chunk),
+                ListingStreamEvent::Complete(complete) => app.emit("listing-complete", complete),
+            };
+        },
+    )
+    .map_err(|e| CommandError::from_io(e, &format!("Failed to start directory listing '{}'", path)))
+}
+
+/// Cancels a listing that's still being streamed. Returns true if it was still streaming.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start_streaming`.
+#[tauri::command]
+pub fn cancel_listing_stream(listing_id: String) -> bool {
+    ops_cancel_listing_stream(&listing_id)
+}
+
+/// Computes the total size of the files in a folder, recursively.
+///
+/// Symlinks aren't followed and hardlinked files count once. While walking, emits `directory-size-progress`
+/// events (`{ token, path, size }`) with the size counted so far.
+///
+/// # Arguments
+/// * `path` - The folder to compute. Supports tilde expansion (~).
+/// * `token` - Chosen by the caller, to cancel with `cancel_directory_size`.
+///
+/// # Returns
+/// The size in bytes, or an error if the path isn't a folder or the computation was cancelled.
+#[tauri::command]
+pub async fn compute_directory_size(app: AppHandle, path: String, token: String) -> Result<u64, String> {
+    let path_buf = PathBuf::from(expand_tilde(&path));
+    if !path_buf.is_dir() {
+        return Err(format!("Not a folder: {}", path));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_compute_directory_size(&path_buf, &token, |size| {
+            let _ = app.emit(
+                "directory-size-progress",
+                serde_json::json!({ "token": token, "path": path, "size": size }),
+            );
+        })
+        .ok_or_else(|| "Cancelled".to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Cancels a running `compute_directory_size`. Returns true if it was still running.
+///
+/// # Arguments
+/// * `token` - The token passed to `compute_directory_size`.
+#[tauri::command]
+pub fn cancel_directory_size(token: String) -> bool {
+    ops_cancel_directory_size(&token)
+}
+
+/// Searches a folder and its subfolders for entries whose names contain `query` (case-insensitive).
+///
+/// Symlinks to folders are followed, but each folder is searched only once. Emits `find-result` events
+/// (`{ token, entries }`) with batches of matches while walking.
+///
+/// # Arguments
+/// * `root` - The folder to search in. Supports tilde expansion (~).
+/// * `query` - Text the names must contain.
+/// * `max_results` - The search stops after this many matches.
+/// * `token` - Chosen by the caller, to cancel with `cancel_find`.
+///
+/// # Returns
+/// The number of entries scanned, or an error if the root isn't a folder.
+#[tauri::command]
+pub async fn find_in_directory(
+    app: AppHandle,
+    root: String,
+    query: String,
+    max_results: usize,
+    token: String,
+) -> Result<usize, String> {
+    let root_buf = PathBuf::from(expand_tilde(&root));
+    if !root_buf.is_dir() {
+        return Err(format!("Not a folder: {}", root));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_find_in_directory(&root_buf, &query, max_results, &token, |entries| {
+            let _ = app.emit("find-result", serde_json::json!({ "token": token, "entries": entries }));
+        })
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Searches the text files in a folder and its subfolders for lines matching a pattern.
+///
+/// Binary files and files larger than `max_file_size` are skipped. Emits a `content-match` event
+/// (`{ token, path, lineNumber, line }`) for every matching line.
+///
+/// # Arguments
+/// * `root` - The folder to search in. Supports tilde expansion (~).
+/// * `pattern` - Text the lines must contain (case-insensitive), or a regex if `is_regex` is true.
+/// * `is_regex` - Whether `pattern` is a regex. Defaults to false.
+/// * `max_file_size` - Larger files are skipped, in bytes. Defaults to `DEFAULT_CONTENT_SEARCH_MAX_FILE_SIZE`.
+/// * `token` - Chosen by the caller, to cancel with `cancel_find`.
+///
+/// # Returns
+/// The number of files searched, or an error if the root isn't a folder or the regex is invalid.
+#[tauri::command]
+pub async fn search_file_contents(
+    app: AppHandle,
+    root: String,
+    pattern: String,
+    is_regex: Option<bool>,
+    max_file_size: Option<u64>,
+    token: String,
+) -> Result<usize, String> {
+    let root_buf = PathBuf::from(expand_tilde(&root));
+    if !root_buf.is_dir() {
+        return Err(format!("Not a folder: {}", root));
+    }
+    let max_file_size = max_file_size.unwrap_or(DEFAULT_CONTENT_SEARCH_MAX_FILE_SIZE);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_search_file_contents(
+            &root_buf,
+            &pattern,
+            is_regex.unwrap_or(false),
+            max_file_size,
+            &token,
+            |m: &ContentMatch| {
+                let _ = app.emit(
+                    "content-match",
+                    serde_json::json!({ "token": token, "path": m.path, "lineNumber": m.line_number, "line": m.line }),
+                );
+            },
+        )
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Cancels a running `find_in_directory` or `search_file_contents`. Returns true if it was still running.
+///
+/// # Arguments
+/// * `token` - The token passed to `find_in_directory` or `search_file_contents`.
+#[tauri::command]
+pub fn cancel_find(token: String) -> bool {
+    ops_cancel_find(&token)
 }
 
 /// Re-sorts an existing cached listing in-place.
@@ -58,21 +1096,174 @@ pub fn list_directory_start(
 /// * `sort_order` - Ascending or descending.
 /// * `cursor_filename` - Optional filename to track; returns its new index after sorting.
 /// * `include_hidden` - Whether to include hidden files when calculating cursor index.
+///
+/// The sort is saved for the listing's directory, for `list_directory_start` with `use_saved_sort`.
 #[tauri::command]
 pub fn resort_listing(
+    app: AppHandle,
     listing_id: String,
     sort_by: SortColumn,
     sort_order: SortOrder,
     cursor_filename: Option<String>,
     include_hidden: bool,
-) -> Result<ResortResult, String> {
-    ops_resort_listing(
+) -> Result<ResortResult, CommandError> {
+    let result = ops_resort_listing(
         &listing_id,
         sort_by,
         sort_order,
         cursor_filename.as_deref(),
         include_hidden,
-    )
+    )?;
+    if let Some(path) = ops_get_listing_path(&listing_id) {
+        crate::settings::set_directory_sort(&app, &path, DirectorySort { sort_by, sort_order });
+    }
+    Ok(result)
+}
+
+/// Forgets the sorts saved for all directories.
+#[tauri::command]
+pub fn clear_saved_sorts(app: AppHandle) {
+    crate::settings::clear_directory_sorts(&app);
+}
+
+/// Fetches extended metadata (addedAt, openedAt) for a batch of paths, after the listing is shown.
+///
+/// Small batches are processed right away. Larger ones run on a blocking thread in parallel chunks, and each
+/// completed chunk is also emitted as an `extended-metadata-chunk` event (`{ entries }`) so the UI can fill in
+/// progressively. Entries arrive in no particular order, merge them by path.
+///
+/// # Arguments
+/// * `paths` - File paths to fetch extended metadata for.
+///
+/// # Returns
+/// Extended metadata for all paths.
+#[tauri::command]
+pub async fn get_extended_metadata_batch(app: AppHandle, paths: Vec<String>) -> Result<Vec<ExtendedMetadata>, String> {
+    if paths.len() <= EXTENDED_METADATA_CHUNK_SIZE {
+        return Ok(ops_get_extended_metadata_batch(paths));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_get_extended_metadata_chunked(paths, |entries| {
+            let _ = app.emit("extended-metadata-chunk", serde_json::json!({ "entries": entries }));
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to fetch extended metadata: {}", e))
+}
+
+/// Reads the Finder tags of a batch of paths, after the listing is shown, so it can show colored dots.
+///
+/// # Arguments
+/// * `paths` - File paths to read tags for.
+///
+/// # Returns
+/// The tags of each path, empty for untagged files.
+#[tauri::command]
+#[cfg(target_os = "macos")]
+pub async fn get_finder_tags_batch(paths: Vec<String>) -> Result<Vec<crate::file_system::FinderTags>, String> {
+    tauri::async_runtime::spawn_blocking(move || crate::file_system::get_finder_tags_batch(paths))
+        .await
+        .map_err(|e| format!("Failed to read Finder tags: {}", e))
+}
+
+/// Sets the Finder tags of a file, replacing the ones it had.
+///
+/// # Arguments
+/// * `path` - The file or directory. Supports tilde expansion (~). Symlinks are followed.
+/// * `tags` - The new tags. Empty to remove all tags.
+#[tauri::command]
+#[cfg(target_os = "macos")]
+pub async fn set_finder_tags(path: String, tags: Vec<crate::file_system::FinderTag>) -> Result<(), String> {
+    let path = PathBuf::from(expand_tilde(&path));
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::file_system::set_finder_tags(&path, &tags)
+            .map_err(|e| format!("Failed to set Finder tags of '{}': {}", path.display(), e))
+    })
+    .await
+    .map_err(|e| format!("Failed to set Finder tags: {}", e))?
+}
+
+/// Looks up owner and group names for a batch of paths listed with `resolve_owners: false`.
+///
+/// # Arguments
+/// * `paths` - File paths to resolve owners for.
+///
+/// # Returns
+/// Owner and group names for each path. None for paths that couldn't be read.
+#[tauri::command]
+pub fn resolve_owners_batch(paths: Vec<String>) -> Vec<OwnerNames> {
+    ops_resolve_owners_batch(paths)
+}
+
+/// Puts a listing's entries in a user-defined order and switches it to manual sorting.
+///
+/// The order is remembered per directory: it's used again when the directory is listed with `sortBy: "manual"`,
+/// including after switching to another sort column and back.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`.
+/// * `ordered_paths` - Entry paths in the desired order. Entries left out go to the end.
+#[tauri::command]
+pub fn set_manual_order(listing_id: String, ordered_paths: Vec<String>) -> Result<(), CommandError> {
+    ops_set_manual_order(&listing_id, &ordered_paths)
+}
+
+/// Sets the glob patterns of names to leave out of listings, like `.DS_Store` or `__pycache__/`.
+///
+/// Call this when the setting changes. Listings started afterward use the new patterns; open listings keep
+/// theirs until they're reloaded, so their counts and ranges stay consistent.
+///
+/// # Arguments
+/// * `patterns` - Globs matched against file names. `*` and `?` are wildcards, a trailing `/` matches folders only.
+#[tauri::command]
+pub fn set_ignore_patterns(patterns: Vec<String>) {
+    ops_set_ignore_patterns(&patterns);
+}
+
+/// Sets whether symlinks to folders are listed as folders, or as files so the link itself can be managed
+/// without accidentally entering its target.
+///
+/// Call this when the setting changes. Listings read afterward use it; reload open listings to apply it there.
+#[tauri::command]
+pub fn set_follow_symlinks_for_type(follow: bool) {
+    ops_set_follow_symlinks_for_type(follow);
+}
+
+/// Sets how long the file watcher waits for changes to settle before refreshing a listing, in milliseconds.
+/// Values outside 50–2000 are clamped.
+///
+/// Call this when the setting changes. Open listings keep their interval; listings opened afterward use the new one.
+#[tauri::command]
+pub fn set_watcher_debounce_ms(debounce_ms: u64) {
+    ops_set_debounce_ms(debounce_ms);
+}
+
+/// Watches a listing as part of a shared session instead of with its own watcher.
+///
+/// Use this for listings shown together, like both panes plus a tree sidebar: a session watches all their
+/// directories with a single OS-level watcher. `directory-diff` events are emitted per listing as before.
+///
+/// # Arguments
+/// * `session_id` - Any ID to group listings by, for example one per window.
+/// * `listing_id` - The listing ID from `list_directory_start`.
+#[tauri::command]
+pub fn add_to_watch_session(session_id: String, listing_id: String) -> Result<(), String> {
+    ops_add_to_watch_session(&session_id, &listing_id)
+}
+
+/// Stops watching a listing as part of a session. The session ends when its last listing leaves.
+///
+/// Ending a listing with `list_directory_end` also removes it from its session.
+#[tauri::command]
+pub fn remove_from_watch_session(session_id: String, listing_id: String) {
+    ops_remove_from_watch_session(&session_id, &listing_id);
+}
+
+/// Stops watching all listings of a session.
+#[tauri::command]
+pub fn end_watch_session(session_id: String) {
+    ops_end_watch_session(&session_id);
 }
 
 /// Gets a range of entries from a cached listing.
@@ -88,17 +1279,37 @@ pub fn get_file_range(
     start: usize,
     count: usize,
     include_hidden: bool,
-) -> Result<Vec<FileEntry>, String> {
+) -> Result<Vec<FileEntry>, CommandError> {
     ops_get_file_range(&listing_id, start, count, include_hidden)
 }
 
+/// Gets a range of entries, re-creating the listing if it has expired.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`.
+/// * `origin` - The directory and sort the listing was started with. The path supports tilde expansion (~).
+/// * `start` - Start index (0-based).
+/// * `count` - Number of entries to return.
+/// * `include_hidden` - Whether to include hidden files.
+#[tauri::command]
+pub fn get_file_range_or_recreate(
+    listing_id: String,
+    mut origin: ListingOrigin,
+    start: usize,
+    count: usize,
+    include_hidden: bool,
+) -> Result<FileRangeResult, CommandError> {
+    origin.path = PathBuf::from(expand_tilde(&origin.path.to_string_lossy()));
+    ops_get_file_range_or_recreate(&listing_id, "root", &origin, start, count, include_hidden)
+}
+
 /// Gets total count of entries in a cached listing.
 ///
 /// # Arguments
 /// * `listing_id` - The listing ID from `list_directory_start`.
 /// * `include_hidden` - Whether to include hidden files in count.
 #[tauri::command]
-pub fn get_total_count(listing_id: String, include_hidden: bool) -> Result<usize, String> {
+pub fn get_total_count(listing_id: String, include_hidden: bool) -> Result<usize, CommandError> {
     ops_get_total_count(&listing_id, include_hidden)
 }
 
@@ -111,7 +1322,7 @@ pub fn get_total_count(listing_id: String, include_hidden: bool) -> Result<usize
 /// * `listing_id` - The listing ID from `list_directory_start`.
 /// * `include_hidden` - Whether to include hidden files.
 #[tauri::command]
-pub fn get_max_filename_width(listing_id: String, include_hidden: bool) -> Result<Option<f32>, String> {
+pub fn get_max_filename_width(listing_id: String, include_hidden: bool) -> Result<Option<f32>, CommandError> {
     ops_get_max_filename_width(&listing_id, include_hidden)
 }
 
@@ -122,10 +1333,45 @@ pub fn get_max_filename_width(listing_id: String, include_hidden: bool) -> Resul
 /// * `name` - File name to find.
 /// * `include_hidden` - Whether to include hidden files when calculating index.
 #[tauri::command]
-pub fn find_file_index(listing_id: String, name: String, include_hidden: bool) -> Result<Option<usize>, String> {
+pub fn find_file_index(listing_id: String, name: String, include_hidden: bool) -> Result<Option<usize>, CommandError> {
     ops_find_file_index(&listing_id, &name, include_hidden)
 }
 
+/// Finds the next entry whose name starts with the typed prefix (case-insensitive), wrapping around.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`.
+/// * `prefix` - The characters typed so far.
+/// * `from_index` - Index to start searching from (inclusive). Pass cursor + 1 to jump to the next match.
+/// * `include_hidden` - Whether to include hidden files when calculating indexes.
+#[tauri::command]
+pub fn typeahead_find(
+    listing_id: String,
+    prefix: String,
+    from_index: usize,
+    include_hidden: bool,
+) -> Result<Option<usize>, CommandError> {
+    ops_typeahead_find(&listing_id, &prefix, from_index, include_hidden)
+}
+
+/// Returns the entries whose names match the query, in listing order, for type-to-filter.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`.
+/// * `query` - The filter text. If empty, all visible entries are returned.
+/// * `include_hidden` - Whether to include hidden files.
+/// * `mode` - How to match: "substring" (the default), "fuzzy", "glob" like `*.rs`, or "regex".
+///   An invalid glob or regex is an `invalid_argument` error.
+#[tauri::command]
+pub fn filter_listing(
+    listing_id: String,
+    query: String,
+    include_hidden: bool,
+    mode: Option<FilterMode>,
+) -> Result<Vec<FileEntry>, CommandError> {
+    ops_filter_listing(&listing_id, &query, include_hidden, mode.unwrap_or_default())
+}
+
 /// Gets a single file at the given index.
 ///
 /// # Arguments
@@ -133,7 +1379,7 @@ pub fn find_file_index(listing_id: String, name: String, include_hidden: bool) -
 /// * `index` - Index of the file to get.
 /// * `include_hidden` - Whether to include hidden files when calculating index.
 #[tauri::command]
-pub fn get_file_at(listing_id: String, index: usize, include_hidden: bool) -> Result<Option<FileEntry>, String> {
+pub fn get_file_at(listing_id: String, index: usize, include_hidden: bool) -> Result<Option<FileEntry>, CommandError> {
     ops_get_file_at(&listing_id, index, include_hidden)
 }
 
@@ -1,19 +1,34 @@
 //! Tauri commands for icon retrieval.
 
-use crate::icons;
+use crate::icons::{self, Appearance};
 use std::collections::HashMap;
 
 /// Gets icon data URLs for the requested icon IDs.
 /// Returns a map of icon_id -> base64 WebP data URL.
 /// Only fetches icons not already cached; clients should cache returned icons.
+/// `appearance` is "light" or "dark"; when omitted, the current system appearance is used.
 #[tauri::command]
-pub fn get_icons(icon_ids: Vec<String>) -> HashMap<String, String> {
-    icons::get_icons(icon_ids)
+pub fn get_icons(icon_ids: Vec<String>, appearance: Option<Appearance>) -> HashMap<String, String> {
+    icons::get_icons(icon_ids, appearance)
+}
+
+/// Gets icon data URLs for the requested icon IDs at several pixel sizes (for example, 32 and 64 for @1x/@2x).
+/// Returns a map of icon_id -> (size -> base64 WebP data URL).
+/// Each (icon_id, size, appearance) combination is cached separately on the backend.
+#[tauri::command]
+pub fn get_icons_with_sizes(
+    icon_ids: Vec<String>,
+    sizes: Vec<u32>,
+    appearance: Option<Appearance>,
+) -> HashMap<String, HashMap<u32, String>> {
+    icons::get_icons_with_sizes(icon_ids, sizes, appearance)
 }
 
 /// Refreshes icons for a directory listing.
-/// Fetches icons in parallel for all directories and extensions.
-/// Returns all fetched icons (frontend can compare with cache to detect changes).
+/// Re-extracts extension icons only when the extension's default app changed (for example, after
+/// "Open with → Change All" in Finder), and fetches directory icons by path in parallel, keeping the ones
+/// that differ from the cache.
+/// Returns only the icons that changed, keyed by icon ID.
 #[tauri::command]
 pub fn refresh_directory_icons(directory_paths: Vec<String>, extensions: Vec<String>) -> HashMap<String, String> {
     icons::refresh_icons_for_directory(directory_paths, extensions)
@@ -1,13 +1,19 @@
 //! Tauri commands module.
 
+#[cfg(target_os = "macos")]
+pub mod config_transfer;
+#[cfg(target_os = "macos")]
+pub mod file_handlers;
 pub mod file_system;
 pub mod font_metrics;
 pub mod icons;
 pub mod licensing;
 #[cfg(target_os = "macos")]
 pub mod network;
+pub mod session_state;
 #[cfg(target_os = "macos")]
 pub mod sync_status;
+pub mod time_format;
 pub mod ui;
 #[cfg(target_os = "macos")]
 pub mod volumes;
@@ -1,10 +1,14 @@
 //! Tauri commands for network host discovery and SMB share listing.
 
+use crate::command_error::CommandError;
+use crate::file_system::{SmbVolume, get_volume_manager};
 use crate::network::{
-    AuthMode, DiscoveryState, NetworkHost, ShareListError, ShareListResult, get_discovered_hosts,
-    get_discovery_state_value, get_host_for_resolution, resolve_host_ip, service_name_to_hostname, smb_client,
-    update_host_resolution,
+    self, AuthMode, ConnectionTestResult, DiscoveryState, NetworkHost, ShareListError, ShareListResult,
+    get_discovered_hosts, get_discovery_state_value, get_host_for_resolution, resolve_host_ip,
+    service_name_to_hostname, smb_client, update_host_resolution,
 };
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Logs a message from the frontend (for debugging).
 #[tauri::command]
@@ -24,6 +28,23 @@ pub fn get_network_discovery_state() -> DiscoveryState {
     get_discovery_state_value()
 }
 
+/// Stops network host discovery and clears the discovered hosts, for example on metered or VPN networks.
+#[tauri::command]
+pub fn stop_network_discovery(app: tauri::AppHandle) -> Result<(), CommandError> {
+    // Bonjour runs on the main run loop
+    let handle = app.clone();
+    app.run_on_main_thread(move || network::stop_network_discovery(&handle))
+        .map_err(|e| CommandError::Io { message: e.to_string() })
+}
+
+/// Restarts network host discovery with an empty host list. Safe to call repeatedly, also when it's stopped.
+#[tauri::command]
+pub fn restart_network_discovery(app: tauri::AppHandle) -> Result<(), CommandError> {
+    let handle = app.clone();
+    app.run_on_main_thread(move || network::restart_network_discovery(handle))
+        .map_err(|e| CommandError::Io { message: e.to_string() })
+}
+
 /// Resolves a network host by ID, returning the host with hostname and IP address populated.
 /// This is an async command that uses spawn_blocking for the DNS lookup to avoid blocking
 /// the main thread pool. Multiple hosts can resolve in parallel.
@@ -67,14 +88,27 @@ pub async fn resolve_host(host_id: String) -> Option<NetworkHost> {
 /// * `hostname` - Hostname to connect to (for example, "TEST_SERVER.local")
 /// * `ip_address` - Optional resolved IP address (preferred over hostname for reliability)
 /// * `port` - SMB port (default 445, but Docker containers may use different ports)
+/// * `with_capacity` - Whether to also get each share's total and free bytes (slower). Defaults to false.
+/// * `refresh` - Whether to query the host even if its shares are cached, for a manual refresh. Defaults to false.
 #[tauri::command]
 pub async fn list_shares_on_host(
     host_id: String,
     hostname: String,
     ip_address: Option<String>,
     port: u16,
+    with_capacity: Option<bool>,
+    refresh: Option<bool>,
 ) -> Result<ShareListResult, ShareListError> {
-    smb_client::list_shares(&host_id, &hostname, ip_address.as_deref(), port, None).await
+    smb_client::list_shares(
+        &host_id,
+        &hostname,
+        ip_address.as_deref(),
+        port,
+        None,
+        with_capacity.unwrap_or(false),
+        refresh.unwrap_or(false),
+    )
+    .await
 }
 
 /// Prefetches shares for a host (for example, on hover).
@@ -83,7 +117,22 @@ pub async fn list_shares_on_host(
 #[tauri::command]
 pub async fn prefetch_shares(host_id: String, hostname: String, ip_address: Option<String>, port: u16) {
     // Fire and forget - we don't care about the result for prefetching
-    let _ = smb_client::list_shares(&host_id, &hostname, ip_address.as_deref(), port, None).await;
+    let _ = smb_client::list_shares(&host_id, &hostname, ip_address.as_deref(), port, None, false, false).await;
+}
+
+/// Lists shares on several discovered hosts at once, as guest. Hosts with cached shares are answered from the cache,
+/// the others are queried concurrently, a few at a time.
+///
+/// Returns the result for each host ID, like `{ "office": { "Ok": { … } }, "nas": { "Err": { … } } }`.
+#[tauri::command]
+pub async fn list_shares_for_hosts(host_ids: Vec<String>) -> HashMap<String, Result<ShareListResult, ShareListError>> {
+    smb_client::list_shares_for_hosts(host_ids).await
+}
+
+/// Sets how long share lists are cached, in seconds. Values over an hour are clamped; 0 turns the cache off.
+#[tauri::command]
+pub fn set_share_cache_ttl_secs(ttl_secs: u64) {
+    smb_client::set_cache_ttl_secs(ttl_secs);
 }
 
 /// Gets auth mode detected for a host (from cached share list if available).
@@ -133,11 +182,30 @@ pub fn update_known_share(
         last_connection_mode,
         last_known_auth_options,
         username,
+        // Counted by update_known_share
+        connect_count: 0,
+        recent_connections: Vec::new(),
     };
 
     known_shares::update_known_share(&app, share);
 }
 
+/// Forgets a known network share, like when the user removes it from the list.
+/// Returns the remaining known shares.
+#[tauri::command]
+pub fn forget_known_share(app: tauri::AppHandle, server_name: String, share_name: String) -> Vec<KnownNetworkShare> {
+    known_shares::remove_known_share(&app, &server_name, &share_name);
+    get_all_known_shares()
+}
+
+/// Forgets all known shares of a server, including the server-level one.
+/// Returns the remaining known shares.
+#[tauri::command]
+pub fn forget_all_shares_for_server(app: tauri::AppHandle, server_name: String) -> Vec<KnownNetworkShare> {
+    known_shares::remove_known_shares_for_server(&app, &server_name);
+    get_all_known_shares()
+}
+
 /// Gets username hints for servers (last used username per server).
 #[tauri::command]
 pub fn get_username_hints() -> std::collections::HashMap<String, String> {
@@ -189,6 +257,7 @@ pub fn delete_smb_credentials(server: String, share: Option<String>) -> Result<(
 /// * `port` - SMB port
 /// * `username` - Username for authentication (or None for guest)
 /// * `password` - Password for authentication (or None for guest)
+/// * `with_capacity` - Whether to also get each share's total and free bytes (slower). Defaults to false.
 #[tauri::command]
 pub async fn list_shares_with_credentials(
     host_id: String,
@@ -197,6 +266,7 @@ pub async fn list_shares_with_credentials(
     port: u16,
     username: Option<String>,
     password: Option<String>,
+    with_capacity: Option<bool>,
 ) -> Result<ShareListResult, ShareListError> {
     let credentials = match (username, password) {
         (Some(u), Some(p)) => Some((u, p)),
@@ -209,10 +279,82 @@ pub async fn list_shares_with_credentials(
         ip_address.as_deref(),
         port,
         credentials.as_ref().map(|(u, p)| (u.as_str(), p.as_str())),
+        with_capacity.unwrap_or(false),
+        false,
+    )
+    .await
+}
+
+/// Tests the connection to a host without listing shares, for a "Test connection" button.
+///
+/// # Arguments
+/// * `hostname` - Hostname to connect to
+/// * `ip_address` - Optional resolved IP address
+/// * `port` - SMB port
+/// * `username` - Username for authentication (or None for guest)
+/// * `password` - Password for authentication (or None for guest)
+#[tauri::command]
+pub async fn test_smb_connection(
+    hostname: String,
+    ip_address: Option<String>,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<ConnectionTestResult, ShareListError> {
+    let credentials = match (username, password) {
+        (Some(u), Some(p)) => Some((u, p)),
+        _ => None,
+    };
+
+    smb_client::test_connection(
+        &hostname,
+        ip_address.as_deref(),
+        port,
+        credentials.as_ref().map(|(u, p)| (u.as_str(), p.as_str())),
     )
     .await
 }
 
+/// Connects to a share and registers it as a volume, so it can be browsed without mounting it.
+///
+/// Read-only for now. Listings of the volume work like local ones, with `list_directory_start_with_volume`.
+///
+/// # Arguments
+/// * `server` - Server hostname, like "nas.local"
+/// * `ip_address` - Optional resolved IP address, preferred over resolving the hostname
+/// * `port` - SMB port
+/// * `share` - Name of the share to browse
+/// * `username` - Username for authentication (or None for guest)
+/// * `password` - Password for authentication (or None for guest)
+///
+/// # Returns
+/// The volume ID, like "nas.local/Media".
+#[tauri::command]
+pub async fn connect_share_volume(
+    server: String,
+    ip_address: Option<String>,
+    port: u16,
+    share: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<String, CommandError> {
+    let address = match ip_address {
+        Some(ip) => {
+            Some(smb_client::socket_address(&ip, port).map_err(|message| CommandError::InvalidArgument { message })?)
+        }
+        None => None,
+    };
+    let credentials = match (&username, &password) {
+        (Some(u), Some(p)) => Some((u.as_str(), p.as_str())),
+        _ => None,
+    };
+
+    let volume = SmbVolume::connect(&server, address, &share, credentials).await?;
+    let volume_id = SmbVolume::volume_id(&server, &share);
+    get_volume_manager().register(&volume_id, Arc::new(volume));
+    Ok(volume_id)
+}
+
 // --- Mount Commands ---
 
 use crate::network::mount::{self, MountError, MountResult};
@@ -222,10 +364,12 @@ use crate::network::mount::{self, MountError, MountResult};
 /// Attempts to mount the specified share on the server. If credentials are
 /// provided, they are used for authentication. If the share is already mounted,
 /// returns the existing mount path without re-mounting.
+/// Emits `smb-mount-progress` events while mounting.
 ///
 /// # Arguments
 /// * `server` - Server hostname or IP address
 /// * `share` - Name of the share to mount
+/// * `subpath` - Optional folder in the share to mount instead of the share root, like "projects/2024"
 /// * `username` - Optional username for authentication
 /// * `password` - Optional password for authentication
 ///
@@ -234,10 +378,20 @@ use crate::network::mount::{self, MountError, MountResult};
 /// * `Err(MountError)` - Mount failed with specific error type
 #[tauri::command]
 pub async fn mount_network_share(
+    app: tauri::AppHandle,
     server: String,
     share: String,
+    subpath: Option<String>,
     username: Option<String>,
     password: Option<String>,
 ) -> Result<MountResult, MountError> {
-    mount::mount_share(server, share, username, password).await
+    mount::mount_share(app, server, share, subpath, username, password).await
+}
+
+/// Unmounts a mounted share, like "/Volumes/Documents".
+///
+/// Fails with `InUse` if files on the share are still open. Succeeds if the path isn't mounted.
+#[tauri::command]
+pub async fn unmount_network_share(mount_path: String) -> Result<(), MountError> {
+    mount::unmount_share(mount_path).await
 }
@@ -0,0 +1,23 @@
+//! Tauri commands for restoring panes after a restart or crash.
+
+use crate::session_state::{self, SessionState};
+use tauri::{AppHandle, Runtime};
+
+/// Gets the saved pane state to restore on startup.
+///
+/// # Returns
+/// The saved state, or null if "restore session" is off or nothing valid was saved.
+/// Panes whose directory no longer exists are null.
+#[tauri::command]
+pub fn load_session_state<R: Runtime>(app: AppHandle<R>) -> Option<SessionState> {
+    session_state::load_session_state(&app)
+}
+
+/// Saves the current pane state. Cheap to call often, writes are debounced.
+///
+/// # Arguments
+/// * `state` - Paths, sort, and selected index of both panes
+#[tauri::command]
+pub fn save_session_state<R: Runtime>(app: AppHandle<R>, state: SessionState) {
+    session_state::save_session_state(&app, state);
+}
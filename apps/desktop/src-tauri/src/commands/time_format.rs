@@ -0,0 +1,24 @@
+//! Tauri commands for formatting timestamps.
+
+use crate::time_format::{self, AbsoluteTimeStyle};
+
+/// Formats a timestamp relative to now, for example "2 hours ago", "Yesterday", or "Jan 3, 2026".
+///
+/// # Arguments
+/// * `timestamp` - Unix timestamp in seconds (can be negative for pre-1970 dates)
+/// * `locale` - For example "de-DE", for the words and date format of that locale. Defaults to English.
+#[tauri::command]
+pub fn format_relative_time(timestamp: i64, locale: Option<String>) -> String {
+    time_format::format_relative_time(timestamp, locale.as_deref())
+}
+
+/// Formats a timestamp as an absolute date in the local time zone, for example "Jan 3, 2026".
+///
+/// # Arguments
+/// * `timestamp` - Unix timestamp in seconds (can be negative for pre-1970 dates)
+/// * `style` - "date", "dateTime", or "iso". Defaults to "date".
+/// * `locale` - For example "de-DE", to use that locale's date format. Defaults to English.
+#[tauri::command]
+pub fn format_absolute_time(timestamp: i64, style: Option<AbsoluteTimeStyle>, locale: Option<String>) -> String {
+    time_format::format_absolute_time(timestamp, style.unwrap_or_default(), locale.as_deref())
+}
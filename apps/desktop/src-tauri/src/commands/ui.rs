@@ -1,4 +1,5 @@
 use crate::menu::{MenuState, build_context_menu};
+use crate::path_format::{PathFormat, format_paths};
 #[cfg(target_os = "macos")]
 use std::process::Command;
 use tauri::menu::ContextMenu;
@@ -77,3 +78,15 @@ pub fn execute_menu_action<R: Runtime>(app: &AppHandle<R>, id: &str) {
         _ => {}
     }
 }
+
+/// Copies one or more paths to the clipboard, one per line.
+///
+/// # Arguments
+/// * `paths` - Absolute paths to copy
+/// * `format` - "plain", "posixEscaped" (quoted for shells), or "fileUrl" (`file://` URL)
+#[tauri::command]
+pub fn copy_path<R: Runtime>(app: AppHandle<R>, paths: Vec<String>, format: PathFormat) -> Result<(), String> {
+    app.clipboard()
+        .write_text(format_paths(&paths, format))
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
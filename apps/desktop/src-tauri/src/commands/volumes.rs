@@ -1,6 +1,8 @@
 //! Tauri commands for volume operations.
 
+use crate::volumes::sidebar_config::{self, SidebarConfig};
 use crate::volumes::{self, DEFAULT_VOLUME_ID, LocationCategory, VolumeInfo};
+use tauri::Emitter;
 
 /// Lists all mounted volumes.
 #[tauri::command]
@@ -41,3 +43,17 @@ pub fn find_containing_volume(path: String) -> Option<VolumeInfo> {
 
     best_match
 }
+
+/// Gets which location categories the sidebar shows, and in what order.
+#[tauri::command]
+pub fn get_sidebar_config() -> SidebarConfig {
+    sidebar_config::get_sidebar_config()
+}
+
+/// Sets which location categories the sidebar shows, and in what order, and saves it.
+/// Emits `locations-changed` so the sidebar can reload `list_volumes`.
+#[tauri::command]
+pub fn set_sidebar_config(app: tauri::AppHandle, config: SidebarConfig) {
+    sidebar_config::set_sidebar_config(&app, config);
+    let _ = app.emit("locations-changed", ());
+}
@@ -0,0 +1,184 @@
+//! Exporting and importing the app's configuration, for backups and moving to another machine.
+//!
+//! The export is a single JSON blob with the settings, known network shares, and sidebar config. Keychain
+//! credentials are left out by design: passwords never leave the Keychain, so an export is safe to store or
+//! share, and users sign in again on the new machine. Favorites and keyboard shortcuts aren't configurable yet,
+//! so there's nothing to export for them.
+//!
+//! The blob has a `version`. Bump `CONFIG_EXPORT_VERSION` when the schema changes, and teach `migrate` to
+//! upgrade older exports.
+
+use crate::network::known_shares::{self, KnownNetworkShare};
+use crate::settings;
+use crate::volumes::sidebar_config::{self, SidebarConfig};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::Emitter;
+use tauri_plugin_store::StoreExt;
+
+/// Current version of the export schema.
+pub const CONFIG_EXPORT_VERSION: u32 = 1;
+
+/// The file the frontend settings store writes to.
+const SETTINGS_STORE_NAME: &str = "settings.json";
+
+/// All exportable configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigExport {
+    /// Schema version, see `CONFIG_EXPORT_VERSION`
+    pub version: u32,
+    /// Contents of the settings store, as the frontend wrote them
+    #[serde(default)]
+    pub settings: Map<String, Value>,
+    /// Network shares the user has connected to. Usernames only, no passwords.
+    #[serde(default)]
+    pub known_shares: Vec<KnownNetworkShare>,
+    #[serde(default)]
+    pub sidebar_config: SidebarConfig,
+}
+
+/// Builds the export from the current configuration.
+pub fn export_config<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<String, String> {
+    let settings = match app.store(SETTINGS_STORE_NAME) {
+        Ok(store) => store.entries().into_iter().collect(),
+        Err(e) => return Err(format!("Failed to open settings: {}", e)),
+    };
+    let export = ConfigExport {
+        version: CONFIG_EXPORT_VERSION,
+        settings,
+        known_shares: known_shares::get_all_known_shares(),
+        sidebar_config: sidebar_config::get_sidebar_config(),
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to serialize config: {}", e))
+}
+
+/// Validates an export and applies it, replacing settings and the sidebar config, and adding the known shares.
+///
+/// Emits `settings-changed` with the new settings and `locations-changed`, so open windows pick up the changes.
+/// Nothing is applied if the export is invalid.
+pub fn import_config<R: tauri::Runtime>(app: &tauri::AppHandle<R>, json: &str) -> Result<(), String> {
+    let export = parse_config_export(json)?;
+
+    let store = app
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| format!("Failed to open settings: {}", e))?;
+    for (key, value) in &export.settings {
+        store.set(key.clone(), value.clone());
+    }
+    store.save().map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    // Apply the settings the backend keeps its own copy of
+    let saved_settings = settings::load_settings(app);
+    crate::file_system::set_ignore_patterns(&saved_settings.ignore_patterns);
+    crate::file_system::set_follow_symlinks_for_type(saved_settings.follow_symlinks_for_type);
+    crate::file_system::set_debounce_ms(saved_settings.watcher_debounce_ms);
+    crate::network::smb_client::set_cache_ttl_secs(saved_settings.share_cache_ttl_secs);
+
+    for share in export.known_shares {
+        known_shares::restore_known_share(app, share);
+    }
+    sidebar_config::set_sidebar_config(app, export.sidebar_config);
+
+    let _ = app.emit("settings-changed", &export.settings);
+    let _ = app.emit("locations-changed", ());
+    Ok(())
+}
+
+/// Parses an export, upgrading it to the current schema if it's from an older version.
+pub fn parse_config_export(json: &str) -> Result<ConfigExport, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("Not a valid config export: {}", e))?;
+    let version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or("Not a valid config export: missing version")?;
+    if version > u64::from(CONFIG_EXPORT_VERSION) {
+        return Err(format!(
+            "This config was exported by a newer version of the app (version {}). Update the app to import it.",
+            version
+        ));
+    }
+    let value = migrate(value, version as u32);
+    serde_json::from_value(value).map_err(|e| format!("Not a valid config export: {}", e))
+}
+
+/// Upgrades an export from `version` to `CONFIG_EXPORT_VERSION`, one version at a time.
+fn migrate(value: Value, _version: u32) -> Value {
+    // No schema changes yet. Add a step for each one, like `if version < 2 { value = migrate_v1_to_v2(value) }`
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::known_shares::{AuthOptions, ConnectionMode};
+    use crate::volumes::LocationCategory;
+
+    fn sample_export() -> ConfigExport {
+        let mut settings = Map::new();
+        settings.insert("showHiddenFiles".to_string(), Value::Bool(false));
+        settings.insert("ignorePatterns".to_string(), serde_json::json!([".DS_Store"]));
+        ConfigExport {
+            version: CONFIG_EXPORT_VERSION,
+            settings,
+            known_shares: vec![KnownNetworkShare {
+                server_name: "nas".to_string(),
+                share_name: "Media".to_string(),
+                protocol: "smb".to_string(),
+                last_connected_at: "2026-01-01T00:00:00Z".to_string(),
+                last_connection_mode: ConnectionMode::Credentials,
+                last_known_auth_options: AuthOptions::CredentialsOnly,
+                username: Some("david".to_string()),
+                connect_count: 0,
+                recent_connections: Vec::new(),
+            }],
+            sidebar_config: SidebarConfig {
+                category_order: vec![LocationCategory::MainVolume, LocationCategory::Favorite],
+                hidden_categories: vec![LocationCategory::CloudDrive],
+            },
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let export = sample_export();
+        let json = serde_json::to_string_pretty(&export).unwrap();
+
+        let parsed = parse_config_export(&json).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&export).unwrap()
+        );
+        assert_eq!(parsed.sidebar_config, export.sidebar_config);
+    }
+
+    #[test]
+    fn test_export_has_no_passwords() {
+        let json = serde_json::to_string(&sample_export()).unwrap();
+        assert!(!json.to_lowercase().contains("password"));
+    }
+
+    #[test]
+    fn test_missing_sections_use_defaults() {
+        let parsed = parse_config_export(r#"{ "version": 1 }"#).unwrap();
+
+        assert!(parsed.settings.is_empty());
+        assert!(parsed.known_shares.is_empty());
+        assert_eq!(parsed.sidebar_config, SidebarConfig::default());
+    }
+
+    #[test]
+    fn test_rejects_invalid_exports() {
+        assert!(parse_config_export("not json").is_err());
+        assert!(parse_config_export(r#"{ "settings": {} }"#).is_err());
+        assert!(parse_config_export(r#"{ "version": 1, "knownShares": "nope" }"#).is_err());
+    }
+
+    #[test]
+    fn test_rejects_newer_versions() {
+        let json = format!(r#"{{ "version": {} }}"#, CONFIG_EXPORT_VERSION + 1);
+        let error = parse_config_export(&json).unwrap_err();
+        assert!(error.contains("newer version"));
+    }
+}
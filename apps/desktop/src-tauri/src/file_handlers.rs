@@ -0,0 +1,130 @@
+//! Lists the applications that can open a given file, for the "Open With" menu.
+//!
+//! Uses Launch Services to find all apps registered for the file's content type (UTI),
+//! reusing the UTI and bundle lookups from `macos_icons`.
+
+use crate::macos_icons::{get_app_url_for_bundle_id, get_default_app_bundle_id, get_uti_for_extension};
+use core_foundation::array::CFArray;
+use core_foundation::base::TCFType;
+use core_foundation::string::CFString;
+use core_services::{LSCopyAllRoleHandlersForContentType, kLSRolesAll};
+use plist::Value;
+use serde::Serialize;
+use std::path::Path;
+
+/// UTI used for files without an extension, same as Finder does.
+const GENERIC_DATA_UTI: &str = "public.data";
+
+/// An application that can open a file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHandler {
+    /// Bundle identifier, for example "com.apple.TextEdit"
+    pub bundle_id: String,
+    /// Display name of the app, for example "TextEdit"
+    pub name: String,
+    /// Path to the app bundle
+    pub path: String,
+    /// Whether this is the default app for the file's type
+    pub is_default: bool,
+    /// Icon ID to request via `get_icons` ("path:{app path}")
+    pub icon_id: String,
+}
+
+/// Gets the content type (UTI) for a path based on its extension.
+/// Files without an extension get the generic data type.
+fn get_uti_for_path(path: &Path) -> Option<CFString> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => get_uti_for_extension(ext),
+        None => Some(CFString::new(GENERIC_DATA_UTI)),
+    }
+}
+
+/// Gets the bundle IDs of all apps registered to handle a content type.
+fn get_all_handler_bundle_ids(uti: &CFString) -> Vec<String> {
+    unsafe {
+        let handlers_ref = LSCopyAllRoleHandlersForContentType(uti.as_concrete_TypeRef(), kLSRolesAll);
+
+        if handlers_ref.is_null() {
+            return Vec::new();
+        }
+
+        let handlers: CFArray<CFString> = CFArray::wrap_under_create_rule(handlers_ref);
+        handlers.iter().map(|bundle_id| bundle_id.to_string()).collect()
+    }
+}
+
+/// Reads the app's display name from its Info.plist, falling back to the bundle's file name.
+fn get_app_display_name(app_path: &Path) -> String {
+    let plist_name = std::fs::read(app_path.join("Contents/Info.plist"))
+        .ok()
+        .and_then(|data| plist::from_bytes::<Value>(&data).ok())
+        .and_then(|plist| {
+            let dict = plist.as_dictionary()?;
+            dict.get("CFBundleDisplayName")
+                .or_else(|| dict.get("CFBundleName"))
+                .and_then(|v| v.as_string())
+                .map(String::from)
+        });
+
+    plist_name.unwrap_or_else(|| {
+        app_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    })
+}
+
+/// Lists the applications that can open the given path.
+/// The default app comes first, then the rest sorted by name.
+/// Returns an empty list if the file's type has no registered handlers.
+pub fn get_handlers_for_path(path: &Path) -> Vec<FileHandler> {
+    let Some(uti) = get_uti_for_path(path) else {
+        return Vec::new();
+    };
+    let default_bundle_id = get_default_app_bundle_id(&uti).map(|b| b.to_string());
+
+    let mut handlers: Vec<FileHandler> = get_all_handler_bundle_ids(&uti)
+        .into_iter()
+        .filter_map(|bundle_id| {
+            // Skip apps that are registered but no longer installed
+            let app_path = get_app_url_for_bundle_id(&CFString::new(&bundle_id))?;
+            let path_str = app_path.to_string_lossy().to_string();
+            Some(FileHandler {
+                is_default: default_bundle_id.as_deref() == Some(bundle_id.as_str()),
+                name: get_app_display_name(&app_path),
+                icon_id: format!("path:{}", path_str),
+                path: path_str,
+                bundle_id,
+            })
+        })
+        .collect();
+
+    handlers.sort_by(|a, b| {
+        b.is_default
+            .cmp(&a.is_default)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    handlers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_uti_for_path_without_extension() {
+        let uti = get_uti_for_path(Path::new("/tmp/Makefile")).unwrap();
+        assert_eq!(uti.to_string(), GENERIC_DATA_UTI);
+    }
+
+    #[test]
+    fn test_get_handlers_for_txt() {
+        let handlers = get_handlers_for_path(Path::new("/tmp/notes.txt"));
+        // At most one default, and it comes first
+        assert!(handlers.iter().filter(|h| h.is_default).count() <= 1);
+        if let Some(default_pos) = handlers.iter().position(|h| h.is_default) {
+            assert_eq!(default_pos, 0);
+        }
+    }
+}
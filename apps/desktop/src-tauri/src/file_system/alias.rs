@@ -0,0 +1,81 @@
+//! Finder alias resolution.
+//!
+//! Finder aliases aren't symlinks: they're regular files holding bookmark data that points
+//! to the target. On macOS, we resolve them via NSURL bookmark resolution. Elsewhere, this is a no-op.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Result of resolving a Finder alias.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasResolution {
+    /// Absolute path of the alias target. None if the path isn't an alias or the target can't be found.
+    pub target: Option<String>,
+    /// Why the target couldn't be resolved, for example because it was moved or deleted. None on success.
+    pub reason: Option<String>,
+}
+
+impl AliasResolution {
+    fn failed(reason: impl Into<String>) -> Self {
+        Self {
+            target: None,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
+/// Resolves a Finder alias file to the path it points to.
+///
+/// Resolution never shows UI and never mounts volumes, so aliases pointing to unmounted
+/// network shares come back as unresolved instead of blocking.
+#[cfg(target_os = "macos")]
+pub fn resolve_alias(path: &Path) -> AliasResolution {
+    use objc2::runtime::Bool;
+    use objc2_foundation::{NSString, NSURL, NSURLBookmarkResolutionOptions};
+
+    let Some(path_str) = path.to_str() else {
+        return AliasResolution::failed("Path is not valid UTF-8");
+    };
+    let url = NSURL::fileURLWithPath(&NSString::from_str(path_str));
+
+    // Fails if the file has no bookmark data, meaning it's not an alias
+    let Ok(bookmark_data) = (unsafe { NSURL::bookmarkDataWithContentsOfURL_error(&url) }) else {
+        return AliasResolution::failed("Not an alias file");
+    };
+
+    let mut is_stale = Bool::NO;
+    let options = NSURLBookmarkResolutionOptions::WithoutUI | NSURLBookmarkResolutionOptions::WithoutMounting;
+    let resolved = unsafe {
+        NSURL::URLByResolvingBookmarkData_options_relativeToURL_bookmarkDataIsStale_error(
+            &bookmark_data,
+            options,
+            None,
+            &mut is_stale,
+        )
+    };
+
+    match resolved {
+        Ok(target_url) => match target_url.path() {
+            Some(target_path) => AliasResolution {
+                target: Some(target_path.to_string()),
+                reason: None,
+            },
+            None => AliasResolution::failed("Alias target is not a file URL"),
+        },
+        Err(error) => AliasResolution::failed(format!(
+            "Alias target can't be found (it may have been moved or deleted): {}",
+            error.localizedDescription()
+        )),
+    }
+}
+
+/// Resolves a Finder alias file to the path it points to.
+/// Aliases only exist on macOS, so this always returns an empty result elsewhere.
+#[cfg(not(target_os = "macos"))]
+pub fn resolve_alias(_path: &Path) -> AliasResolution {
+    AliasResolution {
+        target: None,
+        reason: None,
+    }
+}
@@ -0,0 +1,94 @@
+//! Case sensitivity of file systems, and case-only name collisions.
+//!
+//! APFS and HFS+ are case-insensitive by default, so "README" and "readme" can't coexist there, and
+//! copying or renaming to a name that only differs in case from an existing file replaces that file.
+
+use std::path::Path;
+
+/// `_PC_CASE_SENSITIVE` from macOS's `<unistd.h>`. Not exported by the libc crate.
+#[cfg(target_os = "macos")]
+const PC_CASE_SENSITIVE: libc::c_int = 11;
+
+/// Returns true if the file system that holds `path` is case-sensitive.
+///
+/// Missing paths are checked on their closest existing ancestor. If the answer can't be determined,
+/// it assumes case-sensitive, which is the safe default for collision checks elsewhere.
+#[cfg(target_os = "macos")]
+pub fn is_case_sensitive_volume(path: &Path) -> bool {
+    let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+        return true;
+    };
+    let Ok(c_path) = std::ffi::CString::new(existing.as_os_str().as_encoded_bytes()) else {
+        return true;
+    };
+    // Returns 1 if case-sensitive, 0 if not, -1 on error
+    let result = unsafe { libc::pathconf(c_path.as_ptr(), PC_CASE_SENSITIVE) };
+    result != 0
+}
+
+/// Other Unix file systems are case-sensitive, with rare exceptions we don't handle.
+#[cfg(not(target_os = "macos"))]
+pub fn is_case_sensitive_volume(_path: &Path) -> bool {
+    true
+}
+
+/// Finds an existing name that `new_name` would collide with only because of case insensitivity.
+///
+/// Exact matches aren't reported, they're regular "file exists" conflicts. Neither is `source_name`,
+/// so renaming "readme" to "README" (changing only the case of the same file) is allowed.
+///
+/// # Returns
+/// The existing name, or None if there's no case-only collision.
+pub fn find_case_collision<'a>(
+    existing_names: impl IntoIterator<Item = &'a str>,
+    new_name: &str,
+    source_name: Option<&str>,
+) -> Option<String> {
+    existing_names
+        .into_iter()
+        .find(|name| *name != new_name && Some(*name) != source_name && names_equal_ignoring_case(name, new_name))
+        .map(String::from)
+}
+
+/// Compares names case-insensitively, with full Unicode lowercasing like APFS does.
+pub fn names_equal_ignoring_case(a: &str, b: &str) -> bool {
+    a == b || a.to_lowercase() == b.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_case_only_collision() {
+        let names = ["readme", "main.rs"];
+        assert_eq!(find_case_collision(names, "README", None), Some("readme".to_string()));
+    }
+
+    #[test]
+    fn test_exact_match_is_not_a_case_collision() {
+        let names = ["readme", "main.rs"];
+        assert_eq!(find_case_collision(names, "readme", None), None);
+    }
+
+    #[test]
+    fn test_case_only_rename_of_same_file_is_allowed() {
+        let names = ["readme", "main.rs"];
+        assert_eq!(find_case_collision(names, "README", Some("readme")), None);
+    }
+
+    #[test]
+    fn test_unicode_case_collision() {
+        let names = ["Ärger.txt"];
+        assert_eq!(
+            find_case_collision(names, "ärger.txt", None),
+            Some("Ärger.txt".to_string())
+        );
+    }
+
+    #[test]
+    fn test_temp_dir_case_sensitivity_can_be_queried() {
+        // The answer depends on the file system, but it must not panic on missing paths
+        let _ = is_case_sensitive_volume(&std::env::temp_dir().join("cmdr_missing_dir/missing.txt"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Tests for filtering a cached listing.
+
+use super::operations::{
+    CachedListing, FileEntry, FilterMode, LISTING_CACHE, SortColumn, SortOrder, filter_listing, get_file_range,
+    list_directory_end,
+};
+use crate::command_error::CommandError;
+
+/// Inserts a listing with the given names (in this order) into the cache.
+fn insert_listing(listing_id: &str, names: &[&str]) {
+    LISTING_CACHE.write().unwrap().insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "test".to_string(),
+            path: std::path::PathBuf::from("/"),
+            entries: names
+                .iter()
+                .map(|n| FileEntry::test_entry(&format!("/{}", n), false))
+                .collect(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Default::default(),
+            last_accessed: super::operations::AccessTime::now(),
+        },
+    );
+}
+
+/// Names in listing order (deliberately not alphabetical, to check that the order is kept).
+const NAMES: &[&str] = &[
+    ".report-draft.txt",
+    "zebra-report.pdf",
+    "Annual Report.docx",
+    "vacation-day.mp4",
+    "notes.md",
+];
+
+fn names(entries: &[FileEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.name.as_str()).collect()
+}
+
+#[test]
+fn test_filter_matches_substring_case_insensitively_in_listing_order() {
+    let listing_id = "test-filter-substring";
+    insert_listing(listing_id, NAMES);
+
+    let result = filter_listing(listing_id, "REPORT", false, FilterMode::Substring).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&result), vec!["zebra-report.pdf", "Annual Report.docx"]);
+}
+
+#[test]
+fn test_filter_substring_needs_adjacent_characters() {
+    let listing_id = "test-filter-not-fuzzy";
+    insert_listing(listing_id, NAMES);
+
+    let result = filter_listing(listing_id, "vdmp4", false, FilterMode::Substring).unwrap();
+
+    list_directory_end(listing_id);
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_filter_fuzzy_matches_subsequence() {
+    let listing_id = "test-filter-fuzzy";
+    insert_listing(listing_id, NAMES);
+
+    let result = filter_listing(listing_id, "vdmp4", false, FilterMode::Fuzzy).unwrap();
+    let out_of_order = filter_listing(listing_id, "4mp", false, FilterMode::Fuzzy).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&result), vec!["vacation-day.mp4"]);
+    assert!(out_of_order.is_empty());
+}
+
+#[test]
+fn test_filter_empty_query_returns_all_visible() {
+    let listing_id = "test-filter-empty";
+    insert_listing(listing_id, NAMES);
+
+    let result = filter_listing(listing_id, "", false, FilterMode::Substring).unwrap();
+    let range = get_file_range(listing_id, 0, 100, false).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&result), names(&range));
+    assert_eq!(result.len(), 4);
+}
+
+#[test]
+fn test_filter_respects_include_hidden() {
+    let listing_id = "test-filter-hidden";
+    insert_listing(listing_id, NAMES);
+
+    let hidden_excluded = filter_listing(listing_id, "draft", false, FilterMode::Substring).unwrap();
+    let hidden_included = filter_listing(listing_id, "report", true, FilterMode::Substring).unwrap();
+
+    list_directory_end(listing_id);
+    assert!(hidden_excluded.is_empty());
+    assert_eq!(
+        names(&hidden_included),
+        vec![".report-draft.txt", "zebra-report.pdf", "Annual Report.docx"]
+    );
+}
+
+#[test]
+fn test_filter_glob_matches_whole_name() {
+    let listing_id = "test-filter-glob";
+    insert_listing(listing_id, NAMES);
+
+    let by_extension = filter_listing(listing_id, "*.PDF", false, FilterMode::Glob).unwrap();
+    let single_char = filter_listing(listing_id, "notes.m?", false, FilterMode::Glob).unwrap();
+    // Without a wildcard, a glob must match the whole name
+    let partial = filter_listing(listing_id, "notes", false, FilterMode::Glob).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&by_extension), vec!["zebra-report.pdf"]);
+    assert_eq!(names(&single_char), vec!["notes.md"]);
+    assert!(partial.is_empty());
+}
+
+#[test]
+fn test_filter_glob_character_classes() {
+    let listing_id = "test-filter-glob-classes";
+    insert_listing(listing_id, &["a1.txt", "b2.txt", "c3.txt", "[draft].txt", "a+b.txt"]);
+
+    let range = filter_listing(listing_id, "[a-b]?.txt", false, FilterMode::Glob).unwrap();
+    let negated = filter_listing(listing_id, "[!a]?.txt", false, FilterMode::Glob).unwrap();
+    // Regex characters in a glob are literal, and a lone `[` is too
+    let literal = filter_listing(listing_id, "a+b.*", false, FilterMode::Glob).unwrap();
+    let unclosed = filter_listing(listing_id, "[draft*", false, FilterMode::Glob).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&range), vec!["a1.txt", "b2.txt"]);
+    assert_eq!(names(&negated), vec!["b2.txt", "c3.txt"]);
+    assert_eq!(names(&literal), vec!["a+b.txt"]);
+    assert_eq!(names(&unclosed), vec!["[draft].txt"]);
+}
+
+#[test]
+fn test_filter_glob_keeps_hidden_files_out() {
+    let listing_id = "test-filter-glob-hidden";
+    insert_listing(listing_id, NAMES);
+
+    let hidden_excluded = filter_listing(listing_id, "*.txt", false, FilterMode::Glob).unwrap();
+    let hidden_included = filter_listing(listing_id, "*.txt", true, FilterMode::Glob).unwrap();
+
+    list_directory_end(listing_id);
+    assert!(hidden_excluded.is_empty());
+    assert_eq!(names(&hidden_included), vec![".report-draft.txt"]);
+}
+
+#[test]
+fn test_filter_regex() {
+    let listing_id = "test-filter-regex";
+    insert_listing(
+        listing_id,
+        &["test_one.txt", "test_two.rs", "my_test_three.txt", "Test_four.txt"],
+    );
+
+    let anchored = filter_listing(listing_id, r"^test_.*\.txt$", false, FilterMode::Regex).unwrap();
+    let unanchored = filter_listing(listing_id, r"test_\w+\.txt", false, FilterMode::Regex).unwrap();
+    let ignoring_case = filter_listing(listing_id, r"(?i)^test_", false, FilterMode::Regex).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&anchored), vec!["test_one.txt"]);
+    assert_eq!(names(&unanchored), vec!["test_one.txt", "my_test_three.txt"]);
+    assert_eq!(
+        names(&ignoring_case),
+        vec!["test_one.txt", "test_two.rs", "Test_four.txt"]
+    );
+}
+
+#[test]
+fn test_filter_invalid_regex_is_an_error() {
+    let listing_id = "test-filter-invalid-regex";
+    insert_listing(listing_id, NAMES);
+
+    let result = filter_listing(listing_id, "(unclosed", false, FilterMode::Regex);
+
+    list_directory_end(listing_id);
+    match result {
+        Err(CommandError::InvalidArgument { message }) => assert!(message.contains("(unclosed"), "{}", message),
+        other => panic!("Expected an invalid argument error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_filter_does_not_change_listing() {
+    let listing_id = "test-filter-no-mutation";
+    insert_listing(listing_id, NAMES);
+
+    filter_listing(listing_id, "report", true, FilterMode::Substring).unwrap();
+    let range = get_file_range(listing_id, 0, 100, true).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(names(&range), NAMES.to_vec());
+}
+
+#[test]
+fn test_filter_unknown_listing() {
+    assert!(filter_listing("no-such-listing", "a", true, FilterMode::Substring).is_err());
+}
@@ -0,0 +1,437 @@
+//! Recursive search in a directory subtree, for file names or for text in files.
+//!
+//! Walks the tree level by level with `list_directory_core`, so entries look like those of a listing (owner
+//! names aren't looked up, see `owner_resolved`). Symlinks to folders are followed, but every folder is read
+//! only once, keyed by its device and inode, so links back up the tree can't make the walk loop.
+
+use super::operations::{FileEntry, list_directory_core};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Matches per `on_results` call, so the frontend gets them in batches rather than one by one.
+const FIND_RESULT_BATCH_SIZE: usize = 100;
+
+/// Files larger than this are skipped by content searches, unless the caller sets another limit.
+pub const DEFAULT_CONTENT_SEARCH_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// How much of a file is checked for null bytes to tell whether it's binary. Same as git and grep.
+const BINARY_SNIFF_SIZE: usize = 8 * 1024;
+
+/// Cancellation flags of running searches, by token.
+static FIND_TOKENS: LazyLock<RwLock<HashMap<String, Arc<AtomicBool>>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Searches `root` and its subfolders for entries whose names contain `query` (case-insensitive).
+///
+/// Folders are searched breadth-first, so shallow matches come first. Unreadable folders are skipped.
+/// Cancel the search by calling `cancel_find` with the same token.
+///
+/// # Arguments
+/// * `root` - The folder to search in
+/// * `query` - Text the names must contain
+/// * `max_results` - The search stops after this many matches
+/// * `token` - Chosen by the caller to cancel this search. Should be unique among running searches.
+/// * `on_results` - Called with batches of matching entries, at most `max_results` in total
+///
+/// # Returns
+/// The number of entries scanned, or an error if `root` can't be listed.
+pub fn find_in_directory<F>(
+    root: &Path,
+    query: &str,
+    max_results: usize,
+    token: &str,
+    mut on_results: F,
+) -> io::Result<usize>
+where
+    F: FnMut(&[FileEntry]),
+{
+    let cancelled = register_token(token);
+    let query = query.to_lowercase();
+    let mut found = 0;
+    let mut batch = Vec::new();
+    let result = walk_subtree(root, &cancelled, |entry| {
+        if found < max_results && entry.name.to_lowercase().contains(&query) {
+            found += 1;
+            batch.push(entry);
+            if batch.len() >= FIND_RESULT_BATCH_SIZE {
+                on_results(&batch);
+                batch.clear();
+            }
+        }
+        found < max_results
+    });
+    if !batch.is_empty() {
+        on_results(&batch);
+    }
+    unregister_token(token);
+    result
+}
+
+/// A line of a file that matched a content search.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentMatch {
+    pub path: String,
+    /// 1-based
+    pub line_number: usize,
+    /// The line without its line ending. Invalid UTF-8 is replaced.
+    pub line: String,
+}
+
+/// Searches the text files in `root` and its subfolders for lines matching `pattern`.
+///
+/// Binary files (with a null byte in their first 8 KiB) and files larger than `max_file_size` are skipped,
+/// as are files that can't be read. Cancel the search by calling `cancel_find` with the same token.
+///
+/// # Arguments
+/// * `root` - The folder to search in
+/// * `pattern` - Text the lines must contain (case-insensitive), or a regex if `is_regex` is true
+/// * `is_regex` - Whether `pattern` is a regex
+/// * `max_file_size` - Larger files are skipped, in bytes
+/// * `token` - Chosen by the caller to cancel this search. Should be unique among running searches.
+/// * `on_match` - Called with every matching line, in file order
+///
+/// # Returns
+/// The number of files searched, or an error if the regex is invalid or `root` can't be listed.
+pub fn search_file_contents<F>(
+    root: &Path,
+    pattern: &str,
+    is_regex: bool,
+    max_file_size: u64,
+    token: &str,
+    mut on_match: F,
+) -> io::Result<usize>
+where
+    F: FnMut(&ContentMatch),
+{
+    let matcher = if is_regex {
+        Regex::new(pattern)
+    } else {
+        Regex::new(&format!("(?i){}", regex::escape(pattern)))
+    }
+    .map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid pattern '{}': {}", pattern, e),
+        )
+    })?;
+
+    let cancelled = register_token(token);
+    let mut searched = 0;
+    let result = walk_subtree(root, &cancelled, |entry| {
+        let is_candidate = !entry.is_directory && entry.size.is_some_and(|size| size <= max_file_size);
+        if is_candidate && search_file(&entry.path, &matcher, &cancelled, &mut on_match).is_ok() {
+            searched += 1;
+        }
+        true
+    });
+    unregister_token(token);
+    result.map(|_| searched)
+}
+
+/// Reports the lines of a file that match. Binary files have no matches.
+fn search_file<F>(path: &str, matcher: &Regex, cancelled: &AtomicBool, on_match: &mut F) -> io::Result<()>
+where
+    F: FnMut(&ContentMatch),
+{
+    let mut file = File::open(path)?;
+    let mut head = Vec::with_capacity(BINARY_SNIFF_SIZE);
+    (&mut file).take(BINARY_SNIFF_SIZE as u64).read_to_end(&mut head)?;
+    if head.contains(&0) {
+        return Ok(());
+    }
+    file.rewind()?;
+
+    for (index, line) in BufReader::new(file).split(b'\n').enumerate() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let line = line?;
+        let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(&line));
+        if matcher.is_match(&line) {
+            on_match(&ContentMatch {
+                path: path.to_string(),
+                line_number: index + 1,
+                line: line.into_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Stops a running `find_in_directory` or `search_file_contents`. Results found so far have already been
+/// reported.
+///
+/// # Returns
+/// True if a search with the token was running.
+pub fn cancel_find(token: &str) -> bool {
+    let Ok(tokens) = FIND_TOKENS.read() else {
+        return false;
+    };
+    match tokens.get(token) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+fn register_token(token: &str) -> Arc<AtomicBool> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Ok(mut tokens) = FIND_TOKENS.write() {
+        tokens.insert(token.to_string(), cancelled.clone());
+    }
+    cancelled
+}
+
+fn unregister_token(token: &str) {
+    if let Ok(mut tokens) = FIND_TOKENS.write() {
+        tokens.remove(token);
+    }
+}
+
+/// Calls `visit` with every entry under `root`, breadth-first, until it returns false or `cancelled` is set.
+///
+/// Symlinks to folders are followed, but each folder is read only once. Unreadable folders are skipped.
+///
+/// # Returns
+/// The number of entries visited, or an error if `root` can't be listed.
+fn walk_subtree<F>(root: &Path, cancelled: &AtomicBool, mut visit: F) -> io::Result<usize>
+where
+    F: FnMut(FileEntry) -> bool,
+{
+    // Fail on the root itself, rather than reporting an empty search
+    let root_entries = list_directory_core(root, false)?;
+
+    let mut visited = HashSet::new();
+    visited.extend(folder_id(root));
+    let mut queue = VecDeque::new();
+    let mut scanned = 0;
+
+    let mut next_entries = Some(root_entries);
+    while let Some(entries) = next_entries.take() {
+        for entry in entries {
+            if cancelled.load(Ordering::Relaxed) {
+                return Ok(scanned);
+            }
+            scanned += 1;
+            if entry.is_directory {
+                let path = PathBuf::from(&entry.path);
+                // Symlinks are followed, but a folder already seen (through another link) isn't read again
+                if folder_id(&path).is_some_and(|id| visited.insert(id)) {
+                    queue.push_back(path);
+                }
+            }
+            if !visit(entry) {
+                return Ok(scanned);
+            }
+        }
+        while let Some(dir) = queue.pop_front() {
+            if let Ok(entries) = list_directory_core(&dir, false) {
+                next_entries = Some(entries);
+                break;
+            }
+        }
+    }
+    Ok(scanned)
+}
+
+/// The device and inode of a folder, following symlinks. None if it can't be read.
+fn folder_id(path: &Path) -> Option<(u64, u64)> {
+    fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Runs a search and returns the scanned count and the names of all results.
+    fn find(root: &Path, query: &str, max_results: usize, token: &str) -> (usize, Vec<String>) {
+        let mut names = Vec::new();
+        let scanned = find_in_directory(root, query, max_results, token, |batch| {
+            names.extend(batch.iter().map(|e| e.name.clone()))
+        })
+        .unwrap();
+        (scanned, names)
+    }
+
+    #[test]
+    fn test_finds_matches_in_subfolders() {
+        let dir = create_test_dir("cmdr_find_subfolders_test");
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        fs::write(dir.join("Report.txt"), "").unwrap();
+        fs::write(dir.join("a/old-report.pdf"), "").unwrap();
+        fs::write(dir.join("a/b/report-final.doc"), "").unwrap();
+        fs::write(dir.join("a/b/notes.md"), "").unwrap();
+
+        let (scanned, mut names) = find(&dir, "report", 100, "test-find-subfolders");
+
+        let _ = fs::remove_dir_all(&dir);
+        names.sort();
+        assert_eq!(names, vec!["Report.txt", "old-report.pdf", "report-final.doc"]);
+        // Report.txt, a, old-report.pdf, b, report-final.doc, notes.md
+        assert_eq!(scanned, 6);
+    }
+
+    #[test]
+    fn test_stops_at_max_results() {
+        let dir = create_test_dir("cmdr_find_max_results_test");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        for i in 0..150 {
+            fs::write(dir.join(format!("match_{}.txt", i)), "").unwrap();
+            fs::write(dir.join("sub").join(format!("match_{}.txt", i)), "").unwrap();
+        }
+
+        let mut batch_sizes = Vec::new();
+        let scanned = find_in_directory(&dir, "match", 120, "test-find-max-results", |batch| {
+            batch_sizes.push(batch.len())
+        })
+        .unwrap();
+
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(batch_sizes.iter().sum::<usize>(), 120);
+        assert_eq!(batch_sizes, vec![100, 20]);
+        // The walk stops in the first folder, without reading "sub"
+        assert!(scanned <= 151, "Scanned {} entries", scanned);
+    }
+
+    #[test]
+    fn test_symlink_cycles_are_read_once() {
+        let dir = create_test_dir("cmdr_find_symlink_cycle_test");
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::write(dir.join("a/target.txt"), "").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("a/loop")).unwrap();
+        std::os::unix::fs::symlink(dir.join("a"), dir.join("alias")).unwrap();
+
+        let (_, names) = find(&dir, "target", 100, "test-find-symlink-cycle");
+
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(names, vec!["target.txt"]);
+    }
+
+    #[test]
+    fn test_cancelled_search_stops() {
+        let dir = create_test_dir("cmdr_find_cancel_test");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        for i in 0..150 {
+            fs::write(dir.join(format!("file_{}.txt", i)), "").unwrap();
+        }
+        fs::write(dir.join("sub/file_deep.txt"), "").unwrap();
+
+        // Cancelling from the first batch stops the walk before the rest of the folder and the subfolder
+        let token = "test-find-cancel";
+        let mut names = Vec::new();
+        let scanned = find_in_directory(&dir, "file", 1000, token, |batch| {
+            names.extend(batch.iter().map(|e| e.name.clone()));
+            assert!(cancel_find(token));
+        })
+        .unwrap();
+
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(names.len(), FIND_RESULT_BATCH_SIZE);
+        assert!(!names.contains(&"file_deep.txt".to_string()));
+        assert!(scanned < 151, "Scanned {} entries", scanned);
+        assert!(!cancel_find(token), "Finished searches can't be cancelled");
+    }
+
+    /// Runs a content search and returns the number of files searched and all matches.
+    fn search(root: &Path, pattern: &str, is_regex: bool, max_file_size: u64) -> (usize, Vec<ContentMatch>) {
+        let mut matches = Vec::new();
+        let searched = search_file_contents(root, pattern, is_regex, max_file_size, "test-search", |m| {
+            matches.push(m.clone())
+        })
+        .unwrap();
+        matches.sort_by(|a, b| (&a.path, a.line_number).cmp(&(&b.path, b.line_number)));
+        (searched, matches)
+    }
+
+    fn create_content_tree(name: &str) -> PathBuf {
+        let dir = create_test_dir(name);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("notes.txt"), "first line\nTODO: write tests\nlast line\n").unwrap();
+        fs::write(dir.join("sub/code.rs"), "fn main() {}\r\n// todo later\r\n").unwrap();
+        fs::write(dir.join("other.txt"), "nothing to see here\n").unwrap();
+        let mut binary = b"TODO in a binary\n".to_vec();
+        binary.extend_from_slice(&[0, 1, 2, 3]);
+        fs::write(dir.join("image.bin"), binary).unwrap();
+        fs::write(dir.join("big.log"), "TODO ".repeat(1000)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_content_search_finds_lines_in_text_files() {
+        let dir = create_content_tree("cmdr_search_contents_test");
+
+        let (searched, matches) = search(&dir, "todo", false, 1024);
+
+        let _ = fs::remove_dir_all(&dir);
+        let found: Vec<(String, usize, &str)> = matches
+            .iter()
+            .map(|m| {
+                let name = Path::new(&m.path).file_name().unwrap().to_string_lossy().to_string();
+                (name, m.line_number, m.line.as_str())
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("notes.txt".to_string(), 2, "TODO: write tests"),
+                ("code.rs".to_string(), 2, "// todo later"),
+            ]
+        );
+        // notes.txt, other.txt, image.bin (skipped after sniffing), and sub/code.rs. big.log is too large.
+        assert_eq!(searched, 4);
+    }
+
+    #[test]
+    fn test_content_search_skips_oversized_files() {
+        let dir = create_content_tree("cmdr_search_contents_size_test");
+
+        let (_, small_limit) = search(&dir, "TODO ", false, 1024);
+        let (_, large_limit) = search(&dir, "TODO ", false, DEFAULT_CONTENT_SEARCH_MAX_FILE_SIZE);
+
+        let _ = fs::remove_dir_all(&dir);
+        assert!(small_limit.is_empty());
+        assert_eq!(large_limit.len(), 1);
+        assert!(large_limit[0].path.ends_with("big.log"));
+    }
+
+    #[test]
+    fn test_content_search_with_regex() {
+        let dir = create_content_tree("cmdr_search_contents_regex_test");
+
+        let (_, matches) = search(&dir, r"^(first|last) line$", true, 1024);
+        let invalid = search_file_contents(&dir, "(unclosed", true, 1024, "test-search-invalid", |_| {});
+
+        let _ = fs::remove_dir_all(&dir);
+        let lines: Vec<usize> = matches.iter().map(|m| m.line_number).collect();
+        assert_eq!(lines, vec![1, 3]);
+        assert_eq!(invalid.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_missing_root_is_an_error() {
+        let result = find_in_directory(
+            Path::new("/nonexistent/cmdr/find"),
+            "a",
+            10,
+            "test-find-missing",
+            |_| {},
+        );
+        assert!(result.is_err());
+    }
+}
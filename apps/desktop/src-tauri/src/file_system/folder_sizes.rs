@@ -0,0 +1,216 @@
+//! Background folder size computation for listings, like Finder's "Calculate all sizes".
+//!
+//! Each listing gets a task with a few worker threads that walk its subdirectories one by one.
+//! Folders in the range the frontend last fetched go first, so visible rows fill in before the rest.
+//!
+//! Single folders can also be computed on demand with `compute_directory_size`, which reports progress
+//! and is cancelled by a caller-chosen token.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Worker threads per listing. Walking trees is I/O-bound, more threads mostly add disk contention.
+const FOLDER_SIZE_WORKERS: usize = 2;
+
+/// How often `compute_directory_size` reports the size so far.
+const DIRECTORY_SIZE_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Cancellation flags of running `compute_directory_size` calls, by token.
+static DIRECTORY_SIZE_TOKENS: LazyLock<RwLock<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Running folder size tasks, by listing ID.
+static FOLDER_SIZE_TASKS: LazyLock<RwLock<HashMap<String, Arc<FolderSizeTask>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// State shared by the workers of one listing.
+struct FolderSizeTask {
+    cancelled: AtomicBool,
+    /// Folders still to compute, in listing order
+    pending: Mutex<Vec<PathBuf>>,
+    /// Folders in the range the frontend last fetched
+    visible: RwLock<HashSet<PathBuf>>,
+}
+
+impl FolderSizeTask {
+    /// Takes the next folder to compute, preferring visible ones.
+    fn next_dir(&self) -> Option<PathBuf> {
+        let mut pending = self.pending.lock().ok()?;
+        if pending.is_empty() {
+            return None;
+        }
+        let index = self
+            .visible
+            .read()
+            .ok()
+            .and_then(|visible| pending.iter().position(|p| visible.contains(p)))
+            .unwrap_or(0);
+        Some(pending.remove(index))
+    }
+}
+
+/// Starts computing the sizes of `dirs` in the background, replacing any earlier task for the listing.
+///
+/// # Arguments
+/// * `listing_id` - Listing the folders belong to, used to cancel and prioritize
+/// * `dirs` - Folders to compute, in listing order
+/// * `on_size` - Called from a worker thread with each folder and its size
+pub fn start_folder_sizes<F>(listing_id: &str, dirs: Vec<PathBuf>, on_size: F)
+where
+    F: Fn(&Path, u64) + Send + Sync + 'static,
+{
+    cancel_folder_sizes(listing_id);
+    if dirs.is_empty() {
+        return;
+    }
+
+    let worker_count = dirs.len().min(FOLDER_SIZE_WORKERS);
+    let task = Arc::new(FolderSizeTask {
+        cancelled: AtomicBool::new(false),
+        pending: Mutex::new(dirs),
+        visible: RwLock::new(HashSet::new()),
+    });
+    if let Ok(mut tasks) = FOLDER_SIZE_TASKS.write() {
+        tasks.insert(listing_id.to_string(), task.clone());
+    }
+
+    let on_size = Arc::new(on_size);
+    for _ in 0..worker_count {
+        let task = task.clone();
+        let on_size = on_size.clone();
+        std::thread::spawn(move || {
+            while let Some(dir) = task.next_dir() {
+                if let Some(size) = directory_size(&dir, &task.cancelled) {
+                    on_size(&dir, size);
+                }
+            }
+        });
+    }
+}
+
+/// Tells the task which folders are currently visible, so they're computed first.
+pub fn set_visible_folders(listing_id: &str, dirs: impl IntoIterator<Item = PathBuf>) {
+    let Some(task) = FOLDER_SIZE_TASKS.read().ok().and_then(|t| t.get(listing_id).cloned()) else {
+        return;
+    };
+    if let Ok(mut visible) = task.visible.write() {
+        *visible = dirs.into_iter().collect();
+    }
+}
+
+/// Returns true if a folder size task is running (or finished but not yet cancelled) for the listing.
+pub fn has_folder_size_task(listing_id: &str) -> bool {
+    FOLDER_SIZE_TASKS
+        .read()
+        .map(|tasks| tasks.contains_key(listing_id))
+        .unwrap_or(false)
+}
+
+/// Stops the folder size task of a listing. Folders being walked are abandoned without a result.
+pub fn cancel_folder_sizes(listing_id: &str) {
+    let task = FOLDER_SIZE_TASKS.write().ok().and_then(|mut t| t.remove(listing_id));
+    if let Some(task) = task {
+        task.cancelled.store(true, Ordering::Relaxed);
+        if let Ok(mut pending) = task.pending.lock() {
+            pending.clear();
+        }
+    }
+}
+
+/// Computes the total size of a directory tree, reporting the size so far every
+/// `DIRECTORY_SIZE_PROGRESS_INTERVAL`. Cancel it by calling `cancel_directory_size` with the same token.
+///
+/// # Arguments
+/// * `path` - The directory to compute
+/// * `token` - Chosen by the caller to cancel this computation. Should be unique among running computations.
+/// * `on_progress` - Called with the size counted so far
+///
+/// # Returns
+/// The size in bytes, or None if cancelled.
+pub fn compute_directory_size<F>(path: &Path, token: &str, on_progress: F) -> Option<u64>
+where
+    F: FnMut(u64),
+{
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Ok(mut tokens) = DIRECTORY_SIZE_TOKENS.write() {
+        tokens.insert(token.to_string(), cancelled.clone());
+    }
+    let size = directory_size_with_progress(path, &cancelled, DIRECTORY_SIZE_PROGRESS_INTERVAL, on_progress);
+    if let Ok(mut tokens) = DIRECTORY_SIZE_TOKENS.write() {
+        tokens.remove(token);
+    }
+    size
+}
+
+/// Cancels a running `compute_directory_size`.
+///
+/// # Returns
+/// True if a computation with the token was running.
+pub fn cancel_directory_size(token: &str) -> bool {
+    let Ok(tokens) = DIRECTORY_SIZE_TOKENS.read() else {
+        return false;
+    };
+    match tokens.get(token) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Computes the total size of the files in a directory tree.
+///
+/// Symlinks count with their own size and are never followed. Hardlinked files count once.
+/// Unreadable subdirectories are skipped.
+///
+/// # Returns
+/// The size in bytes, or None if cancelled.
+pub fn directory_size(path: &Path, cancelled: &AtomicBool) -> Option<u64> {
+    directory_size_with_progress(path, cancelled, Duration::MAX, |_| {})
+}
+
+/// Like `directory_size`, calling `on_progress` with the size so far whenever `interval` has passed.
+pub fn directory_size_with_progress<F>(
+    path: &Path,
+    cancelled: &AtomicBool,
+    interval: Duration,
+    mut on_progress: F,
+) -> Option<u64>
+where
+    F: FnMut(u64),
+{
+    let mut total = 0;
+    // (device, inode) of files with more than one link, so each is counted once
+    let mut seen_hardlinks = HashSet::new();
+    let mut last_report = Instant::now();
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                stack.push(entry.path());
+            } else if metadata.nlink() <= 1 || seen_hardlinks.insert((metadata.dev(), metadata.ino())) {
+                total += metadata.len();
+            }
+            if last_report.elapsed() >= interval {
+                on_progress(total);
+                last_report = Instant::now();
+            }
+        }
+    }
+    Some(total)
+}
@@ -0,0 +1,190 @@
+//! Tests for background folder size computation.
+
+use super::folder_sizes::{
+    cancel_directory_size, compute_directory_size, directory_size, directory_size_with_progress, has_folder_size_task,
+};
+use super::operations::{
+    CachedListing, FileEntry, LISTING_CACHE, SortColumn, SortOrder, get_file_range, list_directory_end,
+    start_folder_size_computation,
+};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Creates a fresh temp directory for a test.
+fn create_test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_directory_size_sums_nested_files() {
+    let dir = create_test_dir("cmdr_folder_sizes_sum_test");
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    fs::write(dir.join("one.txt"), vec![0u8; 100]).unwrap();
+    fs::write(dir.join("a/two.txt"), vec![0u8; 200]).unwrap();
+    fs::write(dir.join("a/b/three.txt"), vec![0u8; 300]).unwrap();
+
+    let size = directory_size(&dir, &AtomicBool::new(false));
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(size, Some(600));
+}
+
+#[test]
+fn test_directory_size_does_not_follow_symlinks() {
+    let dir = create_test_dir("cmdr_folder_sizes_symlink_test");
+    let outside = create_test_dir("cmdr_folder_sizes_symlink_outside_test");
+    fs::write(outside.join("big.bin"), vec![0u8; 10_000]).unwrap();
+    fs::write(dir.join("small.txt"), vec![0u8; 10]).unwrap();
+    std::os::unix::fs::symlink(&outside, dir.join("link")).unwrap();
+
+    let size = directory_size(&dir, &AtomicBool::new(false)).unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_dir_all(&outside);
+    assert!(size < 10_000, "Symlinked folder should not be counted, got {}", size);
+}
+
+#[test]
+fn test_directory_size_returns_none_when_cancelled() {
+    let dir = create_test_dir("cmdr_folder_sizes_cancelled_test");
+    fs::write(dir.join("file.txt"), "content").unwrap();
+
+    let size = directory_size(&dir, &AtomicBool::new(true));
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(size, None);
+}
+
+#[test]
+fn test_directory_size_counts_hardlinks_once() {
+    let dir = create_test_dir("cmdr_folder_sizes_hardlink_test");
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("original.bin"), vec![0u8; 500]).unwrap();
+    fs::hard_link(dir.join("original.bin"), dir.join("sub/link.bin")).unwrap();
+    fs::write(dir.join("other.bin"), vec![0u8; 20]).unwrap();
+
+    let size = directory_size(&dir, &AtomicBool::new(false));
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(size, Some(520));
+}
+
+#[test]
+fn test_directory_size_reports_progress() {
+    let dir = create_test_dir("cmdr_folder_sizes_progress_test");
+    fs::create_dir_all(dir.join("a")).unwrap();
+    fs::write(dir.join("one.txt"), vec![0u8; 100]).unwrap();
+    fs::write(dir.join("a/two.txt"), vec![0u8; 200]).unwrap();
+
+    let mut reported = Vec::new();
+    let size = directory_size_with_progress(&dir, &AtomicBool::new(false), Duration::ZERO, |size| {
+        reported.push(size)
+    });
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(size, Some(300));
+    assert!(
+        reported.windows(2).all(|w| w[0] <= w[1]),
+        "Progress went backwards: {:?}",
+        reported
+    );
+    assert_eq!(reported.last(), Some(&300));
+}
+
+#[test]
+fn test_directory_size_stops_when_cancelled_midway() {
+    let dir = create_test_dir("cmdr_folder_sizes_cancel_midway_test");
+    for i in 0..20 {
+        fs::write(dir.join(format!("file{}.txt", i)), "content").unwrap();
+    }
+
+    let cancelled = AtomicBool::new(false);
+    let mut progress_calls = 0;
+    let size = directory_size_with_progress(&dir, &cancelled, Duration::ZERO, |_| {
+        progress_calls += 1;
+        cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
+    });
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(size, None);
+    assert_eq!(progress_calls, 1, "The walk should stop right after being cancelled");
+}
+
+#[test]
+fn test_compute_directory_size_unregisters_token() {
+    let dir = create_test_dir("cmdr_folder_sizes_token_test");
+    fs::write(dir.join("file.txt"), vec![0u8; 64]).unwrap();
+
+    let size = compute_directory_size(&dir, "test-token", |_| {});
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(size, Some(64));
+    assert!(
+        !cancel_directory_size("test-token"),
+        "Finished computations can't be cancelled"
+    );
+}
+
+#[test]
+fn test_folder_sizes_update_cached_entries() {
+    let dir = create_test_dir("cmdr_folder_sizes_cache_test");
+    let sub_a = dir.join("sub_a");
+    let sub_b = dir.join("sub_b");
+    fs::create_dir_all(&sub_a).unwrap();
+    fs::create_dir_all(&sub_b).unwrap();
+    fs::write(sub_a.join("file.bin"), vec![0u8; 1234]).unwrap();
+    fs::write(sub_b.join("file.bin"), vec![0u8; 42]).unwrap();
+
+    let listing_id = "test-folder-sizes-cache";
+    LISTING_CACHE.write().unwrap().insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "root".to_string(),
+            path: dir.clone(),
+            entries: vec![
+                FileEntry::test_entry(&sub_a.to_string_lossy(), true),
+                FileEntry::test_entry(&sub_b.to_string_lossy(), true),
+            ],
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Default::default(),
+            last_accessed: super::operations::AccessTime::now(),
+        },
+    );
+
+    let (sender, receiver) = mpsc::channel();
+    let sender = std::sync::Mutex::new(sender);
+    start_folder_size_computation(listing_id, move |path, size| {
+        let _ = sender.lock().unwrap().send((path.to_path_buf(), size));
+    })
+    .unwrap();
+    let mut events: Vec<(PathBuf, u64)> = (0..2)
+        .map(|_| receiver.recv_timeout(Duration::from_secs(5)).unwrap())
+        .collect();
+    events.sort();
+    let entries = get_file_range(listing_id, 0, 10, true).unwrap();
+    let had_task = has_folder_size_task(listing_id);
+
+    list_directory_end(listing_id);
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(events, vec![(sub_a, 1234), (sub_b, 42)]);
+    assert_eq!(entries[0].size, Some(1234));
+    assert_eq!(entries[1].size, Some(42));
+    assert!(had_task);
+    assert!(
+        !has_folder_size_task(listing_id),
+        "Ending the listing should cancel the task"
+    );
+}
+
+#[test]
+fn test_folder_sizes_unknown_listing() {
+    assert!(start_folder_size_computation("no-such-listing", |_, _| {}).is_err());
+}
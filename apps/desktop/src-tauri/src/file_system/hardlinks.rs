@@ -0,0 +1,108 @@
+//! Finding hardlinks: other names of the same file, like in Time Machine local snapshots or deduped storage.
+//!
+//! Hardlinks share a device and an inode, and can't cross devices, so the search stays on the device of the
+//! file. It never follows symlinks, which also keeps it from looping.
+
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+/// Finds the other paths under `root` that are hardlinks to the same file as `path`.
+///
+/// Unreadable directories are skipped. Directories can't be hardlinked, so for them the result is empty.
+///
+/// # Arguments
+/// * `root` - Directory to search in, recursively
+/// * `path` - The file to find hardlinks of. Not included in the result.
+///
+/// # Returns
+/// The other paths, in no particular order, or an error if `path` can't be read.
+pub fn find_hardlinks(root: &Path, path: &Path) -> io::Result<Vec<PathBuf>> {
+    let target = fs::symlink_metadata(path)?;
+    if target.is_dir() || target.nlink() < 2 {
+        return Ok(Vec::new());
+    }
+    let (device, inode) = (target.dev(), target.ino());
+    let mut remaining = target.nlink() - 1;
+
+    let mut found = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            // Hardlinks can't cross devices, so other devices mounted inside `root` can be skipped
+            if metadata.dev() != device {
+                continue;
+            }
+            if metadata.is_dir() {
+                stack.push(entry.path());
+            } else if metadata.ino() == inode && entry.path() != path {
+                found.push(entry.path());
+                remaining -= 1;
+                // All other links found, no need to walk the rest
+                if remaining == 0 {
+                    return Ok(found);
+                }
+            }
+        }
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_finds_hardlinked_pair() {
+        let dir = create_test_dir("cmdr_hardlinks_pair_test");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        let original = dir.join("original.txt");
+        let link = dir.join("nested/link.txt");
+        fs::write(&original, "shared content").unwrap();
+        fs::hard_link(&original, &link).unwrap();
+        fs::write(dir.join("unrelated.txt"), "shared content").unwrap();
+
+        let from_original = find_hardlinks(&dir, &original).unwrap();
+        let from_link = find_hardlinks(&dir, &link).unwrap();
+        let link_count = fs::symlink_metadata(&original).unwrap().nlink();
+
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(from_original, vec![link.clone()]);
+        assert_eq!(from_link, vec![original]);
+        assert_eq!(link_count, 2);
+    }
+
+    #[test]
+    fn test_file_without_hardlinks_has_none() {
+        let dir = create_test_dir("cmdr_hardlinks_none_test");
+        let file = dir.join("alone.txt");
+        fs::write(&file, "content").unwrap();
+        // A symlink points to the file but isn't a hardlink
+        std::os::unix::fs::symlink(&file, dir.join("symlink.txt")).unwrap();
+
+        let found = find_hardlinks(&dir, &file).unwrap();
+
+        let _ = fs::remove_dir_all(&dir);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_missing_path_is_an_error() {
+        let dir = std::env::temp_dir();
+        assert!(find_hardlinks(&dir, &dir.join("cmdr_hardlinks_missing_test")).is_err());
+    }
+}
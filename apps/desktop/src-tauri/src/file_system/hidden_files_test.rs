@@ -21,13 +21,17 @@ fn make_entry(name: &str, is_dir: bool) -> FileEntry {
         size: if is_dir { None } else { Some(100) },
         modified_at: Some(1_700_000_000),
         created_at: Some(1_700_000_000),
+        changed_at: Some(1_700_000_000),
         added_at: None,
         opened_at: None,
         permissions: if is_dir { 0o755 } else { 0o644 },
         owner: "testuser".to_string(),
         group: "staff".to_string(),
         icon_id: if is_dir { "dir".to_string() } else { "file".to_string() },
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }
 }
 
@@ -67,6 +71,9 @@ fn test_get_total_count_with_hidden_includes_all() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -97,6 +104,9 @@ fn test_get_total_count_without_hidden_excludes_dot_files() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -131,6 +141,9 @@ fn test_get_file_range_with_hidden_returns_all() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -166,6 +179,9 @@ fn test_get_file_range_without_hidden_excludes_dot_files() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -205,6 +221,9 @@ fn test_get_file_range_pagination_respects_hidden_filter() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -251,6 +270,9 @@ fn test_find_file_index_hidden_file_with_hidden_enabled() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -280,6 +302,9 @@ fn test_find_file_index_hidden_file_with_hidden_disabled() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -309,6 +334,9 @@ fn test_find_file_index_visible_file_index_changes_with_hidden_setting() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -355,6 +383,9 @@ fn test_get_file_at_index_0_with_hidden_enabled() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -390,6 +421,9 @@ fn test_get_file_at_index_0_with_hidden_disabled() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -434,6 +468,9 @@ fn test_directory_with_only_hidden_files() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -468,6 +505,9 @@ fn test_directory_with_no_hidden_files() {
                 entries,
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
+                case_sensitive: false,
+                ignore_patterns: Default::default(),
+                last_accessed: super::operations::AccessTime::now(),
             },
         );
     }
@@ -0,0 +1,119 @@
+//! User-defined name patterns to leave out of listings, like `.DS_Store`, `*.tmp`, or `__pycache__/`.
+//!
+//! Patterns are globs matched against the file name only (not the path): `*` matches any run of
+//! characters, `?` matches a single character. A trailing `/` makes a pattern match directories only.
+//! They're applied on top of the hidden files toggle, so an ignored file stays hidden even with
+//! "show hidden files" on.
+//!
+//! Each listing captures the patterns that were active when it started, so the frontend's ranges,
+//! counts, and indexes stay consistent for the listing's lifetime, even if the setting changes meanwhile.
+
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// The patterns from the settings, applied to listings started from now on.
+static IGNORE_PATTERNS: LazyLock<RwLock<Arc<IgnorePatterns>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(IgnorePatterns::default())));
+
+/// A single parsed pattern.
+#[derive(Debug, Clone)]
+struct Pattern {
+    glob: Vec<char>,
+    directories_only: bool,
+}
+
+/// A compiled set of ignore patterns.
+#[derive(Debug, Clone, Default)]
+pub struct IgnorePatterns {
+    patterns: Vec<Pattern>,
+}
+
+impl IgnorePatterns {
+    /// Parses the given patterns. Blank ones are skipped.
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let directories_only = p.ends_with('/');
+                Pattern {
+                    glob: p.trim_end_matches('/').chars().collect(),
+                    directories_only,
+                }
+            })
+            .filter(|p| !p.glob.is_empty())
+            .collect();
+        Self { patterns }
+    }
+
+    /// Returns true if there are no patterns, so nothing is ever ignored.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns true if any pattern matches the entry.
+    pub fn matches(&self, name: &str, is_directory: bool) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let name: Vec<char> = name.chars().collect();
+        self.patterns
+            .iter()
+            .any(|p| (is_directory || !p.directories_only) && glob_matches(&p.glob, &name))
+    }
+}
+
+/// Returns true if the entry should be left out of listings according to `patterns`.
+///
+/// # Arguments
+/// * `name` - The file name (not the full path)
+/// * `is_directory` - Whether the entry is a directory, for patterns ending in `/`
+/// * `patterns` - The compiled patterns
+pub fn is_ignored(name: &str, is_directory: bool, patterns: &IgnorePatterns) -> bool {
+    patterns.matches(name, is_directory)
+}
+
+/// Replaces the patterns used for listings started from now on.
+pub fn set_ignore_patterns(patterns: &[String]) {
+    if let Ok(mut current) = IGNORE_PATTERNS.write() {
+        *current = Arc::new(IgnorePatterns::new(patterns));
+    }
+}
+
+/// Returns the patterns currently in effect, for a new listing to keep.
+pub fn current_ignore_patterns() -> Arc<IgnorePatterns> {
+    IGNORE_PATTERNS
+        .read()
+        .map(|current| current.clone())
+        .unwrap_or_default()
+}
+
+/// Matches `name` against a glob with `*` and `?` wildcards.
+///
+/// Iterative, backtracking only to the last `*`, so it's linear-ish even for names with many wildcards.
+fn glob_matches(glob: &[char], name: &[char]) -> bool {
+    let (mut g, mut n) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                last_star = Some((g, n));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match last_star {
+                // Let the last star swallow one more character and retry
+                Some((star_g, star_n)) => {
+                    g = star_g + 1;
+                    n = star_n + 1;
+                    last_star = Some((star_g, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,119 @@
+//! Tests for ignore patterns and how listings apply them.
+
+use super::ignore_patterns::{IgnorePatterns, is_ignored};
+use super::operations::{
+    CachedListing, FileEntry, LISTING_CACHE, SortColumn, SortOrder, find_file_index, get_file_at, get_file_range,
+    get_total_count, list_directory_end, resort_listing,
+};
+use std::sync::Arc;
+
+fn patterns(globs: &[&str]) -> IgnorePatterns {
+    IgnorePatterns::new(&globs.iter().map(|g| g.to_string()).collect::<Vec<_>>())
+}
+
+#[test]
+fn test_exact_name_pattern() {
+    let ignore = patterns(&[".DS_Store"]);
+
+    assert!(is_ignored(".DS_Store", false, &ignore));
+    assert!(!is_ignored(".DS_Store_backup", false, &ignore));
+    assert!(!is_ignored("DS_Store", false, &ignore));
+}
+
+#[test]
+fn test_wildcard_patterns() {
+    let ignore = patterns(&["*.tmp", "backup-??.zip"]);
+
+    assert!(is_ignored("file.tmp", false, &ignore));
+    assert!(is_ignored(".tmp", false, &ignore));
+    assert!(is_ignored("archive.tar.tmp", false, &ignore));
+    assert!(!is_ignored("file.tmp.txt", false, &ignore));
+    assert!(is_ignored("backup-01.zip", false, &ignore));
+    assert!(!is_ignored("backup-1.zip", false, &ignore));
+}
+
+#[test]
+fn test_directory_patterns_match_directories_only() {
+    let ignore = patterns(&["__pycache__/", "node_modules"]);
+
+    assert!(is_ignored("__pycache__", true, &ignore));
+    assert!(!is_ignored("__pycache__", false, &ignore));
+    // Without a trailing slash, a pattern matches both
+    assert!(is_ignored("node_modules", true, &ignore));
+    assert!(is_ignored("node_modules", false, &ignore));
+}
+
+#[test]
+fn test_empty_and_blank_patterns_ignore_nothing() {
+    let ignore = patterns(&["", "  ", "/"]);
+
+    assert!(ignore.is_empty());
+    assert!(!is_ignored("anything", false, &ignore));
+    assert!(!is_ignored("", true, &ignore));
+}
+
+#[test]
+fn test_counts_stay_consistent_with_ranges() {
+    let listing_id = "test-ignore-patterns-consistency";
+    // Sorted by name, directories first
+    let names = [
+        (".git", true),
+        ("__pycache__", true),
+        ("src", true),
+        (".DS_Store", false),
+        ("a.tmp", false),
+        ("b.txt", false),
+        ("z.tmp", false),
+    ];
+    LISTING_CACHE.write().unwrap().insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "test".to_string(),
+            path: std::path::PathBuf::from("/test"),
+            entries: names
+                .iter()
+                .map(|(n, d)| FileEntry::test_entry(&format!("/test/{}", n), *d))
+                .collect(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Arc::new(patterns(&[".DS_Store", "*.tmp", "__pycache__/"])),
+            last_accessed: super::operations::AccessTime::now(),
+        },
+    );
+
+    let with_hidden_count = get_total_count(listing_id, true).unwrap();
+    let with_hidden: Vec<String> = get_file_range(listing_id, 0, 100, true)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    let without_hidden_count = get_total_count(listing_id, false).unwrap();
+    let without_hidden: Vec<String> = get_file_range(listing_id, 0, 100, false)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    let second_page: Vec<String> = get_file_range(listing_id, 1, 1, true)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    let src_index = find_file_index(listing_id, "src", true).unwrap();
+    let tmp_index = find_file_index(listing_id, "a.tmp", true).unwrap();
+    let at_index = get_file_at(listing_id, 2, true).unwrap().map(|e| e.name);
+    let resorted = resort_listing(listing_id, SortColumn::Name, SortOrder::Descending, Some("b.txt"), true).unwrap();
+
+    list_directory_end(listing_id);
+    // Ignored entries stay out even with hidden files shown
+    assert_eq!(with_hidden, vec![".git", "src", "b.txt"]);
+    assert_eq!(with_hidden_count, with_hidden.len());
+    assert_eq!(without_hidden, vec!["src", "b.txt"]);
+    assert_eq!(without_hidden_count, without_hidden.len());
+    assert_eq!(second_page, vec!["src"]);
+    assert_eq!(src_index, Some(1));
+    assert_eq!(tmp_index, None);
+    assert_eq!(at_index, Some("b.txt".to_string()));
+    // Descending: src, .git, then b.txt
+    assert_eq!(resorted.new_cursor_index, Some(2));
+}
@@ -17,13 +17,17 @@ fn create_test_entry(name: &str, is_dir: bool) -> FileEntry {
         size: if is_dir { None } else { Some(1024) },
         modified_at: Some(1_640_000_000),
         created_at: Some(1_639_000_000),
+        changed_at: Some(1_639_000_000),
         added_at: None,
         opened_at: None,
         permissions: if is_dir { 0o755 } else { 0o644 },
         owner: "testuser".to_string(),
         group: "staff".to_string(),
         icon_id: if is_dir { "dir".to_string() } else { "file".to_string() },
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }
 }
 
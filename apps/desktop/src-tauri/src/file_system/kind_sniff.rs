@@ -0,0 +1,137 @@
+//! Content-based type detection for files without an extension.
+//!
+//! Extension-based icon IDs give every extensionless file the generic "file" icon.
+//! This module reads the first few bytes of such files and matches them against a small
+//! table of magic numbers, so scripts, executables, images, and archives get a fitting icon.
+//! It does file I/O, so it's only run lazily on the entries the frontend actually requests,
+//! and each file is only read again when its modification time changes.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+/// How many bytes to read from the start of the file. Enough for every signature below.
+const SNIFF_BYTES: usize = 16;
+
+/// How many files the sniffed kinds are remembered for. The cache starts over when it's full.
+const SNIFF_CACHE_LIMIT: usize = 10_000;
+
+/// Kinds sniffed so far (path -> modification time when sniffed, kind).
+type SniffCache = RwLock<HashMap<PathBuf, (Option<u64>, Option<SniffedKind>)>>;
+static SNIFF_CACHE: LazyLock<SniffCache> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// A file kind detected from the file's content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SniffedKind {
+    /// ELF or Mach-O binary
+    Executable,
+    /// Text file starting with a shebang (`#!`)
+    Script,
+    /// PNG, JPEG, GIF, or TIFF image
+    Image,
+    /// ZIP, gzip, bzip2, xz, or 7z archive
+    Archive,
+    /// PDF document
+    Pdf,
+    /// Finder alias (bookmark data)
+    Alias,
+}
+
+impl SniffedKind {
+    /// Returns the icon ID for this kind, for example "kind:script".
+    /// Aliases get "alias-file", which the frontend treats like "symlink-file".
+    pub fn icon_id(&self) -> String {
+        let name = match self {
+            Self::Alias => return "alias-file".to_string(),
+            Self::Executable => "executable",
+            Self::Script => "script",
+            Self::Image => "image",
+            Self::Archive => "archive",
+            Self::Pdf => "pdf",
+        };
+        format!("kind:{}", name)
+    }
+}
+
+/// Magic number table: (signature, kind). Checked in order, first match wins.
+const SIGNATURES: &[(&[u8], SniffedKind)] = &[
+    // Executables
+    (b"\x7fELF", SniffedKind::Executable),
+    (b"\xfe\xed\xfa\xce", SniffedKind::Executable), // Mach-O 32-bit
+    (b"\xfe\xed\xfa\xcf", SniffedKind::Executable), // Mach-O 64-bit
+    (b"\xce\xfa\xed\xfe", SniffedKind::Executable), // Mach-O 32-bit, little-endian
+    (b"\xcf\xfa\xed\xfe", SniffedKind::Executable), // Mach-O 64-bit, little-endian
+    (b"\xca\xfe\xba\xbe", SniffedKind::Executable), // Mach-O universal binary
+    // Scripts
+    (b"#!", SniffedKind::Script),
+    // Images
+    (b"\x89PNG\r\n\x1a\n", SniffedKind::Image),
+    (b"\xff\xd8\xff", SniffedKind::Image),
+    (b"GIF87a", SniffedKind::Image),
+    (b"GIF89a", SniffedKind::Image),
+    (b"II*\x00", SniffedKind::Image),
+    (b"MM\x00*", SniffedKind::Image),
+    // Archives
+    (b"PK\x03\x04", SniffedKind::Archive),
+    (b"\x1f\x8b", SniffedKind::Archive),
+    (b"BZh", SniffedKind::Archive),
+    (b"\xfd7zXZ\x00", SniffedKind::Archive),
+    (b"7z\xbc\xaf\x27\x1c", SniffedKind::Archive),
+    // Documents
+    (b"%PDF-", SniffedKind::Pdf),
+    // Finder aliases (bookmark data)
+    (b"book\x00\x00\x00\x00mark", SniffedKind::Alias),
+];
+
+/// Detects the kind of a file from its first bytes.
+///
+/// # Returns
+/// The detected kind, or `None` if the file can't be read or matches no known signature.
+pub fn sniff_kind(path: &Path) -> Option<SniffedKind> {
+    let mut file = File::open(path).ok()?;
+    let mut buffer = [0u8; SNIFF_BYTES];
+    let mut len = 0;
+    // A single read() may return fewer bytes than available, so loop until full or EOF
+    while len < SNIFF_BYTES {
+        match file.read(&mut buffer[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(_) => return None,
+        }
+    }
+    sniff_bytes(&buffer[..len])
+}
+
+/// Detects the kind of a file like `sniff_kind`, but reuses the kind sniffed before if the file's
+/// modification time is still the same, so scrolling through a listing doesn't read the files again.
+///
+/// # Arguments
+/// * `path` - The file to sniff
+/// * `modified_at` - The file's modification time, like `FileEntry::modified_at`
+pub fn sniff_kind_cached(path: &Path, modified_at: Option<u64>) -> Option<SniffedKind> {
+    if let Ok(cache) = SNIFF_CACHE.read()
+        && let Some((sniffed_at, kind)) = cache.get(path)
+        && *sniffed_at == modified_at
+    {
+        return *kind;
+    }
+
+    let kind = sniff_kind(path);
+    if let Ok(mut cache) = SNIFF_CACHE.write() {
+        if cache.len() >= SNIFF_CACHE_LIMIT {
+            cache.clear();
+        }
+        cache.insert(path.to_path_buf(), (modified_at, kind));
+    }
+    kind
+}
+
+/// Matches the given leading bytes against the signature table.
+pub fn sniff_bytes(bytes: &[u8]) -> Option<SniffedKind> {
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+        .map(|(_, kind)| *kind)
+}
@@ -0,0 +1,89 @@
+//! Tests for content-based type sniffing.
+
+use super::kind_sniff::{SniffedKind, sniff_bytes, sniff_kind, sniff_kind_cached};
+use std::fs;
+
+#[test]
+fn test_sniff_shebang_script_without_extension() {
+    let temp_dir = std::env::temp_dir().join("cmdr_kind_sniff_script_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let script_path = temp_dir.join("deploy");
+    fs::write(&script_path, "#!/bin/sh\necho hello\n").unwrap();
+
+    let kind = sniff_kind(&script_path);
+
+    assert_eq!(kind, Some(SniffedKind::Script));
+    assert_eq!(kind.unwrap().icon_id(), "kind:script");
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_sniff_png_without_extension() {
+    let temp_dir = std::env::temp_dir().join("cmdr_kind_sniff_png_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let image_path = temp_dir.join("screenshot");
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    png.extend_from_slice(b"\x00\x00\x00\x0dIHDR");
+    fs::write(&image_path, png).unwrap();
+
+    let kind = sniff_kind(&image_path);
+
+    assert_eq!(kind, Some(SniffedKind::Image));
+    assert_eq!(kind.unwrap().icon_id(), "kind:image");
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_sniff_plain_text_is_unknown() {
+    assert_eq!(sniff_bytes(b"just some notes\n"), None);
+}
+
+#[test]
+fn test_sniff_empty_file_is_unknown() {
+    assert_eq!(sniff_bytes(b""), None);
+}
+
+#[test]
+fn test_sniff_executables() {
+    assert_eq!(sniff_bytes(b"\x7fELF\x02\x01\x01"), Some(SniffedKind::Executable));
+    assert_eq!(sniff_bytes(b"\xcf\xfa\xed\xfe\x07\x00"), Some(SniffedKind::Executable));
+}
+
+#[test]
+fn test_sniff_alias() {
+    let kind = sniff_bytes(b"book\x00\x00\x00\x00mark\x00\x00\x00\x00");
+    assert_eq!(kind, Some(SniffedKind::Alias));
+    assert_eq!(kind.unwrap().icon_id(), "alias-file");
+}
+
+#[test]
+fn test_sniff_nonexistent_file() {
+    assert_eq!(sniff_kind(std::path::Path::new("/nonexistent/cmdr/file")), None);
+}
+
+#[test]
+fn test_sniffed_kind_is_reused_until_modified() {
+    let temp_dir = std::env::temp_dir().join("cmdr_kind_sniff_cached_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let path = temp_dir.join("tool");
+    fs::write(&path, "#!/bin/sh\n").unwrap();
+
+    let first = sniff_kind_cached(&path, Some(1000));
+    fs::write(&path, b"\x7fELF").unwrap();
+    let same_mtime = sniff_kind_cached(&path, Some(1000));
+    let new_mtime = sniff_kind_cached(&path, Some(2000));
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    assert_eq!(first, Some(SniffedKind::Script));
+    assert_eq!(
+        same_mtime,
+        Some(SniffedKind::Script),
+        "The file shouldn't be read again"
+    );
+    assert_eq!(new_mtime, Some(SniffedKind::Executable));
+}
@@ -0,0 +1,92 @@
+//! Tests for evicting idle listings from the listing cache.
+
+use super::operations::{
+    AccessTime, CachedListing, FileEntry, LISTING_CACHE, SortColumn, SortOrder, cache_stats, evict_idle_listings,
+    get_file_range, list_directory_end,
+};
+use super::volume::LocalPosixVolume;
+use super::watcher::{start_watching, watch_sequence};
+use std::time::{Duration, Instant};
+
+/// Inserts a listing that was last accessed `idle_for` ago.
+fn insert_listing(listing_id: &str, names: &[&str], idle_for: Duration) {
+    let last_accessed = AccessTime::at(Instant::now().checked_sub(idle_for).unwrap());
+    LISTING_CACHE.write().unwrap().insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "test".to_string(),
+            path: std::path::PathBuf::from("/"),
+            entries: names
+                .iter()
+                .map(|n| FileEntry::test_entry(&format!("/{}", n), false))
+                .collect(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Default::default(),
+            last_accessed,
+        },
+    );
+}
+
+// The TTLs are long compared to the test run, so listings of tests running in parallel are never evicted
+
+#[test]
+fn test_idle_listing_is_evicted() {
+    insert_listing("test-cache-idle", &["a.txt"], Duration::from_secs(120));
+    insert_listing("test-cache-fresh", &["b.txt"], Duration::ZERO);
+
+    let evicted = evict_idle_listings(Duration::from_secs(60));
+    let idle_range = get_file_range("test-cache-idle", 0, 10, true);
+    let fresh_range = get_file_range("test-cache-fresh", 0, 10, true);
+
+    list_directory_end("test-cache-fresh");
+    assert!(!evicted.contains(&"test-cache-fresh".to_string()));
+    assert!(idle_range.is_err(), "The idle listing should be evicted");
+    assert_eq!(fresh_range.unwrap().len(), 1);
+}
+
+#[test]
+fn test_fetching_entries_keeps_listing_alive() {
+    // Idle for less than the other test's TTL, so only this test's eviction could drop it
+    insert_listing("test-cache-touched", &["a.txt"], Duration::from_secs(30));
+
+    get_file_range("test-cache-touched", 0, 10, true).unwrap();
+    let evicted = evict_idle_listings(Duration::from_secs(20));
+
+    list_directory_end("test-cache-touched");
+    assert!(!evicted.contains(&"test-cache-touched".to_string()));
+}
+
+#[test]
+fn test_idle_watched_listing_is_evicted_with_its_watcher() {
+    let dir = std::env::temp_dir().join("cmdr_listing_cache_watched_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    insert_listing("test-cache-watched", &["a.txt"], Duration::from_secs(120));
+    start_watching("test-cache-watched", &LocalPosixVolume::new("Test", "/"), &dir).unwrap();
+    assert!(watch_sequence("test-cache-watched").is_some());
+
+    let evicted = evict_idle_listings(Duration::from_secs(60));
+    let range = get_file_range("test-cache-watched", 0, 10, true);
+    let sequence = watch_sequence("test-cache-watched");
+
+    list_directory_end("test-cache-watched");
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(evicted.contains(&"test-cache-watched".to_string()));
+    assert!(range.is_err(), "The idle listing should be evicted");
+    assert_eq!(sequence, None, "Evicting the listing should stop its watcher");
+}
+
+#[test]
+fn test_cache_stats_count_cached_entries() {
+    insert_listing("test-cache-stats", &["one.txt", "two.txt", "three.txt"], Duration::ZERO);
+
+    let stats = cache_stats();
+
+    list_directory_end("test-cache-stats");
+    // Other tests may have listings cached at the same time
+    assert!(stats.listing_count >= 1);
+    assert!(stats.entry_count >= 3);
+    assert!(stats.approximate_bytes >= 3 * std::mem::size_of::<FileEntry>());
+}
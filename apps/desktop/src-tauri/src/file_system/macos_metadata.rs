@@ -3,12 +3,28 @@
 //! Provides access to metadata not available through standard `std::fs`:
 //! - `added_at`: When the file was added to its current directory (moved/copied)
 //! - `opened_at`: When the file was last opened
+//! - `kind`: The type description Finder shows, like "JPEG image"
+//!
+//! Also reads extended attributes (xattrs), like `com.apple.quarantine` on downloads, and the Finder tags stored
+//! in one of them.
 
+use core_foundation::base::TCFType;
+use core_foundation::string::CFString;
+use core_services::UTTypeCopyDescription;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
 use std::path::Path;
+use std::sync::{LazyLock, RwLock};
 
 use objc2::rc::Retained;
 use objc2_foundation::{NSDate, NSString, NSURL};
 
+/// Kind descriptions by UTI. There are only so many file types, so it's never cleared.
+static KIND_CACHE: LazyLock<RwLock<HashMap<String, Option<String>>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
 /// Extended macOS metadata for a file.
 pub struct MacOSMetadata {
     /// Unix timestamp: when the file was added to its current directory
@@ -81,3 +97,216 @@ pub fn get_macos_metadata(path: &Path) -> MacOSMetadata {
 
     MacOSMetadata { added_at, opened_at }
 }
+
+/// Returns the kind Finder shows for a path, like "JPEG image" or "Folder", in the user's language.
+///
+/// Goes by the extension, so files without one have no kind. Doesn't follow symlinks.
+pub fn get_kind(path: &Path) -> Option<String> {
+    let is_directory = std::fs::symlink_metadata(path).is_ok_and(|m| m.is_dir());
+    if is_directory {
+        return kind_for_uti(&CFString::new("public.folder"));
+    }
+    kind_for_extension(&path.extension()?.to_string_lossy())
+}
+
+/// Returns the localized description of the type files with the given extension have, like "JPEG image" for "jpg".
+pub fn kind_for_extension(extension: &str) -> Option<String> {
+    kind_for_uti(&crate::macos_icons::get_uti_for_extension(extension)?)
+}
+
+fn kind_for_uti(uti: &CFString) -> Option<String> {
+    let key = uti.to_string();
+    if let Some(kind) = KIND_CACHE.read().ok().and_then(|cache| cache.get(&key).cloned()) {
+        return kind;
+    }
+
+    let kind = unsafe {
+        let description_ref = UTTypeCopyDescription(uti.as_concrete_TypeRef());
+        if description_ref.is_null() {
+            None
+        } else {
+            Some(CFString::wrap_under_create_rule(description_ref).to_string())
+        }
+    };
+    if let Ok(mut cache) = KIND_CACHE.write() {
+        cache.insert(key, kind.clone());
+    }
+    kind
+}
+
+/// Lists the names of a file's extended attributes. Follows symlinks.
+pub fn list_xattrs(path: &Path) -> io::Result<Vec<String>> {
+    let c_path = to_c_string(path.as_os_str().as_bytes())?;
+    let names =
+        read_sized(|buffer, size| unsafe { libc::listxattr(c_path.as_ptr(), buffer as *mut libc::c_char, size, 0) })?;
+    // The names come as one buffer, each ended by a NUL
+    Ok(names
+        .split(|&b| b == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect())
+}
+
+/// Reads an extended attribute of a file. Follows symlinks.
+///
+/// # Returns
+/// The value, or None if the file doesn't have the attribute.
+pub fn get_xattr(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+    let c_path = to_c_string(path.as_os_str().as_bytes())?;
+    let c_name = to_c_string(name.as_bytes())?;
+    let result = read_sized(|buffer, size| unsafe {
+        libc::getxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            buffer as *mut libc::c_void,
+            size,
+            0,
+            0,
+        )
+    });
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.raw_os_error() == Some(libc::ENOATTR) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// The xattr Finder keeps tags in, as a binary plist array of strings.
+const FINDER_TAGS_XATTR: &str = "com.apple.metadata:_kMDItemUserTags";
+
+/// A Finder tag, like "Important" in red.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinderTag {
+    pub name: String,
+    /// Finder's label color: 0 none, 1 gray, 2 green, 3 purple, 4 blue, 5 yellow, 6 red, 7 orange
+    pub color: u8,
+}
+
+/// Finder tags of one path, for `get_finder_tags_batch`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinderTags {
+    /// File path (key for merging)
+    pub path: String,
+    pub tags: Vec<FinderTag>,
+}
+
+/// Reads the Finder tags of a file. Empty if it has none, or they can't be read.
+pub fn get_finder_tags(path: &Path) -> Vec<FinderTag> {
+    match get_xattr(path, FINDER_TAGS_XATTR) {
+        Ok(Some(data)) => parse_finder_tags(&data),
+        _ => Vec::new(),
+    }
+}
+
+/// Reads the Finder tags of a batch of paths, after the listing is shown, so it can show colored dots.
+pub fn get_finder_tags_batch(paths: Vec<String>) -> Vec<FinderTags> {
+    paths
+        .into_iter()
+        .map(|path| {
+            let tags = get_finder_tags(Path::new(&path));
+            FinderTags { path, tags }
+        })
+        .collect()
+}
+
+/// Parses the value of the tags xattr. Each tag is a string of its name, then a newline and the color number if it
+/// has a color. Anything that doesn't fit is skipped.
+pub fn parse_finder_tags(data: &[u8]) -> Vec<FinderTag> {
+    let Ok(plist::Value::Array(items)) = plist::from_bytes::<plist::Value>(data) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| item.as_string())
+        .map(|tag| match tag.split_once('\n') {
+            Some((name, color)) => FinderTag {
+                name: name.to_string(),
+                color: color.parse().unwrap_or(0),
+            },
+            None => FinderTag {
+                name: tag.to_string(),
+                color: 0,
+            },
+        })
+        .collect()
+}
+
+/// Sets the Finder tags of a file, replacing the ones it had. An empty list removes the tags xattr. Follows
+/// symlinks.
+pub fn set_finder_tags(path: &Path, tags: &[FinderTag]) -> io::Result<()> {
+    if tags.is_empty() {
+        return remove_xattr(path, FINDER_TAGS_XATTR);
+    }
+    set_xattr(path, FINDER_TAGS_XATTR, &serialize_finder_tags(tags)?)
+}
+
+/// Builds the value of the tags xattr, the reverse of `parse_finder_tags`.
+pub fn serialize_finder_tags(tags: &[FinderTag]) -> io::Result<Vec<u8>> {
+    let items = tags
+        .iter()
+        .map(|tag| plist::Value::String(format!("{}\n{}", tag.name, tag.color)))
+        .collect();
+    let mut data = Vec::new();
+    plist::to_writer_binary(&mut data, &plist::Value::Array(items)).map_err(io::Error::other)?;
+    Ok(data)
+}
+
+/// Sets an extended attribute of a file, creating or replacing it. Follows symlinks.
+pub fn set_xattr(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+    let c_path = to_c_string(path.as_os_str().as_bytes())?;
+    let c_name = to_c_string(name.as_bytes())?;
+    let result = unsafe {
+        libc::setxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+            0,
+        )
+    };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Removes an extended attribute of a file. Not an error if the file doesn't have it. Follows symlinks.
+pub fn remove_xattr(path: &Path, name: &str) -> io::Result<()> {
+    let c_path = to_c_string(path.as_os_str().as_bytes())?;
+    let c_name = to_c_string(name.as_bytes())?;
+    if unsafe { libc::removexattr(c_path.as_ptr(), c_name.as_ptr(), 0) } != 0 {
+        let error = io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::ENOATTR) {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+fn to_c_string(bytes: &[u8]) -> io::Result<CString> {
+    CString::new(bytes).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Contains a NUL byte"))
+}
+
+/// Calls an xattr function twice: first with a null buffer to get the length, then with a buffer that big. Tries
+/// again if the value grew in between.
+fn read_sized(mut call: impl FnMut(*mut u8, usize) -> isize) -> io::Result<Vec<u8>> {
+    loop {
+        let len = call(std::ptr::null_mut(), 0);
+        if len < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut buffer = vec![0u8; len as usize];
+        let read = call(buffer.as_mut_ptr(), buffer.len());
+        if read >= 0 {
+            buffer.truncate(read as usize);
+            return Ok(buffer);
+        }
+        let error = io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::ERANGE) {
+            return Err(error);
+        }
+    }
+}
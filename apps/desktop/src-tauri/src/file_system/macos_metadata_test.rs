@@ -0,0 +1,171 @@
+//! Tests for kinds, extended attributes, and Finder tags.
+
+use super::macos_metadata::{
+    FinderTag, get_finder_tags, get_kind, get_xattr, kind_for_extension, list_xattrs, parse_finder_tags,
+    serialize_finder_tags, set_finder_tags, set_xattr,
+};
+use std::fs;
+use std::path::Path;
+
+/// Creates a fresh temp directory for a test.
+fn create_test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_xattrs_read_back() {
+    let dir = create_test_dir("cmdr_xattr_test");
+    let file = dir.join("download.zip");
+    fs::write(&file, "zip").unwrap();
+    set_xattr(&file, "com.apple.quarantine", b"0081;6500000;Safari;").unwrap();
+    set_xattr(&file, "com.example.empty", b"").unwrap();
+
+    let names = list_xattrs(&file).unwrap();
+    let quarantine = get_xattr(&file, "com.apple.quarantine").unwrap();
+    let empty = get_xattr(&file, "com.example.empty").unwrap();
+    let missing = get_xattr(&file, "com.example.missing").unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert!(names.contains(&"com.apple.quarantine".to_string()));
+    assert!(names.contains(&"com.example.empty".to_string()));
+    assert_eq!(quarantine, Some(b"0081;6500000;Safari;".to_vec()));
+    assert_eq!(empty, Some(Vec::new()));
+    assert_eq!(missing, None);
+}
+
+#[test]
+fn test_xattrs_of_missing_file_fail() {
+    let path = Path::new("/nonexistent/cmdr_xattr_test.txt");
+
+    assert!(list_xattrs(path).is_err());
+    assert!(get_xattr(path, "com.apple.quarantine").is_err());
+}
+
+/// The tags xattr of a file tagged "Important" in red.
+const IMPORTANT_RED_TAGS: &[u8] = &[
+    0x62, 0x70, 0x6c, 0x69, 0x73, 0x74, 0x30, 0x30, 0xa1, 0x01, 0x5b, 0x49, 0x6d, 0x70, 0x6f, 0x72, 0x74, 0x61, 0x6e,
+    0x74, 0x0a, 0x36, 0x08, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x16,
+];
+
+/// The tags xattr of a file tagged "Work" in blue and "Archive" without a color.
+const WORK_AND_ARCHIVE_TAGS: &[u8] = &[
+    0x62, 0x70, 0x6c, 0x69, 0x73, 0x74, 0x30, 0x30, 0xa2, 0x01, 0x02, 0x56, 0x57, 0x6f, 0x72, 0x6b, 0x0a, 0x34, 0x57,
+    0x41, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x08, 0x0b, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x1a,
+];
+
+#[test]
+fn test_parse_finder_tags_with_color() {
+    assert_eq!(
+        parse_finder_tags(IMPORTANT_RED_TAGS),
+        vec![FinderTag {
+            name: "Important".to_string(),
+            color: 6
+        }]
+    );
+}
+
+#[test]
+fn test_parse_finder_tags_without_color() {
+    assert_eq!(
+        parse_finder_tags(WORK_AND_ARCHIVE_TAGS),
+        vec![
+            FinderTag {
+                name: "Work".to_string(),
+                color: 4
+            },
+            FinderTag {
+                name: "Archive".to_string(),
+                color: 0
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_finder_tags_ignores_garbage() {
+    assert!(parse_finder_tags(b"not a plist").is_empty());
+}
+
+#[test]
+fn test_finder_tags_read_from_file() {
+    let dir = create_test_dir("cmdr_finder_tags_test");
+    let tagged = dir.join("tagged.txt");
+    let untagged = dir.join("untagged.txt");
+    fs::write(&tagged, "tagged").unwrap();
+    fs::write(&untagged, "untagged").unwrap();
+    set_xattr(&tagged, "com.apple.metadata:_kMDItemUserTags", IMPORTANT_RED_TAGS).unwrap();
+
+    let tagged_tags = get_finder_tags(&tagged);
+    let untagged_tags = get_finder_tags(&untagged);
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(tagged_tags.len(), 1);
+    assert_eq!(tagged_tags[0].name, "Important");
+    assert!(untagged_tags.is_empty());
+}
+
+#[test]
+fn test_serialized_tags_parse_back() {
+    let tags = vec![
+        FinderTag {
+            name: "Important".to_string(),
+            color: 6,
+        },
+        FinderTag {
+            name: "Archive".to_string(),
+            color: 0,
+        },
+    ];
+
+    assert_eq!(parse_finder_tags(&serialize_finder_tags(&tags).unwrap()), tags);
+}
+
+#[test]
+fn test_set_finder_tags_then_clear() {
+    let dir = create_test_dir("cmdr_set_finder_tags_test");
+    let file = dir.join("file.txt");
+    fs::write(&file, "content").unwrap();
+    let tags = vec![FinderTag {
+        name: "Important".to_string(),
+        color: 6,
+    }];
+
+    set_finder_tags(&file, &tags).unwrap();
+    let after_set = get_finder_tags(&file);
+    set_finder_tags(&file, &[]).unwrap();
+    let names_after_clear = list_xattrs(&file).unwrap();
+    let cleared_again = set_finder_tags(&file, &[]);
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(after_set, tags);
+    assert!(!names_after_clear.contains(&"com.apple.metadata:_kMDItemUserTags".to_string()));
+    assert!(cleared_again.is_ok());
+}
+
+#[test]
+fn test_kind_for_txt_is_described() {
+    let kind = kind_for_extension("txt");
+
+    assert!(kind.is_some_and(|k| !k.is_empty()));
+    // Second lookup comes from the cache
+    assert_eq!(kind_for_extension("txt"), kind_for_extension("txt"));
+}
+
+#[test]
+fn test_kind_of_folder_and_extensionless_file() {
+    let dir = create_test_dir("cmdr_kind_test");
+    fs::write(dir.join("Makefile"), "all:").unwrap();
+
+    let folder_kind = get_kind(&dir);
+    let no_extension_kind = get_kind(&dir.join("Makefile"));
+
+    let _ = fs::remove_dir_all(&dir);
+    assert!(folder_kind.is_some_and(|k| !k.is_empty()));
+    assert_eq!(no_extension_kind, None);
+}
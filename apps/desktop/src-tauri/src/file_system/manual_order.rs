@@ -0,0 +1,117 @@
+//! Manual (custom) file order per directory, for `SortColumn::Manual`.
+//!
+//! Orders are stored as file names keyed by directory path, so they survive closing the listing and
+//! switching to a real sort column and back. They're saved to `manual-orders.json` in the app data
+//! directory, and read from there the first time an order is needed, so they survive restarts too.
+//! Files not in the stored order (for example, new ones picked up by the watcher) go to the end.
+//! Files that no longer exist simply drop out.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, OnceLock, RwLock};
+use tauri::Manager;
+
+use super::operations::FileEntry;
+
+/// Where the orders are saved, set at startup by `init_manual_orders`. Without it (like in tests),
+/// orders only live in memory.
+static MANUAL_ORDERS_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Directory path → file names in their manual order. Loaded from disk on first use.
+static MANUAL_ORDERS: LazyLock<RwLock<HashMap<PathBuf, Vec<String>>>> = LazyLock::new(|| {
+    let orders = MANUAL_ORDERS_PATH
+        .get()
+        .map(|path| read_manual_orders(path))
+        .unwrap_or_default();
+    RwLock::new(orders)
+});
+
+/// A directory's manual order, as stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManualOrderEntry {
+    path: PathBuf,
+    names: Vec<String>,
+}
+
+/// Sets the file the manual orders are saved to. Call once at startup; the orders themselves are
+/// only read when first needed.
+pub fn init_manual_orders<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if let Ok(dir) = app.path().app_data_dir() {
+        let _ = MANUAL_ORDERS_PATH.set(dir.join("manual-orders.json"));
+    }
+}
+
+/// Reorders entries by the stored manual order of `dir`, then stores the resulting order back.
+///
+/// Entries not in the stored order keep their current relative order and go to the end, so
+/// callers should sort by name first to get a stable position for new files.
+pub(super) fn apply_manual_order(dir: &Path, entries: &mut [FileEntry]) {
+    let stored = MANUAL_ORDERS
+        .read()
+        .ok()
+        .and_then(|orders| orders.get(dir).cloned())
+        .unwrap_or_default();
+    order_entries_by_names(entries, &stored);
+    store_manual_order(dir, entries.iter().map(|e| e.name.clone()).collect());
+}
+
+/// Stores the manual order of `dir`, saving all orders to disk if it changed.
+pub(super) fn store_manual_order(dir: &Path, names: Vec<String>) {
+    let Ok(mut orders) = MANUAL_ORDERS.write() else {
+        return;
+    };
+    if orders.get(dir) == Some(&names) {
+        return;
+    }
+    orders.insert(dir.to_path_buf(), names);
+    if let Some(path) = MANUAL_ORDERS_PATH.get() {
+        write_manual_orders(path, &orders);
+    }
+}
+
+/// Moves entries named in `names` to the front, in that order. The rest keep their relative order.
+pub(super) fn order_entries_by_names(entries: &mut [FileEntry], names: &[String]) {
+    let positions: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+    // Stable, so unlisted entries (all keyed `usize::MAX`) stay in their current order
+    entries.sort_by_key(|e| positions.get(e.name.as_str()).copied().unwrap_or(usize::MAX));
+}
+
+/// Reads saved orders. A missing or unreadable file gives no orders.
+pub(super) fn read_manual_orders(path: &Path) -> HashMap<PathBuf, Vec<String>> {
+    let entries: Vec<ManualOrderEntry> = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+    entries.into_iter().map(|entry| (entry.path, entry.names)).collect()
+}
+
+/// Saves orders, ignoring errors: the orders are still in memory for this session.
+pub(super) fn write_manual_orders(path: &Path, orders: &HashMap<PathBuf, Vec<String>>) {
+    let mut entries: Vec<ManualOrderEntry> = orders
+        .iter()
+        .map(|(dir, names)| ManualOrderEntry {
+            path: dir.clone(),
+            names: names.clone(),
+        })
+        .collect();
+    // Stable file content, so that saving the same orders gives the same file
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    let Ok(json) = serde_json::to_string_pretty(&entries) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(path, json);
+}
+
+/// Forgets the manual order of `dir`. For tests, which share the global store.
+#[cfg(test)]
+pub(super) fn clear_manual_order(dir: &Path) {
+    if let Ok(mut orders) = MANUAL_ORDERS.write() {
+        orders.remove(dir);
+    }
+}
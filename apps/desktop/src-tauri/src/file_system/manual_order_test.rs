@@ -0,0 +1,166 @@
+//! Tests for manual (custom) ordering.
+
+use super::manual_order::{clear_manual_order, read_manual_orders, write_manual_orders};
+use super::operations::{
+    CachedListing, FileEntry, LISTING_CACHE, SortColumn, SortOrder, list_directory_end, resort_listing,
+    set_manual_order, update_listing_entries,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Creates a test file entry with the given name in the given directory.
+fn make_entry(dir: &str, name: &str) -> FileEntry {
+    FileEntry::test_entry(&format!("{}/{}", dir, name), false).with_times(Some(1_700_000_000))
+}
+
+/// Inserts a name-sorted listing of `dir` into the cache. Each test uses its own `dir` because the
+/// manual order store is global.
+fn insert_listing(listing_id: &str, dir: &str, names: &[&str]) {
+    clear_manual_order(&PathBuf::from(dir));
+    let mut cache = LISTING_CACHE.write().unwrap();
+    cache.insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "test".to_string(),
+            path: PathBuf::from(dir),
+            entries: names.iter().map(|n| make_entry(dir, n)).collect(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Default::default(),
+            last_accessed: super::operations::AccessTime::now(),
+        },
+    );
+}
+
+/// Returns the names in the listing, in order.
+fn listing_names(listing_id: &str) -> Vec<String> {
+    let cache = LISTING_CACHE.read().unwrap();
+    cache[listing_id].entries.iter().map(|e| e.name.clone()).collect()
+}
+
+#[test]
+fn test_set_manual_order_reorders_entries() {
+    let listing_id = "test-manual-reorder";
+    let dir = "/manual-reorder";
+    insert_listing(listing_id, dir, &["a.txt", "b.txt", "c.txt"]);
+
+    let ordered = vec![format!("{dir}/c.txt"), format!("{dir}/a.txt"), format!("{dir}/b.txt")];
+    set_manual_order(listing_id, &ordered).unwrap();
+    let names = listing_names(listing_id);
+    let sort_by = LISTING_CACHE.read().unwrap()[listing_id].sort_by;
+
+    list_directory_end(listing_id);
+    assert_eq!(names, vec!["c.txt", "a.txt", "b.txt"]);
+    assert_eq!(sort_by, SortColumn::Manual);
+}
+
+#[test]
+fn test_set_manual_order_puts_unlisted_entries_last() {
+    let listing_id = "test-manual-partial";
+    let dir = "/manual-partial";
+    insert_listing(listing_id, dir, &["a.txt", "b.txt", "c.txt"]);
+
+    set_manual_order(listing_id, &[format!("{dir}/c.txt")]).unwrap();
+    let names = listing_names(listing_id);
+
+    list_directory_end(listing_id);
+    assert_eq!(names, vec!["c.txt", "a.txt", "b.txt"]);
+}
+
+#[test]
+fn test_new_file_from_watcher_is_appended() {
+    let listing_id = "test-manual-new-file";
+    let dir = "/manual-new-file";
+    insert_listing(listing_id, dir, &["a.txt", "b.txt", "c.txt"]);
+    set_manual_order(listing_id, &[format!("{dir}/c.txt"), format!("{dir}/b.txt")]).unwrap();
+
+    // The watcher passes the fresh, name-sorted listing
+    let fresh = ["a.txt", "aa.txt", "b.txt", "c.txt"]
+        .iter()
+        .map(|n| make_entry(dir, n))
+        .collect();
+    update_listing_entries(listing_id, fresh);
+    let names = listing_names(listing_id);
+
+    list_directory_end(listing_id);
+    assert_eq!(names, vec!["c.txt", "b.txt", "a.txt", "aa.txt"]);
+}
+
+#[test]
+fn test_removed_file_drops_out() {
+    let listing_id = "test-manual-removed-file";
+    let dir = "/manual-removed-file";
+    insert_listing(listing_id, dir, &["a.txt", "b.txt", "c.txt"]);
+    let ordered = vec![format!("{dir}/c.txt"), format!("{dir}/b.txt"), format!("{dir}/a.txt")];
+    set_manual_order(listing_id, &ordered).unwrap();
+
+    let fresh = ["a.txt", "c.txt"].iter().map(|n| make_entry(dir, n)).collect();
+    update_listing_entries(listing_id, fresh);
+    let names = listing_names(listing_id);
+
+    list_directory_end(listing_id);
+    assert_eq!(names, vec!["c.txt", "a.txt"]);
+}
+
+#[test]
+fn test_manual_order_is_remembered_after_switching_sort() {
+    let listing_id = "test-manual-remembered";
+    let dir = "/manual-remembered";
+    insert_listing(listing_id, dir, &["a.txt", "b.txt", "c.txt"]);
+    let ordered = vec![format!("{dir}/b.txt"), format!("{dir}/c.txt"), format!("{dir}/a.txt")];
+    set_manual_order(listing_id, &ordered).unwrap();
+
+    resort_listing(listing_id, SortColumn::Name, SortOrder::Ascending, None, true).unwrap();
+    let names_by_name = listing_names(listing_id);
+    resort_listing(listing_id, SortColumn::Manual, SortOrder::Ascending, None, true).unwrap();
+    let names_manual = listing_names(listing_id);
+
+    list_directory_end(listing_id);
+    assert_eq!(names_by_name, vec!["a.txt", "b.txt", "c.txt"]);
+    assert_eq!(names_manual, vec!["b.txt", "c.txt", "a.txt"]);
+}
+
+#[test]
+fn test_manual_sort_without_stored_order_uses_name_order() {
+    let listing_id = "test-manual-no-order";
+    let dir = "/manual-no-order";
+    insert_listing(listing_id, dir, &["c.txt", "a.txt", "b.txt"]);
+
+    resort_listing(listing_id, SortColumn::Manual, SortOrder::Ascending, None, true).unwrap();
+    let names = listing_names(listing_id);
+
+    list_directory_end(listing_id);
+    assert_eq!(names, vec!["a.txt", "b.txt", "c.txt"]);
+}
+
+#[test]
+fn test_set_manual_order_unknown_listing() {
+    assert!(set_manual_order("nonexistent-manual-listing", &[]).is_err());
+}
+
+#[test]
+fn test_manual_orders_survive_saving_and_loading() {
+    let dir = std::env::temp_dir().join("cmdr_manual_order_persist_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("manual-orders.json");
+    let mut orders = HashMap::new();
+    orders.insert(
+        PathBuf::from("/Users/test/Desktop"),
+        vec!["b.txt".to_string(), "a.txt".to_string()],
+    );
+    orders.insert(PathBuf::from("/Users/test/Ünïcode dir"), vec!["é.txt".to_string()]);
+
+    write_manual_orders(&path, &orders);
+    let loaded = read_manual_orders(&path);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(loaded, orders);
+}
+
+#[test]
+fn test_missing_manual_orders_file_gives_no_orders() {
+    let path = std::env::temp_dir().join("cmdr_manual_order_missing_test.json");
+    let _ = std::fs::remove_file(&path);
+    assert!(read_manual_orders(&path).is_empty());
+}
@@ -14,13 +14,17 @@ fn test_mock_provider_returns_entries() {
             size: Some(1024),
             modified_at: Some(1640000000),
             created_at: Some(1639000000),
+            changed_at: Some(1639000000),
             added_at: Some(1638000000),
             opened_at: Some(1641000000),
             permissions: 0o644,
             owner: "testuser".to_string(),
             group: "staff".to_string(),
             icon_id: "ext:txt".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "folder".to_string(),
@@ -30,13 +34,17 @@ fn test_mock_provider_returns_entries() {
             size: None,
             modified_at: Some(1640000000),
             created_at: Some(1639000000),
+            changed_at: Some(1639000000),
             added_at: Some(1638000000),
             opened_at: None,
             permissions: 0o755,
             owner: "testuser".to_string(),
             group: "staff".to_string(),
             icon_id: "dir".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
     ];
 
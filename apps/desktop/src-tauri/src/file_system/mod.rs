@@ -1,9 +1,18 @@
 //! File system module - operations, watchers, volumes, and providers.
 
+mod alias;
+mod case_sensitivity;
+mod find;
+mod folder_sizes;
+mod hardlinks;
+mod ignore_patterns;
+mod kind_sniff;
 #[cfg(target_os = "macos")]
 mod macos_metadata;
+mod manual_order;
 #[cfg(test)]
 mod mock_provider;
+mod mount_type;
 pub(crate) mod operations;
 #[cfg(test)]
 mod provider;
@@ -11,29 +20,65 @@ mod provider;
 mod real_provider;
 #[cfg(target_os = "macos")]
 pub mod sync_status;
+mod transfer;
 pub mod volume;
 mod volume_manager;
 mod watcher;
+mod write_access;
 
 use std::sync::{Arc, LazyLock};
 
 // Re-export public types
+pub use alias::{AliasResolution, resolve_alias};
+pub use case_sensitivity::is_case_sensitive_volume;
+pub use find::{
+    ContentMatch, DEFAULT_CONTENT_SEARCH_MAX_FILE_SIZE, cancel_find, find_in_directory, search_file_contents,
+};
+pub use folder_sizes::{cancel_directory_size, compute_directory_size};
+pub use hardlinks::find_hardlinks;
+pub use ignore_patterns::set_ignore_patterns;
+#[cfg(target_os = "macos")]
+pub use macos_metadata::{
+    FinderTag, FinderTags, get_finder_tags, get_finder_tags_batch, get_xattr, list_xattrs, set_finder_tags,
+};
+pub use manual_order::init_manual_orders;
 #[cfg(test)]
 pub use mock_provider::MockFileSystemProvider;
+#[allow(unused_imports)] // invalidate_mount_cache is only used by the macOS volume watcher
+pub use mount_type::{invalidate_mount_cache, is_remote_volume};
 pub use operations::{
-    FileEntry, ListingStartResult, ResortResult, SortColumn, SortOrder, find_file_index, get_file_at, get_file_range,
-    get_max_filename_width, get_total_count, list_directory_end, list_directory_start_with_volume, resort_listing,
+    EXTENDED_METADATA_CHUNK_SIZE, ExtendedMetadata, FileEntry, FileRangeResult, FilterMode, ListingOrigin,
+    ListingStartResult, ListingStreamEvent, OwnerNames, PathInfo, ResortResult, SortColumn, SortOrder,
+    cancel_listing_stream, filter_listing, find_file_index, get_extended_metadata_batch, get_extended_metadata_chunked,
+    get_file_at, get_file_range, get_file_range_or_recreate, get_listing_path, get_max_filename_width, get_path_info,
+    get_path_metadata, get_total_count, list_directory_end, list_directory_start_streaming,
+    list_directory_start_with_volume, resolve_owners_batch, resort_listing, set_follow_symlinks_for_type,
+    set_manual_order, start_folder_size_computation, typeahead_find,
+};
+pub use transfer::{
+    CopyProgress, CopyProgressEvent, TransferOptions, cancel_copy, copy_file_with_progress, copy_with_progress,
 };
+pub use write_access::{WriteAccess, get_write_access, is_writable};
 // FileEntry also re-exported for internal test modules
 #[cfg(test)]
 pub use provider::FileSystemProvider;
 // Re-export volume types (some not used externally yet)
+#[cfg(target_os = "macos")]
+pub use volume::SmbVolume;
 #[allow(unused_imports)]
-pub use volume::{InMemoryVolume, LocalPosixVolume, Volume, VolumeError};
+pub use volume::{
+    ConflictPolicy, DeleteOutcome, DeleteResult, InMemoryVolume, LinkKind, LocalPosixVolume, MAX_MODE, TransferResult,
+    Volume, VolumeError, cancel_delete, check_name_collision, check_space_for_copy, check_space_for_paths,
+    delete_paths, delete_recursive, duplicate, register_delete_operation, resolve_conflict, transfer_paths,
+    unregister_delete_operation,
+};
 #[allow(unused_imports)]
 pub use volume_manager::VolumeManager;
 // Watcher management - init_watcher_manager must be called from lib.rs
-pub use watcher::init_watcher_manager;
+pub use watcher::{
+    DEFAULT_DEBOUNCE_MS, add_to_watch_session, end_watch_session, init_watcher_manager, remove_from_watch_session,
+    set_debounce_ms,
+};
 
 /// Global volume manager instance
 static VOLUME_MANAGER: LazyLock<VolumeManager> = LazyLock::new(VolumeManager::new);
@@ -71,3 +116,33 @@ mod hidden_files_test;
 
 #[cfg(test)]
 mod sorting_test;
+
+#[cfg(test)]
+mod kind_sniff_test;
+
+#[cfg(test)]
+mod typeahead_test;
+
+#[cfg(test)]
+mod manual_order_test;
+
+#[cfg(test)]
+mod transfer_test;
+
+#[cfg(test)]
+mod folder_sizes_test;
+
+#[cfg(test)]
+mod ignore_patterns_test;
+
+#[cfg(test)]
+mod listing_cache_test;
+
+#[cfg(test)]
+mod filter_listing_test;
+
+#[cfg(test)]
+mod test_entries;
+
+#[cfg(all(test, target_os = "macos"))]
+mod macos_metadata_test;
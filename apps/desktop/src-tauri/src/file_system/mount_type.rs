@@ -0,0 +1,141 @@
+//! Detects whether a path is on a network (remote) volume.
+//!
+//! Live size computation, recursive watching, and short debounces are fine on local disks but slow
+//! or flaky on SMB/NFS/AFP mounts, so callers use this to pick gentler behavior there.
+//!
+//! The mount table comes from `getmntinfo` with `MNT_NOWAIT`, which returns the cached `statfs`
+//! records without touching the (possibly unresponsive) servers. It's cached and only re-read when
+//! volumes are mounted or unmounted.
+
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+/// File system types (`statfs.f_fstypename`) that are backed by a network server.
+const REMOTE_FS_TYPES: &[&str] = &["smbfs", "nfs", "afpfs", "webdav", "ftp", "cifs"];
+
+/// A mount point and whether it's remote.
+#[derive(Debug, Clone)]
+struct Mount {
+    mount_point: PathBuf,
+    is_remote: bool,
+}
+
+/// Cached mount table. `None` means it needs to be (re)loaded.
+static MOUNT_CACHE: LazyLock<RwLock<Option<Vec<Mount>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Classifies a file system type name, as found in `statfs.f_fstypename`, as remote or local.
+/// For example, "smbfs" and "nfs" are remote, "apfs" and "hfs" are local.
+pub(crate) fn is_remote_fs_type(fs_type: &str) -> bool {
+    let fs_type = fs_type.trim_end_matches('\0').to_ascii_lowercase();
+    REMOTE_FS_TYPES.contains(&fs_type.as_str())
+}
+
+/// Returns true if the path is on a network volume. Unknown paths count as local.
+pub fn is_remote_volume(path: &Path) -> bool {
+    if let Ok(cache) = MOUNT_CACHE.read()
+        && let Some(mounts) = cache.as_ref()
+    {
+        return find_mount(mounts, path).is_some_and(|m| m.is_remote);
+    }
+
+    let mounts = load_mounts();
+    let is_remote = find_mount(&mounts, path).is_some_and(|m| m.is_remote);
+    if let Ok(mut cache) = MOUNT_CACHE.write() {
+        *cache = Some(mounts);
+    }
+    is_remote
+}
+
+/// Drops the cached mount table. Call this when volumes are mounted or unmounted.
+pub fn invalidate_mount_cache() {
+    if let Ok(mut cache) = MOUNT_CACHE.write() {
+        *cache = None;
+    }
+}
+
+/// Finds the mount a path is on: the one with the longest mount point that contains the path.
+fn find_mount<'a>(mounts: &'a [Mount], path: &Path) -> Option<&'a Mount> {
+    mounts
+        .iter()
+        .filter(|m| path.starts_with(&m.mount_point))
+        .max_by_key(|m| m.mount_point.as_os_str().len())
+}
+
+#[cfg(target_os = "macos")]
+fn load_mounts() -> Vec<Mount> {
+    use std::ffi::CStr;
+
+    let mut stats: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut stats, libc::MNT_NOWAIT) };
+    if count <= 0 || stats.is_null() {
+        return Vec::new();
+    }
+
+    // The buffer is owned by libc and stays valid until the next getmntinfo call on this thread
+    let stats = unsafe { std::slice::from_raw_parts(stats, count as usize) };
+    stats
+        .iter()
+        .map(|stat| {
+            let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) }.to_string_lossy();
+            let mount_point = unsafe { CStr::from_ptr(stat.f_mntonname.as_ptr()) }.to_string_lossy();
+            Mount {
+                mount_point: PathBuf::from(mount_point.as_ref()),
+                is_remote: is_remote_fs_type(&fs_type),
+            }
+        })
+        .collect()
+}
+
+/// `f_fstypename` only exists on BSD-style systems, so elsewhere everything counts as local.
+#[cfg(not(target_os = "macos"))]
+fn load_mounts() -> Vec<Mount> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_fs_types_are_remote() {
+        for fs_type in ["smbfs", "nfs", "afpfs", "webdav"] {
+            assert!(is_remote_fs_type(fs_type), "{fs_type} should be remote");
+        }
+    }
+
+    #[test]
+    fn test_local_fs_types_are_not_remote() {
+        for fs_type in ["apfs", "hfs", "msdos", "exfat", "devfs", "autofs", ""] {
+            assert!(!is_remote_fs_type(fs_type), "{fs_type} should be local");
+        }
+    }
+
+    #[test]
+    fn test_fs_type_parsing_ignores_case_and_nul_padding() {
+        assert!(is_remote_fs_type("SMBFS"));
+        assert!(is_remote_fs_type("nfs\0\0\0"));
+    }
+
+    #[test]
+    fn test_find_mount_picks_longest_mount_point() {
+        let mounts = vec![
+            Mount {
+                mount_point: PathBuf::from("/"),
+                is_remote: false,
+            },
+            Mount {
+                mount_point: PathBuf::from("/Volumes/share"),
+                is_remote: true,
+            },
+        ];
+
+        assert!(
+            find_mount(&mounts, Path::new("/Volumes/share/docs/a.txt"))
+                .unwrap()
+                .is_remote
+        );
+        assert!(!find_mount(&mounts, Path::new("/Users/test")).unwrap().is_remote);
+        // Not a path component match, so it's on the root volume
+        assert!(!find_mount(&mounts, Path::new("/Volumes/shared")).unwrap().is_remote);
+    }
+}
@@ -2,18 +2,30 @@
 
 #![allow(dead_code)] // Boilerplate for future use
 
+use rayon::prelude::*;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::os::unix::fs::{MetadataExt, PermissionsExt};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
-use uzers::{get_group_by_gid, get_user_by_uid};
 
+use super::case_sensitivity::{is_case_sensitive_volume, names_equal_ignoring_case};
+use super::folder_sizes::{cancel_folder_sizes, has_folder_size_task, set_visible_folders, start_folder_sizes};
+use super::ignore_patterns::{IgnorePatterns, current_ignore_patterns, is_ignored};
+use super::kind_sniff::sniff_kind_cached;
+use super::manual_order::{apply_manual_order, order_entries_by_names, store_manual_order};
+use super::volume::{Volume, VolumeError};
 use super::watcher::{start_watching, stop_watching};
 use crate::benchmark;
+use crate::command_error::CommandError;
+use crate::owner_cache::{get_group_name, get_owner_name, prewarm_owners};
 
 // ============================================================================
 // Sorting configuration
@@ -29,6 +41,17 @@ pub enum SortColumn {
     Size,
     Modified,
     Created,
+    /// The inode change time (`changed_at`), not the creation time
+    Changed,
+    /// Date added to the directory (macOS only). Needs extended metadata: core listings leave `added_at` empty
+    /// until `get_extended_metadata_batch` fills it, so load that before sorting by this.
+    Added,
+    /// Date last opened (macOS only). Needs extended metadata loaded first, like `Added`.
+    Opened,
+    /// User-defined order, set with `set_manual_order` and remembered per directory
+    Manual,
+    /// Grouped by kind (images, documents, archives, …), then by name within each group. See `get_file_kind`.
+    Kind,
 }
 
 /// Sort order (ascending or descending).
@@ -40,10 +63,6 @@ pub enum SortOrder {
     Descending,
 }
 
-/// Cache for uid→username and gid→groupname resolution.
-static OWNER_CACHE: LazyLock<RwLock<HashMap<u32, String>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
-static GROUP_CACHE: LazyLock<RwLock<HashMap<u32, String>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
-
 /// Cache for directory listings (on-demand virtual scrolling).
 /// Key: listing_id, Value: cached listing with all entries.
 #[cfg(not(test))]
@@ -65,6 +84,12 @@ struct CachedListing {
     sort_by: SortColumn,
     /// Current sort order
     sort_order: SortOrder,
+    /// Whether names are compared case-sensitively, see `sort_entries`
+    case_sensitive: bool,
+    /// Ignore patterns in effect when the listing started
+    ignore_patterns: Arc<IgnorePatterns>,
+    /// When the frontend last fetched entries, for evicting listings it forgot to end
+    last_accessed: AccessTime,
 }
 
 /// Cached directory listing for on-demand virtual scrolling.
@@ -80,6 +105,64 @@ pub(super) struct CachedListing {
     pub sort_by: SortColumn,
     /// Current sort order
     pub sort_order: SortOrder,
+    /// Whether names are compared case-sensitively, see `sort_entries`
+    pub case_sensitive: bool,
+    /// Ignore patterns in effect when the listing started
+    pub ignore_patterns: Arc<IgnorePatterns>,
+    /// When the frontend last fetched entries, for evicting listings it forgot to end
+    pub last_accessed: AccessTime,
+}
+
+/// Fixed reference point for `AccessTime`, so access times fit in an integer.
+static ACCESS_CLOCK_START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// When a listing was last read, in milliseconds since `ACCESS_CLOCK_START`.
+///
+/// Atomic, so reads can update it while holding only the cache's read lock. Signed, so that times before the
+/// clock started fit too, which tests use to backdate listings.
+pub(super) struct AccessTime(AtomicI64);
+
+impl AccessTime {
+    pub(super) fn now() -> Self {
+        Self(AtomicI64::new(millis_since_clock_start(Instant::now())))
+    }
+
+    #[cfg(test)]
+    pub(super) fn at(instant: Instant) -> Self {
+        Self(AtomicI64::new(millis_since_clock_start(instant)))
+    }
+
+    fn touch(&self) {
+        self.0
+            .store(millis_since_clock_start(Instant::now()), Ordering::Relaxed);
+    }
+
+    fn elapsed(&self) -> Duration {
+        let millis = millis_since_clock_start(Instant::now()) - self.0.load(Ordering::Relaxed);
+        Duration::from_millis(u64::try_from(millis).unwrap_or(0))
+    }
+}
+
+fn millis_since_clock_start(instant: Instant) -> i64 {
+    let start = *ACCESS_CLOCK_START;
+    match instant.checked_duration_since(start) {
+        Some(after) => i64::try_from(after.as_millis()).unwrap_or(i64::MAX),
+        None => -i64::try_from(start.duration_since(instant).as_millis()).unwrap_or(i64::MAX),
+    }
+}
+
+impl CachedListing {
+    /// Returns the entries the frontend sees: hidden ones only if `include_hidden`, and never ignored ones.
+    fn visible_entries(&self, include_hidden: bool) -> impl Iterator<Item = &FileEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| is_visible(e, include_hidden, &self.ignore_patterns))
+    }
+}
+
+/// Returns true if an entry shows up in listings, given the hidden files toggle and the ignore patterns.
+fn is_visible(entry: &FileEntry, include_hidden: bool, ignore_patterns: &IgnorePatterns) -> bool {
+    (include_hidden || !entry.name.starts_with('.')) && !is_ignored(&entry.name, entry.is_directory, ignore_patterns)
 }
 
 // ============================================================================
@@ -87,7 +170,7 @@ pub(super) struct CachedListing {
 // ============================================================================
 
 /// Extracts file extension for sorting purposes.
-/// Returns: (is_dotfile, has_extension, extension_lowercase)
+/// Returns: (is_dotfile, has_extension, extension)
 /// Dotfiles (names starting with .) sort first, then files without extension, then by extension.
 fn extract_extension_for_sort(name: &str) -> (bool, bool, String) {
     // Dotfiles (e.g., .gitignore) sort first
@@ -100,18 +183,71 @@ fn extract_extension_for_sort(name: &str) -> (bool, bool, String) {
         && dot_pos > 0
         && dot_pos < name.len() - 1
     {
-        let ext = name[dot_pos + 1..].to_lowercase();
-        return (false, true, ext);
+        return (false, true, name[dot_pos + 1..].to_string());
     }
 
     // No extension
     (false, false, String::new())
 }
 
+/// Compares two names naturally and case-insensitively: runs of digits compare as numbers, so "img_2" comes
+/// before "img_10" and "v1.2.9" before "v1.2.10". Zero-padded numbers equal their unpadded forms, with the shorter
+/// one first as a tiebreaker, so "track 1" comes right before "track 01".
+fn compare_names(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    // The first difference in zero padding, used if the names are otherwise equal
+    let mut padding_tiebreak = Ordering::Equal;
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let a_end = i + a[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let b_end = j + b[j..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let a_digits = trim_leading_zeros(&a[i..a_end]);
+            let b_digits = trim_leading_zeros(&b[j..b_end]);
+            // With the zeros trimmed, a longer number is a bigger one. Same length compares digit by digit.
+            let number_cmp = a_digits.len().cmp(&b_digits.len()).then_with(|| a_digits.cmp(b_digits));
+            if number_cmp != Ordering::Equal {
+                return number_cmp;
+            }
+            if padding_tiebreak == Ordering::Equal {
+                padding_tiebreak = (a_end - i).cmp(&(b_end - j));
+            }
+            (i, j) = (a_end, b_end);
+        } else {
+            // Bytes compare the same as chars in UTF-8, so multi-byte chars need no special handling
+            let byte_cmp = a[i].cmp(&b[j]);
+            if byte_cmp != Ordering::Equal {
+                return byte_cmp;
+            }
+            (i, j) = (i + 1, j + 1);
+        }
+    }
+    (a.len() - i).cmp(&(b.len() - j)).then(padding_tiebreak)
+}
+
+/// Strips leading zeros from a run of ASCII digits. All zeros becomes empty, which compares as the smallest number.
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|&&c| c == b'0').count();
+    &digits[zeros..]
+}
+
 /// Sorts file entries by the specified column and order.
-/// Directories always come first, then files.
-/// Uses natural sorting for string comparisons (e.g., "img_2" before "img_10").
-pub fn sort_entries(entries: &mut [FileEntry], sort_by: SortColumn, sort_order: SortOrder) {
+/// Directories always come first, then files. Entries that are equal by the column are sorted by name.
+/// Uses natural sorting for string comparisons (e.g., "img_2" before "img_10"), see `compare_names`.
+/// With `case_sensitive`, names and extensions are compared as raw bytes instead, so "Zebra" comes before "apple".
+pub fn sort_entries(entries: &mut [FileEntry], sort_by: SortColumn, sort_order: SortOrder, case_sensitive: bool) {
+    let compare_text = |a: &str, b: &str| {
+        if case_sensitive {
+            a.as_bytes().cmp(b.as_bytes())
+        } else {
+            compare_names(a, b)
+        }
+    };
     entries.sort_by(|a, b| {
         // Directories always come first
         match (a.is_directory, b.is_directory) {
@@ -120,9 +256,22 @@ pub fn sort_entries(entries: &mut [FileEntry], sort_by: SortColumn, sort_order:
             _ => {}
         }
 
+        // Entries without the date go to the end in both orders, like in Finder's "Date Added"
+        let optional_dates = match sort_by {
+            SortColumn::Added => Some((a.added_at, b.added_at)),
+            SortColumn::Opened => Some((a.opened_at, b.opened_at)),
+            _ => None,
+        };
+        match optional_dates {
+            Some((None, Some(_))) => return std::cmp::Ordering::Greater,
+            Some((Some(_), None)) => return std::cmp::Ordering::Less,
+            _ => {}
+        }
+
         // Compare by the selected column
         let primary = match sort_by {
-            SortColumn::Name => alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase()),
+            // Manual order can't be expressed as a comparison, `sort_listing_entries` applies it on top
+            SortColumn::Name | SortColumn::Manual => compare_text(&a.name, &b.name),
             SortColumn::Extension => {
                 let (a_dotfile, a_has_ext, a_ext) = extract_extension_for_sort(&a.name);
                 let (b_dotfile, b_has_ext, b_ext) = extract_extension_for_sort(&b.name);
@@ -131,15 +280,15 @@ pub fn sort_entries(entries: &mut [FileEntry], sort_by: SortColumn, sort_order:
                 match (a_dotfile, b_dotfile) {
                     (true, false) => std::cmp::Ordering::Less,
                     (false, true) => std::cmp::Ordering::Greater,
-                    (true, true) => alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase()),
+                    (true, true) => compare_text(&a.name, &b.name),
                     (false, false) => match (a_has_ext, b_has_ext) {
                         (false, true) => std::cmp::Ordering::Less,
                         (true, false) => std::cmp::Ordering::Greater,
-                        (false, false) => alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase()),
+                        (false, false) => compare_text(&a.name, &b.name),
                         (true, true) => {
-                            let ext_cmp = alphanumeric_sort::compare_str(&a_ext, &b_ext);
+                            let ext_cmp = compare_text(&a_ext, &b_ext);
                             if ext_cmp == std::cmp::Ordering::Equal {
-                                alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase())
+                                compare_text(&a.name, &b.name)
                             } else {
                                 ext_cmp
                             }
@@ -147,28 +296,43 @@ pub fn sort_entries(entries: &mut [FileEntry], sort_by: SortColumn, sort_order:
                     },
                 }
             }
+            SortColumn::Kind => get_file_kind(a.is_directory, &a.name)
+                .cmp(&get_file_kind(b.is_directory, &b.name))
+                .then_with(|| compare_text(&a.name, &b.name)),
             SortColumn::Size => {
                 // For directories, size is None - sort them by name among themselves
                 match (a.size, b.size) {
-                    (None, None) => alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase()),
+                    (None, None) => compare_text(&a.name, &b.name),
                     (None, Some(_)) => std::cmp::Ordering::Less,
                     (Some(_), None) => std::cmp::Ordering::Greater,
                     (Some(a_size), Some(b_size)) => a_size.cmp(&b_size),
                 }
             }
             SortColumn::Modified => match (a.modified_at, b.modified_at) {
-                (None, None) => alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase()),
+                (None, None) => compare_text(&a.name, &b.name),
                 (None, Some(_)) => std::cmp::Ordering::Less,
                 (Some(_), None) => std::cmp::Ordering::Greater,
                 (Some(a_time), Some(b_time)) => a_time.cmp(&b_time),
             },
             SortColumn::Created => match (a.created_at, b.created_at) {
-                (None, None) => alphanumeric_sort::compare_str(a.name.to_lowercase(), b.name.to_lowercase()),
+                (None, None) => compare_text(&a.name, &b.name),
+                (None, Some(_)) => std::cmp::Ordering::Less,
+                (Some(_), None) => std::cmp::Ordering::Greater,
+                (Some(a_time), Some(b_time)) => a_time.cmp(&b_time),
+            },
+            SortColumn::Changed => match (a.changed_at, b.changed_at) {
+                (None, None) => compare_text(&a.name, &b.name),
                 (None, Some(_)) => std::cmp::Ordering::Less,
                 (Some(_), None) => std::cmp::Ordering::Greater,
                 (Some(a_time), Some(b_time)) => a_time.cmp(&b_time),
             },
+            // Either both have the date or neither, see above
+            SortColumn::Added => a.added_at.cmp(&b.added_at),
+            SortColumn::Opened => a.opened_at.cmp(&b.opened_at),
         };
+        // Equal sizes or dates fall back to the name, so the order doesn't change between re-listings. The watcher
+        // diff relies on this, otherwise it'd see entries moving around.
+        let primary = primary.then_with(|| compare_text(&a.name, &b.name));
 
         // Apply sort order
         match sort_order {
@@ -178,42 +342,30 @@ pub fn sort_entries(entries: &mut [FileEntry], sort_by: SortColumn, sort_order:
     });
 }
 
-/// Resolves a uid to a username, with caching.
-fn get_owner_name(uid: u32) -> String {
-    // Try read lock first
-    if let Ok(cache) = OWNER_CACHE.read()
-        && let Some(name) = cache.get(&uid)
-    {
-        return name.clone();
-    }
-    // Cache miss, resolve and store
-    let name = get_user_by_uid(uid)
-        .map(|u| u.name().to_string_lossy().into_owned())
-        .unwrap_or_else(|| uid.to_string());
-    if let Ok(mut cache) = OWNER_CACHE.write() {
-        cache.insert(uid, name.clone());
+/// Sorts the entries of a listing. Same as `sort_entries`, plus it applies the stored manual order of
+/// `dir` for `SortColumn::Manual`. Entries without a manual position go to the end, sorted by name.
+fn sort_listing_entries(
+    dir: &Path,
+    entries: &mut [FileEntry],
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    case_sensitive: bool,
+) {
+    sort_entries(entries, sort_by, sort_order, case_sensitive);
+    if sort_by == SortColumn::Manual {
+        apply_manual_order(dir, entries);
     }
-    name
 }
 
-/// Resolves a gid to a group name, with caching.
-fn get_group_name(gid: u32) -> String {
-    if let Ok(cache) = GROUP_CACHE.read()
-        && let Some(name) = cache.get(&gid)
-    {
-        return name.clone();
-    }
-    let name = get_group_by_gid(gid)
-        .map(|g| g.name().to_string_lossy().into_owned())
-        .unwrap_or_else(|| gid.to_string());
-    if let Ok(mut cache) = GROUP_CACHE.write() {
-        cache.insert(gid, name.clone());
-    }
-    name
+/// Returns the lowercase extension of a file name, if it has one.
+fn get_extension(name: &str) -> Option<String> {
+    Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
 }
 
 /// Generates icon ID based on file type and extension.
-fn get_icon_id(is_dir: bool, is_symlink: bool, name: &str) -> String {
+pub(crate) fn get_icon_id(is_dir: bool, is_symlink: bool, name: &str) -> String {
     if is_symlink {
         // Distinguish symlinks to directories vs files
         return if is_dir {
@@ -225,11 +377,46 @@ fn get_icon_id(is_dir: bool, is_symlink: bool, name: &str) -> String {
     if is_dir {
         return "dir".to_string();
     }
-    // Extract extension
-    if let Some(ext) = Path::new(name).extension() {
-        return format!("ext:{}", ext.to_string_lossy().to_lowercase());
+    match get_extension(name) {
+        Some(ext) => format!("ext:{}", ext),
+        None => "file".to_string(),
+    }
+}
+
+/// What kind of file an entry is, for `SortColumn::Kind`. Groups sort in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum FileKind {
+    Folder,
+    Image,
+    Video,
+    Audio,
+    Document,
+    Archive,
+    Code,
+    Other,
+}
+
+/// Derives the kind of an entry from its extension, the same one `get_icon_id` uses.
+fn get_file_kind(is_dir: bool, name: &str) -> FileKind {
+    if is_dir {
+        return FileKind::Folder;
+    }
+    let Some(ext) = get_extension(name) else {
+        return FileKind::Other;
+    };
+    match ext.as_str() {
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "tif" | "tiff" | "webp" | "heic" | "heif" | "svg" | "ico" | "raw"
+        | "cr2" | "nef" | "dng" | "psd" => FileKind::Image,
+        "mp4" | "mov" | "m4v" | "avi" | "mkv" | "webm" | "wmv" | "flv" | "mpg" | "mpeg" => FileKind::Video,
+        "mp3" | "m4a" | "aac" | "wav" | "flac" | "ogg" | "opus" | "aiff" | "aif" | "wma" => FileKind::Audio,
+        "txt" | "md" | "rtf" | "pdf" | "doc" | "docx" | "odt" | "pages" | "xls" | "xlsx" | "ods" | "numbers"
+        | "csv" | "ppt" | "pptx" | "odp" | "key" | "epub" => FileKind::Document,
+        "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "7z" | "rar" | "dmg" | "iso" | "pkg" => FileKind::Archive,
+        "rs" | "ts" | "js" | "jsx" | "tsx" | "svelte" | "py" | "rb" | "go" | "java" | "kt" | "swift" | "c" | "h"
+        | "cpp" | "hpp" | "cs" | "php" | "sh" | "zsh" | "html" | "css" | "scss" | "json" | "yaml" | "yml" | "toml"
+        | "xml" | "sql" => FileKind::Code,
+        _ => FileKind::Other,
     }
-    "file".to_string()
 }
 
 /// Represents a file or directory entry with extended metadata.
@@ -243,6 +430,8 @@ pub struct FileEntry {
     pub size: Option<u64>,
     pub modified_at: Option<u64>,
     pub created_at: Option<u64>,
+    /// When the file's metadata (inode) last changed, as `st_ctime`. Not the same as `created_at` (birthtime).
+    pub changed_at: Option<u64>,
     /// When the file was added to its current directory (macOS only)
     pub added_at: Option<u64>,
     /// When the file was last opened (macOS only)
@@ -251,10 +440,19 @@ pub struct FileEntry {
     pub owner: String,
     pub group: String,
     pub icon_id: String,
+    /// Inode number, to tell a rename apart from a delete and an add. None if it couldn't be read.
+    pub inode: Option<u64>,
     /// Whether extended metadata (addedAt, openedAt) has been loaded
     /// Always true for legacy list_directory(), false for list_directory_core()
     #[serde(default = "default_extended_loaded")]
     pub extended_metadata_loaded: bool,
+    /// Whether `owner` and `group` are names. If false, they're the numeric uid and gid, see
+    /// `resolve_owners_batch`.
+    #[serde(default = "default_owner_resolved")]
+    pub owner_resolved: bool,
+    /// Where a symlink points, as stored in the link (so possibly relative). Also set for broken symlinks.
+    /// None for other entries, or if the link couldn't be read.
+    pub symlink_target: Option<String>,
 }
 
 /// Default value for extended_metadata_loaded (for backwards compatibility)
@@ -262,6 +460,18 @@ fn default_extended_loaded() -> bool {
     true
 }
 
+/// Default value for owner_resolved (for backwards compatibility)
+fn default_owner_resolved() -> bool {
+    true
+}
+
+/// Reads where a symlink points, without following it. Works for broken symlinks too.
+fn read_symlink_target(path: &Path) -> Option<String> {
+    fs::read_link(path)
+        .ok()
+        .map(|target| target.to_string_lossy().to_string())
+}
+
 /// Lists the contents of a directory.
 ///
 /// # Arguments
@@ -271,142 +481,111 @@ fn default_extended_loaded() -> bool {
 /// A vector of FileEntry representing the directory contents, sorted with directories first,
 /// then files, both alphabetically.
 pub fn list_directory(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
-    let overall_start = std::time::Instant::now();
+    let overall_start = TimingStamp::now();
     let mut entries = Vec::new();
 
-    let mut metadata_time = std::time::Duration::ZERO;
-    let mut owner_lookup_time = std::time::Duration::ZERO;
-    let mut entry_creation_time = std::time::Duration::ZERO;
+    let mut metadata_time = PhaseTime::default();
+    let mut owner_lookup_time = PhaseTime::default();
 
-    let read_start = std::time::Instant::now();
+    let read_start = TimingStamp::now();
     let dir_entries: Vec<_> = fs::read_dir(path)?.collect();
-    let read_dir_time = read_start.elapsed();
+    let read_dir_time = PhaseTime::since(read_start);
 
     for entry in dir_entries {
-        let entry = entry?;
-
-        let meta_start = std::time::Instant::now();
-        let file_type = entry.file_type()?;
-        let is_symlink = file_type.is_symlink();
-
-        // For symlinks, check if the TARGET is a directory by following the link
-        // fs::metadata follows symlinks, fs::symlink_metadata does not
-        let target_is_dir = if is_symlink {
-            fs::metadata(entry.path()).map(|m| m.is_dir()).unwrap_or(false) // Broken symlink = treat as file
-        } else {
-            false
-        };
-
-        // For symlinks, get metadata of the link itself (not target) for size/timestamps
-        let metadata = if is_symlink {
-            fs::symlink_metadata(entry.path())
-        } else {
-            entry.metadata()
-        };
-        metadata_time += meta_start.elapsed();
-
-        match metadata {
-            Ok(metadata) => {
-                let name = entry.file_name().to_string_lossy().to_string();
-                // is_directory: true if it's a real dir OR a symlink pointing to a dir
-                let is_dir = metadata.is_dir() || target_is_dir;
-
-                let modified = metadata
-                    .modified()
-                    .ok()
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs());
-
-                let created = metadata
-                    .created()
-                    .ok()
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs());
-
-                let uid = metadata.uid();
-                let gid = metadata.gid();
-
-                let owner_start = std::time::Instant::now();
-                let owner = get_owner_name(uid);
-                let group = get_group_name(gid);
-                owner_lookup_time += owner_start.elapsed();
-
-                let create_start = std::time::Instant::now();
-                // Get macOS-specific metadata (added_at, opened_at)
-                #[cfg(target_os = "macos")]
-                let (added_at, opened_at) = {
-                    let macos_meta = super::macos_metadata::get_macos_metadata(&entry.path());
-                    (macos_meta.added_at, macos_meta.opened_at)
-                };
-                #[cfg(not(target_os = "macos"))]
-                let (added_at, opened_at) = (None, None);
-
-                entries.push(FileEntry {
-                    name: name.clone(),
-                    path: entry.path().to_string_lossy().to_string(),
-                    is_directory: is_dir,
-                    is_symlink,
-                    size: if metadata.is_file() { Some(metadata.len()) } else { None },
-                    modified_at: modified,
-                    created_at: created,
-                    added_at,
-                    opened_at,
-                    permissions: metadata.permissions().mode(),
-                    owner,
-                    group,
-                    icon_id: get_icon_id(is_dir, is_symlink, &name),
-                    extended_metadata_loaded: true,
-                });
-                entry_creation_time += create_start.elapsed();
-            }
-            Err(_) => {
-                // Permission denied or broken symlink—return minimal entry
-                let name = entry.file_name().to_string_lossy().to_string();
-                entries.push(FileEntry {
-                    name: name.clone(),
-                    path: entry.path().to_string_lossy().to_string(),
-                    is_directory: false,
-                    is_symlink,
-                    size: None,
-                    modified_at: None,
-                    created_at: None,
-                    added_at: None,
-                    opened_at: None,
-                    permissions: 0,
-                    owner: String::new(),
-                    group: String::new(),
-                    icon_id: if is_symlink {
-                        "symlink-broken".to_string()
-                    } else {
-                        "file".to_string()
-                    },
-                    extended_metadata_loaded: true,
-                });
-            }
-        }
+        // Symlinks to directories are always directories here, whatever `set_follow_symlinks_for_type` says
+        entries.push(file_entry_from_dir_entry(
+            &entry?,
+            true,
+            true,
+            &mut metadata_time,
+            &mut owner_lookup_time,
+        )?);
     }
 
-    let sort_start = std::time::Instant::now();
+    let sort_start = TimingStamp::now();
     // Sort: directories first, then files, both alphabetically (using natural sort)
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
-    let sort_time = sort_start.elapsed();
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
+    let sort_time = PhaseTime::since(sort_start);
 
-    let total_time = overall_start.elapsed();
-    eprintln!(
-        "[RUST TIMING] list_directory: path={}, entries={}, read_dir={}ms, metadata={}ms, owner={}ms, create={}ms, sort={}ms, total={}ms",
-        path.display(),
+    log_timing(
+        "list_directory",
+        path,
         entries.len(),
-        read_dir_time.as_millis(),
-        metadata_time.as_millis(),
-        owner_lookup_time.as_millis(),
-        entry_creation_time.as_millis(),
-        sort_time.as_millis(),
-        total_time.as_millis()
+        &[
+            ("read_dir", read_dir_time),
+            ("metadata", metadata_time),
+            ("owner", owner_lookup_time),
+            ("sort", sort_time),
+            ("total", PhaseTime::since(overall_start)),
+        ],
     );
 
     Ok(entries)
 }
 
+/// A point in time, for the `[RUST TIMING]` logs of the `timing` feature.
+/// Without the feature, it's zero-sized and doesn't read the clock, so listings aren't slowed down by timing.
+#[derive(Clone, Copy)]
+struct TimingStamp {
+    #[cfg(feature = "timing")]
+    at: std::time::Instant,
+}
+
+impl TimingStamp {
+    fn now() -> Self {
+        Self {
+            #[cfg(feature = "timing")]
+            at: std::time::Instant::now(),
+        }
+    }
+}
+
+/// Time spent in a phase of a listing, summed up from `TimingStamp`s. Zero-sized without the `timing` feature.
+#[derive(Clone, Copy, Default)]
+struct PhaseTime {
+    #[cfg(feature = "timing")]
+    total: std::time::Duration,
+}
+
+impl PhaseTime {
+    /// The time since `start`.
+    fn since(start: TimingStamp) -> Self {
+        let mut time = Self::default();
+        time.add_since(start);
+        time
+    }
+
+    /// Adds the time since `start`.
+    fn add_since(&mut self, start: TimingStamp) {
+        #[cfg(feature = "timing")]
+        {
+            self.total += start.at.elapsed();
+        }
+        #[cfg(not(feature = "timing"))]
+        let _ = start;
+    }
+}
+
+/// Prints a `[RUST TIMING]` line with the time of each phase of a listing. Only with the `timing` feature.
+fn log_timing(operation: &str, path: &Path, entry_count: usize, phases: &[(&str, PhaseTime)]) {
+    #[cfg(feature = "timing")]
+    {
+        let phases: Vec<String> = phases
+            .iter()
+            .map(|(name, time)| format!("{}={}ms", name, time.total.as_millis()))
+            .collect();
+        eprintln!(
+            "[RUST TIMING] {}: path={}, entries={}, {}",
+            operation,
+            path.display(),
+            entry_count,
+            phases.join(", ")
+        );
+    }
+    #[cfg(not(feature = "timing"))]
+    let _ = (operation, path, entry_count, phases);
+}
+
 // ============================================================================
 // On-demand virtual scrolling API (listing-based, fetch by range)
 // ============================================================================
@@ -422,6 +601,11 @@ pub struct ListingStartResult {
     /// Maximum filename width in pixels (for Brief mode columns)
     /// None if font metrics are not available
     pub max_filename_width: Option<f32>,
+    /// The sort the listing uses, which differs from the requested one if a saved sort was applied
+    pub sort_by: SortColumn,
+    pub sort_order: SortOrder,
+    /// True if the directory can't be read for lack of permissions. The listing is then empty.
+    pub access_denied: bool,
 }
 
 /// Starts a new directory listing.
@@ -432,12 +616,25 @@ pub struct ListingStartResult {
 /// # Arguments
 /// * `path` - The directory path to list
 /// * `include_hidden` - Whether to include hidden files in total count
+/// * `resolve_owners` - Whether to look up owner and group names, see `list_directory_core`
 ///
 /// # Returns
 /// A `ListingStartResult` with listing ID and total count.
-pub fn list_directory_start(path: &Path, include_hidden: bool) -> Result<ListingStartResult, std::io::Error> {
+pub fn list_directory_start(
+    path: &Path,
+    include_hidden: bool,
+    resolve_owners: bool,
+) -> Result<ListingStartResult, std::io::Error> {
     // Use the default volume from VolumeManager with default sorting
-    list_directory_start_with_volume("root", path, include_hidden, SortColumn::Name, SortOrder::Ascending)
+    list_directory_start_with_volume(
+        "root",
+        path,
+        include_hidden,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        resolve_owners,
+    )
 }
 
 /// Starts a new directory listing using a specific volume.
@@ -450,15 +647,21 @@ pub fn list_directory_start(path: &Path, include_hidden: bool) -> Result<Listing
 /// * `include_hidden` - Whether to include hidden files in total count
 /// * `sort_by` - Column to sort by
 /// * `sort_order` - Ascending or descending
+/// * `case_sensitive` - Whether to compare names case-sensitively
+/// * `resolve_owners` - Whether to look up owner and group names. If false, volumes that can skip the lookup
+///   leave them as numeric IDs, see `Volume::list_directory_numeric_owners`.
 ///
 /// # Returns
-/// A `ListingStartResult` with listing ID and total count.
+/// A `ListingStartResult` with listing ID and total count. A directory the user may not read gives an empty
+/// listing with `access_denied`, rather than an error.
 pub fn list_directory_start_with_volume(
     volume_id: &str,
     path: &Path,
     include_hidden: bool,
     sort_by: SortColumn,
     sort_order: SortOrder,
+    case_sensitive: bool,
+    resolve_owners: bool,
 ) -> Result<ListingStartResult, std::io::Error> {
     // Reset benchmark epoch for this navigation
     benchmark::reset_epoch();
@@ -473,26 +676,35 @@ pub fn list_directory_start_with_volume(
     })?;
 
     // Use the Volume trait to list the directory
-    let all_entries = volume
-        .list_directory(path)
-        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    let listed = if resolve_owners {
+        volume.list_directory(path)
+    } else {
+        volume.list_directory_numeric_owners(path)
+    };
+    let (all_entries, access_denied) = match listed {
+        Ok(entries) => (entries, false),
+        // Still a listing, so the UI can show the folder as locked instead of failing to navigate
+        Err(VolumeError::PermissionDenied(_)) => (Vec::new(), true),
+        Err(e) => return Err(std::io::Error::other(e.to_string())),
+    };
     benchmark::log_event_value("volume.list_directory COMPLETE, entries", all_entries.len());
 
     // Generate listing ID
     let listing_id = Uuid::new_v4().to_string();
 
-    // Count visible entries based on include_hidden setting
-    let total_count = if include_hidden {
-        all_entries.len()
-    } else {
-        all_entries.iter().filter(|e| !e.name.starts_with('.')).count()
-    };
+    // Count visible entries based on include_hidden setting and ignore patterns
+    let ignore_patterns = current_ignore_patterns();
+    let total_count = all_entries
+        .iter()
+        .filter(|e| is_visible(e, include_hidden, &ignore_patterns))
+        .count();
 
     // Sort the entries
     let mut all_entries = all_entries;
-    sort_entries(&mut all_entries, sort_by, sort_order);
+    sort_listing_entries(path, &mut all_entries, sort_by, sort_order, case_sensitive);
 
     // Cache the entries FIRST (watcher will read from here)
+    start_listing_cache_sweeper();
     if let Ok(mut cache) = LISTING_CACHE.write() {
         cache.insert(
             listing_id.clone(),
@@ -502,17 +714,16 @@ pub fn list_directory_start_with_volume(
                 entries: all_entries.clone(),
                 sort_by,
                 sort_order,
+                case_sensitive,
+                ignore_patterns,
+                last_accessed: AccessTime::now(),
             },
         );
     }
 
-    // Start watching the directory (only if volume supports it)
-    // For now, we still use the absolute path for watching
-    // TODO: Update watcher to be volume-aware
-    if volume.supports_watching() {
-        // For LocalPosixVolume, the path is already absolute or needs to be resolved
-        // We use the original path since LocalPosixVolume root is "/"
-        if let Err(e) = start_watching(&listing_id, path) {
+    // Start watching the directory (only if volume supports it, and it can be read)
+    if volume.supports_watching() && !access_denied {
+        if let Err(e) = start_watching(&listing_id, volume.as_ref(), path) {
             eprintln!("[LISTING] Failed to start watcher: {}", e);
             // Continue anyway - watcher is optional enhancement
         }
@@ -530,9 +741,260 @@ pub fn list_directory_start_with_volume(
         listing_id,
         total_count,
         max_filename_width,
+        sort_by,
+        sort_order,
+        access_denied,
     })
 }
 
+// ============================================================================
+// Streaming directory listing (for very large directories)
+// ============================================================================
+
+/// Number of entries per `listing-chunk` event.
+const STREAM_CHUNK_SIZE: usize = 1000;
+
+/// Cancellation flags for listings that are still being streamed (listing_id -> cancelled).
+static STREAM_CANCEL_FLAGS: LazyLock<RwLock<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// A batch of entries read while streaming a listing. Entries are sorted within the chunk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingChunk {
+    pub listing_id: String,
+    /// The entries in this batch (hidden files are left out unless `include_hidden` was set)
+    pub entries: Vec<FileEntry>,
+    /// Number of entries read so far, including this chunk and hidden files
+    pub loaded_count: usize,
+}
+
+/// Sent when a streamed listing is fully read, sorted, and cached.
+/// From this point, `get_file_range` and friends work with the listing ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingComplete {
+    pub listing_id: String,
+    /// Total number of (visible) entries in the directory
+    pub total_count: usize,
+    /// Maximum filename width in pixels (for Brief mode columns)
+    pub max_filename_width: Option<f32>,
+}
+
+/// Events sent while streaming a directory listing.
+#[derive(Debug, Clone)]
+pub enum ListingStreamEvent {
+    Chunk(ListingChunk),
+    Complete(ListingComplete),
+}
+
+/// Starts streaming a directory listing in the background.
+///
+/// Unlike `list_directory_start`, this returns the listing ID right away, then reads the directory on a
+/// background thread and reports `ListingStreamEvent::Chunk`s as batches of entries are stat'd, so the UI
+/// can render the first rows immediately. Each chunk is sorted on its own. When the whole directory is read,
+/// the entries are sorted as a whole (the stable sort merges the pre-sorted chunks cheaply), cached, watched,
+/// and `ListingStreamEvent::Complete` is sent.
+///
+/// The stream is cancelled by `cancel_listing_stream` or `list_directory_end`; a cancelled listing is
+/// never cached and no completion event is sent.
+///
+/// Only volumes on the local file system are supported, as it reads the directory directly rather than through
+/// the `Volume`. The volume is used for watching.
+///
+/// # Arguments
+/// * `volume_id` - ID of the volume the directory is on
+/// * `path` - The directory path to list, relative to the volume root
+/// * `include_hidden` - Whether to include hidden files in chunks and the total count
+/// * `sort_by` - Column to sort by
+/// * `sort_order` - Ascending or descending
+/// * `case_sensitive` - Whether to compare names case-sensitively
+/// * `on_event` - Called (from the background thread) for every chunk and on completion
+///
+/// # Returns
+/// The listing ID, or an error if the directory can't be opened.
+pub fn list_directory_start_streaming<F>(
+    volume_id: &str,
+    path: &Path,
+    include_hidden: bool,
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    case_sensitive: bool,
+    on_event: F,
+) -> Result<String, std::io::Error>
+where
+    F: Fn(ListingStreamEvent) + Send + 'static,
+{
+    let volume = super::get_volume_manager().get(volume_id).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Volume '{}' not found", volume_id),
+        )
+    })?;
+    let local_path = volume.root().join(path.strip_prefix("/").unwrap_or(path));
+    // Open the directory synchronously so errors (not found, permission denied) reach the caller
+    let read_dir = fs::read_dir(&local_path)?;
+
+    let listing_id = Uuid::new_v4().to_string();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Ok(mut flags) = STREAM_CANCEL_FLAGS.write() {
+        flags.insert(listing_id.clone(), cancelled.clone());
+    }
+
+    let thread_listing_id = listing_id.clone();
+    let thread_volume_id = volume_id.to_string();
+    let path = path.to_path_buf();
+    std::thread::spawn(move || {
+        stream_directory(
+            &thread_listing_id,
+            (&thread_volume_id, volume.as_ref(), &path),
+            read_dir,
+            include_hidden,
+            (sort_by, sort_order, case_sensitive),
+            &cancelled,
+            &on_event,
+        );
+        if let Ok(mut flags) = STREAM_CANCEL_FLAGS.write() {
+            flags.remove(&thread_listing_id);
+        }
+    });
+
+    Ok(listing_id)
+}
+
+/// Cancels a listing that's still being streamed.
+///
+/// # Returns
+/// True if the listing was still streaming.
+pub fn cancel_listing_stream(listing_id: &str) -> bool {
+    let flags = match STREAM_CANCEL_FLAGS.read() {
+        Ok(flags) => flags,
+        Err(_) => return false,
+    };
+    match flags.get(listing_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Reads the directory in chunks, then sorts, caches, and watches it. Runs on the streaming thread.
+/// `path` is relative to the volume, like in listings started with `list_directory_start_with_volume`.
+fn stream_directory(
+    listing_id: &str,
+    (volume_id, volume, path): (&str, &dyn Volume, &Path),
+    read_dir: fs::ReadDir,
+    include_hidden: bool,
+    (sort_by, sort_order, case_sensitive): (SortColumn, SortOrder, bool),
+    cancelled: &AtomicBool,
+    on_event: &dyn Fn(ListingStreamEvent),
+) {
+    let mut metadata_time = PhaseTime::default();
+    let mut owner_lookup_time = PhaseTime::default();
+    let mut all_entries: Vec<FileEntry> = Vec::new();
+    let mut chunk: Vec<FileEntry> = Vec::with_capacity(STREAM_CHUNK_SIZE);
+    let ignore_patterns = current_ignore_patterns();
+    let follow_symlinks = follow_symlinks_for_type();
+
+    let send_chunk = |chunk: &mut Vec<FileEntry>, loaded_count: usize| {
+        sort_entries(chunk, sort_by, sort_order, case_sensitive);
+        let entries = chunk
+            .iter()
+            .filter(|e| is_visible(e, include_hidden, &ignore_patterns))
+            .cloned()
+            .collect();
+        on_event(ListingStreamEvent::Chunk(ListingChunk {
+            listing_id: listing_id.to_string(),
+            entries,
+            loaded_count,
+        }));
+    };
+
+    for dir_entry in read_dir {
+        if cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        // Skip entries that vanish or can't be read mid-listing rather than failing the whole stream
+        let Ok(dir_entry) = dir_entry else { continue };
+        let Ok(entry) = file_entry_from_dir_entry(
+            &dir_entry,
+            follow_symlinks,
+            false,
+            &mut metadata_time,
+            &mut owner_lookup_time,
+        ) else {
+            continue;
+        };
+        chunk.push(entry);
+
+        if chunk.len() >= STREAM_CHUNK_SIZE {
+            let loaded_count = all_entries.len() + chunk.len();
+            send_chunk(&mut chunk, loaded_count);
+            all_entries.append(&mut chunk);
+        }
+    }
+    if !chunk.is_empty() {
+        let loaded_count = all_entries.len() + chunk.len();
+        send_chunk(&mut chunk, loaded_count);
+        all_entries.append(&mut chunk);
+    }
+
+    // Final sort over the concatenated, individually sorted chunks
+    sort_listing_entries(path, &mut all_entries, sort_by, sort_order, case_sensitive);
+
+    let total_count = all_entries
+        .iter()
+        .filter(|e| is_visible(e, include_hidden, &ignore_patterns))
+        .count();
+    let max_filename_width = {
+        let filenames: Vec<&str> = all_entries.iter().map(|e| e.name.as_str()).collect();
+        crate::font_metrics::calculate_max_width(&filenames, "system-400-12")
+    };
+
+    // Check for cancellation while holding the cache lock, so `list_directory_end` can't slip in between
+    start_listing_cache_sweeper();
+    {
+        let Ok(mut cache) = LISTING_CACHE.write() else {
+            return;
+        };
+        if cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        cache.insert(
+            listing_id.to_string(),
+            CachedListing {
+                volume_id: volume_id.to_string(),
+                path: path.to_path_buf(),
+                entries: all_entries,
+                sort_by,
+                sort_order,
+                case_sensitive,
+                ignore_patterns,
+                last_accessed: AccessTime::now(),
+            },
+        );
+    }
+
+    if volume.supports_watching()
+        && let Err(e) = start_watching(listing_id, volume, path)
+    {
+        eprintln!("[LISTING] Failed to start watcher: {}", e);
+    }
+    // The listing may have been ended while the watcher was starting
+    if cancelled.load(Ordering::Relaxed) {
+        stop_watching(listing_id);
+        return;
+    }
+
+    on_event(ListingStreamEvent::Complete(ListingComplete {
+        listing_id: listing_id.to_string(),
+        total_count,
+        max_filename_width,
+    }));
+}
+
 /// Gets a range of entries from a cached listing.
 ///
 /// # Arguments
@@ -548,22 +1010,120 @@ pub fn get_file_range(
     start: usize,
     count: usize,
     include_hidden: bool,
-) -> Result<Vec<FileEntry>, String> {
-    let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
+) -> Result<Vec<FileEntry>, CommandError> {
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
 
     let listing = cache
         .get(listing_id)
-        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+    listing.last_accessed.touch();
 
-    // Filter entries if not including hidden
-    if include_hidden {
+    // Filter entries if not including hidden or if there are ignore patterns
+    let mut entries: Vec<FileEntry> = if include_hidden && listing.ignore_patterns.is_empty() {
         let end = (start + count).min(listing.entries.len());
-        Ok(listing.entries[start..end].to_vec())
+        listing.entries[start..end].to_vec()
     } else {
-        // Need to filter and then slice
-        let visible: Vec<&FileEntry> = listing.entries.iter().filter(|e| !e.name.starts_with('.')).collect();
-        let end = (start + count).min(visible.len());
-        Ok(visible[start..end].iter().cloned().cloned().collect())
+        listing
+            .visible_entries(include_hidden)
+            .skip(start)
+            .take(count)
+            .cloned()
+            .collect()
+    };
+    drop(cache);
+
+    // Let the background folder size task compute the visible folders first
+    if has_folder_size_task(listing_id) {
+        let visible_dirs = entries.iter().filter(|e| e.is_directory && !e.is_symlink);
+        set_visible_folders(listing_id, visible_dirs.map(|e| PathBuf::from(&e.path)));
+    }
+
+    apply_sniffed_icon_ids(&mut entries);
+    Ok(entries)
+}
+
+/// What a listing was started with, so it can be re-created if it expires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingOrigin {
+    /// The directory that was listed
+    pub path: PathBuf,
+    pub sort_by: SortColumn,
+    pub sort_order: SortOrder,
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+/// Result of getting a range with `get_file_range_or_recreate`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRangeResult {
+    pub entries: Vec<FileEntry>,
+    /// The ID of the re-created listing, if the original one had expired. The frontend should use this ID
+    /// from now on. None if the original listing was still there.
+    pub new_listing_id: Option<String>,
+}
+
+/// Gets a range of entries like `get_file_range`, but re-creates the listing if it's no longer in the cache.
+///
+/// Listings can disappear from under the frontend, for example when a listing is ended while a range request
+/// is in flight. Instead of failing, this lists `origin.path` again with the same sort and serves the range
+/// from the new listing.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`
+/// * `volume_id` - The volume to re-create the listing on
+/// * `origin` - What the listing was started with
+/// * `start` - Start index (0-based)
+/// * `count` - Number of entries to return
+/// * `include_hidden` - Whether to include hidden files
+pub fn get_file_range_or_recreate(
+    listing_id: &str,
+    volume_id: &str,
+    origin: &ListingOrigin,
+    start: usize,
+    count: usize,
+    include_hidden: bool,
+) -> Result<FileRangeResult, CommandError> {
+    match get_file_range(listing_id, start, count, include_hidden) {
+        Ok(entries) => Ok(FileRangeResult {
+            entries,
+            new_listing_id: None,
+        }),
+        Err(CommandError::ListingExpired { .. }) => {
+            let started = list_directory_start_with_volume(
+                volume_id,
+                &origin.path,
+                include_hidden,
+                origin.sort_by,
+                origin.sort_order,
+                origin.case_sensitive,
+                true,
+            )
+            .map_err(|e| {
+                CommandError::from_io(
+                    e,
+                    &format!("Failed to re-create listing for '{}'", origin.path.display()),
+                )
+            })?;
+            let entries = get_file_range(&started.listing_id, start, count, include_hidden)?;
+            Ok(FileRangeResult {
+                entries,
+                new_listing_id: Some(started.listing_id),
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Replaces the generic "file" icon ID with a content-based one (like "kind:script") for extensionless files.
+/// Only runs on the entries being returned to the frontend, so the I/O stays off the core listing path.
+/// Each file is only read once per modification time, however often its range is requested.
+fn apply_sniffed_icon_ids(entries: &mut [FileEntry]) {
+    for entry in entries.iter_mut().filter(|e| e.icon_id == "file") {
+        if let Some(kind) = sniff_kind_cached(Path::new(&entry.path), entry.modified_at) {
+            entry.icon_id = kind.icon_id();
+        }
     }
 }
 
@@ -575,18 +1135,14 @@ pub fn get_file_range(
 ///
 /// # Returns
 /// Total count of (visible) entries.
-pub fn get_total_count(listing_id: &str, include_hidden: bool) -> Result<usize, String> {
-    let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
+pub fn get_total_count(listing_id: &str, include_hidden: bool) -> Result<usize, CommandError> {
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
 
     let listing = cache
         .get(listing_id)
-        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
 
-    if include_hidden {
-        Ok(listing.entries.len())
-    } else {
-        Ok(listing.entries.iter().filter(|e| !e.name.starts_with('.')).count())
-    }
+    Ok(listing.visible_entries(include_hidden).count())
 }
 
 /// Gets the maximum filename width for a cached listing.
@@ -600,33 +1156,29 @@ pub fn get_total_count(listing_id: &str, include_hidden: bool) -> Result<usize,
 ///
 /// # Returns
 /// Maximum filename width in pixels, or None if font metrics are not available.
-pub fn get_max_filename_width(listing_id: &str, include_hidden: bool) -> Result<Option<f32>, String> {
-    let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
+pub fn get_max_filename_width(listing_id: &str, include_hidden: bool) -> Result<Option<f32>, CommandError> {
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
 
     let listing = cache
         .get(listing_id)
-        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
 
     let font_id = "system-400-12"; // Default font (must match list_directory_start_with_volume)
 
-    let max_width = if include_hidden {
-        let filenames: Vec<&str> = listing.entries.iter().map(|e| e.name.as_str()).collect();
-        crate::font_metrics::calculate_max_width(&filenames, font_id)
-    } else {
-        let filenames: Vec<&str> = listing
-            .entries
-            .iter()
-            .filter(|e| !e.name.starts_with('.'))
-            .map(|e| e.name.as_str())
-            .collect();
-        crate::font_metrics::calculate_max_width(&filenames, font_id)
-    };
+    let filenames: Vec<&str> = listing
+        .visible_entries(include_hidden)
+        .map(|e| e.name.as_str())
+        .collect();
+    let max_width = crate::font_metrics::calculate_max_width(&filenames, font_id);
 
     Ok(max_width)
 }
 
 /// Finds the index of a file by name in a cached listing.
 ///
+/// Prefers an exact match. On case-insensitive volumes, falls back to a case-insensitive match, since
+/// "readme" and "README" name the same file there (for example, when navigating to a typed path).
+///
 /// # Arguments
 /// * `listing_id` - The listing ID from `list_directory_start`
 /// * `name` - File name to find
@@ -634,23 +1186,206 @@ pub fn get_max_filename_width(listing_id: &str, include_hidden: bool) -> Result<
 ///
 /// # Returns
 /// Index of the file, or None if not found.
-pub fn find_file_index(listing_id: &str, name: &str, include_hidden: bool) -> Result<Option<usize>, String> {
-    let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
+pub fn find_file_index(listing_id: &str, name: &str, include_hidden: bool) -> Result<Option<usize>, CommandError> {
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
 
     let listing = cache
         .get(listing_id)
-        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
 
-    if include_hidden {
-        Ok(listing.entries.iter().position(|e| e.name == name))
-    } else {
-        // Find index in filtered list
-        let visible: Vec<&FileEntry> = listing.entries.iter().filter(|e| !e.name.starts_with('.')).collect();
-        Ok(visible.iter().position(|e| e.name == name))
+    let visible: Vec<&FileEntry> = listing.visible_entries(include_hidden).collect();
+
+    if let Some(index) = visible.iter().position(|e| e.name == name) {
+        return Ok(Some(index));
+    }
+    if is_case_sensitive_volume(&listing.path) {
+        return Ok(None);
     }
+    Ok(visible.iter().position(|e| names_equal_ignoring_case(&e.name, name)))
 }
 
-/// Gets a single file at the given index.
+/// Finds the next entry whose name starts with `prefix` (case-insensitive), for Finder-style type-ahead.
+///
+/// The search starts at `from_index` (inclusive, so an accumulated prefix like "do" keeps the cursor on "dog"
+/// if it's already there) and wraps around to the start of the listing. Pass `cursor + 1` to jump to the
+/// next match instead. Unlike a filter, this keeps the listing as is and only moves the cursor.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`
+/// * `prefix` - The characters typed so far
+/// * `from_index` - Index to start searching from; values past the end start at the beginning
+/// * `include_hidden` - Whether to include hidden files when calculating indexes
+///
+/// # Returns
+/// Index of the matching entry, or None if no entry matches (or the prefix is empty).
+pub fn typeahead_find(
+    listing_id: &str,
+    prefix: &str,
+    from_index: usize,
+    include_hidden: bool,
+) -> Result<Option<usize>, CommandError> {
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
+
+    let listing = cache
+        .get(listing_id)
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+
+    let names: Vec<&str> = listing
+        .visible_entries(include_hidden)
+        .map(|e| e.name.as_str())
+        .collect();
+    Ok(find_prefix_match_wrapping(&names, prefix, from_index))
+}
+
+/// Returns the index of the first name at or after `from_index` that starts with `prefix` (case-insensitive),
+/// wrapping around past the end.
+fn find_prefix_match_wrapping(names: &[&str], prefix: &str, from_index: usize) -> Option<usize> {
+    if prefix.is_empty() || names.is_empty() {
+        return None;
+    }
+    let prefix = prefix.to_lowercase();
+    let start = if from_index < names.len() { from_index } else { 0 };
+    (start..names.len())
+        .chain(0..start)
+        .find(|&i| names[i].to_lowercase().starts_with(&prefix))
+}
+
+/// How `filter_listing` matches names against the query.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterMode {
+    /// The name contains the query, ignoring case
+    #[default]
+    Substring,
+    /// The query's characters appear in the name in order, but not necessarily next to each other, ignoring
+    /// case. So "vdmp4" matches "vacation-day.mp4".
+    Fuzzy,
+    /// The whole name matches a glob with `*`, `?`, and character classes like `[a-z]` or `[!0-9]`, ignoring case
+    Glob,
+    /// The name matches a regular expression, anywhere unless anchored with `^` and `$`. Case-sensitive unless
+    /// the expression starts with `(?i)`.
+    Regex,
+}
+
+/// A compiled filter query.
+enum NameFilter {
+    Substring(String),
+    Fuzzy(String),
+    Pattern(Regex),
+}
+
+impl NameFilter {
+    fn new(query: &str, mode: FilterMode) -> Result<Self, CommandError> {
+        let pattern = match mode {
+            FilterMode::Substring => return Ok(Self::Substring(query.to_lowercase())),
+            FilterMode::Fuzzy => return Ok(Self::Fuzzy(query.to_lowercase())),
+            FilterMode::Glob => format!("(?i){}", glob_to_regex(query)),
+            FilterMode::Regex => query.to_string(),
+        };
+        Regex::new(&pattern)
+            .map(Self::Pattern)
+            .map_err(|e| CommandError::InvalidArgument {
+                message: format!("Invalid filter '{}': {}", query, e),
+            })
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Substring(query) => name.to_lowercase().contains(query),
+            Self::Fuzzy(query) => {
+                let mut name_chars = name.chars().flat_map(char::to_lowercase);
+                query.chars().all(|q| name_chars.any(|c| c == q))
+            }
+            Self::Pattern(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// Translates a glob to an anchored regular expression: `*` is any run of characters, `?` is a single one,
+/// and `[…]` is a character class (negated with `[!…]`). Everything else is literal. A `[` without a matching
+/// `]` is literal too.
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut pattern = String::from("^");
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '[' => {
+                // A `]` right after the opening (or after `!`) is part of the class
+                let content_start = if chars.get(i + 1) == Some(&'!') { i + 2 } else { i + 1 };
+                let close = (content_start + 1..chars.len()).find(|&j| chars[j] == ']');
+                match close {
+                    Some(close) => {
+                        pattern.push('[');
+                        if content_start == i + 2 {
+                            pattern.push('^');
+                        }
+                        for &c in &chars[content_start..close] {
+                            if c == '-' {
+                                pattern.push(c);
+                            } else {
+                                pattern.push_str(&regex::escape(&c.to_string()));
+                            }
+                        }
+                        pattern.push(']');
+                        i = close;
+                    }
+                    None => pattern.push_str(&regex::escape("[")),
+                }
+            }
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// Returns the entries of a listing whose names match `query`, for type-to-filter.
+///
+/// Matching happens on the visible entries, so hidden and ignored files stay out unless `include_hidden`, and
+/// results keep the listing's sort order. The cached listing isn't changed.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`
+/// * `query` - The filter text. If empty, all visible entries are returned.
+/// * `include_hidden` - Whether to include hidden files
+/// * `mode` - How to match names, see `FilterMode`
+///
+/// # Returns
+/// The matching entries, in listing order, or `InvalidArgument` if the glob or regex doesn't parse.
+pub fn filter_listing(
+    listing_id: &str,
+    query: &str,
+    include_hidden: bool,
+    mode: FilterMode,
+) -> Result<Vec<FileEntry>, CommandError> {
+    let filter = if query.is_empty() {
+        None
+    } else {
+        Some(NameFilter::new(query, mode)?)
+    };
+
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
+
+    let listing = cache
+        .get(listing_id)
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+
+    let mut entries: Vec<FileEntry> = listing
+        .visible_entries(include_hidden)
+        .filter(|e| filter.as_ref().is_none_or(|f| f.matches(&e.name)))
+        .cloned()
+        .collect();
+    drop(cache);
+
+    apply_sniffed_icon_ids(&mut entries);
+    Ok(entries)
+}
+
+/// Gets a single file at the given index.
 ///
 /// # Arguments
 /// * `listing_id` - The listing ID from `list_directory_start`
@@ -659,19 +1394,21 @@ pub fn find_file_index(listing_id: &str, name: &str, include_hidden: bool) -> Re
 ///
 /// # Returns
 /// FileEntry at the index, or None if out of bounds.
-pub fn get_file_at(listing_id: &str, index: usize, include_hidden: bool) -> Result<Option<FileEntry>, String> {
-    let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
+pub fn get_file_at(listing_id: &str, index: usize, include_hidden: bool) -> Result<Option<FileEntry>, CommandError> {
+    let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
 
     let listing = cache
         .get(listing_id)
-        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+    listing.last_accessed.touch();
 
-    if include_hidden {
-        Ok(listing.entries.get(index).cloned())
-    } else {
-        let visible: Vec<&FileEntry> = listing.entries.iter().filter(|e| !e.name.starts_with('.')).collect();
-        Ok(visible.get(index).cloned().cloned())
+    let mut entry = listing.visible_entries(include_hidden).nth(index).cloned();
+    drop(cache);
+
+    if let Some(ref mut entry) = entry {
+        apply_sniffed_icon_ids(std::slice::from_mut(entry));
     }
+    Ok(entry)
 }
 
 /// Ends a directory listing and cleans up the cache.
@@ -679,15 +1416,99 @@ pub fn get_file_at(listing_id: &str, index: usize, include_hidden: bool) -> Resu
 /// # Arguments
 /// * `listing_id` - The listing ID to clean up
 pub fn list_directory_end(listing_id: &str) {
+    // Stop streaming, if the listing is still being read
+    cancel_listing_stream(listing_id);
+
     // Stop the file watcher
     stop_watching(listing_id);
 
+    // Stop computing folder sizes
+    cancel_folder_sizes(listing_id);
+
     // Remove from listing cache
     if let Ok(mut cache) = LISTING_CACHE.write() {
         cache.remove(listing_id);
     }
 }
 
+/// How long an unwatched listing may go without `get_file_range` or `get_file_at` before it's evicted.
+/// Catches listings that a crashed or reloaded frontend never ended.
+const LISTING_IDLE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// How often the sweeper looks for idle listings.
+const LISTING_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Whether the sweeper thread was started.
+static LISTING_SWEEPER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts the background thread that evicts idle listings, unless it's already running.
+fn start_listing_cache_sweeper() {
+    if LISTING_SWEEPER_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    std::thread::spawn(|| {
+        loop {
+            std::thread::sleep(LISTING_SWEEP_INTERVAL);
+            evict_idle_listings(LISTING_IDLE_TTL);
+        }
+    });
+}
+
+/// Ends the listings that haven't been accessed for longer than `ttl`, stopping their watchers too.
+///
+/// # Returns
+/// The IDs of the evicted listings.
+pub fn evict_idle_listings(ttl: Duration) -> Vec<String> {
+    let idle: Vec<String> = match LISTING_CACHE.read() {
+        Ok(cache) => cache
+            .iter()
+            .filter(|(_, listing)| listing.last_accessed.elapsed() > ttl)
+            .map(|(id, _)| id.clone())
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+    for listing_id in &idle {
+        list_directory_end(listing_id);
+    }
+    idle
+}
+
+/// Size of the listing cache, for diagnostics.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingCacheStats {
+    pub listing_count: usize,
+    pub entry_count: usize,
+    /// Estimated memory used by the entries, counting their strings but not allocator overhead
+    pub approximate_bytes: usize,
+}
+
+/// Returns how many listings are cached and roughly how much memory they take.
+pub fn cache_stats() -> ListingCacheStats {
+    let Ok(cache) = LISTING_CACHE.read() else {
+        return ListingCacheStats {
+            listing_count: 0,
+            entry_count: 0,
+            approximate_bytes: 0,
+        };
+    };
+    let entries = cache.values().flat_map(|listing| listing.entries.iter());
+    ListingCacheStats {
+        listing_count: cache.len(),
+        entry_count: cache.values().map(|listing| listing.entries.len()).sum(),
+        approximate_bytes: entries
+            .map(|e| {
+                std::mem::size_of::<FileEntry>()
+                    + e.name.capacity()
+                    + e.path.capacity()
+                    + e.owner.capacity()
+                    + e.group.capacity()
+                    + e.icon_id.capacity()
+            })
+            .sum(),
+    }
+}
+
 /// Result of re-sorting a directory listing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -716,38 +1537,108 @@ pub fn resort_listing(
     sort_order: SortOrder,
     cursor_filename: Option<&str>,
     include_hidden: bool,
-) -> Result<ResortResult, String> {
-    let mut cache = LISTING_CACHE.write().map_err(|_| "Failed to acquire cache lock")?;
+) -> Result<ResortResult, CommandError> {
+    let mut cache = LISTING_CACHE.write().map_err(|_| CommandError::lock_failed("cache"))?;
 
     let listing = cache
         .get_mut(listing_id)
-        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
-
-    // Re-sort the entries
-    sort_entries(&mut listing.entries, sort_by, sort_order);
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+
+    // Re-sort the entries. Switching away from manual order keeps it stored for next time.
+    sort_listing_entries(
+        &listing.path,
+        &mut listing.entries,
+        sort_by,
+        sort_order,
+        listing.case_sensitive,
+    );
     listing.sort_by = sort_by;
     listing.sort_order = sort_order;
 
     // Find the new cursor position
-    let new_cursor_index = cursor_filename.and_then(|name| {
-        if include_hidden {
-            listing.entries.iter().position(|e| e.name == name)
-        } else {
-            listing
-                .entries
-                .iter()
-                .filter(|e| !e.name.starts_with('.'))
-                .position(|e| e.name == name)
-        }
-    });
+    let new_cursor_index =
+        cursor_filename.and_then(|name| listing.visible_entries(include_hidden).position(|e| e.name == name));
 
     Ok(ResortResult { new_cursor_index })
 }
 
+/// Sets a manual order for a listing and switches it to `SortColumn::Manual`.
+///
+/// The order is remembered for the directory, so it comes back when the directory is listed again
+/// with manual sorting, or when switching back to manual from another sort column.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`
+/// * `ordered_paths` - Paths of the listing's entries in the desired order. Entries left out go to
+///   the end, in their current order. Paths not in the listing are ignored.
+pub fn set_manual_order(listing_id: &str, ordered_paths: &[String]) -> Result<(), CommandError> {
+    let mut cache = LISTING_CACHE.write().map_err(|_| CommandError::lock_failed("cache"))?;
+
+    let listing = cache
+        .get_mut(listing_id)
+        .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+
+    let names: Vec<String> = ordered_paths
+        .iter()
+        .filter_map(|p| Path::new(p).file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .collect();
+    order_entries_by_names(&mut listing.entries, &names);
+    store_manual_order(&listing.path, listing.entries.iter().map(|e| e.name.clone()).collect());
+    listing.sort_by = SortColumn::Manual;
+
+    Ok(())
+}
+
+/// Starts computing the sizes of all subdirectories of a listing in the background.
+///
+/// Each computed size is stored in the cached entry (so `get_file_range` returns it and size sorting
+/// uses it), then passed to `on_size`. Folders in the range last fetched with `get_file_range` go first.
+/// Stops when the listing ends.
+///
+/// # Arguments
+/// * `listing_id` - The listing ID from `list_directory_start`
+/// * `on_size` - Called from a background thread with each folder path and its size in bytes
+pub fn start_folder_size_computation<F>(listing_id: &str, on_size: F) -> Result<(), CommandError>
+where
+    F: Fn(&Path, u64) + Send + Sync + 'static,
+{
+    let dirs: Vec<PathBuf> = {
+        let cache = LISTING_CACHE.read().map_err(|_| CommandError::lock_failed("cache"))?;
+        let listing = cache
+            .get(listing_id)
+            .ok_or_else(|| CommandError::listing_expired(listing_id))?;
+        listing
+            .entries
+            .iter()
+            .filter(|e| e.is_directory && !e.is_symlink)
+            .map(|e| PathBuf::from(&e.path))
+            .collect()
+    };
+
+    let owned_listing_id = listing_id.to_string();
+    start_folder_sizes(listing_id, dirs, move |dir, size| {
+        if let Ok(mut cache) = LISTING_CACHE.write()
+            && let Some(listing) = cache.get_mut(&owned_listing_id)
+            && let Some(entry) = listing.entries.iter_mut().find(|e| Path::new(&e.path) == dir)
+        {
+            entry.size = Some(size);
+        }
+        on_size(dir, size);
+    });
+    Ok(())
+}
+
 // ============================================================================
 // Internal cache accessors for file watcher
 // ============================================================================
 
+/// Returns the directory a cached listing is for, or None if the listing isn't in the cache.
+pub fn get_listing_path(listing_id: &str) -> Option<PathBuf> {
+    let cache = LISTING_CACHE.read().ok()?;
+    cache.get(listing_id).map(|listing| listing.path.clone())
+}
+
 /// Gets entries and path from the listing cache (for watcher diff computation).
 /// Returns None if listing not found.
 pub(super) fn get_listing_entries(listing_id: &str) -> Option<(std::path::PathBuf, Vec<FileEntry>)> {
@@ -757,10 +1648,18 @@ pub(super) fn get_listing_entries(listing_id: &str) -> Option<(std::path::PathBu
 }
 
 /// Updates the entries in the listing cache (after watcher detects changes).
-pub(super) fn update_listing_entries(listing_id: &str, entries: Vec<FileEntry>) {
+/// Re-applies the listing's sort, so new files land in the right place (at the end for manual order).
+pub(super) fn update_listing_entries(listing_id: &str, mut entries: Vec<FileEntry>) {
     if let Ok(mut cache) = LISTING_CACHE.write()
         && let Some(listing) = cache.get_mut(listing_id)
     {
+        sort_listing_entries(
+            &listing.path,
+            &mut entries,
+            listing.sort_by,
+            listing.sort_order,
+            listing.case_sensitive,
+        );
         listing.entries = entries;
     }
 }
@@ -769,6 +1668,217 @@ pub(super) fn update_listing_entries(listing_id: &str, entries: Vec<FileEntry>)
 // Two-phase metadata loading: Fast core data, then extended metadata
 // ============================================================================
 
+/// A directory entry with its stat results, before owner names and icons are resolved.
+struct StattedEntry {
+    name: String,
+    path: String,
+    is_symlink: bool,
+    /// For symlinks: whether the TARGET is a directory
+    target_is_dir: bool,
+    /// For symlinks: where the link points, as stored in the link
+    symlink_target: Option<String>,
+    /// None if the metadata couldn't be read (permission denied, broken symlink)
+    metadata: Option<fs::Metadata>,
+}
+
+/// Whether symlinks to directories are listed as directories. See `set_follow_symlinks_for_type`.
+static FOLLOW_SYMLINKS_FOR_TYPE: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether listings classify symlinks by their target (a link to a folder navigates like a folder)
+/// or by the link itself (links are always files, so they can be managed without entering the target).
+///
+/// Applies to listings read from now on, including watcher refreshes of open listings.
+pub fn set_follow_symlinks_for_type(follow: bool) {
+    FOLLOW_SYMLINKS_FOR_TYPE.store(follow, Ordering::Relaxed);
+}
+
+fn follow_symlinks_for_type() -> bool {
+    FOLLOW_SYMLINKS_FOR_TYPE.load(Ordering::Relaxed)
+}
+
+/// Size to show for an entry: None for directories, including symlinks to them, like for links and other
+/// non-files. Takes the metadata of the link itself for symlinks.
+fn entry_size(metadata: &fs::Metadata, is_dir: bool) -> Option<u64> {
+    if metadata.is_file() && !is_dir {
+        Some(metadata.len())
+    } else {
+        None
+    }
+}
+
+/// Reads the metadata of a directory entry (of the link itself for symlinks).
+///
+/// With `follow_symlinks_for_type`, it also checks whether a symlink's target is a directory. Without it,
+/// that extra stat is skipped, which also avoids waiting on links to slow network locations.
+fn stat_dir_entry(entry: &fs::DirEntry, follow_symlinks_for_type: bool) -> Result<StattedEntry, std::io::Error> {
+    let file_type = entry.file_type()?;
+    let is_symlink = file_type.is_symlink();
+
+    // For symlinks, check if the TARGET is a directory
+    let target_is_dir = if is_symlink && follow_symlinks_for_type {
+        fs::metadata(entry.path()).map(|m| m.is_dir()).unwrap_or(false)
+    } else {
+        false
+    };
+
+    // For symlinks, get metadata of the link itself (not target)
+    let metadata = if is_symlink {
+        fs::symlink_metadata(entry.path())
+    } else {
+        entry.metadata()
+    };
+
+    Ok(StattedEntry {
+        name: entry.file_name().to_string_lossy().to_string(),
+        path: entry.path().to_string_lossy().to_string(),
+        is_symlink,
+        target_is_dir,
+        symlink_target: if is_symlink {
+            read_symlink_target(&entry.path())
+        } else {
+            None
+        },
+        metadata: metadata.ok(),
+    })
+}
+
+/// Reads the metadata of a single path (of the link itself for symlinks), like `stat_dir_entry` does for
+/// directory entries. Symlinks to directories count as directories. Fails if the path doesn't exist.
+fn stat_path(path: &Path) -> Result<StattedEntry, std::io::Error> {
+    let metadata = fs::symlink_metadata(path)?;
+    let is_symlink = metadata.file_type().is_symlink();
+
+    Ok(StattedEntry {
+        name: path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string()),
+        path: path.to_string_lossy().to_string(),
+        is_symlink,
+        target_is_dir: is_symlink && fs::metadata(path).is_ok_and(|m| m.is_dir()),
+        symlink_target: if is_symlink { read_symlink_target(path) } else { None },
+        metadata: Some(metadata),
+    })
+}
+
+/// Builds a FileEntry from stat results. This is the one place entries are made, for listings and single paths.
+/// Entries whose metadata couldn't be read get a placeholder entry.
+/// Without `resolve_owner`, owner and group are the numeric uid and gid.
+/// Without `include_extended`, the macOS-specific fields (added_at, opened_at) are left to load later, which
+/// saves system calls per entry, and `extended_metadata_loaded` is false.
+fn build_file_entry(statted: StattedEntry, resolve_owner: bool, include_extended: bool) -> FileEntry {
+    let StattedEntry {
+        name,
+        path,
+        is_symlink,
+        target_is_dir,
+        symlink_target,
+        metadata,
+    } = statted;
+
+    match metadata {
+        Some(metadata) => {
+            let is_dir = metadata.is_dir() || target_is_dir;
+
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+
+            let created = metadata
+                .created()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+
+            let (owner, group) = if resolve_owner {
+                (get_owner_name(metadata.uid()), get_group_name(metadata.gid()))
+            } else {
+                (metadata.uid().to_string(), metadata.gid().to_string())
+            };
+
+            // Skipping macOS metadata is the key optimization of core listings
+            #[cfg(target_os = "macos")]
+            let (added_at, opened_at) = if include_extended {
+                let macos_meta = super::macos_metadata::get_macos_metadata(Path::new(&path));
+                (macos_meta.added_at, macos_meta.opened_at)
+            } else {
+                (None, None)
+            };
+            #[cfg(not(target_os = "macos"))]
+            let (added_at, opened_at) = (None, None);
+
+            FileEntry {
+                icon_id: get_icon_id(is_dir, is_symlink, &name),
+                name,
+                path,
+                is_directory: is_dir,
+                is_symlink,
+                size: entry_size(&metadata, is_dir),
+                modified_at: modified,
+                created_at: created,
+                changed_at: u64::try_from(metadata.ctime()).ok(),
+                added_at,
+                opened_at,
+                permissions: metadata.permissions().mode(),
+                owner,
+                group,
+                inode: Some(metadata.ino()),
+                extended_metadata_loaded: include_extended,
+                owner_resolved: resolve_owner,
+                symlink_target,
+            }
+        }
+        None => {
+            // Permission denied or broken symlink
+            FileEntry {
+                name,
+                path,
+                is_directory: false,
+                is_symlink,
+                size: None,
+                modified_at: None,
+                created_at: None,
+                changed_at: None,
+                added_at: None,
+                opened_at: None,
+                permissions: 0,
+                owner: String::new(),
+                group: String::new(),
+                icon_id: if is_symlink {
+                    "symlink-broken".to_string()
+                } else {
+                    "file".to_string()
+                },
+                inode: None,
+                extended_metadata_loaded: true, // Nothing to load for broken entries
+                owner_resolved: true,
+                symlink_target,
+            }
+        }
+    }
+}
+
+/// Builds a FileEntry from a directory entry, see `build_file_entry`.
+/// Adds the time spent on stat calls and on owner lookups (plus extended metadata) to the given accumulators.
+fn file_entry_from_dir_entry(
+    entry: &fs::DirEntry,
+    follow_symlinks_for_type: bool,
+    include_extended: bool,
+    metadata_time: &mut PhaseTime,
+    owner_lookup_time: &mut PhaseTime,
+) -> Result<FileEntry, std::io::Error> {
+    let meta_start = TimingStamp::now();
+    let statted = stat_dir_entry(entry, follow_symlinks_for_type)?;
+    metadata_time.add_since(meta_start);
+
+    let owner_start = TimingStamp::now();
+    let file_entry = build_file_entry(statted, true, include_extended);
+    owner_lookup_time.add_since(owner_start);
+    Ok(file_entry)
+}
+
 /// Lists the contents of a directory with CORE metadata only.
 ///
 /// This is significantly faster than `list_directory()` because it skips
@@ -776,139 +1886,143 @@ pub(super) fn update_listing_entries(listing_id: &str, entries: Vec<FileEntry>)
 ///
 /// Use `get_extended_metadata_batch()` to fetch extended metadata later.
 ///
+/// Symlinks are classified according to `set_follow_symlinks_for_type`.
+///
 /// # Arguments
 /// * `path` - The directory path to list
+/// * `resolve_owner` - Whether to look up owner and group names, the biggest cost after stat. If false,
+///   they're left as the numeric uid and gid with `owner_resolved = false`, for views that don't show them.
+///   Use `resolve_owners_batch()` to fetch the names later.
 ///
 /// # Returns
 /// A vector of FileEntry with `extended_metadata_loaded = false`
-pub fn list_directory_core(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
+pub fn list_directory_core(path: &Path, resolve_owner: bool) -> Result<Vec<FileEntry>, std::io::Error> {
+    list_directory_core_with_symlink_mode(path, follow_symlinks_for_type(), resolve_owner)
+}
+
+/// Like `list_directory_core`, with an explicit choice of how to classify symlinks.
+///
+/// # Arguments
+/// * `path` - The directory path to list
+/// * `follow_symlinks_for_type` - If true, symlinks to directories are directories. If false, `is_directory`
+///   comes from the link itself, so all symlinks are files. `is_symlink` is set either way.
+/// * `resolve_owner` - Whether to look up owner and group names, see `list_directory_core`
+pub fn list_directory_core_with_symlink_mode(
+    path: &Path,
+    follow_symlinks_for_type: bool,
+    resolve_owner: bool,
+) -> Result<Vec<FileEntry>, std::io::Error> {
     benchmark::log_event("list_directory_core START");
-    let overall_start = std::time::Instant::now();
+    let overall_start = TimingStamp::now();
     let mut entries = Vec::new();
 
     benchmark::log_event("readdir START");
-    let read_start = std::time::Instant::now();
-    let dir_entries: Vec<_> = fs::read_dir(path)?.collect();
-    let read_dir_time = read_start.elapsed();
+    let read_start = TimingStamp::now();
+    let dir_entries = fs::read_dir(path)?.collect::<Result<Vec<_>, _>>()?;
+    let read_dir_time = PhaseTime::since(read_start);
     benchmark::log_event_value("readdir END, count", dir_entries.len());
 
+    // Stat in parallel (uses rayon's global pool). Collecting an indexed iterator keeps the read_dir order.
     benchmark::log_event("stat_loop START");
-    let mut metadata_time = std::time::Duration::ZERO;
-    let mut owner_lookup_time = std::time::Duration::ZERO;
-
-    for entry in dir_entries {
-        let entry = entry?;
-
-        let meta_start = std::time::Instant::now();
-        let file_type = entry.file_type()?;
-        let is_symlink = file_type.is_symlink();
-
-        // For symlinks, check if the TARGET is a directory
-        let target_is_dir = if is_symlink {
-            fs::metadata(entry.path()).map(|m| m.is_dir()).unwrap_or(false)
-        } else {
-            false
-        };
-
-        // For symlinks, get metadata of the link itself (not target)
-        let metadata = if is_symlink {
-            fs::symlink_metadata(entry.path())
-        } else {
-            entry.metadata()
-        };
-        metadata_time += meta_start.elapsed();
-
-        match metadata {
-            Ok(metadata) => {
-                let name = entry.file_name().to_string_lossy().to_string();
-                let is_dir = metadata.is_dir() || target_is_dir;
-
-                let modified = metadata
-                    .modified()
-                    .ok()
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs());
-
-                let created = metadata
-                    .created()
-                    .ok()
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs());
-
-                let uid = metadata.uid();
-                let gid = metadata.gid();
-
-                let owner_start = std::time::Instant::now();
-                let owner = get_owner_name(uid);
-                let group = get_group_name(gid);
-                owner_lookup_time += owner_start.elapsed();
-
-                // SKIP macOS metadata - that's the key optimization!
-                entries.push(FileEntry {
-                    name: name.clone(),
-                    path: entry.path().to_string_lossy().to_string(),
-                    is_directory: is_dir,
-                    is_symlink,
-                    size: if metadata.is_file() { Some(metadata.len()) } else { None },
-                    modified_at: modified,
-                    created_at: created,
-                    added_at: None,  // Will be loaded later
-                    opened_at: None, // Will be loaded later
-                    permissions: metadata.permissions().mode(),
-                    owner,
-                    group,
-                    icon_id: get_icon_id(is_dir, is_symlink, &name),
-                    extended_metadata_loaded: false, // Not loaded yet!
-                });
-            }
-            Err(_) => {
-                // Permission denied or broken symlink
-                let name = entry.file_name().to_string_lossy().to_string();
-                entries.push(FileEntry {
-                    name: name.clone(),
-                    path: entry.path().to_string_lossy().to_string(),
-                    is_directory: false,
-                    is_symlink,
-                    size: None,
-                    modified_at: None,
-                    created_at: None,
-                    added_at: None,
-                    opened_at: None,
-                    permissions: 0,
-                    owner: String::new(),
-                    group: String::new(),
-                    icon_id: if is_symlink {
-                        "symlink-broken".to_string()
-                    } else {
-                        "file".to_string()
-                    },
-                    extended_metadata_loaded: true, // Nothing to load for broken entries
-                });
-            }
-        }
+    let meta_start = TimingStamp::now();
+    let statted_entries = dir_entries
+        .par_iter()
+        .map(|entry| stat_dir_entry(entry, follow_symlinks_for_type))
+        .collect::<Result<Vec<_>, _>>()?;
+    let metadata_time = PhaseTime::since(meta_start);
+
+    // Resolve each distinct owner and group once, so the per-entry lookups below are all cache hits
+    let owner_start = TimingStamp::now();
+    if resolve_owner {
+        let (uids, gids): (Vec<u32>, Vec<u32>) = statted_entries
+            .iter()
+            .filter_map(|e| e.metadata.as_ref())
+            .map(|m| (m.uid(), m.gid()))
+            .unzip();
+        prewarm_owners(uids, gids);
     }
+    // The owner caches are behind RwLocks, so the entries can be built in parallel too
+    entries.par_extend(
+        statted_entries
+            .into_par_iter()
+            .map(|statted| build_file_entry(statted, resolve_owner, false)),
+    );
+    let owner_lookup_time = PhaseTime::since(owner_start);
     benchmark::log_event_value("stat_loop END, entries", entries.len());
 
     // Sort: directories first, then files, both alphabetically (using natural sort)
     benchmark::log_event("sort START");
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
     benchmark::log_event("sort END");
 
-    let total_time = overall_start.elapsed();
-    eprintln!(
-        "[RUST TIMING] list_directory_core: path={}, entries={}, read_dir={}ms, metadata={}ms, owner={}ms, total={}ms",
-        path.display(),
+    log_timing(
+        "list_directory_core",
+        path,
         entries.len(),
-        read_dir_time.as_millis(),
-        metadata_time.as_millis(),
-        owner_lookup_time.as_millis(),
-        total_time.as_millis()
+        &[
+            ("read_dir", read_dir_time),
+            ("metadata", metadata_time),
+            ("owner", owner_lookup_time),
+            ("total", PhaseTime::since(overall_start)),
+        ],
     );
     benchmark::log_event("list_directory_core END");
 
     Ok(entries)
 }
 
+/// What kind of file system object a path points to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PathKind {
+    File,
+    Directory,
+    /// A symlink (the path itself, not its target). Check `canonical` to see whether the target resolves.
+    Symlink,
+    /// Anything else, like sockets, FIFOs, or device files
+    Other,
+}
+
+/// The different forms of a path, so the frontend doesn't have to round-trip lossy strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathInfo {
+    /// The path as given, lossily converted to UTF-8 for display
+    pub display: String,
+    /// The absolute path with all symlinks and `..` resolved. None if it can't be resolved
+    /// (missing path, broken symlink, or no permission to traverse a parent).
+    pub canonical: Option<String>,
+    /// Whether the path itself exists (a broken symlink exists, its target doesn't)
+    pub exists: bool,
+    /// What the path itself is. None if it doesn't exist.
+    pub kind: Option<PathKind>,
+}
+
+/// Gets the display, canonical, and existence info for a path.
+///
+/// Never fails: anything that can't be determined (for example, canonicalizing a broken symlink) is `None`.
+pub fn get_path_info(path: &Path) -> PathInfo {
+    let kind = fs::symlink_metadata(path).ok().map(|meta| {
+        let file_type = meta.file_type();
+        if file_type.is_symlink() {
+            PathKind::Symlink
+        } else if file_type.is_dir() {
+            PathKind::Directory
+        } else if file_type.is_file() {
+            PathKind::File
+        } else {
+            PathKind::Other
+        }
+    });
+
+    PathInfo {
+        display: path.to_string_lossy().to_string(),
+        canonical: fs::canonicalize(path).ok().map(|p| p.to_string_lossy().to_string()),
+        exists: kind.is_some(),
+        kind,
+    }
+}
+
 /// Gets metadata for a single file or directory path.
 ///
 /// This is used when we need metadata for a single path rather than listing
@@ -920,62 +2034,21 @@ pub fn list_directory_core(path: &Path) -> Result<Vec<FileEntry>, std::io::Error
 /// # Returns
 /// A FileEntry with metadata for the path
 pub fn get_single_entry(path: &Path) -> Result<FileEntry, std::io::Error> {
-    // Check if it's a symlink first
-    let symlink_meta = fs::symlink_metadata(path)?;
-    let is_symlink = symlink_meta.file_type().is_symlink();
-
-    // For symlinks, check if the target is a directory
-    let target_is_dir = if is_symlink {
-        fs::metadata(path).map(|m| m.is_dir()).unwrap_or(false)
-    } else {
-        false
-    };
-
-    // Use symlink metadata for the entry (not following the link)
-    let metadata = &symlink_meta;
-    let is_dir = metadata.is_dir() || target_is_dir;
-
-    let name = path
-        .file_name()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| path.to_string_lossy().to_string());
-
-    let modified = metadata
-        .modified()
-        .ok()
-        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d| d.as_secs());
-
-    let created = metadata
-        .created()
-        .ok()
-        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|d| d.as_secs());
-
-    let uid = metadata.uid();
-    let gid = metadata.gid();
-    let owner = get_owner_name(uid);
-    let group = get_group_name(gid);
+    Ok(build_file_entry(stat_path(path)?, true, false))
+}
 
-    Ok(FileEntry {
-        name: name.clone(),
-        path: path.to_string_lossy().to_string(),
-        is_directory: is_dir,
-        is_symlink,
-        size: if metadata.is_file() { Some(metadata.len()) } else { None },
-        modified_at: modified,
-        created_at: created,
-        added_at: None,
-        opened_at: None,
-        permissions: metadata.permissions().mode(),
-        owner,
-        group,
-        icon_id: get_icon_id(is_dir, is_symlink, &name),
-        extended_metadata_loaded: false,
-    })
+/// Gets the full metadata of a single path, including the extended fields a listing loads later.
+///
+/// For showing a file reached without listing its parent. Symlinks are described themselves, not their
+/// targets, so broken ones work too.
+///
+/// # Arguments
+/// * `path` - Absolute path to the file or directory
+pub fn get_path_metadata(path: &Path) -> Result<FileEntry, std::io::Error> {
+    Ok(build_file_entry(stat_path(path)?, true, true))
 }
 
-/// Extended metadata for a single file (macOS-specific fields).
+/// Extended metadata for a single file (macOS-specific fields, plus inode info for hardlink detection).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExtendedMetadata {
@@ -985,6 +2058,99 @@ pub struct ExtendedMetadata {
     pub added_at: Option<u64>,
     /// When the file was last opened (macOS only)
     pub opened_at: Option<u64>,
+    /// Inode number. Entries with the same device and inode are hardlinks to the same file.
+    pub inode: Option<u64>,
+    /// Number of hardlinks to the file (1 for regular files without extra links)
+    pub hardlink_count: Option<u64>,
+    /// ID of the device holding the file
+    pub device: Option<u64>,
+    /// The type description Finder shows, like "JPEG image" (macOS only)
+    pub kind: Option<String>,
+}
+
+/// Owner and group names for a file, for entries listed with `owner_resolved = false`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerNames {
+    /// File path (key for merging)
+    pub path: String,
+    /// None if the file couldn't be stat'd
+    pub owner: Option<String>,
+    pub group: Option<String>,
+}
+
+/// Looks up the owner and group names for a batch of file paths.
+///
+/// This is called after a listing made without owner names (see `list_directory_core`) when a view that
+/// shows them needs them.
+///
+/// # Arguments
+/// * `paths` - File paths to resolve owners for
+///
+/// # Returns
+/// Vector of OwnerNames for each path
+pub fn resolve_owners_batch(paths: Vec<String>) -> Vec<OwnerNames> {
+    let ids: Vec<Option<(u32, u32)>> = paths
+        .iter()
+        .map(|path| fs::symlink_metadata(path).ok().map(|m| (m.uid(), m.gid())))
+        .collect();
+    let (uids, gids): (Vec<u32>, Vec<u32>) = ids.iter().flatten().copied().unzip();
+    prewarm_owners(uids, gids);
+    paths
+        .into_iter()
+        .zip(ids)
+        .map(|(path, ids)| OwnerNames {
+            path,
+            owner: ids.map(|(uid, _)| get_owner_name(uid)),
+            group: ids.map(|(_, gid)| get_group_name(gid)),
+        })
+        .collect()
+}
+
+/// Paths per `extended-metadata-chunk` event.
+pub const EXTENDED_METADATA_CHUNK_SIZE: usize = 250;
+
+/// Maximum number of threads fetching extended metadata at the same time.
+const EXTENDED_METADATA_MAX_WORKERS: usize = 4;
+
+/// Fetches extended metadata for a single path.
+#[cfg(target_os = "macos")]
+fn fetch_extended_metadata(path_str: String) -> ExtendedMetadata {
+    let macos_meta = super::macos_metadata::get_macos_metadata(Path::new(&path_str));
+    let (inode, hardlink_count, device) = fetch_inode_info(Path::new(&path_str));
+    let kind = super::macos_metadata::get_kind(Path::new(&path_str));
+    ExtendedMetadata {
+        path: path_str,
+        added_at: macos_meta.added_at,
+        opened_at: macos_meta.opened_at,
+        inode,
+        hardlink_count,
+        device,
+        kind,
+    }
+}
+
+/// On non-macOS, there are no added/opened dates or kinds to fetch, only inode info.
+#[cfg(not(target_os = "macos"))]
+fn fetch_extended_metadata(path_str: String) -> ExtendedMetadata {
+    let (inode, hardlink_count, device) = fetch_inode_info(Path::new(&path_str));
+    ExtendedMetadata {
+        path: path_str,
+        added_at: None,
+        opened_at: None,
+        inode,
+        hardlink_count,
+        device,
+        kind: None,
+    }
+}
+
+/// Returns the inode, hardlink count, and device of a path, without following symlinks.
+fn fetch_inode_info(path: &Path) -> (Option<u64>, Option<u64>, Option<u64>) {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) => (Some(metadata.ino()), Some(metadata.nlink()), Some(metadata.dev())),
+        Err(_) => (None, None, None),
+    }
 }
 
 /// Fetches extended metadata for a batch of file paths.
@@ -997,37 +2163,53 @@ pub struct ExtendedMetadata {
 ///
 /// # Returns
 /// Vector of ExtendedMetadata for each path
-#[cfg(target_os = "macos")]
 pub fn get_extended_metadata_batch(paths: Vec<String>) -> Vec<ExtendedMetadata> {
-    use std::path::Path;
-
     benchmark::log_event_value("get_extended_metadata_batch START, count", paths.len());
-    let result: Vec<ExtendedMetadata> = paths
-        .into_iter()
-        .map(|path_str| {
-            let path = Path::new(&path_str);
-            let macos_meta = super::macos_metadata::get_macos_metadata(path);
-            ExtendedMetadata {
-                path: path_str,
-                added_at: macos_meta.added_at,
-                opened_at: macos_meta.opened_at,
-            }
-        })
-        .collect();
+    let result: Vec<ExtendedMetadata> = paths.into_iter().map(fetch_extended_metadata).collect();
     benchmark::log_event_value("get_extended_metadata_batch END, count", result.len());
     result
 }
 
-#[cfg(not(target_os = "macos"))]
-pub fn get_extended_metadata_batch(paths: Vec<String>) -> Vec<ExtendedMetadata> {
-    benchmark::log_event_value("get_extended_metadata_batch (non-macOS), count", paths.len());
-    // On non-macOS, there's no extended metadata to fetch
-    paths
-        .into_iter()
-        .map(|path_str| ExtendedMetadata {
-            path: path_str,
-            added_at: None,
-            opened_at: None,
-        })
-        .collect()
+/// Fetches extended metadata in chunks of `EXTENDED_METADATA_CHUNK_SIZE` paths, on up to
+/// `EXTENDED_METADATA_MAX_WORKERS` threads, calling `on_chunk` as each chunk completes.
+///
+/// Chunks complete in no particular order, so results are keyed by path, not by position.
+///
+/// # Arguments
+/// * `paths` - File paths to fetch extended metadata for
+/// * `on_chunk` - Called (from a worker thread) with the metadata of each completed chunk
+///
+/// # Returns
+/// Extended metadata for all paths, in no particular order.
+pub fn get_extended_metadata_chunked<F>(paths: Vec<String>, on_chunk: F) -> Vec<ExtendedMetadata>
+where
+    F: Fn(&[ExtendedMetadata]) + Sync,
+{
+    benchmark::log_event_value("get_extended_metadata_chunked START, count", paths.len());
+    let total = paths.len();
+    let chunks: Vec<Vec<String>> = paths
+        .chunks(EXTENDED_METADATA_CHUNK_SIZE)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+    let next_chunk = std::sync::atomic::AtomicUsize::new(0);
+    let results = std::sync::Mutex::new(Vec::with_capacity(total));
+    let worker_count = chunks.len().min(EXTENDED_METADATA_MAX_WORKERS);
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| {
+                while let Some(chunk) = chunks.get(next_chunk.fetch_add(1, Ordering::Relaxed)) {
+                    let metadata: Vec<ExtendedMetadata> = chunk.iter().cloned().map(fetch_extended_metadata).collect();
+                    on_chunk(&metadata);
+                    if let Ok(mut results) = results.lock() {
+                        results.extend(metadata);
+                    }
+                }
+            });
+        }
+    });
+
+    let result = results.into_inner().unwrap_or_default();
+    benchmark::log_event_value("get_extended_metadata_chunked END, count", result.len());
+    result
 }
@@ -1,9 +1,16 @@
 //! Tests for file system operations
 
-use super::operations::{get_extended_metadata_batch, list_directory_core};
+use super::operations::{
+    ListingOrigin, ListingStreamEvent, PathKind, SortColumn, SortOrder, cancel_listing_stream,
+    get_extended_metadata_batch, get_extended_metadata_chunked, get_file_range, get_file_range_or_recreate,
+    get_path_info, get_path_metadata, get_total_count, list_directory, list_directory_core,
+    list_directory_core_with_symlink_mode, list_directory_end, list_directory_start_streaming,
+    list_directory_start_with_volume, resolve_owners_batch,
+};
 use super::provider::FileSystemProvider;
 use super::real_provider::RealFileSystemProvider;
 use std::fs;
+use std::os::unix::fs::MetadataExt;
 
 #[test]
 fn test_list_directory() {
@@ -51,7 +58,7 @@ fn test_list_directory_core_returns_entries_without_extended_metadata() {
     let test_file = temp_dir.join("core_test.txt");
     fs::write(&test_file, "content").unwrap();
 
-    let entries = list_directory_core(&temp_dir).unwrap();
+    let entries = list_directory_core(&temp_dir, true).unwrap();
 
     // Cleanup
     let _ = fs::remove_file(&test_file);
@@ -71,6 +78,114 @@ fn test_list_directory_core_returns_entries_without_extended_metadata() {
     assert!(file_entry.opened_at.is_none());
 }
 
+#[test]
+fn test_list_directory_core_matches_sequential_listing() {
+    let temp_dir = std::env::temp_dir().join("cmdr_parallel_listing_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    for i in 0..500 {
+        fs::write(temp_dir.join(format!("file_{}.txt", i)), "x".repeat(i)).unwrap();
+    }
+    for i in 0..20 {
+        fs::create_dir(temp_dir.join(format!("dir_{}", i))).unwrap();
+    }
+    std::os::unix::fs::symlink(temp_dir.join("dir_0"), temp_dir.join("link_to_dir")).unwrap();
+    std::os::unix::fs::symlink(temp_dir.join("missing"), temp_dir.join("broken_link")).unwrap();
+
+    let parallel = list_directory_core_with_symlink_mode(&temp_dir, true, true).unwrap();
+    // The same listing on a single thread, so one entry at a time
+    let sequential = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(|| list_directory_core_with_symlink_mode(&temp_dir, true, true))
+        .unwrap();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+
+    assert_eq!(parallel.len(), 522);
+    assert_eq!(
+        serde_json::to_value(&parallel).unwrap(),
+        serde_json::to_value(&sequential).unwrap()
+    );
+}
+
+#[test]
+fn test_list_directory_core_resolves_owner_names() {
+    let temp_dir = std::env::temp_dir().join("cmdr_core_owner_test");
+    fs::create_dir_all(&temp_dir).unwrap();
+    let test_file = temp_dir.join("owned.txt");
+    fs::write(&test_file, "content").unwrap();
+
+    let entries = list_directory_core(&temp_dir, true).unwrap();
+    let uid = fs::metadata(&test_file).unwrap().uid();
+
+    // Cleanup
+    let _ = fs::remove_file(&test_file);
+    let _ = fs::remove_dir(&temp_dir);
+
+    let entry = entries.iter().find(|e| e.name == "owned.txt").unwrap();
+    assert!(entry.owner_resolved);
+    assert_eq!(entry.owner, crate::owner_cache::get_owner_name(uid));
+}
+
+#[test]
+fn test_list_directory_core_can_defer_owner_names() {
+    let temp_dir = std::env::temp_dir().join("cmdr_core_deferred_owner_test");
+    fs::create_dir_all(&temp_dir).unwrap();
+    let test_file = temp_dir.join("owned.txt");
+    fs::write(&test_file, "content").unwrap();
+
+    let entries = list_directory_core(&temp_dir, false).unwrap();
+    let resolved = list_directory_core(&temp_dir, true).unwrap();
+    let metadata = fs::metadata(&test_file).unwrap();
+    let owners = resolve_owners_batch(vec![
+        test_file.to_string_lossy().to_string(),
+        temp_dir.join("missing.txt").to_string_lossy().to_string(),
+    ]);
+
+    // Cleanup
+    let _ = fs::remove_file(&test_file);
+    let _ = fs::remove_dir(&temp_dir);
+
+    let entry = entries.iter().find(|e| e.name == "owned.txt").unwrap();
+    assert!(!entry.owner_resolved);
+    assert_eq!(entry.owner, metadata.uid().to_string());
+    assert_eq!(entry.group, metadata.gid().to_string());
+
+    // The batch fills in the same names as a resolved listing
+    let resolved_entry = resolved.iter().find(|e| e.name == "owned.txt").unwrap();
+    assert_eq!(owners.len(), 2);
+    assert_eq!(owners[0].path, test_file.to_string_lossy());
+    assert_eq!(owners[0].owner.as_deref(), Some(resolved_entry.owner.as_str()));
+    assert_eq!(owners[0].group.as_deref(), Some(resolved_entry.group.as_str()));
+    assert_eq!(owners[1].owner, None);
+    assert_eq!(owners[1].group, None);
+}
+
+/// Timing is compiled out by default, so this checks that the listings work without it.
+#[test]
+#[cfg(not(feature = "timing"))]
+fn test_listings_work_without_timing() {
+    let temp_dir = std::env::temp_dir().join("cmdr_no_timing_test");
+    fs::create_dir_all(temp_dir.join("folder")).unwrap();
+    fs::write(temp_dir.join("file.txt"), "content").unwrap();
+
+    let provider = RealFileSystemProvider;
+    let full = provider.list_directory(&temp_dir);
+    let core = list_directory_core(&temp_dir, true);
+
+    // Cleanup
+    let _ = fs::remove_file(temp_dir.join("file.txt"));
+    let _ = fs::remove_dir(temp_dir.join("folder"));
+    let _ = fs::remove_dir(&temp_dir);
+
+    for entries in [full.unwrap(), core.unwrap()] {
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["folder", "file.txt"]);
+    }
+}
+
 #[test]
 fn test_list_directory_core_is_sorted() {
     let temp_dir = std::env::temp_dir().join("cmdr_sort_test");
@@ -81,7 +196,7 @@ fn test_list_directory_core_is_sorted() {
     fs::write(temp_dir.join("alpha.txt"), "").unwrap();
     fs::create_dir(temp_dir.join("a_dir")).unwrap();
 
-    let entries = list_directory_core(&temp_dir).unwrap();
+    let entries = list_directory_core(&temp_dir, true).unwrap();
 
     // Cleanup
     let _ = fs::remove_file(temp_dir.join("zebra.txt"));
@@ -95,6 +210,66 @@ fn test_list_directory_core_is_sorted() {
     assert!(entries[0].is_directory);
 }
 
+/// Creates a directory with a real folder, a file, and a symlink to the folder.
+fn create_symlink_fixture(name: &str) -> std::path::PathBuf {
+    let temp_dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(temp_dir.join("real_dir")).unwrap();
+    fs::write(temp_dir.join("file.txt"), "content").unwrap();
+    std::os::unix::fs::symlink(temp_dir.join("real_dir"), temp_dir.join("link_to_dir")).unwrap();
+    temp_dir
+}
+
+#[test]
+fn test_list_directory_core_follows_symlinks_for_type() {
+    let temp_dir = create_symlink_fixture("cmdr_symlink_follow_test");
+
+    let entries = list_directory_core_with_symlink_mode(&temp_dir, true, true).unwrap();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    let link = entries.iter().find(|e| e.name == "link_to_dir").unwrap();
+    assert!(link.is_directory, "A symlink to a folder should be listed as a folder");
+    assert!(link.is_symlink);
+}
+
+#[test]
+fn test_list_directory_core_can_list_symlinks_as_files() {
+    let temp_dir = create_symlink_fixture("cmdr_symlink_no_follow_test");
+
+    let entries = list_directory_core_with_symlink_mode(&temp_dir, false, true).unwrap();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    let link = entries.iter().find(|e| e.name == "link_to_dir").unwrap();
+    assert!(!link.is_directory, "The symlink itself should be listed as a file");
+    assert!(link.is_symlink);
+    // Real folders are unaffected
+    let real_dir = entries.iter().find(|e| e.name == "real_dir").unwrap();
+    assert!(real_dir.is_directory);
+    assert!(!real_dir.is_symlink);
+}
+
+#[test]
+fn test_listings_report_symlink_targets() {
+    let temp_dir = create_symlink_fixture("cmdr_symlink_target_test");
+    std::os::unix::fs::symlink("file.txt", temp_dir.join("relative_link")).unwrap();
+    std::os::unix::fs::symlink("/missing/path", temp_dir.join("broken_link")).unwrap();
+
+    let core_entries = list_directory_core(&temp_dir, true).unwrap();
+    let full_entries = list_directory(&temp_dir).unwrap();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    for entries in [core_entries, full_entries] {
+        let target_of = |name: &str| entries.iter().find(|e| e.name == name).unwrap().symlink_target.clone();
+        let absolute_target = temp_dir.join("real_dir").to_string_lossy().to_string();
+        assert_eq!(target_of("link_to_dir"), Some(absolute_target));
+        // Targets are reported as stored in the link, not resolved
+        assert_eq!(target_of("relative_link").as_deref(), Some("file.txt"));
+        assert_eq!(target_of("broken_link").as_deref(), Some("/missing/path"));
+        assert_eq!(target_of("file.txt"), None);
+        assert_eq!(target_of("real_dir"), None);
+    }
+}
+
 #[test]
 fn test_get_extended_metadata_batch() {
     let temp_dir = std::env::temp_dir().join("cmdr_extended_test");
@@ -121,12 +296,57 @@ fn test_get_extended_metadata_batch() {
     }
 }
 
+#[test]
+fn test_extended_metadata_has_inode_info_for_hardlinks() {
+    let temp_dir = std::env::temp_dir().join("cmdr_extended_hardlink_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let original = temp_dir.join("original.txt");
+    let link = temp_dir.join("link.txt");
+    let other = temp_dir.join("other.txt");
+    fs::write(&original, "content").unwrap();
+    fs::hard_link(&original, &link).unwrap();
+    fs::write(&other, "content").unwrap();
+
+    let paths = [&original, &link, &other].map(|p| p.to_string_lossy().to_string());
+    let extended = get_extended_metadata_batch(paths.to_vec());
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    let (original, link, other) = (&extended[0], &extended[1], &extended[2]);
+    assert!(original.inode.is_some());
+    assert_eq!((original.inode, original.device), (link.inode, link.device));
+    assert_ne!(original.inode, other.inode);
+    assert_eq!(original.hardlink_count, Some(2));
+    assert_eq!(other.hardlink_count, Some(1));
+}
+
 #[test]
 fn test_get_extended_metadata_batch_empty_input() {
     let extended = get_extended_metadata_batch(vec![]);
     assert!(extended.is_empty());
 }
 
+#[test]
+fn test_get_extended_metadata_chunked_returns_all_paths() {
+    // Paths don't need to exist, missing files just get no extended metadata
+    let paths: Vec<String> = (0..2_345)
+        .map(|i| format!("/nonexistent/cmdr_chunked_{}.txt", i))
+        .collect();
+    let chunk_count = std::sync::atomic::AtomicUsize::new(0);
+
+    let extended = get_extended_metadata_chunked(paths.clone(), |chunk| {
+        assert!(chunk.len() <= super::operations::EXTENDED_METADATA_CHUNK_SIZE);
+        chunk_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    });
+
+    let mut returned: Vec<String> = extended.into_iter().map(|e| e.path).collect();
+    returned.sort();
+    let mut expected = paths;
+    expected.sort();
+    assert_eq!(returned, expected);
+    assert_eq!(chunk_count.into_inner(), 10);
+}
+
 // ============================================================================
 // Tests for get_single_entry
 // ============================================================================
@@ -173,3 +393,319 @@ fn test_get_single_entry_nonexistent() {
     let result = super::operations::get_single_entry(std::path::Path::new("/definitely_does_not_exist_12345"));
     assert!(result.is_err());
 }
+
+#[test]
+fn test_get_path_metadata_file_and_directory() {
+    let temp_dir = std::env::temp_dir().join("cmdr_path_metadata_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(temp_dir.join("folder")).unwrap();
+    fs::write(temp_dir.join("file.txt"), "content").unwrap();
+
+    let file = get_path_metadata(&temp_dir.join("file.txt")).unwrap();
+    let folder = get_path_metadata(&temp_dir.join("folder")).unwrap();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    assert_eq!(file.name, "file.txt");
+    assert!(!file.is_directory);
+    assert_eq!(file.size, Some(7));
+    assert!(file.extended_metadata_loaded);
+    assert_eq!(folder.name, "folder");
+    assert!(folder.is_directory);
+    assert_eq!(folder.size, None);
+    assert!(folder.extended_metadata_loaded);
+}
+
+#[test]
+fn test_get_path_metadata_broken_symlink() {
+    let temp_dir = std::env::temp_dir().join("cmdr_path_metadata_symlink_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    std::os::unix::fs::symlink("missing.txt", temp_dir.join("broken")).unwrap();
+
+    let broken = get_path_metadata(&temp_dir.join("broken")).unwrap();
+    let missing = get_path_metadata(&temp_dir.join("missing.txt"));
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    assert!(broken.is_symlink);
+    assert!(!broken.is_directory);
+    assert_eq!(broken.symlink_target.as_deref(), Some("missing.txt"));
+    assert!(missing.is_err());
+}
+
+// ============================================================================
+// Tests for path info
+// ============================================================================
+
+#[test]
+fn test_path_info_existing_file() {
+    let temp_dir = std::env::temp_dir().join("cmdr_path_info_file_test");
+    fs::create_dir_all(&temp_dir).unwrap();
+    let test_file = temp_dir.join("file.txt");
+    fs::write(&test_file, "content").unwrap();
+
+    let info = get_path_info(&test_file);
+
+    let expected_canonical = fs::canonicalize(&test_file).unwrap();
+    let _ = fs::remove_dir_all(&temp_dir);
+
+    assert!(info.exists);
+    assert_eq!(info.kind, Some(PathKind::File));
+    assert_eq!(info.display, test_file.to_string_lossy());
+    assert_eq!(info.canonical, Some(expected_canonical.to_string_lossy().to_string()));
+}
+
+#[test]
+fn test_path_info_missing_path() {
+    let info = get_path_info(std::path::Path::new("/nonexistent/cmdr/path"));
+
+    assert!(!info.exists);
+    assert_eq!(info.kind, None);
+    assert_eq!(info.canonical, None);
+    assert_eq!(info.display, "/nonexistent/cmdr/path");
+}
+
+#[test]
+fn test_path_info_broken_symlink() {
+    let temp_dir = std::env::temp_dir().join("cmdr_path_info_symlink_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let link = temp_dir.join("broken_link");
+    std::os::unix::fs::symlink(temp_dir.join("missing_target"), &link).unwrap();
+
+    let info = get_path_info(&link);
+
+    let _ = fs::remove_dir_all(&temp_dir);
+
+    assert!(info.exists);
+    assert_eq!(info.kind, Some(PathKind::Symlink));
+    assert_eq!(info.canonical, None);
+}
+
+// ============================================================================
+// Tests for streaming listings
+// ============================================================================
+
+#[test]
+fn test_streaming_listing_sends_chunks_then_completes() {
+    let temp_dir = std::env::temp_dir().join("cmdr_streaming_listing_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    for i in 0..2500 {
+        fs::write(temp_dir.join(format!("file_{:04}.txt", i)), "").unwrap();
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    super::init_volume_manager();
+    let listing_id = list_directory_start_streaming(
+        "root",
+        &temp_dir,
+        true,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        move |e| {
+            let _ = sender.send(e);
+        },
+    )
+    .unwrap();
+
+    let mut loaded_counts = Vec::new();
+    let complete = loop {
+        match receiver.recv_timeout(std::time::Duration::from_secs(10)).unwrap() {
+            ListingStreamEvent::Chunk(chunk) => {
+                assert_eq!(chunk.listing_id, listing_id);
+                loaded_counts.push(chunk.loaded_count);
+            }
+            ListingStreamEvent::Complete(complete) => break complete,
+        }
+    };
+
+    assert_eq!(loaded_counts, vec![1000, 2000, 2500]);
+    assert_eq!(complete.total_count, 2500);
+
+    // The listing is cached and sorted once complete
+    assert_eq!(get_total_count(&listing_id, true).unwrap(), 2500);
+    let first = get_file_range(&listing_id, 0, 2, true).unwrap();
+    assert_eq!(first[0].name, "file_0000.txt");
+    assert_eq!(first[1].name, "file_0001.txt");
+
+    list_directory_end(&listing_id);
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_streaming_listing_nonexistent_dir_fails_immediately() {
+    super::init_volume_manager();
+    let result = list_directory_start_streaming(
+        "root",
+        std::path::Path::new("/nonexistent/cmdr/streaming"),
+        true,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        |_| {},
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_streaming_listing_unknown_volume_fails_immediately() {
+    let result = list_directory_start_streaming(
+        "no-such-volume",
+        &std::env::temp_dir(),
+        true,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        |_| {},
+    );
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_cancelled_streaming_listing_is_neither_completed_nor_cached() {
+    let temp_dir = std::env::temp_dir().join("cmdr_streaming_cancel_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    for i in 0..2500 {
+        fs::write(temp_dir.join(format!("file_{:04}.txt", i)), "").unwrap();
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let (resume, paused) = std::sync::mpsc::channel::<()>();
+    super::init_volume_manager();
+    let listing_id = list_directory_start_streaming(
+        "root",
+        &temp_dir,
+        true,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        move |e| {
+            let is_chunk = matches!(e, ListingStreamEvent::Chunk(_));
+            let _ = sender.send(e);
+            // Holds the stream after each chunk until the test lets it go on
+            if is_chunk {
+                let _ = paused.recv();
+            }
+        },
+    )
+    .unwrap();
+
+    // Cancels while the stream is held after its first chunk
+    let first = receiver.recv_timeout(std::time::Duration::from_secs(10)).unwrap();
+    let was_streaming = cancel_listing_stream(&listing_id);
+    drop(resume);
+    let mut later_events = Vec::new();
+    while let Ok(event) = receiver.recv_timeout(std::time::Duration::from_secs(10)) {
+        later_events.push(event);
+    }
+    let range = get_file_range(&listing_id, 0, 10, true);
+
+    list_directory_end(&listing_id);
+    let _ = fs::remove_dir_all(&temp_dir);
+    assert!(matches!(first, ListingStreamEvent::Chunk(_)));
+    assert!(was_streaming);
+    assert!(
+        later_events.iter().all(|e| matches!(e, ListingStreamEvent::Chunk(_))),
+        "A cancelled stream must not complete"
+    );
+    assert!(range.is_err(), "A cancelled stream must not be cached");
+}
+
+#[test]
+fn test_get_file_range_recreates_expired_listing() {
+    let temp_dir = std::env::temp_dir().join("cmdr_recreate_listing_test");
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    fs::write(temp_dir.join("a.txt"), "").unwrap();
+    fs::write(temp_dir.join("b.txt"), "").unwrap();
+    fs::write(temp_dir.join(".hidden"), "").unwrap();
+    super::init_volume_manager();
+
+    let started = list_directory_start_with_volume(
+        "root",
+        &temp_dir,
+        false,
+        SortColumn::Name,
+        SortOrder::Descending,
+        false,
+        true,
+    )
+    .unwrap();
+    let origin = ListingOrigin {
+        path: temp_dir.clone(),
+        sort_by: SortColumn::Name,
+        sort_order: SortOrder::Descending,
+        case_sensitive: false,
+    };
+
+    // While the listing is cached, it's used as is
+    let cached = get_file_range_or_recreate(&started.listing_id, "root", &origin, 0, 10, false).unwrap();
+    assert_eq!(cached.new_listing_id, None);
+
+    // Evict it, like ending the listing while a range request is in flight
+    list_directory_end(&started.listing_id);
+    assert!(get_file_range(&started.listing_id, 0, 10, false).is_err());
+
+    let recreated = get_file_range_or_recreate(&started.listing_id, "root", &origin, 0, 10, false).unwrap();
+    let new_listing_id = recreated.new_listing_id.expect("listing should be re-created");
+    let names: Vec<String> = recreated.entries.into_iter().map(|e| e.name).collect();
+    let count = get_total_count(&new_listing_id, false).unwrap();
+
+    list_directory_end(&new_listing_id);
+    let _ = fs::remove_dir_all(&temp_dir);
+    assert_ne!(new_listing_id, started.listing_id);
+    // Same sort and hidden settings as the original listing
+    assert_eq!(names, vec!["b.txt", "a.txt"]);
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_cancel_unknown_stream() {
+    assert!(!cancel_listing_stream("no-such-listing"));
+}
+
+#[test]
+fn test_listing_unreadable_directory_is_empty_and_access_denied() {
+    use super::volume::InMemoryVolume;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    let volume = InMemoryVolume::new("Locked").with_unreadable_directory("/private");
+    volume.create_directory(Path::new("/private")).unwrap();
+    volume.create_file(Path::new("/private/secret.txt"), b"secret").unwrap();
+    volume.create_file(Path::new("/public.txt"), b"public").unwrap();
+    super::get_volume_manager().register("access_denied_test", Arc::new(volume));
+
+    let denied = list_directory_start_with_volume(
+        "access_denied_test",
+        Path::new("/private"),
+        false,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        true,
+    )
+    .unwrap();
+    let readable = list_directory_start_with_volume(
+        "access_denied_test",
+        Path::new("/"),
+        false,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        false,
+        true,
+    )
+    .unwrap();
+    let denied_entries = get_file_range(&denied.listing_id, 0, 10, false).unwrap();
+
+    list_directory_end(&denied.listing_id);
+    list_directory_end(&readable.listing_id);
+    super::get_volume_manager().unregister("access_denied_test");
+    assert!(denied.access_denied);
+    assert_eq!(denied.total_count, 0);
+    assert!(denied_entries.is_empty());
+    assert!(!readable.access_denied);
+    assert_eq!(readable.total_count, 2);
+}
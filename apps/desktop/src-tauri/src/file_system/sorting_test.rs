@@ -1,7 +1,7 @@
 //! Tests for file sorting logic.
 //!
 //! These tests verify that sort_entries correctly sorts files by
-//! name, extension, size, modified date, and created date.
+//! name, extension, size, and the modified, created, changed, added, and opened dates.
 
 use super::operations::{FileEntry, SortColumn, SortOrder, sort_entries};
 
@@ -15,13 +15,17 @@ fn make_entry(name: &str, is_dir: bool, size: Option<u64>, modified: Option<u64>
         size,
         modified_at: modified,
         created_at: modified, // Use same value for simplicity
+        changed_at: modified,
         added_at: None,
         opened_at: None,
         permissions: if is_dir { 0o755 } else { 0o644 },
         owner: "testuser".to_string(),
         group: "staff".to_string(),
         icon_id: if is_dir { "dir".to_string() } else { "file".to_string() },
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }
 }
 
@@ -38,7 +42,7 @@ fn test_natural_sort_by_name() {
         make_entry("img_20.jpg", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["img_1.jpg", "img_2.jpg", "img_10.jpg", "img_20.jpg"]);
@@ -52,12 +56,54 @@ fn test_natural_sort_descending() {
         make_entry("file10.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Descending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Descending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["file10.txt", "file2.txt", "file1.txt"]);
 }
 
+#[test]
+fn test_natural_sort_version_like_names() {
+    let mut entries = vec![
+        make_entry("v1.10.0", false, Some(100), None),
+        make_entry("v1.2.10", false, Some(100), None),
+        make_entry("v1.2.9", false, Some(100), None),
+        make_entry("v1.9.5", false, Some(100), None),
+        make_entry("v2.0", false, Some(100), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["v1.2.9", "v1.2.10", "v1.9.5", "v1.10.0", "v2.0"]);
+}
+
+#[test]
+fn test_natural_sort_zero_padded_names() {
+    let mut entries = vec![
+        make_entry("track 10.mp3", false, Some(100), None),
+        make_entry("track 01.mp3", false, Some(100), None),
+        make_entry("track 2.mp3", false, Some(100), None),
+        make_entry("track 1.mp3", false, Some(100), None),
+        make_entry("track 002.mp3", false, Some(100), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
+
+    // Same numbers sort together, the shorter form first
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "track 1.mp3",
+            "track 01.mp3",
+            "track 2.mp3",
+            "track 002.mp3",
+            "track 10.mp3"
+        ]
+    );
+}
+
 // ============================================================================
 // Directories first tests
 // ============================================================================
@@ -71,7 +117,7 @@ fn test_directories_first() {
         make_entry("docs", true, None, None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     // Directories first, then files, both sorted alphabetically
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -87,7 +133,7 @@ fn test_directories_first_descending() {
         make_entry("docs", true, None, None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Descending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Descending, false);
 
     // Directories still first, but both groups sorted descending
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -109,7 +155,7 @@ fn test_sort_by_extension() {
         make_entry(".bashrc", false, Some(100), None), // Dotfile
     ];
 
-    sort_entries(&mut entries, SortColumn::Extension, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Extension, SortOrder::Ascending, false);
 
     // Order: dotfiles first, then no extension, then by extension alphabetically
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -134,7 +180,7 @@ fn test_extension_sort_same_ext_by_name() {
         make_entry("beta.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Extension, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Extension, SortOrder::Ascending, false);
 
     // Same extension - fall back to name sorting
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -153,7 +199,7 @@ fn test_sort_by_size_ascending() {
         make_entry("small.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["small.txt", "medium.txt", "large.txt"]);
@@ -167,7 +213,7 @@ fn test_sort_by_size_descending() {
         make_entry("small.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Size, SortOrder::Descending);
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Descending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["large.txt", "medium.txt", "small.txt"]);
@@ -182,13 +228,50 @@ fn test_sort_by_size_with_directories() {
         make_entry("small.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending, false);
 
     // Directories first (sorted by name), then files by size
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["dir_a", "dir_b", "small.txt", "medium.txt"]);
 }
 
+#[test]
+fn test_equal_sizes_are_sorted_by_name() {
+    let mut entries = vec![
+        make_entry("file10.txt", false, Some(100), None),
+        make_entry("charlie.txt", false, Some(100), None),
+        make_entry("big.txt", false, Some(1000), None),
+        make_entry("Alpha.txt", false, Some(100), None),
+        make_entry("file2.txt", false, Some(100), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Alpha.txt", "charlie.txt", "file2.txt", "file10.txt", "big.txt"]
+    );
+}
+
+#[test]
+fn test_equal_sizes_order_doesnt_depend_on_input_order() {
+    let mut entries = vec![
+        make_entry("b.txt", false, Some(100), None),
+        make_entry("c.txt", false, Some(100), None),
+        make_entry("a.txt", false, Some(100), None),
+    ];
+    let mut reversed: Vec<FileEntry> = entries.iter().rev().cloned().collect();
+
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Descending, false);
+    sort_entries(&mut reversed, SortColumn::Size, SortOrder::Descending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    let reversed_names: Vec<&str> = reversed.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["c.txt", "b.txt", "a.txt"]);
+    assert_eq!(names, reversed_names);
+}
+
 // ============================================================================
 // Modified date sorting tests
 // ============================================================================
@@ -201,7 +284,7 @@ fn test_sort_by_modified_ascending() {
         make_entry("middle.txt", false, Some(100), Some(1700000002)),
     ];
 
-    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Ascending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["oldest.txt", "middle.txt", "newest.txt"]);
@@ -215,7 +298,7 @@ fn test_sort_by_modified_descending() {
         make_entry("middle.txt", false, Some(100), Some(1700000002)),
     ];
 
-    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Descending);
+    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Descending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["newest.txt", "middle.txt", "oldest.txt"]);
@@ -229,13 +312,168 @@ fn test_sort_by_modified_with_none() {
         make_entry("also_has.txt", false, Some(100), Some(1700000002)),
     ];
 
-    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Ascending, false);
 
     // None comes first
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["no_date.txt", "has_date.txt", "also_has.txt"]);
 }
 
+#[test]
+fn test_equal_modified_dates_are_sorted_by_name() {
+    let mut entries = vec![
+        make_entry("report_10.pdf", false, Some(100), Some(1700000000)),
+        make_entry("report_9.pdf", false, Some(100), Some(1700000000)),
+        make_entry("older.pdf", false, Some(100), Some(1600000000)),
+        make_entry("notes.pdf", false, Some(100), Some(1700000000)),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Modified, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["older.pdf", "notes.pdf", "report_9.pdf", "report_10.pdf"]);
+}
+
+// ============================================================================
+// Case sensitivity tests
+// ============================================================================
+
+fn mixed_case_entries() -> Vec<FileEntry> {
+    vec![
+        make_entry("banana.txt", false, Some(100), None),
+        make_entry("Zebra.txt", false, Some(100), None),
+        make_entry("apple.txt", false, Some(100), None),
+        make_entry("Apple.txt", false, Some(100), None),
+        make_entry("readme.MD", false, Some(100), None),
+        make_entry("notes.md", false, Some(100), None),
+    ]
+}
+
+#[test]
+fn test_name_sort_is_case_insensitive_by_default() {
+    let mut entries = mixed_case_entries();
+
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "apple.txt",
+            "Apple.txt",
+            "banana.txt",
+            "notes.md",
+            "readme.MD",
+            "Zebra.txt"
+        ]
+    );
+}
+
+#[test]
+fn test_case_sensitive_name_sort_puts_uppercase_first() {
+    let mut entries = mixed_case_entries();
+
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, true);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "Apple.txt",
+            "Zebra.txt",
+            "apple.txt",
+            "banana.txt",
+            "notes.md",
+            "readme.MD"
+        ]
+    );
+}
+
+#[test]
+fn test_extension_sort_case_sensitivity() {
+    let mut insensitive = mixed_case_entries();
+    let mut sensitive = mixed_case_entries();
+
+    sort_entries(&mut insensitive, SortColumn::Extension, SortOrder::Ascending, false);
+    sort_entries(&mut sensitive, SortColumn::Extension, SortOrder::Ascending, true);
+
+    let insensitive_names: Vec<&str> = insensitive.iter().map(|e| e.name.as_str()).collect();
+    let sensitive_names: Vec<&str> = sensitive.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        insensitive_names,
+        vec![
+            "notes.md",
+            "readme.MD",
+            "apple.txt",
+            "Apple.txt",
+            "banana.txt",
+            "Zebra.txt"
+        ]
+    );
+    // "MD" comes before "md", and names within an extension are compared as bytes too
+    assert_eq!(
+        sensitive_names,
+        vec![
+            "readme.MD",
+            "notes.md",
+            "Apple.txt",
+            "Zebra.txt",
+            "apple.txt",
+            "banana.txt"
+        ]
+    );
+}
+
+// ============================================================================
+// Kind sorting tests
+// ============================================================================
+
+#[test]
+fn test_sort_by_kind_groups_images_before_text() {
+    let mut entries = vec![
+        make_entry("a_notes.txt", false, Some(100), None),
+        make_entry("z_photo.jpg", false, Some(100), None),
+        make_entry("b_readme.txt", false, Some(100), None),
+        make_entry("y_screenshot.png", false, Some(100), None),
+        make_entry("m_photo.JPG", false, Some(100), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Kind, SortOrder::Ascending, false);
+
+    // Images cluster together ahead of the text files, sorted by name within each group
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "m_photo.JPG",
+            "y_screenshot.png",
+            "z_photo.jpg",
+            "a_notes.txt",
+            "b_readme.txt"
+        ]
+    );
+}
+
+#[test]
+fn test_sort_by_kind_keeps_directories_first() {
+    let mut entries = vec![
+        make_entry("song.mp3", false, Some(100), None),
+        make_entry("photos.jpg", true, None, None),
+        make_entry("archive.zip", false, Some(100), None),
+        make_entry("unknown", false, Some(100), None),
+        make_entry("image.png", false, Some(100), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Kind, SortOrder::Ascending, false);
+
+    // A directory with an image-like name is still a folder. Files without a known kind go last.
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["photos.jpg", "image.png", "song.mp3", "archive.zip", "unknown"]
+    );
+}
+
 // ============================================================================
 // Edge cases
 // ============================================================================
@@ -243,14 +481,14 @@ fn test_sort_by_modified_with_none() {
 #[test]
 fn test_empty_list() {
     let mut entries: Vec<FileEntry> = vec![];
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
     assert!(entries.is_empty());
 }
 
 #[test]
 fn test_single_entry() {
     let mut entries = vec![make_entry("only.txt", false, Some(100), None)];
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
     assert_eq!(entries.len(), 1);
     assert_eq!(entries[0].name, "only.txt");
 }
@@ -263,7 +501,7 @@ fn test_case_insensitive_sort() {
         make_entry("BETA.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["alpha.txt", "BETA.txt", "Zebra.txt"]);
@@ -283,7 +521,7 @@ fn test_unicode_filenames() {
     ];
 
     // Should not panic and should produce a stable sort
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     assert_eq!(entries.len(), 4);
 }
@@ -298,7 +536,7 @@ fn test_long_filenames() {
         make_entry(&long_name_a, false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     assert_eq!(entries[0].name, long_name_a);
     assert_eq!(entries[1].name, long_name_z);
@@ -314,13 +552,17 @@ fn make_symlink(name: &str, size: Option<u64>) -> FileEntry {
         size,
         modified_at: None,
         created_at: None,
+        changed_at: None,
         added_at: None,
         opened_at: None,
         permissions: 0o777,
         owner: "testuser".to_string(),
         group: "staff".to_string(),
         icon_id: "symlink".to_string(),
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }
 }
 
@@ -332,7 +574,7 @@ fn test_symlinks_sorted_as_files() {
         make_entry("file.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     // Directories first, then symlinks and files sorted together by name
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -347,7 +589,7 @@ fn test_size_with_none_values() {
         make_entry("big_size.txt", false, Some(1000), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Ascending, false);
 
     // None comes first (treated as 0 or less than any size)
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -362,7 +604,7 @@ fn test_size_descending_with_none() {
         make_entry("small.txt", false, Some(100), None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Size, SortOrder::Descending);
+    sort_entries(&mut entries, SortColumn::Size, SortOrder::Descending, false);
 
     // Descending: big first, then small, then None last
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -377,10 +619,122 @@ fn test_sort_by_created() {
         make_entry("middle.txt", false, Some(100), Some(1700000002)),
     ];
 
-    sort_entries(&mut entries, SortColumn::Created, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Created, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["oldest.txt", "middle.txt", "newest.txt"]);
+}
+
+#[test]
+fn test_sort_by_changed() {
+    let mut entries = vec![
+        make_entry("newest.txt", false, Some(100), Some(1700000003)),
+        make_entry("oldest.txt", false, Some(100), Some(1700000001)),
+        make_entry("middle.txt", false, Some(100), Some(1700000002)),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Changed, SortOrder::Descending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["newest.txt", "middle.txt", "oldest.txt"]);
+}
+
+#[test]
+fn test_sort_by_changed_ignores_created() {
+    let mut renamed = make_entry("renamed.txt", false, Some(100), Some(1700000001));
+    renamed.changed_at = Some(1700000009);
+    let mut entries = vec![renamed, make_entry("untouched.txt", false, Some(100), Some(1700000002))];
+
+    sort_entries(&mut entries, SortColumn::Changed, SortOrder::Ascending, false);
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["untouched.txt", "renamed.txt"]);
+
+    sort_entries(&mut entries, SortColumn::Created, SortOrder::Ascending, false);
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["renamed.txt", "untouched.txt"]);
+}
+
+#[test]
+fn test_changed_with_none_values() {
+    let mut entries = vec![
+        make_entry("has_date.txt", false, Some(100), Some(1700000001)),
+        make_entry("no_date.txt", false, Some(100), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Changed, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["no_date.txt", "has_date.txt"]);
+}
+
+/// Creates a file entry with the given date added and date last opened.
+fn make_dated_entry(name: &str, added: Option<u64>, opened: Option<u64>) -> FileEntry {
+    let mut entry = make_entry(name, false, Some(100), Some(1700000000));
+    entry.added_at = added;
+    entry.opened_at = opened;
+    entry
+}
+
+#[test]
+fn test_sort_by_added() {
+    let mut entries = vec![
+        make_dated_entry("newest.txt", Some(1700000003), None),
+        make_dated_entry("oldest.txt", Some(1700000001), None),
+        make_dated_entry("middle.txt", Some(1700000002), None),
+    ];
 
+    sort_entries(&mut entries, SortColumn::Added, SortOrder::Ascending, false);
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
     assert_eq!(names, vec!["oldest.txt", "middle.txt", "newest.txt"]);
+
+    sort_entries(&mut entries, SortColumn::Added, SortOrder::Descending, false);
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["newest.txt", "middle.txt", "oldest.txt"]);
+}
+
+#[test]
+fn test_sort_by_opened() {
+    let mut entries = vec![
+        make_dated_entry("recent.txt", Some(1700000001), Some(1700000009)),
+        make_dated_entry("stale.txt", Some(1700000002), Some(1700000005)),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Opened, SortOrder::Ascending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["stale.txt", "recent.txt"]);
+}
+
+#[test]
+fn test_missing_added_dates_go_last_in_both_orders() {
+    let mut entries = vec![
+        make_dated_entry("b_undated.txt", None, None),
+        make_dated_entry("old.txt", Some(1700000001), None),
+        make_dated_entry("a_undated.txt", None, None),
+        make_dated_entry("new.txt", Some(1700000002), None),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Added, SortOrder::Ascending, false);
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["old.txt", "new.txt", "a_undated.txt", "b_undated.txt"]);
+
+    sort_entries(&mut entries, SortColumn::Added, SortOrder::Descending, false);
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["new.txt", "old.txt", "b_undated.txt", "a_undated.txt"]);
+}
+
+#[test]
+fn test_missing_opened_dates_go_last_and_directories_stay_first() {
+    let mut entries = vec![
+        make_dated_entry("never_opened.txt", Some(1700000001), None),
+        make_entry("dir", true, None, None),
+        make_dated_entry("opened.txt", Some(1700000002), Some(1700000003)),
+    ];
+
+    sort_entries(&mut entries, SortColumn::Opened, SortOrder::Descending, false);
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["dir", "opened.txt", "never_opened.txt"]);
 }
 
 #[test]
@@ -392,7 +746,7 @@ fn test_dotfiles_sorted_before_regular_files_by_name() {
         make_entry(".git", true, None, None),
     ];
 
-    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending);
+    sort_entries(&mut entries, SortColumn::Name, SortOrder::Ascending, false);
 
     // Directories first (alphabetically, dotdirs before regular), then files
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
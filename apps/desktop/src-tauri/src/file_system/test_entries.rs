@@ -0,0 +1,44 @@
+//! Shared `FileEntry` fixtures for the file system tests.
+
+use super::FileEntry;
+use std::path::Path;
+
+impl FileEntry {
+    /// Creates an entry for tests, named after the last component of `path`.
+    ///
+    /// Files are 100 bytes with the "file" icon; directories have no size and the "dir" icon. Both have no
+    /// timestamps, are owned by testuser:staff, and have all their metadata loaded.
+    pub(crate) fn test_entry(path: &str, is_directory: bool) -> Self {
+        let name = Path::new(path)
+            .file_name()
+            .map_or_else(|| path.to_string(), |n| n.to_string_lossy().into_owned());
+        FileEntry {
+            name,
+            path: path.to_string(),
+            is_directory,
+            is_symlink: false,
+            size: if is_directory { None } else { Some(100) },
+            modified_at: None,
+            created_at: None,
+            changed_at: None,
+            added_at: None,
+            opened_at: None,
+            permissions: if is_directory { 0o755 } else { 0o644 },
+            owner: "testuser".to_string(),
+            group: "staff".to_string(),
+            icon_id: if is_directory { "dir" } else { "file" }.to_string(),
+            inode: None,
+            extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
+        }
+    }
+
+    /// Sets the modified, created, and changed times.
+    pub(crate) fn with_times(mut self, timestamp: Option<u64>) -> Self {
+        self.modified_at = timestamp;
+        self.created_at = timestamp;
+        self.changed_at = timestamp;
+        self
+    }
+}
@@ -0,0 +1,358 @@
+//! Streaming file copy with progress reporting and optional bandwidth throttling.
+//!
+//! Copies in chunks rather than with `fs::copy`, so progress can be reported along the way, and so
+//! the pace can be limited, for example to avoid saturating the link when copying to a network share.
+//! Whole directory trees can be copied the same way, with cancellation that removes the partial copy.
+
+use super::folder_sizes::directory_size;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// Bytes read and written per iteration of the copy loop.
+const COPY_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Minimum time between two progress reports. The final report is always sent.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Cancellation flags of running `copy_with_progress` calls, by token.
+static COPY_TOKENS: LazyLock<RwLock<HashMap<String, Arc<AtomicBool>>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Options for a copy.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferOptions {
+    /// Maximum copy speed in bytes per second. `None` copies at full speed.
+    pub max_bytes_per_sec: Option<u64>,
+}
+
+/// Progress of a copy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    /// Bytes copied so far
+    pub copied: u64,
+    /// Total bytes to copy
+    pub total: u64,
+    /// Average throughput since the copy started, in bytes per second
+    pub bytes_per_sec: u64,
+}
+
+/// Payload of `copy-progress` events, the same for single files and whole trees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgressEvent {
+    /// Chosen by the caller to tell its copies apart. None if it didn't pass one.
+    pub token: Option<String>,
+    #[serde(flatten)]
+    pub progress: CopyProgress,
+}
+
+/// Token bucket rate limiter.
+///
+/// Tokens (bytes) refill continuously at `rate` per second, up to one second's worth. Taking more
+/// tokens than available is allowed, the caller then sleeps until the debt is paid off. This keeps
+/// the average rate exact even when single chunks are larger than the bucket.
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates an empty bucket, so that even the first chunk is paced.
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let rate = bytes_per_sec.max(1) as f64;
+        Self {
+            rate,
+            capacity: rate,
+            tokens: 0.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes `bytes` tokens, sleeping as long as needed to stay within the rate.
+    pub fn take(&mut self, bytes: u64) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+
+        self.tokens -= bytes as f64;
+        if self.tokens < 0.0 {
+            std::thread::sleep(Duration::from_secs_f64(-self.tokens / self.rate));
+        }
+    }
+}
+
+/// Copies a single file in chunks, reporting progress and optionally limiting the speed.
+/// The copy gets the source's permissions.
+///
+/// An existing destination is only replaced with `replace_existing`. Then the file is copied next to it first and
+/// renamed over it at the end, so a failed copy leaves the original untouched.
+///
+/// # Arguments
+/// * `from` - Source file
+/// * `to` - Destination file path
+/// * `options` - Throttling options
+/// * `replace_existing` - If false, fails with `AlreadyExists` when `to` exists
+/// * `on_progress` - Called at most every 100 ms while copying, and once at the end
+///
+/// # Returns
+/// The number of bytes copied.
+pub fn copy_file_with_progress(
+    from: &Path,
+    to: &Path,
+    options: &TransferOptions,
+    replace_existing: bool,
+    mut on_progress: impl FnMut(&CopyProgress),
+) -> io::Result<u64> {
+    let metadata = fs::metadata(from)?;
+    if metadata.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Not a file: {}", from.display()),
+        ));
+    }
+    let exists = fs::symlink_metadata(to).is_ok();
+    if exists && !replace_existing {
+        return Err(already_exists_error(to));
+    }
+
+    let target = if exists {
+        partial_copy_path(to)
+    } else {
+        to.to_path_buf()
+    };
+    let mut copy = Copy::new(metadata.len(), options);
+    let result = copy
+        .copy_content(from, &target, &AtomicBool::new(false), &mut on_progress)
+        .and_then(|()| fs::set_permissions(&target, metadata.permissions()))
+        .and_then(|()| if exists { fs::rename(&target, to) } else { Ok(()) });
+    if result.is_err() {
+        remove_partial_copy(&target);
+    }
+    result?;
+
+    on_progress(&copy.progress());
+    Ok(copy.copied)
+}
+
+/// Copies a file, or a directory with everything in it, to a path that doesn't exist yet, reporting progress.
+///
+/// For directories, the total is computed first with `directory_size`, then files are copied one by one, with
+/// progress counting the bytes copied across all of them. Symlinks are copied as links. If the copy is cancelled
+/// with `cancel_copy` or fails, whatever was copied so far is removed again.
+///
+/// # Arguments
+/// * `from` - Source file or directory
+/// * `to` - Destination path, which must not exist yet
+/// * `options` - Throttling options
+/// * `token` - Chosen by the caller to cancel this copy. Should be unique among running copies.
+/// * `on_progress` - Called at most every 100 ms while copying, and once at the end
+///
+/// # Returns
+/// The number of bytes copied, or an `Interrupted` error if cancelled.
+pub fn copy_with_progress(
+    from: &Path,
+    to: &Path,
+    options: &TransferOptions,
+    token: &str,
+    mut on_progress: impl FnMut(&CopyProgress),
+) -> io::Result<u64> {
+    if fs::symlink_metadata(to).is_ok() {
+        return Err(already_exists_error(to));
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Ok(mut tokens) = COPY_TOKENS.write() {
+        tokens.insert(token.to_string(), cancelled.clone());
+    }
+    let result = copy_tree_with_progress(from, to, options, &cancelled, &mut on_progress);
+    if let Ok(mut tokens) = COPY_TOKENS.write() {
+        tokens.remove(token);
+    }
+
+    if result.is_err() {
+        // Nothing was at `to` before, so everything there is a partial copy
+        remove_partial_copy(to);
+    }
+    result
+}
+
+/// Stops a running `copy_with_progress`, which then removes what it copied so far.
+///
+/// # Returns
+/// True if a copy with the token was running.
+pub fn cancel_copy(token: &str) -> bool {
+    let Ok(tokens) = COPY_TOKENS.read() else {
+        return false;
+    };
+    match tokens.get(token) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+fn copy_tree_with_progress(
+    from: &Path,
+    to: &Path,
+    options: &TransferOptions,
+    cancelled: &AtomicBool,
+    on_progress: &mut impl FnMut(&CopyProgress),
+) -> io::Result<u64> {
+    let metadata = fs::symlink_metadata(from)?;
+    let total = if metadata.is_dir() {
+        directory_size(from, cancelled).ok_or_else(cancelled_error)?
+    } else {
+        metadata.len()
+    };
+
+    let mut copy = Copy::new(total, options);
+    copy.copy_entry(from, to, cancelled, on_progress)?;
+
+    on_progress(&copy.progress());
+    Ok(copy.copied)
+}
+
+/// State of a running copy: the bytes copied so far, the pace, and when progress was last reported.
+struct Copy {
+    total: u64,
+    copied: u64,
+    limiter: Option<TokenBucket>,
+    buffer: Vec<u8>,
+    started_at: Instant,
+    last_report: Option<Instant>,
+}
+
+impl Copy {
+    fn new(total: u64, options: &TransferOptions) -> Self {
+        Self {
+            total,
+            copied: 0,
+            limiter: options.max_bytes_per_sec.map(TokenBucket::new),
+            buffer: vec![0u8; COPY_CHUNK_SIZE],
+            started_at: Instant::now(),
+            last_report: None,
+        }
+    }
+
+    /// Copies a file, directory, or symlink. Directories get their permissions after they're filled, so
+    /// read-only ones can still be filled.
+    fn copy_entry(
+        &mut self,
+        from: &Path,
+        to: &Path,
+        cancelled: &AtomicBool,
+        on_progress: &mut impl FnMut(&CopyProgress),
+    ) -> io::Result<()> {
+        let metadata = fs::symlink_metadata(from)?;
+        if metadata.file_type().is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(from)?, to)?;
+            // Counted with their own size, like in `directory_size`
+            self.add(metadata.len(), on_progress);
+            return Ok(());
+        }
+
+        if metadata.is_dir() {
+            fs::create_dir(to)?;
+            for entry in fs::read_dir(from)? {
+                let entry = entry?;
+                self.copy_entry(&entry.path(), &to.join(entry.file_name()), cancelled, on_progress)?;
+            }
+        } else {
+            self.copy_content(from, to, cancelled, on_progress)?;
+        }
+        fs::set_permissions(to, metadata.permissions())
+    }
+
+    /// Copies the content of a file in chunks. Stops with an `Interrupted` error once `cancelled` is set.
+    fn copy_content(
+        &mut self,
+        from: &Path,
+        to: &Path,
+        cancelled: &AtomicBool,
+        on_progress: &mut impl FnMut(&CopyProgress),
+    ) -> io::Result<()> {
+        let mut reader = fs::File::open(from)?;
+        let mut writer = fs::File::create(to)?;
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(cancelled_error());
+            }
+            let read = reader.read(&mut self.buffer)?;
+            if read == 0 {
+                break;
+            }
+            if let Some(limiter) = self.limiter.as_mut() {
+                limiter.take(read as u64);
+            }
+            writer.write_all(&self.buffer[..read])?;
+            self.add(read as u64, on_progress);
+        }
+        writer.flush()
+    }
+
+    /// Counts copied bytes, reporting progress if it's been long enough since the last report.
+    fn add(&mut self, bytes: u64, on_progress: &mut impl FnMut(&CopyProgress)) {
+        self.copied += bytes;
+        if self.last_report.is_none_or(|t| t.elapsed() >= PROGRESS_INTERVAL) {
+            on_progress(&self.progress());
+            self.last_report = Some(Instant::now());
+        }
+    }
+
+    fn progress(&self) -> CopyProgress {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        let bytes_per_sec = if elapsed > 0.0 {
+            (self.copied as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        CopyProgress {
+            copied: self.copied,
+            // Hardlinked files count once in the total but are copied each time, so the total could be too low
+            total: self.total.max(self.copied),
+            bytes_per_sec,
+        }
+    }
+}
+
+fn cancelled_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "Cancelled")
+}
+
+fn already_exists_error(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("Already exists: {}", path.display()),
+    )
+}
+
+/// Hidden sibling of `to` that a replacing copy is written to before it's renamed over `to`.
+fn partial_copy_path(to: &Path) -> PathBuf {
+    let name = to.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    to.with_file_name(format!(".{}.cmdr-partial", name))
+}
+
+/// Removes a partial copy, ignoring errors: it's cleanup after a failure that's already reported.
+fn remove_partial_copy(to: &Path) {
+    match fs::symlink_metadata(to) {
+        Ok(metadata) if metadata.is_dir() => {
+            let _ = fs::remove_dir_all(to);
+        }
+        Ok(_) => {
+            let _ = fs::remove_file(to);
+        }
+        Err(_) => {}
+    }
+}
@@ -0,0 +1,231 @@
+//! Tests for streaming copy, bandwidth throttling, and cancellable tree copies.
+
+use super::transfer::{
+    CopyProgress, CopyProgressEvent, TokenBucket, TransferOptions, cancel_copy, copy_file_with_progress,
+    copy_with_progress,
+};
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// Creates a fresh temp directory for a test.
+fn create_test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_copy_copies_content_and_reports_final_progress() {
+    let dir = create_test_dir("cmdr_transfer_copy_test");
+    let from = dir.join("source.bin");
+    let to = dir.join("destination.bin");
+    let content: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&from, &content).unwrap();
+
+    let mut reports: Vec<CopyProgress> = Vec::new();
+    let copied = copy_file_with_progress(&from, &to, &TransferOptions::default(), false, |p| {
+        reports.push(p.clone())
+    })
+    .unwrap();
+    let copied_content = fs::read(&to).unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(copied, content.len() as u64);
+    assert_eq!(copied_content, content);
+    let last = reports.last().unwrap();
+    assert_eq!(last.copied, last.total);
+    assert_eq!(last.total, content.len() as u64);
+}
+
+#[test]
+fn test_throttled_copy_takes_at_least_the_minimum_time() {
+    let dir = create_test_dir("cmdr_transfer_throttle_test");
+    let from = dir.join("source.bin");
+    let to = dir.join("destination.bin");
+    let size: u64 = 600 * 1024;
+    let max_bytes_per_sec: u64 = 2 * 1024 * 1024;
+    fs::write(&from, vec![7u8; size as usize]).unwrap();
+
+    let options = TransferOptions {
+        max_bytes_per_sec: Some(max_bytes_per_sec),
+    };
+    let mut last_progress = None;
+    let started_at = Instant::now();
+    copy_file_with_progress(&from, &to, &options, false, |p| last_progress = Some(p.clone())).unwrap();
+    let elapsed = started_at.elapsed();
+
+    let _ = fs::remove_dir_all(&dir);
+    let expected_minimum = Duration::from_secs_f64(size as f64 / max_bytes_per_sec as f64);
+    assert!(
+        elapsed + Duration::from_millis(1) >= expected_minimum,
+        "Copy took {:?}, expected at least {:?}",
+        elapsed,
+        expected_minimum
+    );
+    let throughput = last_progress.unwrap().bytes_per_sec;
+    assert!(throughput > 0);
+    assert!(throughput <= max_bytes_per_sec + max_bytes_per_sec / 100);
+}
+
+#[test]
+fn test_copy_rejects_directories() {
+    let dir = create_test_dir("cmdr_transfer_dir_test");
+    let result = copy_file_with_progress(&dir, &dir.join("copy"), &TransferOptions::default(), false, |_| {});
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_copy_refuses_existing_destination_unless_replacing() {
+    let dir = create_test_dir("cmdr_transfer_replace_test");
+    let from = dir.join("source.txt");
+    let to = dir.join("destination.txt");
+    fs::write(&from, b"new").unwrap();
+    fs::write(&to, b"old").unwrap();
+
+    let refused = copy_file_with_progress(&from, &to, &TransferOptions::default(), false, |_| {});
+    let content_after_refusal = fs::read(&to).unwrap();
+    let replaced = copy_file_with_progress(&from, &to, &TransferOptions::default(), true, |_| {});
+    let content_after_replace = fs::read(&to).unwrap();
+    let leftovers = fs::read_dir(&dir).unwrap().count();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(refused.unwrap_err().kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(content_after_refusal, b"old");
+    assert_eq!(replaced.unwrap(), 3);
+    assert_eq!(content_after_replace, b"new");
+    assert_eq!(leftovers, 2);
+}
+
+#[test]
+fn test_failed_replacing_copy_keeps_the_original() {
+    let dir = create_test_dir("cmdr_transfer_replace_failure_test");
+    let to = dir.join("destination.txt");
+    fs::write(&to, b"old").unwrap();
+
+    let result = copy_file_with_progress(&dir.join("missing.txt"), &to, &TransferOptions::default(), true, |_| {});
+    let to_content = fs::read(&to).unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert!(result.is_err());
+    assert_eq!(to_content, b"old");
+}
+
+#[test]
+fn test_progress_event_has_the_same_shape_with_and_without_token() {
+    let progress = CopyProgress {
+        copied: 10,
+        total: 20,
+        bytes_per_sec: 5,
+    };
+    let with_token = serde_json::to_value(CopyProgressEvent {
+        token: Some("copy-1".to_string()),
+        progress: progress.clone(),
+    })
+    .unwrap();
+    let without_token = serde_json::to_value(CopyProgressEvent { token: None, progress }).unwrap();
+
+    assert_eq!(
+        with_token,
+        serde_json::json!({ "token": "copy-1", "copied": 10, "total": 20, "bytesPerSec": 5 })
+    );
+    assert_eq!(
+        without_token,
+        serde_json::json!({ "token": null, "copied": 10, "total": 20, "bytesPerSec": 5 })
+    );
+}
+
+#[test]
+fn test_token_bucket_paces_large_takes() {
+    let mut bucket = TokenBucket::new(1_000_000);
+    let started_at = Instant::now();
+    // 50 ms worth of tokens from an empty bucket
+    bucket.take(50_000);
+    assert!(started_at.elapsed() >= Duration::from_millis(49));
+}
+
+#[test]
+fn test_copy_with_progress_reports_exact_bytes_for_large_file() {
+    let dir = create_test_dir("cmdr_transfer_progress_test");
+    let from = dir.join("large.bin");
+    let to = dir.join("large copy.bin");
+    let content: Vec<u8> = (0..5_000_000u32).map(|i| (i % 253) as u8).collect();
+    fs::write(&from, &content).unwrap();
+
+    let mut reports: Vec<CopyProgress> = Vec::new();
+    let copied = copy_with_progress(&from, &to, &TransferOptions::default(), "progress-test", |p| {
+        reports.push(p.clone())
+    })
+    .unwrap();
+    let copied_content = fs::read(&to).unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(copied, content.len() as u64);
+    assert_eq!(copied_content, content);
+    assert!(reports.iter().all(|p| p.total == content.len() as u64));
+    assert!(reports.windows(2).all(|w| w[0].copied <= w[1].copied));
+    assert_eq!(reports.last().unwrap().copied, content.len() as u64);
+}
+
+#[test]
+fn test_copy_with_progress_copies_tree_with_cumulative_progress() {
+    let dir = create_test_dir("cmdr_transfer_tree_test");
+    let from = dir.join("source");
+    let to = dir.join("destination");
+    fs::create_dir_all(from.join("sub")).unwrap();
+    fs::write(from.join("a.txt"), vec![b'a'; 1000]).unwrap();
+    fs::write(from.join("sub/b.txt"), vec![b'b'; 2000]).unwrap();
+
+    let mut reports: Vec<CopyProgress> = Vec::new();
+    let copied = copy_with_progress(&from, &to, &TransferOptions::default(), "tree-test", |p| {
+        reports.push(p.clone())
+    })
+    .unwrap();
+    let b_content = fs::read(to.join("sub/b.txt")).unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(copied, 3000);
+    assert_eq!(b_content, vec![b'b'; 2000]);
+    let last = reports.last().unwrap();
+    assert_eq!((last.copied, last.total), (3000, 3000));
+}
+
+#[test]
+fn test_copy_with_progress_removes_partial_copy_on_cancel() {
+    let dir = create_test_dir("cmdr_transfer_cancel_test");
+    let from = dir.join("source");
+    let to = dir.join("destination");
+    fs::create_dir(&from).unwrap();
+    for i in 0..5 {
+        fs::write(from.join(format!("{}.bin", i)), vec![0u8; 1_000_000]).unwrap();
+    }
+
+    // Cancels at the first report, which comes after the first chunk
+    let result = copy_with_progress(&from, &to, &TransferOptions::default(), "cancel-test", |_| {
+        cancel_copy("cancel-test");
+    });
+    let destination_exists = to.exists();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::Interrupted);
+    assert!(!destination_exists);
+    assert!(!cancel_copy("cancel-test"));
+}
+
+#[test]
+fn test_copy_with_progress_refuses_existing_destination() {
+    let dir = create_test_dir("cmdr_transfer_existing_test");
+    let from = dir.join("source.txt");
+    let to = dir.join("destination.txt");
+    fs::write(&from, b"new").unwrap();
+    fs::write(&to, b"old").unwrap();
+
+    let result = copy_with_progress(&from, &to, &TransferOptions::default(), "existing-test", |_| {});
+    let to_content = fs::read(&to).unwrap();
+
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(to_content, b"old");
+}
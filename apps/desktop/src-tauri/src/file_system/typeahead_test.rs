@@ -0,0 +1,99 @@
+//! Tests for type-ahead navigation.
+
+use super::operations::{
+    CachedListing, FileEntry, LISTING_CACHE, SortColumn, SortOrder, list_directory_end, typeahead_find,
+};
+
+/// Inserts a listing with the given names (in this order) into the cache.
+fn insert_listing(listing_id: &str, names: &[&str]) {
+    let mut cache = LISTING_CACHE.write().unwrap();
+    cache.insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "test".to_string(),
+            path: std::path::PathBuf::from("/"),
+            entries: names
+                .iter()
+                .map(|n| FileEntry::test_entry(&format!("/{}", n), false).with_times(Some(1_700_000_000)))
+                .collect(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Default::default(),
+            last_accessed: super::operations::AccessTime::now(),
+        },
+    );
+}
+
+const NAMES: &[&str] = &[".dotfile", "apple.txt", "Dog.png", "dogfood.md", "elephant", "zebra"];
+
+#[test]
+fn test_typeahead_finds_first_match_case_insensitive() {
+    let listing_id = "test-typeahead-first-match";
+    insert_listing(listing_id, NAMES);
+
+    let result = typeahead_find(listing_id, "d", 0, false).unwrap();
+
+    list_directory_end(listing_id);
+    // Visible names: apple.txt, Dog.png, dogfood.md, elephant, zebra
+    assert_eq!(result, Some(1));
+}
+
+#[test]
+fn test_typeahead_multi_character_prefix_stays_on_current_match() {
+    let listing_id = "test-typeahead-multi-char";
+    insert_listing(listing_id, NAMES);
+
+    let stays = typeahead_find(listing_id, "do", 1, false).unwrap();
+    let moves = typeahead_find(listing_id, "dogf", 1, false).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(stays, Some(1));
+    assert_eq!(moves, Some(2));
+}
+
+#[test]
+fn test_typeahead_wraps_past_end() {
+    let listing_id = "test-typeahead-wrap";
+    insert_listing(listing_id, NAMES);
+
+    // Starting after the last "d" entry wraps around to the first one
+    let result = typeahead_find(listing_id, "d", 3, false).unwrap();
+    // Starting past the end of the listing also wraps
+    let past_end = typeahead_find(listing_id, "a", 100, false).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(result, Some(1));
+    assert_eq!(past_end, Some(0));
+}
+
+#[test]
+fn test_typeahead_respects_include_hidden() {
+    let listing_id = "test-typeahead-hidden";
+    insert_listing(listing_id, NAMES);
+
+    let hidden_excluded = typeahead_find(listing_id, ".", 0, false).unwrap();
+    let hidden_included = typeahead_find(listing_id, "d", 0, true).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(hidden_excluded, None);
+    assert_eq!(hidden_included, Some(2));
+}
+
+#[test]
+fn test_typeahead_no_match_or_empty_prefix() {
+    let listing_id = "test-typeahead-no-match";
+    insert_listing(listing_id, NAMES);
+
+    let no_match = typeahead_find(listing_id, "q", 0, false).unwrap();
+    let empty = typeahead_find(listing_id, "", 0, false).unwrap();
+
+    list_directory_end(listing_id);
+    assert_eq!(no_match, None);
+    assert_eq!(empty, None);
+}
+
+#[test]
+fn test_typeahead_unknown_listing() {
+    assert!(typeahead_find("no-such-listing", "a", 0, true).is_err());
+}
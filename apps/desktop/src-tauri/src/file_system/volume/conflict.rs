@@ -0,0 +1,259 @@
+//! Copying and moving onto paths that are already taken.
+//!
+//! `transfer_with_policy` copies or moves one entry within a volume, and `transfer_paths` does it for a
+//! multi-selection, going on past items that fail. When the destination exists, a `ConflictPolicy` decides what
+//! happens, and the result says which policy was applied.
+
+use super::duplicate::free_copy_path;
+use super::name_collision::check_name_collision;
+use super::{Volume, VolumeError, delete_tree};
+use crate::file_system::VolumeManager;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What to do when the destination of a copy or move already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictPolicy {
+    /// Leave both alone and go on
+    Skip,
+    /// Replace what's at the destination, once the copy or move is done
+    Overwrite,
+    /// Copy or move to the next free "name copy" name, like Duplicate does
+    Rename,
+    /// Fail the item
+    #[default]
+    Error,
+}
+
+/// How copying or moving one entry went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transferred {
+    /// Where the entry ended up. None if it was skipped.
+    pub destination: Option<PathBuf>,
+    /// The policy that was applied because the destination existed. None if it didn't.
+    pub conflict: Option<ConflictPolicy>,
+}
+
+/// Result of copying or moving one of the paths passed to `transfer_paths`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferResult {
+    pub path: String,
+    /// Where the entry ended up. None if it was skipped or failed.
+    pub destination: Option<String>,
+    pub success: bool,
+    /// The policy that was applied because the destination existed. None if it didn't.
+    pub conflict: Option<ConflictPolicy>,
+    /// Why the copy or move failed. None if it succeeded.
+    pub error: Option<String>,
+}
+
+/// Decides where a copy or move to `to` goes, applying `policy` if `to` already exists.
+///
+/// Changes nothing on the volume. For `Overwrite`, the destination is `to` itself, and it's up to the caller to
+/// replace it without losing it when the copy fails.
+///
+/// On case-insensitive volumes, an entry whose name only differs in case ("readme" for "README") is a conflict too.
+/// `Skip` and `Rename` handle it like any other, but `Error` and `Overwrite` fail with `CaseCollision`, since
+/// overwriting would silently replace an entry with a different name.
+///
+/// # Arguments
+/// * `volume` - Volume `to` is on
+/// * `to` - Where the entry should go, relative to the volume
+/// * `is_directory` - Whether the entry is a directory, for picking copy names
+/// * `policy` - What to do if `to` exists
+///
+/// # Returns
+/// Where to put the entry (None to skip it) and the policy that was applied, or an error for `Error`.
+pub fn resolve_conflict(
+    volume: &dyn Volume,
+    to: &Path,
+    is_directory: bool,
+    policy: ConflictPolicy,
+) -> Result<Transferred, VolumeError> {
+    let case_collision = match (to.parent(), to.file_name()) {
+        (Some(dir), Some(name)) => match check_name_collision(volume, dir, &name.to_string_lossy(), None) {
+            Ok(()) => None,
+            Err(collision @ VolumeError::CaseCollision { .. }) => Some(collision),
+            Err(e) => return Err(e),
+        },
+        _ => None,
+    };
+    let conflict = (case_collision.is_some() || volume.exists(to)).then_some(policy);
+    let destination = match (conflict, case_collision) {
+        (Some(ConflictPolicy::Skip), _) => None,
+        (Some(ConflictPolicy::Error | ConflictPolicy::Overwrite), Some(collision)) => return Err(collision),
+        (Some(ConflictPolicy::Error), None) => {
+            return Err(VolumeError::IoError(format!("Already exists: {}", to.display())));
+        }
+        (Some(ConflictPolicy::Rename), _) => Some(free_copy_path(volume, to, is_directory)),
+        (Some(ConflictPolicy::Overwrite) | None, _) => Some(to.to_path_buf()),
+    };
+    Ok(Transferred { destination, conflict })
+}
+
+/// Copies or moves a file or directory within a volume, applying `policy` if `to` already exists.
+///
+/// With `Overwrite`, the entry is first copied or moved next to `to`, and `to` is only deleted once that worked.
+///
+/// # Arguments
+/// * `volume` - Volume both paths are on
+/// * `from` - What to copy or move, relative to the volume
+/// * `to` - Where to put it, relative to the volume
+/// * `is_move` - Whether to move instead of copy
+/// * `policy` - What to do if `to` exists
+pub fn transfer_with_policy(
+    volume: &dyn Volume,
+    from: &Path,
+    to: &Path,
+    is_move: bool,
+    policy: ConflictPolicy,
+) -> Result<Transferred, VolumeError> {
+    let source = volume.get_metadata(from)?;
+    let transferred = resolve_conflict(volume, to, source.is_directory, policy)?;
+    let Some(destination) = &transferred.destination else {
+        return Ok(transferred);
+    };
+
+    if transferred.conflict == Some(ConflictPolicy::Overwrite) {
+        // Deleting the destination would delete the source too
+        if from.starts_with(to) {
+            return Err(VolumeError::IoError(format!(
+                "Can't replace {} with something inside it",
+                to.display()
+            )));
+        }
+        replace(volume, from, to, is_move)?;
+    } else if is_move {
+        volume.rename(from, destination)?;
+    } else {
+        volume.copy(from, destination)?;
+    }
+    Ok(transferred)
+}
+
+/// Replaces `to` with a copy of `from`, or with `from` itself for moves.
+///
+/// The entry goes to a hidden sibling of `to` first. Only when that worked is `to` moved aside to another hidden
+/// sibling and the entry renamed into its place, so a failure at any step leaves `to` as it was.
+fn replace(volume: &dyn Volume, from: &Path, to: &Path, is_move: bool) -> Result<(), VolumeError> {
+    let staged = hidden_sibling(volume, to, "cmdr-partial");
+    let staged_result = if is_move {
+        volume.rename(from, &staged)
+    } else {
+        volume.copy(from, &staged)
+    };
+    if let Err(e) = staged_result {
+        if !is_move && volume.exists(&staged) {
+            let _ = delete_tree(volume, &staged);
+        }
+        return Err(e);
+    }
+
+    let backup = hidden_sibling(volume, to, "cmdr-backup");
+    let result = volume.rename(to, &backup).and_then(|()| {
+        volume.rename(&staged, to).inspect_err(|_| {
+            let _ = volume.rename(&backup, to);
+        })
+    });
+    match result {
+        // The replacement is in place, so failing to clean up the old entry only leaves a hidden leftover
+        Ok(()) => {
+            let _ = delete_tree(volume, &backup);
+        }
+        // Put the entry back where it came from, or drop the copy
+        Err(_) => {
+            let _ = if is_move {
+                volume.rename(&staged, from)
+            } else {
+                delete_tree(volume, &staged)
+            };
+        }
+    }
+    result
+}
+
+/// Free hidden path next to `to`, like ".report.txt.cmdr-partial", for staging a replacement or keeping the
+/// replaced entry until it's done.
+fn hidden_sibling(volume: &dyn Volume, to: &Path, suffix: &str) -> PathBuf {
+    let name = to.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let mut sibling = to.with_file_name(format!(".{}.{}", name, suffix));
+    let mut number = 2;
+    while volume.exists(&sibling) {
+        sibling = to.with_file_name(format!(".{}.{}-{}", name, suffix, number));
+        number += 1;
+    }
+    sibling
+}
+
+/// Copies or moves several files and directories into a directory, keeping their names.
+///
+/// Each path must be on the same volume as `destination_dir`. A path that fails doesn't stop the others.
+///
+/// # Arguments
+/// * `volume_manager` - Resolves the absolute paths to volumes
+/// * `paths` - What to copy or move, as absolute paths
+/// * `destination_dir` - Where to put them, as an absolute path
+/// * `is_move` - Whether to move instead of copy
+/// * `policy` - What to do for each item whose destination exists
+/// * `on_item` - Called after each item, with its result and the number of items done so far
+///
+/// # Returns
+/// One result per path, in the same order.
+pub fn transfer_paths(
+    volume_manager: &VolumeManager,
+    paths: &[PathBuf],
+    destination_dir: &Path,
+    is_move: bool,
+    policy: ConflictPolicy,
+    mut on_item: impl FnMut(&TransferResult, usize),
+) -> Vec<TransferResult> {
+    let destination_volume = volume_manager.resolve(destination_dir);
+    let mut results = Vec::with_capacity(paths.len());
+    for path in paths {
+        let outcome = match (&destination_volume, volume_manager.resolve(path), path.file_name()) {
+            (None, _, _) => Err((format!("No volume found for {}", destination_dir.display()), None)),
+            (_, None, _) => Err((format!("No volume found for {}", path.display()), None)),
+            (_, _, None) => Err((format!("Can't copy or move {}", path.display()), None)),
+            (Some((to_volume_id, _, _)), Some((from_volume_id, _, _)), _) if *to_volume_id != from_volume_id => Err((
+                format!(
+                    "{} and {} are on different volumes",
+                    path.display(),
+                    destination_dir.display()
+                ),
+                None,
+            )),
+            (Some((_, volume, relative_dir)), Some((_, _, relative_path)), Some(name)) => {
+                let to = relative_dir.join(name);
+                // Checked upfront, so failures can tell whether the policy was applied
+                let conflict = volume.exists(&to).then_some(policy);
+                transfer_with_policy(volume.as_ref(), &relative_path, &to, is_move, policy)
+                    .map_err(|e| (e.to_string(), conflict))
+            }
+        };
+        let result = match outcome {
+            Ok(transferred) => TransferResult {
+                path: path.to_string_lossy().to_string(),
+                // Back to an absolute path, like the one passed in
+                destination: transferred
+                    .destination
+                    .and_then(|d| d.file_name().map(|name| destination_dir.join(name)))
+                    .map(|d| d.to_string_lossy().to_string()),
+                success: true,
+                conflict: transferred.conflict,
+                error: None,
+            },
+            Err((error, conflict)) => TransferResult {
+                path: path.to_string_lossy().to_string(),
+                destination: None,
+                success: false,
+                conflict,
+                error: Some(error),
+            },
+        };
+        on_item(&result, results.len() + 1);
+        results.push(result);
+    }
+    results
+}
@@ -0,0 +1,383 @@
+//! Tests for copying and moving onto taken names with each conflict policy.
+
+use super::*;
+use crate::file_system::VolumeManager;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Volume with "/src/report.txt", "/src/new.txt", and a "/dest" that already has a "report.txt".
+fn create_volume() -> InMemoryVolume {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/src")).unwrap();
+    volume.create_file(Path::new("/src/report.txt"), b"new").unwrap();
+    volume.create_file(Path::new("/src/new.txt"), b"fresh").unwrap();
+    volume.create_directory(Path::new("/dest")).unwrap();
+    volume.create_file(Path::new("/dest/report.txt"), b"old").unwrap();
+    volume
+}
+
+fn read(volume: &InMemoryVolume, path: &str) -> Vec<u8> {
+    volume.read_file(Path::new(path), 0, 100).unwrap()
+}
+
+#[test]
+fn test_default_policy_is_error() {
+    assert_eq!(ConflictPolicy::default(), ConflictPolicy::Error);
+}
+
+#[test]
+fn test_no_conflict_copies_without_policy() {
+    let volume = create_volume();
+
+    let transferred = transfer_with_policy(
+        &volume,
+        Path::new("/src/new.txt"),
+        Path::new("/dest/new.txt"),
+        false,
+        ConflictPolicy::Error,
+    )
+    .unwrap();
+
+    assert_eq!(
+        transferred,
+        Transferred {
+            destination: Some(PathBuf::from("/dest/new.txt")),
+            conflict: None
+        }
+    );
+    assert_eq!(read(&volume, "/dest/new.txt"), b"fresh");
+}
+
+#[test]
+fn test_skip_leaves_both_alone() {
+    let volume = create_volume();
+
+    let transferred = transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/dest/report.txt"),
+        true,
+        ConflictPolicy::Skip,
+    )
+    .unwrap();
+
+    assert_eq!(transferred.destination, None);
+    assert_eq!(transferred.conflict, Some(ConflictPolicy::Skip));
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+    assert!(volume.exists(Path::new("/src/report.txt")));
+}
+
+#[test]
+fn test_overwrite_replaces_destination() {
+    let volume = create_volume();
+
+    let transferred = transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/dest/report.txt"),
+        true,
+        ConflictPolicy::Overwrite,
+    )
+    .unwrap();
+
+    assert_eq!(transferred.destination, Some(PathBuf::from("/dest/report.txt")));
+    assert_eq!(transferred.conflict, Some(ConflictPolicy::Overwrite));
+    assert_eq!(read(&volume, "/dest/report.txt"), b"new");
+    assert!(!volume.exists(Path::new("/src/report.txt")));
+}
+
+#[test]
+fn test_overwrite_replaces_directory_with_contents() {
+    let volume = create_volume();
+    volume.create_directory(Path::new("/dest/folder")).unwrap();
+    volume.create_file(Path::new("/dest/folder/inside.txt"), b"x").unwrap();
+
+    transfer_with_policy(
+        &volume,
+        Path::new("/src/new.txt"),
+        Path::new("/dest/folder"),
+        false,
+        ConflictPolicy::Overwrite,
+    )
+    .unwrap();
+
+    assert_eq!(read(&volume, "/dest/folder"), b"fresh");
+}
+
+#[test]
+fn test_overwrite_keeps_destination_when_copy_fails() {
+    let volume = create_volume();
+
+    // Copying "/dest" into itself fails, so the existing file must survive
+    let result = transfer_with_policy(
+        &volume,
+        Path::new("/dest"),
+        Path::new("/dest/report.txt"),
+        false,
+        ConflictPolicy::Overwrite,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+    let names: Vec<String> = volume
+        .list_directory(Path::new("/dest"))
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect();
+    assert_eq!(names, vec!["report.txt"]);
+}
+
+#[test]
+fn test_overwrite_restores_destination_when_final_rename_fails() {
+    for is_move in [false, true] {
+        // The old file is moved aside fine, but putting the new one in its place fails
+        let volume = create_volume().with_failing_rename_to("/dest/report.txt");
+
+        let result = transfer_with_policy(
+            &volume,
+            Path::new("/src/report.txt"),
+            Path::new("/dest/report.txt"),
+            is_move,
+            ConflictPolicy::Overwrite,
+        );
+
+        assert!(matches!(result, Err(VolumeError::PermissionDenied(_))));
+        assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+        assert_eq!(read(&volume, "/src/report.txt"), b"new");
+        let names: Vec<String> = volume
+            .list_directory(Path::new("/dest"))
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(names, vec!["report.txt"]);
+    }
+}
+
+#[test]
+fn test_overwrite_leaves_no_staged_copy_behind() {
+    let volume = create_volume();
+
+    transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/dest/report.txt"),
+        false,
+        ConflictPolicy::Overwrite,
+    )
+    .unwrap();
+
+    assert_eq!(read(&volume, "/dest/report.txt"), b"new");
+    assert_eq!(volume.list_directory(Path::new("/dest")).unwrap().len(), 1);
+}
+
+#[test]
+fn test_resolve_conflict_changes_nothing() {
+    let volume = create_volume();
+    let resolve = |to: &str, policy| resolve_conflict(&volume, Path::new(to), false, policy);
+
+    assert_eq!(
+        resolve("/dest/new.txt", ConflictPolicy::Error).unwrap().destination,
+        Some(PathBuf::from("/dest/new.txt"))
+    );
+    assert_eq!(
+        resolve("/dest/report.txt", ConflictPolicy::Skip).unwrap().destination,
+        None
+    );
+    assert_eq!(
+        resolve("/dest/report.txt", ConflictPolicy::Rename).unwrap().destination,
+        Some(PathBuf::from("/dest/report copy.txt"))
+    );
+    assert!(resolve("/dest/report.txt", ConflictPolicy::Error).is_err());
+    assert_eq!(volume.list_directory(Path::new("/dest")).unwrap().len(), 1);
+}
+
+#[test]
+fn test_case_only_collision_is_never_overwritten() {
+    let volume = create_volume().with_case_sensitivity(false);
+
+    let transfer = |policy| {
+        transfer_with_policy(
+            &volume,
+            Path::new("/src/report.txt"),
+            Path::new("/dest/REPORT.txt"),
+            false,
+            policy,
+        )
+    };
+    let overwritten = transfer(ConflictPolicy::Overwrite);
+    let failed = transfer(ConflictPolicy::Error);
+    let skipped = transfer(ConflictPolicy::Skip).unwrap();
+
+    assert!(matches!(overwritten, Err(VolumeError::CaseCollision { .. })));
+    assert!(matches!(failed, Err(VolumeError::CaseCollision { .. })));
+    assert_eq!(skipped.destination, None);
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+    assert!(!volume.exists(Path::new("/dest/REPORT.txt")));
+}
+
+#[test]
+fn test_case_only_collision_can_be_renamed() {
+    let volume = create_volume().with_case_sensitivity(false);
+
+    let transferred = transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/dest/REPORT.txt"),
+        false,
+        ConflictPolicy::Rename,
+    )
+    .unwrap();
+
+    assert_eq!(transferred.conflict, Some(ConflictPolicy::Rename));
+    assert_eq!(transferred.destination, Some(PathBuf::from("/dest/REPORT copy.txt")));
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+}
+
+#[test]
+fn test_case_only_name_is_no_conflict_on_case_sensitive_volumes() {
+    let volume = create_volume();
+
+    let transferred = transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/dest/REPORT.txt"),
+        false,
+        ConflictPolicy::Error,
+    )
+    .unwrap();
+
+    assert_eq!(transferred.conflict, None);
+    assert_eq!(read(&volume, "/dest/REPORT.txt"), b"new");
+}
+
+#[test]
+fn test_overwrite_refuses_to_delete_the_source() {
+    let volume = create_volume();
+
+    let result = transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/src"),
+        false,
+        ConflictPolicy::Overwrite,
+    );
+
+    assert!(matches!(result, Err(VolumeError::IoError(_))));
+    assert!(volume.exists(Path::new("/src/report.txt")));
+}
+
+#[test]
+fn test_rename_uses_copy_names() {
+    let volume = create_volume();
+    let transfer = |policy| {
+        transfer_with_policy(
+            &volume,
+            Path::new("/src/report.txt"),
+            Path::new("/dest/report.txt"),
+            false,
+            policy,
+        )
+        .unwrap()
+    };
+
+    let first = transfer(ConflictPolicy::Rename);
+    let second = transfer(ConflictPolicy::Rename);
+
+    assert_eq!(first.destination, Some(PathBuf::from("/dest/report copy.txt")));
+    assert_eq!(second.destination, Some(PathBuf::from("/dest/report copy 2.txt")));
+    assert_eq!(first.conflict, Some(ConflictPolicy::Rename));
+    assert_eq!(read(&volume, "/dest/report copy.txt"), b"new");
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+}
+
+#[test]
+fn test_error_fails_without_changes() {
+    let volume = create_volume();
+
+    let result = transfer_with_policy(
+        &volume,
+        Path::new("/src/report.txt"),
+        Path::new("/dest/report.txt"),
+        true,
+        ConflictPolicy::Error,
+    );
+
+    assert!(matches!(result, Err(VolumeError::IoError(_))));
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+    assert!(volume.exists(Path::new("/src/report.txt")));
+}
+
+fn create_manager() -> (VolumeManager, Arc<InMemoryVolume>) {
+    let volume = Arc::new(create_volume());
+    let manager = VolumeManager::new();
+    manager.register("test", volume.clone());
+    (manager, volume)
+}
+
+#[test]
+fn test_transfer_paths_applies_policy_per_item() {
+    let (manager, volume) = create_manager();
+    let paths = [PathBuf::from("/src/report.txt"), PathBuf::from("/src/new.txt")];
+    let mut done_counts = Vec::new();
+
+    let results = transfer_paths(
+        &manager,
+        &paths,
+        Path::new("/dest"),
+        false,
+        ConflictPolicy::Rename,
+        |_, done| done_counts.push(done),
+    );
+
+    assert_eq!(done_counts, vec![1, 2]);
+    assert!(results.iter().all(|r| r.success));
+    assert_eq!(results[0].destination.as_deref(), Some("/dest/report copy.txt"));
+    assert_eq!(results[0].conflict, Some(ConflictPolicy::Rename));
+    assert_eq!(results[1].destination.as_deref(), Some("/dest/new.txt"));
+    assert_eq!(results[1].conflict, None);
+    assert!(volume.exists(Path::new("/dest/report copy.txt")));
+}
+
+#[test]
+fn test_transfer_paths_reports_conflict_errors_and_goes_on() {
+    let (manager, volume) = create_manager();
+    let paths = [PathBuf::from("/src/report.txt"), PathBuf::from("/src/new.txt")];
+
+    let results = transfer_paths(
+        &manager,
+        &paths,
+        Path::new("/dest"),
+        true,
+        ConflictPolicy::Error,
+        |_, _| {},
+    );
+
+    assert!(!results[0].success);
+    assert_eq!(results[0].conflict, Some(ConflictPolicy::Error));
+    assert!(results[0].error.is_some());
+    assert!(results[1].success);
+    assert!(volume.exists(Path::new("/src/report.txt")));
+    assert!(!volume.exists(Path::new("/src/new.txt")));
+}
+
+#[test]
+fn test_transfer_paths_skip_succeeds_without_destination() {
+    let (manager, volume) = create_manager();
+    let paths = [PathBuf::from("/src/report.txt")];
+
+    let results = transfer_paths(
+        &manager,
+        &paths,
+        Path::new("/dest"),
+        false,
+        ConflictPolicy::Skip,
+        |_, _| {},
+    );
+
+    assert!(results[0].success);
+    assert_eq!(results[0].destination, None);
+    assert_eq!(results[0].conflict, Some(ConflictPolicy::Skip));
+    assert_eq!(read(&volume, "/dest/report.txt"), b"old");
+}
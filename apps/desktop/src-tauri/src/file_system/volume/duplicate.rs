@@ -0,0 +1,71 @@
+//! Finder-style "Duplicate": copies an entry next to itself as "name copy.ext", "name copy 2.ext", and so on.
+
+use super::{Volume, VolumeError};
+use std::path::{Path, PathBuf};
+
+/// Copies a file, or a directory with everything in it, into the same directory under the next free name.
+///
+/// Names follow Finder: "report.pdf" becomes "report copy.pdf", then "report copy 2.pdf", and so on. Duplicating a
+/// duplicate continues the sequence, so "report copy.pdf" becomes "report copy 2.pdf" rather than
+/// "report copy copy.pdf". Directories keep their whole name, like "Photos copy".
+///
+/// # Arguments
+/// * `volume` - Volume the entry is on
+/// * `path` - What to duplicate, relative to the volume
+///
+/// # Returns
+/// The path of the copy.
+pub fn duplicate(volume: &dyn Volume, path: &Path) -> Result<PathBuf, VolumeError> {
+    let entry = volume.get_metadata(path)?;
+    let new_path = free_copy_path(volume, path, entry.is_directory);
+    volume.copy(path, &new_path)?;
+    Ok(new_path)
+}
+
+/// The first of "name copy.ext", "name copy 2.ext", and so on next to `path` that doesn't exist yet.
+pub(super) fn free_copy_path(volume: &dyn Volume, path: &Path, is_directory: bool) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new("/"));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let (base, extension) = split_name(&name, is_directory);
+
+    let mut number = 1;
+    let mut new_path = parent.join(copy_name(base, extension, number));
+    while volume.exists(&new_path) {
+        number += 1;
+        new_path = parent.join(copy_name(base, extension, number));
+    }
+    new_path
+}
+
+/// Splits a name into the part to append " copy" to and its extension (with the dot), dropping any " copy" or
+/// " copy N" suffix from an earlier duplicate.
+fn split_name(name: &str, is_directory: bool) -> (&str, &str) {
+    let (stem, extension) = match name.rfind('.') {
+        // A leading dot, like in ".bashrc", is part of the name
+        Some(dot) if dot > 0 && !is_directory => name.split_at(dot),
+        _ => (name, ""),
+    };
+    (strip_copy_suffix(stem), extension)
+}
+
+fn strip_copy_suffix(stem: &str) -> &str {
+    if let Some(base) = stem.strip_suffix(" copy") {
+        return base;
+    }
+    if let Some((base, number)) = stem.rsplit_once(" copy ")
+        && !number.is_empty()
+        && number.bytes().all(|b| b.is_ascii_digit())
+    {
+        return base;
+    }
+    stem
+}
+
+/// The name of the `number`th copy: "base copy.ext" for the first, "base copy N.ext" for later ones.
+fn copy_name(base: &str, extension: &str, number: u32) -> String {
+    if number == 1 {
+        format!("{} copy{}", base, extension)
+    } else {
+        format!("{} copy {}{}", base, number, extension)
+    }
+}
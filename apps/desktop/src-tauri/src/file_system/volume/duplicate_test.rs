@@ -0,0 +1,85 @@
+//! Tests for duplicating entries under Finder-style copy names.
+
+use super::*;
+use std::path::{Path, PathBuf};
+
+/// Volume with "report.pdf", "Makefile", and a "Photos" directory with a file in "/docs".
+fn create_volume() -> InMemoryVolume {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/docs")).unwrap();
+    volume.create_file(Path::new("/docs/report.pdf"), b"report").unwrap();
+    volume.create_file(Path::new("/docs/Makefile"), b"all:").unwrap();
+    volume.create_directory(Path::new("/docs/Photos")).unwrap();
+    volume.create_file(Path::new("/docs/Photos/cat.jpg"), b"meow").unwrap();
+    volume
+}
+
+#[test]
+fn test_first_duplicate_gets_copy_before_extension() {
+    let volume = create_volume();
+
+    let copy = duplicate(&volume, Path::new("/docs/report.pdf")).unwrap();
+
+    assert_eq!(copy, PathBuf::from("/docs/report copy.pdf"));
+    assert_eq!(volume.read_file(&copy, 0, 100).unwrap(), b"report");
+    assert!(volume.exists(Path::new("/docs/report.pdf")));
+}
+
+#[test]
+fn test_later_duplicates_are_numbered() {
+    let volume = create_volume();
+
+    let first = duplicate(&volume, Path::new("/docs/report.pdf")).unwrap();
+    let second = duplicate(&volume, Path::new("/docs/report.pdf")).unwrap();
+    let third = duplicate(&volume, Path::new("/docs/report.pdf")).unwrap();
+
+    assert_eq!(first, PathBuf::from("/docs/report copy.pdf"));
+    assert_eq!(second, PathBuf::from("/docs/report copy 2.pdf"));
+    assert_eq!(third, PathBuf::from("/docs/report copy 3.pdf"));
+}
+
+#[test]
+fn test_duplicating_a_copy_continues_the_sequence() {
+    let volume = create_volume();
+    let first = duplicate(&volume, Path::new("/docs/report.pdf")).unwrap();
+
+    let from_copy = duplicate(&volume, &first).unwrap();
+
+    assert_eq!(from_copy, PathBuf::from("/docs/report copy 2.pdf"));
+}
+
+#[test]
+fn test_names_without_extension() {
+    let volume = create_volume();
+    volume.create_file(Path::new("/docs/.bashrc"), b"alias").unwrap();
+
+    let makefile = duplicate(&volume, Path::new("/docs/Makefile")).unwrap();
+    let makefile_again = duplicate(&volume, Path::new("/docs/Makefile")).unwrap();
+    let dotfile = duplicate(&volume, Path::new("/docs/.bashrc")).unwrap();
+
+    assert_eq!(makefile, PathBuf::from("/docs/Makefile copy"));
+    assert_eq!(makefile_again, PathBuf::from("/docs/Makefile copy 2"));
+    assert_eq!(dotfile, PathBuf::from("/docs/.bashrc copy"));
+}
+
+#[test]
+fn test_directories_are_copied_with_contents() {
+    let volume = create_volume();
+    volume.create_directory(Path::new("/docs/v1.0")).unwrap();
+
+    let photos = duplicate(&volume, Path::new("/docs/Photos")).unwrap();
+    let dotted = duplicate(&volume, Path::new("/docs/v1.0")).unwrap();
+
+    assert_eq!(photos, PathBuf::from("/docs/Photos copy"));
+    assert!(volume.exists(Path::new("/docs/Photos copy/cat.jpg")));
+    assert_eq!(dotted, PathBuf::from("/docs/v1.0 copy"));
+}
+
+#[test]
+fn test_missing_path_fails() {
+    let volume = create_volume();
+
+    let result = duplicate(&volume, Path::new("/docs/missing.txt"));
+
+    assert!(matches!(result, Err(VolumeError::NotFound(_))));
+}
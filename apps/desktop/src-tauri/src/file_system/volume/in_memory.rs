@@ -4,16 +4,17 @@
 //! including create, delete, and list. Useful for unit and integration tests
 //! without touching the real file system.
 
-use super::{Volume, VolumeError};
+use super::{MAX_READ_LEN, SpaceInfo, Volume, VolumeError, check_destination, check_mode};
 use crate::file_system::FileEntry;
+use crate::file_system::operations::get_icon_id;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
 /// Entry in the in-memory file system.
+#[derive(Clone)]
 struct InMemoryEntry {
     metadata: FileEntry,
-    #[allow(dead_code)] // Will be used for future read_file support
     content: Option<Vec<u8>>,
 }
 
@@ -23,13 +24,24 @@ struct InMemoryEntry {
 /// over the file system state for testing. It supports:
 /// - Listing directories
 /// - Getting single entry metadata
-/// - Creating files and directories
-/// - Deleting entries
+/// - Creating, reading, copying, and moving files and directories
+/// - Deleting entries, or "trashing" them (which removes them the same way)
+/// - Symlinks, including broken ones, and hard links (as copies that don't share later changes)
+/// - Reporting a fixed capacity, for testing free space checks
+/// - Acting case-insensitive, for testing case collision checks
+/// - Directories that can't be listed, for testing permission errors
+/// - Renames that fail once, for testing recovery from half-done operations
 /// - Stress testing with large file counts
 pub struct InMemoryVolume {
     name: String,
     root: PathBuf,
     entries: RwLock<HashMap<PathBuf, InMemoryEntry>>,
+    space: Option<SpaceInfo>,
+    case_sensitive: bool,
+    /// Directories whose listing fails with `PermissionDenied`
+    unreadable: Vec<PathBuf>,
+    /// Paths the next rename onto fails with `PermissionDenied`. Each one fails once.
+    failing_renames: RwLock<Vec<PathBuf>>,
 }
 
 impl InMemoryVolume {
@@ -39,9 +51,45 @@ impl InMemoryVolume {
             name: name.into(),
             root: PathBuf::from("/"),
             entries: RwLock::new(HashMap::new()),
+            space: None,
+            case_sensitive: true,
+            unreadable: Vec::new(),
+            failing_renames: RwLock::new(Vec::new()),
         }
     }
 
+    /// Makes the volume report the given capacity and free space from `get_space_info`.
+    /// Without this, `get_space_info` returns `NotSupported`.
+    pub fn with_space(mut self, total_bytes: u64, available_bytes: u64) -> Self {
+        self.space = Some(SpaceInfo {
+            total_bytes,
+            available_bytes,
+        });
+        self
+    }
+
+    /// Sets what `is_case_sensitive` reports. Defaults to case-sensitive.
+    /// Only affects that report, lookups stay exact.
+    pub fn with_case_sensitivity(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Makes listing the given directory fail with `PermissionDenied`, like a folder the user may not read.
+    /// Its entries can still be reached by their paths.
+    pub fn with_unreadable_directory(mut self, path: impl AsRef<Path>) -> Self {
+        let normalized = self.normalize(path.as_ref());
+        self.unreadable.push(normalized);
+        self
+    }
+
+    /// Makes the next rename onto the given path fail with `PermissionDenied`. Later renames onto it work again.
+    pub fn with_failing_rename_to(mut self, path: impl AsRef<Path>) -> Self {
+        let normalized = self.normalize(path.as_ref());
+        self.failing_renames.get_mut().unwrap().push(normalized);
+        self
+    }
+
     /// Creates an in-memory volume pre-populated with entries.
     pub fn with_entries(name: impl Into<String>, entries: Vec<FileEntry>) -> Self {
         let volume = Self::new(name);
@@ -80,6 +128,7 @@ impl InMemoryVolume {
                     size: Some(1024 * (i as u64)),
                     modified_at: Some(1_640_000_000 + i as u64),
                     created_at: Some(1_639_000_000 + i as u64),
+                    changed_at: Some(1_639_000_000 + i as u64),
                     added_at: None,
                     opened_at: None,
                     permissions: 0o644,
@@ -90,7 +139,10 @@ impl InMemoryVolume {
                     } else {
                         "ext:txt".to_string()
                     },
+                    inode: None,
                     extended_metadata_loaded: true,
+                    owner_resolved: true,
+                    symlink_target: None,
                 }
             })
             .collect();
@@ -140,6 +192,9 @@ impl Volume for InMemoryVolume {
             .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
 
         let target_dir = self.normalize(path);
+        if self.unreadable.contains(&target_dir) {
+            return Err(VolumeError::PermissionDenied(target_dir.display().to_string()));
+        }
 
         // Find all entries whose parent matches this directory
         let mut result: Vec<FileEntry> = entries
@@ -206,13 +261,17 @@ impl Volume for InMemoryVolume {
             size: Some(content.len() as u64),
             modified_at: Some(Self::now_secs()),
             created_at: Some(Self::now_secs()),
+            changed_at: Some(Self::now_secs()),
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "testuser".to_string(),
             group: "staff".to_string(),
             icon_id: "file".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         };
 
         entries.insert(
@@ -247,13 +306,74 @@ impl Volume for InMemoryVolume {
             size: None,
             modified_at: Some(Self::now_secs()),
             created_at: Some(Self::now_secs()),
+            changed_at: Some(Self::now_secs()),
             added_at: None,
             opened_at: None,
             permissions: 0o755,
             owner: "testuser".to_string(),
             group: "staff".to_string(),
             icon_id: "dir".to_string(),
+            inode: None,
+            extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
+        };
+
+        entries.insert(
+            normalized,
+            InMemoryEntry {
+                metadata,
+                content: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn create_symlink(&self, link: &Path, target: &Path) -> Result<(), VolumeError> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let normalized = self.normalize(link);
+        if entries.contains_key(&normalized) {
+            return Err(VolumeError::IoError(format!(
+                "Already exists: {}",
+                normalized.display()
+            )));
+        }
+        // Like on disk, the link is listed as a folder if its target is one. The root is "/", so absolute targets
+        // are volume paths too.
+        let resolved_target = Self::parent_of(&normalized).join(target);
+        let is_dir = entries
+            .get(&resolved_target)
+            .is_some_and(|entry| entry.metadata.is_directory);
+
+        let name = normalized
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let metadata = FileEntry {
+            icon_id: get_icon_id(is_dir, true, &name),
+            name,
+            path: normalized.display().to_string(),
+            is_directory: is_dir,
+            is_symlink: true,
+            size: None,
+            modified_at: Some(Self::now_secs()),
+            created_at: Some(Self::now_secs()),
+            changed_at: Some(Self::now_secs()),
+            added_at: None,
+            opened_at: None,
+            permissions: 0o755,
+            owner: "testuser".to_string(),
+            group: "staff".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: Some(target.to_string_lossy().to_string()),
         };
 
         entries.insert(
@@ -267,6 +387,144 @@ impl Volume for InMemoryVolume {
         Ok(())
     }
 
+    fn create_hardlink(&self, link: &Path, target: &Path) -> Result<(), VolumeError> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let link = self.normalize(link);
+        let target = self.normalize(target);
+        if entries.contains_key(&link) {
+            return Err(VolumeError::IoError(format!("Already exists: {}", link.display())));
+        }
+        let mut entry = entries
+            .get(&target)
+            .cloned()
+            .ok_or_else(|| VolumeError::NotFound(target.display().to_string()))?;
+        if entry.metadata.is_directory {
+            return Err(VolumeError::IoError(format!(
+                "Can't hard link a folder: {}",
+                target.display()
+            )));
+        }
+
+        entry.metadata.path = link.display().to_string();
+        entry.metadata.name = link
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        entries.insert(link, entry);
+
+        Ok(())
+    }
+
+    fn read_file(&self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>, VolumeError> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let normalized = self.normalize(path);
+        let entry = entries
+            .get(&normalized)
+            .ok_or_else(|| VolumeError::NotFound(normalized.display().to_string()))?;
+        if entry.metadata.is_directory {
+            return Err(VolumeError::NotSupported);
+        }
+        let content = entry.content.as_deref().unwrap_or_default();
+        let start = usize::try_from(offset).unwrap_or(usize::MAX).min(content.len());
+        let end = start.saturating_add(len.min(MAX_READ_LEN)).min(content.len());
+        Ok(content[start..end].to_vec())
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let from = self.normalize(from);
+        let to = self.normalize(to);
+        if !entries.contains_key(&from) {
+            return Err(VolumeError::NotFound(from.display().to_string()));
+        }
+        check_destination(&from, &to, entries.contains_key(&to))?;
+
+        // The entry itself and everything under it, with their content
+        let copies: Vec<(PathBuf, InMemoryEntry)> = entries
+            .iter()
+            .filter_map(|(path, entry)| {
+                let relative = path.strip_prefix(&from).ok()?;
+                let new_path = if relative.as_os_str().is_empty() {
+                    to.clone()
+                } else {
+                    to.join(relative)
+                };
+                let mut copy = entry.clone();
+                copy.metadata.path = new_path.display().to_string();
+                copy.metadata.name = new_path
+                    .file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Some((new_path, copy))
+            })
+            .collect();
+        entries.extend(copies);
+
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let from = self.normalize(from);
+        let to = self.normalize(to);
+        if !entries.contains_key(&from) {
+            return Err(VolumeError::NotFound(from.display().to_string()));
+        }
+        check_destination(&from, &to, entries.contains_key(&to))?;
+        if let Ok(mut failing) = self.failing_renames.write()
+            && let Some(index) = failing.iter().position(|path| *path == to)
+        {
+            failing.remove(index);
+            return Err(VolumeError::PermissionDenied(to.display().to_string()));
+        }
+
+        // Re-key the entry itself and everything under it
+        let moved_paths: Vec<PathBuf> = entries.keys().filter(|path| path.starts_with(&from)).cloned().collect();
+        for old_path in moved_paths {
+            let Some(mut entry) = entries.remove(&old_path) else {
+                continue;
+            };
+            let relative = old_path.strip_prefix(&from).unwrap_or(Path::new(""));
+            let new_path = if relative.as_os_str().is_empty() {
+                to.clone()
+            } else {
+                to.join(relative)
+            };
+            entry.metadata.path = new_path.display().to_string();
+            entry.metadata.name = new_path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            entries.insert(new_path, entry);
+        }
+
+        Ok(())
+    }
+
+    fn get_space_info(&self, _path: &Path) -> Result<SpaceInfo, VolumeError> {
+        self.space.ok_or(VolumeError::NotSupported)
+    }
+
+    fn is_case_sensitive(&self, _path: &Path) -> bool {
+        self.case_sensitive
+    }
+
     fn delete(&self, path: &Path) -> Result<(), VolumeError> {
         let mut entries = self
             .entries
@@ -280,4 +538,36 @@ impl Volume for InMemoryVolume {
             .map(|_| ())
             .ok_or_else(|| VolumeError::NotFound(normalized.display().to_string()))
     }
+
+    fn set_permissions(&self, path: &Path, mode: u32) -> Result<(), VolumeError> {
+        check_mode(mode)?;
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let normalized = self.normalize(path);
+        let entry = entries
+            .get_mut(&normalized)
+            .ok_or_else(|| VolumeError::NotFound(normalized.display().to_string()))?;
+        entry.metadata.permissions = mode;
+
+        Ok(())
+    }
+
+    fn trash(&self, path: &Path) -> Result<Option<PathBuf>, VolumeError> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| VolumeError::IoError("Lock poisoned".into()))?;
+
+        let normalized = self.normalize(path);
+        if !entries.contains_key(&normalized) {
+            return Err(VolumeError::NotFound(normalized.display().to_string()));
+        }
+        // There's no Trash to move to, so the entry and everything under it are just removed
+        entries.retain(|entry_path, _| !entry_path.starts_with(&normalized));
+
+        Ok(None)
+    }
 }
@@ -2,6 +2,7 @@
 
 use super::*;
 use std::path::Path;
+use std::time::Duration;
 
 #[test]
 fn test_new_creates_empty_volume() {
@@ -24,13 +25,17 @@ fn test_with_entries_populates_volume() {
             size: Some(1024),
             modified_at: Some(1_640_000_000),
             created_at: Some(1_639_000_000),
+            changed_at: Some(1_639_000_000),
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "testuser".to_string(),
             group: "staff".to_string(),
             icon_id: "ext:txt".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "folder".to_string(),
@@ -40,13 +45,17 @@ fn test_with_entries_populates_volume() {
             size: None,
             modified_at: Some(1_640_000_000),
             created_at: Some(1_639_000_000),
+            changed_at: Some(1_639_000_000),
             added_at: None,
             opened_at: None,
             permissions: 0o755,
             owner: "testuser".to_string(),
             group: "staff".to_string(),
             icon_id: "dir".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
     ];
 
@@ -89,13 +98,17 @@ fn test_exists_returns_true_for_existing() {
         size: Some(100),
         modified_at: None,
         created_at: None,
+        changed_at: None,
         added_at: None,
         opened_at: None,
         permissions: 0o644,
         owner: "user".to_string(),
         group: "group".to_string(),
         icon_id: "file".to_string(),
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }];
 
     let volume = InMemoryVolume::with_entries("Test", entries);
@@ -120,13 +133,17 @@ fn test_get_metadata_returns_correct_entry() {
         size: Some(1024),
         modified_at: Some(1_640_000_000),
         created_at: None,
+        changed_at: None,
         added_at: None,
         opened_at: None,
         permissions: 0o644,
         owner: "user".to_string(),
         group: "group".to_string(),
         icon_id: "file".to_string(),
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }];
 
     let volume = InMemoryVolume::with_entries("Test", entries);
@@ -192,6 +209,164 @@ fn test_delete_nonexistent_returns_error() {
     assert!(matches!(result.unwrap_err(), VolumeError::NotFound(_)));
 }
 
+#[test]
+fn test_copy_nested_directory_copies_whole_tree() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/src")).unwrap();
+    volume.create_directory(Path::new("/src/nested")).unwrap();
+    volume.create_file(Path::new("/src/top.txt"), b"top").unwrap();
+    volume
+        .create_file(Path::new("/src/nested/deep.txt"), b"deep content")
+        .unwrap();
+
+    volume.copy(Path::new("/src"), Path::new("/dest")).unwrap();
+
+    let names = |path: &str| -> Vec<String> {
+        volume
+            .list_directory(Path::new(path))
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect()
+    };
+    assert_eq!(names("/dest"), names("/src"));
+    assert_eq!(names("/dest/nested"), names("/src/nested"));
+    let deep = volume.get_metadata(Path::new("/dest/nested/deep.txt")).unwrap();
+    assert_eq!(deep.path, "/dest/nested/deep.txt");
+    assert_eq!(deep.size, Some(12));
+    assert_eq!(volume.get_metadata(Path::new("/dest")).unwrap().name, "dest");
+    assert_eq!(
+        volume
+            .read_file(Path::new("/dest/nested/deep.txt"), 0, MAX_READ_LEN)
+            .unwrap(),
+        b"deep content"
+    );
+    assert_eq!(
+        volume.read_file(Path::new("/dest/top.txt"), 0, MAX_READ_LEN).unwrap(),
+        b"top"
+    );
+    // The source is untouched
+    assert_eq!(
+        volume.read_file(Path::new("/src/top.txt"), 0, MAX_READ_LEN).unwrap(),
+        b"top"
+    );
+}
+
+#[test]
+fn test_copy_missing_source_returns_not_found() {
+    let volume = InMemoryVolume::new("Test");
+
+    let result = volume.copy(Path::new("/missing"), Path::new("/dest"));
+    assert!(matches!(result, Err(VolumeError::NotFound(_))));
+}
+
+#[test]
+fn test_copy_refuses_existing_destination_and_copying_into_itself() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/src")).unwrap();
+    volume.create_file(Path::new("/taken.txt"), b"keep").unwrap();
+
+    assert!(volume.copy(Path::new("/src"), Path::new("/taken.txt")).is_err());
+    assert!(volume.copy(Path::new("/src"), Path::new("/src/inside")).is_err());
+    assert_eq!(
+        volume.read_file(Path::new("/taken.txt"), 0, MAX_READ_LEN).unwrap(),
+        b"keep"
+    );
+}
+
+#[test]
+fn test_rename_moves_file_into_subdirectory() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/docs")).unwrap();
+    volume.create_file(Path::new("/report.txt"), b"numbers").unwrap();
+
+    volume
+        .rename(Path::new("/report.txt"), Path::new("/docs/report.txt"))
+        .unwrap();
+
+    assert!(!volume.exists(Path::new("/report.txt")));
+    let moved = volume.get_metadata(Path::new("/docs/report.txt")).unwrap();
+    assert_eq!(moved.path, "/docs/report.txt");
+    assert_eq!(moved.name, "report.txt");
+    assert_eq!(
+        volume
+            .read_file(Path::new("/docs/report.txt"), 0, MAX_READ_LEN)
+            .unwrap(),
+        b"numbers"
+    );
+}
+
+#[test]
+fn test_rename_in_place_renames_directory_with_children() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/old")).unwrap();
+    volume.create_file(Path::new("/old/child.txt"), b"child").unwrap();
+
+    volume.rename(Path::new("/old"), Path::new("/new")).unwrap();
+
+    assert!(!volume.exists(Path::new("/old")));
+    assert!(!volume.exists(Path::new("/old/child.txt")));
+    assert_eq!(volume.get_metadata(Path::new("/new")).unwrap().name, "new");
+    let child = volume.get_metadata(Path::new("/new/child.txt")).unwrap();
+    assert_eq!(child.path, "/new/child.txt");
+    assert_eq!(volume.list_directory(Path::new("/new")).unwrap().len(), 1);
+}
+
+#[test]
+fn test_rename_refuses_to_overwrite() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_file(Path::new("/a.txt"), b"a").unwrap();
+    volume.create_file(Path::new("/b.txt"), b"b").unwrap();
+
+    let result = volume.rename(Path::new("/a.txt"), Path::new("/b.txt"));
+
+    assert!(matches!(result, Err(VolumeError::IoError(_))));
+    assert_eq!(volume.read_file(Path::new("/a.txt"), 0, MAX_READ_LEN).unwrap(), b"a");
+    assert_eq!(volume.read_file(Path::new("/b.txt"), 0, MAX_READ_LEN).unwrap(), b"b");
+}
+
+#[test]
+fn test_read_file_returns_requested_range() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_file(Path::new("/text.txt"), b"Hello, world!").unwrap();
+
+    let read = |offset: u64, len: usize| volume.read_file(Path::new("/text.txt"), offset, len).unwrap();
+
+    assert_eq!(read(0, 5), b"Hello");
+    assert_eq!(read(7, 5), b"world");
+    // Stops at the end of the file
+    assert_eq!(read(7, 100), b"world!");
+    assert!(read(13, 10).is_empty());
+    assert!(read(u64::MAX, 10).is_empty());
+}
+
+#[test]
+fn test_read_file_caps_length() {
+    let volume = InMemoryVolume::new("Test");
+    let content = vec![b'x'; MAX_READ_LEN + 10];
+    volume.create_file(Path::new("/big.bin"), &content).unwrap();
+
+    let chunk = volume.read_file(Path::new("/big.bin"), 0, usize::MAX).unwrap();
+    let rest = volume
+        .read_file(Path::new("/big.bin"), MAX_READ_LEN as u64, usize::MAX)
+        .unwrap();
+
+    assert_eq!(chunk.len(), MAX_READ_LEN);
+    assert_eq!(rest.len(), 10);
+}
+
+#[test]
+fn test_read_file_on_directory_is_not_supported() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/folder")).unwrap();
+
+    let dir = volume.read_file(Path::new("/folder"), 0, 10);
+    let missing = volume.read_file(Path::new("/missing.txt"), 0, 10);
+
+    assert!(matches!(dir, Err(VolumeError::NotSupported)));
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+}
+
 #[test]
 fn test_list_directory_sorts_correctly() {
     let entries = vec![
@@ -203,13 +378,17 @@ fn test_list_directory_sorts_correctly() {
             size: Some(100),
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "file".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "alpha".to_string(),
@@ -219,13 +398,17 @@ fn test_list_directory_sorts_correctly() {
             size: None,
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o755,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "dir".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "apple.txt".to_string(),
@@ -235,13 +418,17 @@ fn test_list_directory_sorts_correctly() {
             size: Some(50),
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "file".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "beta".to_string(),
@@ -251,13 +438,17 @@ fn test_list_directory_sorts_correctly() {
             size: None,
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o755,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "dir".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
     ];
 
@@ -286,13 +477,17 @@ fn test_list_subdirectory() {
             size: None,
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o755,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "dir".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "file_in_subdir.txt".to_string(),
@@ -302,13 +497,17 @@ fn test_list_subdirectory() {
             size: Some(100),
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "file".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
         FileEntry {
             name: "root_file.txt".to_string(),
@@ -318,13 +517,17 @@ fn test_list_subdirectory() {
             size: Some(50),
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "file".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         },
     ];
 
@@ -344,6 +547,107 @@ fn test_list_subdirectory() {
 fn test_supports_watching_returns_false() {
     let volume = InMemoryVolume::new("Test");
     assert!(!volume.supports_watching());
+    assert!(matches!(
+        volume.watch(Path::new("/"), Duration::from_millis(200), Box::new(|| {})),
+        Err(VolumeError::NotSupported)
+    ));
+}
+
+#[test]
+fn test_symlinks_report_their_target() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/docs")).unwrap();
+    volume.create_file(Path::new("/docs/readme.md"), b"hello").unwrap();
+    volume
+        .create_symlink(Path::new("/docs_link"), Path::new("docs"))
+        .unwrap();
+    volume
+        .create_symlink(Path::new("/docs/readme_link"), Path::new("/docs/readme.md"))
+        .unwrap();
+    volume
+        .create_symlink(Path::new("/broken"), Path::new("/missing/path"))
+        .unwrap();
+
+    let root_entries = volume.list_directory(Path::new("")).unwrap();
+
+    let dir_link = root_entries.iter().find(|e| e.name == "docs_link").unwrap();
+    assert!(dir_link.is_symlink);
+    assert!(dir_link.is_directory, "A link to a folder should be listed as a folder");
+    assert_eq!(dir_link.symlink_target.as_deref(), Some("docs"));
+    assert_eq!(dir_link.icon_id, "symlink-dir");
+
+    let broken = root_entries.iter().find(|e| e.name == "broken").unwrap();
+    assert!(broken.is_symlink);
+    assert!(!broken.is_directory);
+    assert_eq!(broken.symlink_target.as_deref(), Some("/missing/path"));
+
+    let file_link = volume.get_metadata(Path::new("/docs/readme_link")).unwrap();
+    assert!(!file_link.is_directory);
+    assert_eq!(file_link.symlink_target.as_deref(), Some("/docs/readme.md"));
+    let file = volume.get_metadata(Path::new("/docs/readme.md")).unwrap();
+    assert_eq!(file.symlink_target, None);
+}
+
+#[test]
+fn test_create_directory_all_creates_parents() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/a")).unwrap();
+
+    volume.create_directory_all(Path::new("/a/b/c")).unwrap();
+    let again = volume.create_directory_all(Path::new("/a/b/c"));
+
+    assert!(volume.get_metadata(Path::new("/a/b")).unwrap().is_directory);
+    assert!(volume.get_metadata(Path::new("/a/b/c")).unwrap().is_directory);
+    assert_eq!(volume.list_directory(Path::new("/a")).unwrap().len(), 1);
+    assert!(again.is_ok(), "An existing directory isn't an error");
+}
+
+#[test]
+fn test_create_directory_all_fails_on_file_in_the_way() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_file(Path::new("/file.txt"), b"content").unwrap();
+
+    let on_file = volume.create_directory_all(Path::new("/file.txt"));
+
+    assert!(matches!(on_file, Err(VolumeError::IoError(_))));
+    assert!(!volume.get_metadata(Path::new("/file.txt")).unwrap().is_directory);
+}
+
+#[test]
+fn test_hardlink_copies_the_file() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_file(Path::new("/original.txt"), b"content").unwrap();
+    volume.create_directory(Path::new("/folder")).unwrap();
+
+    volume
+        .create_hardlink(Path::new("/folder/link.txt"), Path::new("/original.txt"))
+        .unwrap();
+    let folder_link = volume.create_hardlink(Path::new("/folder_link"), Path::new("/folder"));
+    let missing = volume.create_hardlink(Path::new("/missing_link"), Path::new("/missing.txt"));
+
+    let link = volume.get_metadata(Path::new("/folder/link.txt")).unwrap();
+    assert_eq!(link.name, "link.txt");
+    assert!(!link.is_symlink);
+    assert_eq!(
+        volume.read_file(Path::new("/folder/link.txt"), 0, 100).unwrap(),
+        b"content"
+    );
+    assert!(matches!(folder_link, Err(VolumeError::IoError(_))));
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+}
+
+#[test]
+fn test_links_refuse_to_replace_existing_entries() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_file(Path::new("/a.txt"), b"a").unwrap();
+    volume.create_file(Path::new("/b.txt"), b"b").unwrap();
+
+    let symlink = volume.create_symlink(Path::new("/b.txt"), Path::new("a.txt"));
+    let hardlink = volume.create_hardlink(Path::new("/b.txt"), Path::new("/a.txt"));
+
+    assert!(matches!(symlink, Err(VolumeError::IoError(_))));
+    assert!(matches!(hardlink, Err(VolumeError::IoError(_))));
+    assert_eq!(volume.read_file(Path::new("/b.txt"), 0, 100).unwrap(), b"b");
 }
 
 // ============================================================================
@@ -452,3 +756,20 @@ fn test_concurrent_create_delete() {
     // Permanent file should still exist
     assert!(volume.exists(std::path::Path::new("/permanent.txt")));
 }
+
+#[test]
+fn test_set_permissions_round_trips_through_metadata() {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_file(Path::new("/script.sh"), b"echo").unwrap();
+    volume.create_directory(Path::new("/shared")).unwrap();
+
+    volume.set_permissions(Path::new("/script.sh"), 0o755).unwrap();
+    volume.set_permissions(Path::new("/shared"), 0o1777).unwrap();
+    let too_high = volume.set_permissions(Path::new("/script.sh"), 0o10000);
+    let missing = volume.set_permissions(Path::new("/missing.txt"), 0o644);
+
+    assert_eq!(volume.get_metadata(Path::new("/script.sh")).unwrap().permissions, 0o755);
+    assert_eq!(volume.get_metadata(Path::new("/shared")).unwrap().permissions, 0o1777);
+    assert!(matches!(too_high, Err(VolumeError::IoError(_))));
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+}
@@ -1,9 +1,14 @@
 //! Local POSIX file system volume implementation.
 
-use super::{Volume, VolumeError};
+use super::{MAX_READ_LEN, SpaceInfo, Volume, VolumeError, WatchGuard, check_destination, check_mode};
 use crate::file_system::FileEntry;
+use crate::file_system::case_sensitivity::is_case_sensitive_volume;
 use crate::file_system::operations::{get_single_entry, list_directory_core};
+use notify_debouncer_full::{DebounceEventResult, new_debouncer, notify::RecursiveMode};
+use std::io::{Read, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// A volume backed by the local POSIX file system.
 ///
@@ -69,7 +74,12 @@ impl Volume for LocalPosixVolume {
 
     fn list_directory(&self, path: &Path) -> Result<Vec<FileEntry>, VolumeError> {
         let abs_path = self.resolve(path);
-        list_directory_core(&abs_path).map_err(VolumeError::from)
+        list_directory_core(&abs_path, true).map_err(VolumeError::from)
+    }
+
+    fn list_directory_numeric_owners(&self, path: &Path) -> Result<Vec<FileEntry>, VolumeError> {
+        let abs_path = self.resolve(path);
+        list_directory_core(&abs_path, false).map_err(VolumeError::from)
     }
 
     fn get_metadata(&self, path: &Path) -> Result<FileEntry, VolumeError> {
@@ -83,7 +93,181 @@ impl Volume for LocalPosixVolume {
         std::fs::symlink_metadata(self.resolve(path)).is_ok()
     }
 
+    fn delete(&self, path: &Path) -> Result<(), VolumeError> {
+        let abs_path = self.resolve(path);
+        // symlink_metadata, so that symlinks to directories are removed as links
+        let metadata = std::fs::symlink_metadata(&abs_path)?;
+        if metadata.is_dir() {
+            std::fs::remove_dir(&abs_path)?;
+        } else {
+            std::fs::remove_file(&abs_path)?;
+        }
+        Ok(())
+    }
+
+    fn create_directory(&self, path: &Path) -> Result<(), VolumeError> {
+        std::fs::create_dir(self.resolve(path))?;
+        Ok(())
+    }
+
+    fn create_directory_all(&self, path: &Path) -> Result<(), VolumeError> {
+        std::fs::create_dir_all(self.resolve(path))?;
+        Ok(())
+    }
+
+    fn create_symlink(&self, link: &Path, target: &Path) -> Result<(), VolumeError> {
+        std::os::unix::fs::symlink(target, self.resolve(link))?;
+        Ok(())
+    }
+
+    fn create_hardlink(&self, link: &Path, target: &Path) -> Result<(), VolumeError> {
+        std::fs::hard_link(self.resolve(target), self.resolve(link))?;
+        Ok(())
+    }
+
+    fn set_permissions(&self, path: &Path, mode: u32) -> Result<(), VolumeError> {
+        use std::os::unix::fs::PermissionsExt;
+
+        check_mode(mode)?;
+        std::fs::set_permissions(self.resolve(path), std::fs::Permissions::from_mode(mode))?;
+        Ok(())
+    }
+
+    fn set_owner(&self, path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<(), VolumeError> {
+        // EPERM, which is what unprivileged users get, maps to PermissionDenied
+        std::os::unix::fs::chown(self.resolve(path), uid, gid)?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn trash(&self, path: &Path) -> Result<Option<PathBuf>, VolumeError> {
+        use objc2_foundation::{NSFileManager, NSString, NSURL};
+
+        let abs_path = self.resolve(path);
+        // Check first, to report a missing path as NotFound rather than a Foundation error
+        std::fs::symlink_metadata(&abs_path)?;
+        let url = NSURL::fileURLWithPath(&NSString::from_str(&abs_path.to_string_lossy()));
+        let mut trashed_url = None;
+        unsafe { NSFileManager::defaultManager().trashItemAtURL_resultingItemURL_error(&url, Some(&mut trashed_url)) }
+            .map_err(|e| VolumeError::IoError(format!("Failed to move to Trash: {}", e.localizedDescription())))?;
+        Ok(trashed_url
+            .and_then(|url| url.path())
+            .map(|path| PathBuf::from(path.to_string())))
+    }
+
+    fn read_file(&self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>, VolumeError> {
+        let mut file = std::fs::File::open(self.resolve(path))?;
+        if file.metadata()?.is_dir() {
+            return Err(VolumeError::NotSupported);
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buffer = Vec::new();
+        file.take(len.min(MAX_READ_LEN) as u64).read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+        let abs_from = self.resolve(from);
+        let abs_to = self.resolve(to);
+        let to_exists = std::fs::symlink_metadata(&abs_to).is_ok();
+        check_destination(&abs_from, &abs_to, to_exists)?;
+        copy_tree(&abs_from, &abs_to)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+        let abs_from = self.resolve(from);
+        let abs_to = self.resolve(to);
+        // fs::rename would silently replace an existing file. But on a case-insensitive volume, `to` also "exists"
+        // when only the case of the name changes, and then it's the same file as `from`.
+        let to_exists = match (std::fs::symlink_metadata(&abs_from), std::fs::symlink_metadata(&abs_to)) {
+            (Ok(from_meta), Ok(to_meta)) => from_meta.dev() != to_meta.dev() || from_meta.ino() != to_meta.ino(),
+            (_, to_meta) => to_meta.is_ok(),
+        };
+        check_destination(&abs_from, &abs_to, to_exists)?;
+        match std::fs::rename(&abs_from, &abs_to) {
+            // A volume like "/" spans several file systems, and rename can't cross them
+            Err(e) if e.raw_os_error() == Some(libc::EXDEV) => {
+                copy_tree(&abs_from, &abs_to)?;
+                if std::fs::symlink_metadata(&abs_from)?.is_dir() {
+                    std::fs::remove_dir_all(&abs_from)?;
+                } else {
+                    std::fs::remove_file(&abs_from)?;
+                }
+                Ok(())
+            }
+            result => Ok(result?),
+        }
+    }
+
+    fn get_space_info(&self, path: &Path) -> Result<SpaceInfo, VolumeError> {
+        let abs_path = self.resolve(path);
+        let c_path = std::ffi::CString::new(abs_path.as_os_str().as_encoded_bytes())
+            .map_err(|_| VolumeError::IoError(format!("Invalid path: {}", abs_path.display())))?;
+
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return Err(VolumeError::from(std::io::Error::last_os_error()));
+        }
+
+        // Field widths differ between platforms, hence the casts
+        let fragment_size = stat.f_frsize as u64;
+        Ok(SpaceInfo {
+            total_bytes: stat.f_blocks as u64 * fragment_size,
+            available_bytes: stat.f_bavail as u64 * fragment_size,
+        })
+    }
+
+    fn is_case_sensitive(&self, path: &Path) -> bool {
+        is_case_sensitive_volume(&self.resolve(path))
+    }
+
     fn supports_watching(&self) -> bool {
         true
     }
+
+    fn watch(
+        &self,
+        path: &Path,
+        debounce: Duration,
+        on_change: Box<dyn Fn() + Send + 'static>,
+    ) -> Result<WatchGuard, VolumeError> {
+        let mut debouncer = new_debouncer(
+            debounce,
+            None, // No tick rate limit
+            move |result: DebounceEventResult| {
+                if result.is_ok() {
+                    on_change();
+                }
+            },
+        )
+        .map_err(|e| VolumeError::IoError(format!("Failed to create watcher: {}", e)))?;
+        debouncer
+            .watch(&self.resolve(path), RecursiveMode::NonRecursive)
+            .map_err(|e| VolumeError::IoError(format!("Failed to watch path: {}", e)))?;
+        Ok(WatchGuard::new(debouncer))
+    }
+}
+
+/// Copies a file or directory tree, keeping permissions and modification times. Symlinks are copied as links.
+fn copy_tree(from: &Path, to: &Path) -> Result<(), VolumeError> {
+    let metadata = std::fs::symlink_metadata(from)?;
+    if metadata.file_type().is_symlink() {
+        std::os::unix::fs::symlink(std::fs::read_link(from)?, to)?;
+        return Ok(());
+    }
+
+    if metadata.is_dir() {
+        std::fs::create_dir(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        // Only now, so a read-only directory can still be filled, and its mtime isn't bumped by the filling
+        std::fs::set_permissions(to, metadata.permissions())?;
+    } else {
+        // Also copies the permissions
+        std::fs::copy(from, to)?;
+    }
+    std::fs::File::open(to)?.set_modified(metadata.modified()?)?;
+    Ok(())
 }
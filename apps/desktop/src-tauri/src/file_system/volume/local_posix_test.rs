@@ -101,18 +101,79 @@ fn test_supports_watching_returns_true() {
     assert!(volume.supports_watching());
 }
 
+#[test]
+fn test_watch_calls_back_on_change_until_dropped() {
+    use std::fs;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_watch_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    let guard = volume
+        .watch(
+            Path::new(""),
+            Duration::from_millis(100),
+            Box::new(move || {
+                let _ = sender.send(());
+            }),
+        )
+        .unwrap();
+    fs::write(test_dir.join("touched.txt"), "").unwrap();
+    let called = receiver.recv_timeout(Duration::from_secs(5));
+    drop(guard);
+    // Dropping the guard drops the callback, and with it the sender
+    let after_drop = receiver.recv_timeout(Duration::from_secs(1));
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert!(called.is_ok());
+    assert!(matches!(after_drop, Err(mpsc::RecvTimeoutError::Disconnected)));
+}
+
 #[test]
 fn test_optional_methods_return_not_supported() {
     let volume = LocalPosixVolume::new("Test", "/tmp");
 
     let result = volume.create_file(Path::new("test.txt"), b"content");
     assert!(matches!(result, Err(VolumeError::NotSupported)));
+}
 
-    let result = volume.create_directory(Path::new("testdir"));
-    assert!(matches!(result, Err(VolumeError::NotSupported)));
+#[test]
+fn test_create_directory_needs_parent_unlike_create_directory_all() {
+    use std::fs;
 
-    let result = volume.delete(Path::new("test.txt"));
-    assert!(matches!(result, Err(VolumeError::NotSupported)));
+    let test_dir = std::env::temp_dir().join("cmdr_local_create_dir_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let single = volume.create_directory(Path::new("single"));
+    let without_parent = volume.create_directory(Path::new("a/b/c"));
+    let deep = volume.create_directory_all(Path::new("a/b/c"));
+    let again = volume.create_directory_all(Path::new("a/b/c"));
+    let single_again = volume.create_directory(Path::new("single"));
+    let deep_is_dir = test_dir.join("a/b/c").is_dir();
+    let single_is_dir = test_dir.join("single").is_dir();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert!(single.is_ok());
+    assert!(single_is_dir);
+    assert!(matches!(without_parent, Err(VolumeError::NotFound(_))));
+    assert!(deep.is_ok());
+    assert!(deep_is_dir);
+    assert!(again.is_ok(), "An existing directory isn't an error");
+    assert!(matches!(single_again, Err(VolumeError::IoError(_))));
+}
+
+#[test]
+fn test_delete_missing_path_returns_not_found() {
+    let volume = LocalPosixVolume::new("Test", "/tmp");
+
+    let result = volume.delete(Path::new("cmdr_local_posix_delete_missing_test.txt"));
+    assert!(matches!(result, Err(VolumeError::NotFound(_))));
 }
 
 // ============================================================================
@@ -241,3 +302,285 @@ fn test_list_directory_includes_symlinks() {
     // Cleanup
     let _ = fs::remove_dir_all(&test_dir);
 }
+
+#[test]
+fn test_get_space_info_reports_capacity() {
+    let volume = LocalPosixVolume::new("Root", "/");
+    let space = volume.get_space_info(Path::new("")).unwrap();
+
+    assert!(space.total_bytes > 0);
+    assert!(space.available_bytes <= space.total_bytes);
+}
+
+#[test]
+fn test_delete_recursive_removes_tree_without_following_symlinks() {
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use std::sync::atomic::AtomicBool;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_delete_recursive_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    let outside = test_dir.join("outside");
+    let tree = test_dir.join("tree");
+    fs::create_dir_all(tree.join("sub")).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    fs::write(tree.join("sub/file.txt"), "content").unwrap();
+    fs::write(outside.join("precious.txt"), "keep me").unwrap();
+    symlink(&outside, tree.join("link_to_outside")).unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let outcome = delete_recursive(&volume, Path::new("tree"), &AtomicBool::new(false), |_| {}).unwrap();
+    let tree_exists = tree.exists();
+    let outside_intact = outside.join("precious.txt").exists();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert_eq!(outcome.removed, 4);
+    assert!(!tree_exists);
+    assert!(outside_intact);
+}
+
+#[test]
+fn test_copy_keeps_content_permissions_and_mtime() {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_copy_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(test_dir.join("src/nested")).unwrap();
+    let script = test_dir.join("src/nested/run.sh");
+    fs::write(&script, "#!/bin/sh").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o750)).unwrap();
+    let mtime = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    fs::File::open(&script).unwrap().set_modified(mtime).unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    volume.copy(Path::new("src"), Path::new("dest")).unwrap();
+    let copied = test_dir.join("dest/nested/run.sh");
+    let content = fs::read_to_string(&copied).unwrap();
+    let metadata = fs::metadata(&copied).unwrap();
+    let missing = volume.copy(Path::new("missing"), Path::new("dest2"));
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert_eq!(content, "#!/bin/sh");
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o750);
+    assert_eq!(metadata.modified().unwrap(), mtime);
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+}
+
+#[test]
+fn test_read_file_reads_partial_ranges() {
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_read_file_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(test_dir.join("folder")).unwrap();
+    let mut content = b"0123456789".to_vec();
+    content.resize(MAX_READ_LEN + 20, b'x');
+    fs::write(test_dir.join("data.bin"), &content).unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let read = |offset: u64, len: usize| volume.read_file(Path::new("data.bin"), offset, len);
+    let start = read(0, 4).unwrap();
+    let middle = read(3, 4).unwrap();
+    let capped = read(0, usize::MAX).unwrap();
+    let tail = read(MAX_READ_LEN as u64 + 15, 100).unwrap();
+    let past_end = read(MAX_READ_LEN as u64 + 100, 10).unwrap();
+    let dir = volume.read_file(Path::new("folder"), 0, 10);
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert_eq!(start, b"0123");
+    assert_eq!(middle, b"3456");
+    assert_eq!(capped.len(), MAX_READ_LEN);
+    assert_eq!(tail, b"xxxxx");
+    assert!(past_end.is_empty());
+    assert!(matches!(dir, Err(VolumeError::NotSupported)));
+}
+
+#[test]
+fn test_rename_moves_into_subdirectory_and_refuses_to_overwrite() {
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_rename_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(test_dir.join("sub")).unwrap();
+    fs::write(test_dir.join("a.txt"), "a").unwrap();
+    fs::write(test_dir.join("b.txt"), "b").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let overwrite = volume.rename(Path::new("a.txt"), Path::new("b.txt"));
+    volume.rename(Path::new("a.txt"), Path::new("sub/renamed.txt")).unwrap();
+    let moved = fs::read_to_string(test_dir.join("sub/renamed.txt")).unwrap();
+    let source_exists = test_dir.join("a.txt").exists();
+    let untouched = fs::read_to_string(test_dir.join("b.txt")).unwrap();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert!(matches!(overwrite, Err(VolumeError::IoError(_))));
+    assert_eq!(moved, "a");
+    assert!(!source_exists);
+    assert_eq!(untouched, "b");
+}
+
+#[test]
+fn test_create_symlink_and_hardlink() {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_links_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(test_dir.join("sub")).unwrap();
+    fs::write(test_dir.join("file.txt"), "content").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    volume
+        .create_symlink(Path::new("sub/symlink"), Path::new("../file.txt"))
+        .unwrap();
+    volume
+        .create_symlink(Path::new("broken"), Path::new("/missing/path"))
+        .unwrap();
+    volume
+        .create_hardlink(Path::new("/sub/hardlink.txt"), Path::new("/file.txt"))
+        .unwrap();
+    let symlink = volume.get_metadata(Path::new("sub/symlink")).unwrap();
+    let symlink_content = fs::read_to_string(test_dir.join("sub/symlink")).unwrap();
+    let broken_exists = volume.exists(Path::new("broken"));
+    let original_meta = fs::metadata(test_dir.join("file.txt")).unwrap();
+    let hardlink_meta = fs::symlink_metadata(test_dir.join("sub/hardlink.txt")).unwrap();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert!(symlink.is_symlink);
+    assert_eq!(symlink.symlink_target.as_deref(), Some("../file.txt"));
+    assert_eq!(symlink_content, "content");
+    assert!(broken_exists);
+    assert!(!hardlink_meta.file_type().is_symlink());
+    assert_eq!(hardlink_meta.ino(), original_meta.ino());
+    assert_eq!(original_meta.nlink(), 2);
+}
+
+#[test]
+fn test_create_links_refuse_to_replace_existing_entries() {
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_links_exist_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+    fs::write(test_dir.join("a.txt"), "a").unwrap();
+    fs::write(test_dir.join("b.txt"), "b").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let symlink = volume.create_symlink(Path::new("b.txt"), Path::new("a.txt"));
+    let hardlink = volume.create_hardlink(Path::new("b.txt"), Path::new("a.txt"));
+    let untouched = fs::read_to_string(test_dir.join("b.txt")).unwrap();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert!(matches!(symlink, Err(VolumeError::IoError(_))));
+    assert!(matches!(hardlink, Err(VolumeError::IoError(_))));
+    assert_eq!(untouched, "b");
+}
+
+#[cfg(target_os = "macos")]
+#[test]
+fn test_trash_removes_file_from_its_directory() {
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_trash_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+    fs::write(test_dir.join("trash_me.txt"), "bye").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let trashed = volume.trash(Path::new("trash_me.txt"));
+    let still_there = test_dir.join("trash_me.txt").exists();
+    let missing = volume.trash(Path::new("never_existed.txt"));
+
+    let _ = fs::remove_dir_all(&test_dir);
+    if let Ok(Some(location)) = &trashed {
+        let _ = fs::remove_file(location);
+    }
+    assert!(trashed.is_ok(), "Trashing failed: {:?}", trashed);
+    assert!(!still_there);
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_trash_is_not_supported() {
+    let volume = LocalPosixVolume::new("Test", "/tmp");
+
+    let result = volume.trash(Path::new("cmdr_local_posix_trash_test.txt"));
+    assert!(matches!(result, Err(VolumeError::NotSupported)));
+}
+
+#[test]
+fn test_set_permissions_round_trips_through_metadata() {
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_chmod_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(test_dir.join("folder")).unwrap();
+    fs::write(test_dir.join("file.txt"), "content").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    volume.set_permissions(Path::new("file.txt"), 0o600).unwrap();
+    volume.set_permissions(Path::new("folder"), 0o1750).unwrap();
+    let file = volume.get_metadata(Path::new("file.txt")).unwrap();
+    let folder = volume.get_metadata(Path::new("folder")).unwrap();
+    let too_high = volume.set_permissions(Path::new("file.txt"), 0o100644);
+    let missing = volume.set_permissions(Path::new("missing.txt"), 0o644);
+    let after_too_high = volume.get_metadata(Path::new("file.txt")).unwrap();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    // Local metadata includes the file type bits
+    assert_eq!(file.permissions & 0o7777, 0o600);
+    assert_eq!(folder.permissions & 0o7777, 0o1750);
+    assert!(matches!(too_high, Err(VolumeError::IoError(_))));
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+    assert_eq!(after_too_high.permissions & 0o7777, 0o600);
+}
+
+#[test]
+fn test_set_owner_keeps_or_sets_ids() {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_chown_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+    fs::write(test_dir.join("file.txt"), "content").unwrap();
+    let before = fs::metadata(test_dir.join("file.txt")).unwrap();
+
+    // Setting our own ids needs no privileges
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let unchanged = volume.set_owner(Path::new("file.txt"), None, None);
+    let same_ids = volume.set_owner(Path::new("file.txt"), Some(before.uid()), Some(before.gid()));
+    let missing = volume.set_owner(Path::new("missing.txt"), Some(before.uid()), None);
+    let after = fs::metadata(test_dir.join("file.txt")).unwrap();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    assert!(unchanged.is_ok());
+    assert!(same_ids.is_ok());
+    assert!(matches!(missing, Err(VolumeError::NotFound(_))));
+    assert_eq!((after.uid(), after.gid()), (before.uid(), before.gid()));
+}
+
+#[test]
+fn test_symlink_to_directory_has_no_size() {
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_local_dir_symlink_size_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(test_dir.join("folder")).unwrap();
+    fs::write(test_dir.join("folder/file.txt"), "content").unwrap();
+    std::os::unix::fs::symlink("folder", test_dir.join("folder_link")).unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let entries = volume.list_directory(Path::new("")).unwrap();
+    let metadata = volume.get_metadata(Path::new("folder_link")).unwrap();
+
+    let _ = fs::remove_dir_all(&test_dir);
+    let listed = entries.iter().find(|e| e.name == "folder_link").unwrap();
+    assert!(listed.is_symlink);
+    assert_eq!(listed.size, None);
+    assert!(metadata.is_symlink);
+    assert_eq!(metadata.size, None);
+}
@@ -7,7 +7,9 @@
 #![allow(dead_code)]
 
 use super::FileEntry;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Error type for volume operations.
 #[derive(Debug, Clone)]
@@ -20,6 +22,10 @@ pub enum VolumeError {
     NotSupported,
     /// Generic I/O error
     IoError(String),
+    /// Not enough free space on the destination for the operation
+    InsufficientSpace { needed: u64, available: u64 },
+    /// The name only differs in case from an existing entry, on a case-insensitive volume
+    CaseCollision { name: String, existing: String },
 }
 
 impl std::fmt::Display for VolumeError {
@@ -29,12 +35,49 @@ impl std::fmt::Display for VolumeError {
             Self::PermissionDenied(path) => write!(f, "Permission denied: {}", path),
             Self::NotSupported => write!(f, "Operation not supported"),
             Self::IoError(msg) => write!(f, "I/O error: {}", msg),
+            Self::InsufficientSpace { needed, available } => write!(
+                f,
+                "Not enough free space: {} bytes needed, {} bytes available",
+                needed, available
+            ),
+            Self::CaseCollision { name, existing } => write!(
+                f,
+                "\"{}\" would replace \"{}\", names that only differ in case are the same on this volume",
+                name, existing
+            ),
         }
     }
 }
 
 impl std::error::Error for VolumeError {}
 
+/// Highest mode `Volume::set_permissions` accepts: the permission bits plus setuid, setgid, and sticky.
+pub const MAX_MODE: u32 = 0o7777;
+
+/// Checks that a mode for `Volume::set_permissions` has no bits above `MAX_MODE`, like file type bits.
+pub fn check_mode(mode: u32) -> Result<(), VolumeError> {
+    if mode > MAX_MODE {
+        return Err(VolumeError::IoError(format!("Invalid mode: {:o}", mode)));
+    }
+    Ok(())
+}
+
+/// Most bytes `Volume::read_file` returns in one call. Longer reads are cut to this.
+pub const MAX_READ_LEN: usize = 1024 * 1024;
+
+/// Keeps a `Volume::watch` going. Watching stops when this is dropped.
+pub struct WatchGuard {
+    #[allow(dead_code)] // Only held, to keep watching
+    inner: Box<dyn std::any::Any + Send + Sync>,
+}
+
+impl WatchGuard {
+    /// Wraps whatever has to be kept alive for the watch to go on, like a debouncer.
+    pub fn new(inner: impl std::any::Any + Send + Sync) -> Self {
+        Self { inner: Box::new(inner) }
+    }
+}
+
 impl From<std::io::Error> for VolumeError {
     fn from(err: std::io::Error) -> Self {
         match err.kind() {
@@ -45,6 +88,51 @@ impl From<std::io::Error> for VolumeError {
     }
 }
 
+/// Checks that `to` is a valid destination for copying or moving `from`: it must not exist yet, and must not be
+/// inside `from`.
+fn check_destination(from: &Path, to: &Path, to_exists: bool) -> Result<(), VolumeError> {
+    if to_exists {
+        return Err(VolumeError::IoError(format!("Already exists: {}", to.display())));
+    }
+    if to.starts_with(from) {
+        return Err(VolumeError::IoError(format!(
+            "Can't put {} inside itself",
+            from.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Deletes a file, or a directory with everything in it, using only `Volume::delete`. Doesn't follow symlinks.
+fn delete_tree<V: Volume + ?Sized>(volume: &V, path: &Path) -> Result<(), VolumeError> {
+    let metadata = volume.get_metadata(path)?;
+    if metadata.is_directory && !metadata.is_symlink {
+        for entry in volume.list_directory(path)? {
+            delete_tree(volume, &path.join(&entry.name))?;
+        }
+    }
+    volume.delete(path)
+}
+
+/// The kind of link `create_symlink` and `create_hardlink` make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkKind {
+    /// Points to a path, which may not exist
+    Symbolic,
+    /// Another name for an existing file
+    Hard,
+}
+
+/// Capacity and free space of the file system a path is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceInfo {
+    /// Total size of the file system in bytes
+    pub total_bytes: u64,
+    /// Bytes available to the current (non-root) user
+    pub available_bytes: u64,
+}
+
 /// Trait for volume file system operations.
 ///
 /// Implementations provide access to different storage backends:
@@ -69,6 +157,12 @@ pub trait Volume: Send + Sync {
     /// Returns entries sorted with directories first, then files, both alphabetically.
     fn list_directory(&self, path: &Path) -> Result<Vec<FileEntry>, VolumeError>;
 
+    /// Like `list_directory`, but owner and group may be left as numeric IDs (with `owner_resolved` false)
+    /// when looking up their names costs extra. Defaults to `list_directory`.
+    fn list_directory_numeric_owners(&self, path: &Path) -> Result<Vec<FileEntry>, VolumeError> {
+        self.list_directory(path)
+    }
+
     /// Gets metadata for a single path (relative to volume root).
     fn get_metadata(&self, path: &Path) -> Result<FileEntry, VolumeError>;
 
@@ -85,18 +179,135 @@ pub trait Volume: Send + Sync {
         Err(VolumeError::NotSupported)
     }
 
-    /// Creates a directory.
+    /// Creates a directory. Its parent must exist.
     fn create_directory(&self, path: &Path) -> Result<(), VolumeError> {
         let _ = path;
         Err(VolumeError::NotSupported)
     }
 
-    /// Deletes a file or empty directory.
+    /// Creates a directory and any missing parents. A directory that already exists is not an error.
+    ///
+    /// The default implementation calls `create_directory` for each missing directory, outermost first.
+    fn create_directory_all(&self, path: &Path) -> Result<(), VolumeError> {
+        let mut missing: Vec<&Path> = path
+            .ancestors()
+            .filter(|dir| dir.file_name().is_some())
+            .take_while(|dir| !self.exists(dir))
+            .collect();
+        if missing.is_empty() && !self.get_metadata(path)?.is_directory {
+            return Err(VolumeError::IoError(format!("Not a directory: {}", path.display())));
+        }
+        missing.reverse();
+        for dir in missing {
+            self.create_directory(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a symlink at `link`, which must not exist yet, pointing to `target`.
+    ///
+    /// `target` is stored as given: it may be relative to the link's folder, and doesn't have to exist. Unlike
+    /// other paths, an absolute `target` isn't relative to the volume root.
+    fn create_symlink(&self, link: &Path, target: &Path) -> Result<(), VolumeError> {
+        let _ = (link, target);
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Creates a hard link at `link`, which must not exist yet, to the existing file at `target`.
+    fn create_hardlink(&self, link: &Path, target: &Path) -> Result<(), VolumeError> {
+        let _ = (link, target);
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Sets the permission bits of a file or directory, like `chmod`. Follows symlinks.
+    ///
+    /// `mode` can't be above `MAX_MODE`: it replaces the permission, setuid, setgid, and sticky bits.
+    fn set_permissions(&self, path: &Path, mode: u32) -> Result<(), VolumeError> {
+        let _ = (path, mode);
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Changes the owner and group of a file or directory, like `chown`. Follows symlinks.
+    ///
+    /// None leaves that part unchanged. Giving files away usually needs privileges, so expect `PermissionDenied`.
+    fn set_owner(&self, path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<(), VolumeError> {
+        let _ = (path, uid, gid);
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Reads up to `len` bytes of a file, starting at `offset`. For previews, so it never reads whole files.
+    ///
+    /// `len` is capped at `MAX_READ_LEN`. Returns fewer bytes if the file ends first, and none if `offset` is at or
+    /// past the end. `NotSupported` for directories.
+    fn read_file(&self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>, VolumeError> {
+        let _ = (path, offset, len);
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Deletes a file or empty directory, permanently.
     fn delete(&self, path: &Path) -> Result<(), VolumeError> {
         let _ = path;
         Err(VolumeError::NotSupported)
     }
 
+    /// Moves a file or directory, with everything in it, to the Trash.
+    ///
+    /// Returns where it ended up if the system tells, as an absolute path outside the volume.
+    fn trash(&self, path: &Path) -> Result<Option<PathBuf>, VolumeError> {
+        let _ = path;
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Copies a file, or a directory with everything in it, to a path that doesn't exist yet.
+    ///
+    /// The default implementation walks the tree with `get_metadata` and `list_directory`, and recreates it with
+    /// `create_directory`, `read_file`, and `create_file`, so it's `NotSupported` unless those are.
+    fn copy(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+        check_destination(from, to, self.exists(to))?;
+        let metadata = self.get_metadata(from)?;
+        if metadata.is_directory {
+            self.create_directory(to)?;
+            for entry in self.list_directory(from)? {
+                self.copy(&from.join(&entry.name), &to.join(&entry.name))?;
+            }
+            Ok(())
+        } else {
+            let mut content = Vec::new();
+            loop {
+                let chunk = self.read_file(from, content.len() as u64, MAX_READ_LEN)?;
+                let is_last = chunk.len() < MAX_READ_LEN;
+                content.extend_from_slice(&chunk);
+                if is_last {
+                    break;
+                }
+            }
+            self.create_file(to, &content)
+        }
+    }
+
+    /// Moves or renames a file or directory to a path that doesn't exist yet.
+    ///
+    /// The default implementation copies, then deletes the source, so it's only as capable as `copy` and `delete`.
+    /// Volumes that can move in place should override it, which also makes same-volume moves atomic.
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+        self.copy(from, to)?;
+        delete_tree(self, from)
+    }
+
+    /// Gets the capacity and free space of the file system that holds the given path.
+    ///
+    /// Takes a path because a single volume (like "/") can span several mounted file systems.
+    fn get_space_info(&self, path: &Path) -> Result<SpaceInfo, VolumeError> {
+        let _ = path;
+        Err(VolumeError::NotSupported)
+    }
+
+    /// Returns true if names that only differ in case are different entries at the given path.
+    fn is_case_sensitive(&self, path: &Path) -> bool {
+        let _ = path;
+        true
+    }
+
     // ========================================
     // Watching: Optional, default no-op
     // ========================================
@@ -105,16 +316,61 @@ pub trait Volume: Send + Sync {
     fn supports_watching(&self) -> bool {
         false
     }
+
+    /// Watches a directory, not recursively, and calls `on_change` when its contents change.
+    ///
+    /// Changes are debounced by `debounce`, so a burst of them results in a single call. Watching goes on until
+    /// the returned guard is dropped. `NotSupported` unless `supports_watching` is true.
+    fn watch(
+        &self,
+        path: &Path,
+        debounce: Duration,
+        on_change: Box<dyn Fn() + Send + 'static>,
+    ) -> Result<WatchGuard, VolumeError> {
+        let _ = (path, debounce, on_change);
+        Err(VolumeError::NotSupported)
+    }
 }
 
 // Implementations
+mod conflict;
+mod duplicate;
 mod in_memory;
 mod local_posix;
+mod name_collision;
+mod recursive_delete;
+#[cfg(target_os = "macos")]
+mod smb_share;
+mod space_guard;
 
+pub use conflict::{
+    ConflictPolicy, TransferResult, Transferred, resolve_conflict, transfer_paths, transfer_with_policy,
+};
+pub use duplicate::duplicate;
 pub use in_memory::InMemoryVolume;
 pub use local_posix::LocalPosixVolume;
+pub use name_collision::check_name_collision;
+pub use recursive_delete::{
+    DeleteOutcome, DeleteProgress, DeleteResult, cancel_delete, delete_paths, delete_recursive,
+    register_delete_operation, unregister_delete_operation,
+};
+#[cfg(target_os = "macos")]
+pub use smb_share::SmbVolume;
+pub use space_guard::{check_space_for_copy, check_space_for_paths, total_size};
 
+#[cfg(test)]
+mod conflict_test;
+#[cfg(test)]
+mod duplicate_test;
 #[cfg(test)]
 mod in_memory_test;
 #[cfg(test)]
 mod local_posix_test;
+#[cfg(test)]
+mod name_collision_test;
+#[cfg(test)]
+mod recursive_delete_test;
+#[cfg(all(test, target_os = "macos"))]
+mod smb_share_test;
+#[cfg(test)]
+mod space_guard_test;
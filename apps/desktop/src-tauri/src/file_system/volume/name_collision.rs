@@ -0,0 +1,35 @@
+//! Case-only name collision check that runs before rename and copy operations.
+
+use super::{Volume, VolumeError};
+use crate::file_system::case_sensitivity::find_case_collision;
+use std::path::Path;
+
+/// Checks that creating `new_name` in `dir` won't silently replace an entry whose name only differs in case.
+///
+/// This only matters on case-insensitive volumes; on case-sensitive ones it always passes.
+/// Exact name matches aren't reported here, they're regular "already exists" conflicts.
+///
+/// # Arguments
+/// * `volume` - Volume the directory is on
+/// * `dir` - Target directory, relative to the volume
+/// * `new_name` - Name of the entry to create (the rename target or the copied file's name)
+/// * `source_name` - For renames within `dir`, the current name, so that changing only the case of a
+///   name ("readme" → "README") is allowed. None for copies.
+pub fn check_name_collision(
+    volume: &dyn Volume,
+    dir: &Path,
+    new_name: &str,
+    source_name: Option<&str>,
+) -> Result<(), VolumeError> {
+    if volume.is_case_sensitive(dir) {
+        return Ok(());
+    }
+    let entries = volume.list_directory(dir)?;
+    match find_case_collision(entries.iter().map(|e| e.name.as_str()), new_name, source_name) {
+        Some(existing) => Err(VolumeError::CaseCollision {
+            name: new_name.to_string(),
+            existing,
+        }),
+        None => Ok(()),
+    }
+}
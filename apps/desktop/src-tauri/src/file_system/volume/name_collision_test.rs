@@ -0,0 +1,52 @@
+//! Tests for the case-only name collision check.
+
+use super::*;
+use std::path::Path;
+
+/// Volume with "readme" and "notes.txt" in "/docs".
+fn create_volume(case_sensitive: bool) -> InMemoryVolume {
+    let volume = InMemoryVolume::new("Test").with_case_sensitivity(case_sensitive);
+    volume.create_directory(Path::new("/docs")).unwrap();
+    volume.create_file(Path::new("/docs/readme"), b"old").unwrap();
+    volume.create_file(Path::new("/docs/notes.txt"), b"notes").unwrap();
+    volume
+}
+
+#[test]
+fn test_rename_to_case_only_different_name_collides() {
+    let volume = create_volume(false);
+
+    let result = check_name_collision(&volume, Path::new("/docs"), "README", Some("notes.txt"));
+    match result {
+        Err(VolumeError::CaseCollision { name, existing }) => {
+            assert_eq!(name, "README");
+            assert_eq!(existing, "readme");
+        }
+        other => panic!("Expected CaseCollision, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_changing_case_of_same_file_is_allowed() {
+    let volume = create_volume(false);
+    assert!(check_name_collision(&volume, Path::new("/docs"), "README", Some("readme")).is_ok());
+}
+
+#[test]
+fn test_copy_with_case_only_different_name_collides() {
+    let volume = create_volume(false);
+    let result = check_name_collision(&volume, Path::new("/docs"), "ReadMe", None);
+    assert!(matches!(result, Err(VolumeError::CaseCollision { .. })));
+}
+
+#[test]
+fn test_case_sensitive_volume_never_collides() {
+    let volume = create_volume(true);
+    assert!(check_name_collision(&volume, Path::new("/docs"), "README", Some("notes.txt")).is_ok());
+}
+
+#[test]
+fn test_unrelated_name_does_not_collide() {
+    let volume = create_volume(false);
+    assert!(check_name_collision(&volume, Path::new("/docs"), "todo.txt", None).is_ok());
+}
@@ -0,0 +1,179 @@
+//! Recursive delete with progress reporting and cancellation.
+//!
+//! Deletes entry by entry (children before their directory) instead of all at once, so the UI can
+//! show progress and the user can stop a long delete. Items deleted before cancelling stay deleted.
+//!
+//! Also deletes or trashes multi-selections with `delete_paths`, which goes on past items that fail.
+
+use super::{Volume, VolumeError};
+use crate::file_system::VolumeManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Cancellation flags of running deletes, by operation ID.
+static DELETE_CANCEL_FLAGS: LazyLock<RwLock<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Progress of a recursive delete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteProgress {
+    /// Entries removed so far
+    pub removed: u64,
+    /// Total entries to remove, including the root itself
+    pub total: u64,
+}
+
+/// Result of a recursive delete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteOutcome {
+    /// Entries removed
+    pub removed: u64,
+    /// Total entries that were to be removed
+    pub total: u64,
+    /// True if the delete stopped early because it was cancelled
+    pub cancelled: bool,
+}
+
+/// Result of deleting one of the paths passed to `delete_paths`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteResult {
+    pub path: String,
+    pub success: bool,
+    /// Why the delete failed. None if it succeeded.
+    pub error: Option<String>,
+}
+
+/// Registers a cancellable delete operation and returns its cancellation flag.
+pub fn register_delete_operation(operation_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    if let Ok(mut flags) = DELETE_CANCEL_FLAGS.write() {
+        flags.insert(operation_id.to_string(), flag.clone());
+    }
+    flag
+}
+
+/// Forgets a delete operation once it's done.
+pub fn unregister_delete_operation(operation_id: &str) {
+    if let Ok(mut flags) = DELETE_CANCEL_FLAGS.write() {
+        flags.remove(operation_id);
+    }
+}
+
+/// Asks a running delete to stop after the current entry.
+///
+/// # Returns
+/// True if the operation was still running.
+pub fn cancel_delete(operation_id: &str) -> bool {
+    let Ok(flags) = DELETE_CANCEL_FLAGS.read() else {
+        return false;
+    };
+    match flags.get(operation_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Deletes a file, or a directory with everything in it.
+///
+/// Symlinks are deleted themselves and never followed, even if they point to directories, so a
+/// delete can't escape the tree or loop forever.
+///
+/// # Arguments
+/// * `volume` - Volume the path is on
+/// * `path` - What to delete, relative to the volume
+/// * `cancelled` - Checked before each entry; once set, the delete stops
+/// * `on_progress` - Called after each removed entry
+pub fn delete_recursive(
+    volume: &dyn Volume,
+    path: &Path,
+    cancelled: &AtomicBool,
+    mut on_progress: impl FnMut(&DeleteProgress),
+) -> Result<DeleteOutcome, VolumeError> {
+    // Collect everything first (children before parents), so the total is known upfront
+    let mut to_delete = Vec::new();
+    collect_post_order(volume, path, &mut to_delete)?;
+    let total = to_delete.len() as u64;
+
+    let mut removed = 0;
+    for entry_path in to_delete {
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(DeleteOutcome {
+                removed,
+                total,
+                cancelled: true,
+            });
+        }
+        volume.delete(&entry_path)?;
+        removed += 1;
+        on_progress(&DeleteProgress { removed, total });
+    }
+
+    Ok(DeleteOutcome {
+        removed,
+        total,
+        cancelled: false,
+    })
+}
+
+/// Moves each path to the Trash, or deletes it permanently with everything in it.
+///
+/// Each path is resolved to its volume with `volume_manager`, so the paths can be on different volumes. A failing
+/// path doesn't stop the others: its error is in its result.
+///
+/// # Arguments
+/// * `volume_manager` - Resolves the absolute paths to volumes
+/// * `paths` - What to delete, as absolute paths
+/// * `permanent` - Whether to delete permanently (like `delete_recursive`) instead of trashing
+/// * `on_item` - Called after each path, with its result and the number of paths done so far
+///
+/// # Returns
+/// One result per path, in the same order.
+pub fn delete_paths(
+    volume_manager: &VolumeManager,
+    paths: &[PathBuf],
+    permanent: bool,
+    mut on_item: impl FnMut(&DeleteResult, usize),
+) -> Vec<DeleteResult> {
+    let never_cancelled = AtomicBool::new(false);
+    let mut results = Vec::with_capacity(paths.len());
+    for path in paths {
+        let outcome = match volume_manager.resolve(path) {
+            Some((_, volume, relative_path)) if permanent => {
+                delete_recursive(volume.as_ref(), &relative_path, &never_cancelled, |_| {})
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+            Some((_, volume, relative_path)) => volume.trash(&relative_path).map(|_| ()).map_err(|e| e.to_string()),
+            None => Err(format!("No volume found for {}", path.display())),
+        };
+        let result = DeleteResult {
+            path: path.to_string_lossy().to_string(),
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        };
+        on_item(&result, results.len() + 1);
+        results.push(result);
+    }
+    results
+}
+
+/// Appends `path` and everything below it to `out`, children first.
+fn collect_post_order(volume: &dyn Volume, path: &Path, out: &mut Vec<PathBuf>) -> Result<(), VolumeError> {
+    let entry = volume.get_metadata(path)?;
+    if entry.is_directory && !entry.is_symlink {
+        for child in volume.list_directory(path)? {
+            collect_post_order(volume, &path.join(&child.name), out)?;
+        }
+    }
+    out.push(path.to_path_buf());
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Tests for recursive delete with progress and cancellation.
+
+use super::*;
+use crate::file_system::{FileEntry, VolumeManager};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Volume with "/tree" holding 2 files and a subdirectory with 2 more files: 6 entries with the root.
+fn create_tree() -> InMemoryVolume {
+    let volume = InMemoryVolume::new("Test");
+    volume.create_directory(Path::new("/tree")).unwrap();
+    volume.create_file(Path::new("/tree/a.txt"), b"a").unwrap();
+    volume.create_file(Path::new("/tree/b.txt"), b"b").unwrap();
+    volume.create_directory(Path::new("/tree/sub")).unwrap();
+    volume.create_file(Path::new("/tree/sub/c.txt"), b"c").unwrap();
+    volume.create_file(Path::new("/tree/sub/d.txt"), b"d").unwrap();
+    volume.create_file(Path::new("/keep.txt"), b"keep").unwrap();
+    volume
+}
+
+#[test]
+fn test_deletes_whole_tree_with_progress() {
+    let volume = create_tree();
+    let cancelled = AtomicBool::new(false);
+    let mut reports = Vec::new();
+
+    let outcome = delete_recursive(&volume, Path::new("/tree"), &cancelled, |p| reports.push(*p)).unwrap();
+
+    assert_eq!(
+        outcome,
+        DeleteOutcome {
+            removed: 6,
+            total: 6,
+            cancelled: false
+        }
+    );
+    assert_eq!(reports.len(), 6);
+    assert_eq!(reports.last().unwrap(), &DeleteProgress { removed: 6, total: 6 });
+    assert!(!volume.exists(Path::new("/tree")));
+    assert!(!volume.exists(Path::new("/tree/sub/c.txt")));
+    assert!(volume.exists(Path::new("/keep.txt")));
+}
+
+#[test]
+fn test_deletes_children_before_their_directory() {
+    let volume = create_tree();
+    let cancelled = AtomicBool::new(false);
+    let mut sub_exists_when_c_removed = Vec::new();
+
+    delete_recursive(&volume, Path::new("/tree"), &cancelled, |_| {
+        if !volume.exists(Path::new("/tree/sub/c.txt")) {
+            sub_exists_when_c_removed.push(volume.exists(Path::new("/tree/sub")));
+        }
+    })
+    .unwrap();
+
+    // The first report after c.txt is gone must still see its parent
+    assert_eq!(sub_exists_when_c_removed.first(), Some(&true));
+}
+
+#[test]
+fn test_cancel_stops_cleanly_and_reports_removed_count() {
+    let volume = create_tree();
+    let cancelled = AtomicBool::new(false);
+
+    let outcome = delete_recursive(&volume, Path::new("/tree"), &cancelled, |p| {
+        if p.removed == 3 {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    })
+    .unwrap();
+
+    assert_eq!(
+        outcome,
+        DeleteOutcome {
+            removed: 3,
+            total: 6,
+            cancelled: true
+        }
+    );
+    // Already deleted items stay deleted, the root is still there
+    assert!(volume.exists(Path::new("/tree")));
+    let remaining = [
+        "/tree/a.txt",
+        "/tree/b.txt",
+        "/tree/sub",
+        "/tree/sub/c.txt",
+        "/tree/sub/d.txt",
+    ]
+    .iter()
+    .filter(|p| volume.exists(Path::new(p)))
+    .count();
+    assert_eq!(remaining, 2);
+}
+
+#[test]
+fn test_does_not_follow_symlinked_directories() {
+    let volume = create_tree();
+    let link = FileEntry {
+        name: "link".to_string(),
+        path: "/tree/link".to_string(),
+        is_directory: true,
+        is_symlink: true,
+        size: None,
+        modified_at: None,
+        created_at: None,
+        changed_at: None,
+        added_at: None,
+        opened_at: None,
+        permissions: 0o755,
+        owner: "testuser".to_string(),
+        group: "staff".to_string(),
+        icon_id: "symlink-dir".to_string(),
+        inode: None,
+        extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
+    };
+    // An in-memory "symlink" to /tree itself, which would loop if followed
+    let volume = {
+        let mut entries: Vec<FileEntry> = ["/tree", "/tree/a.txt", "/tree/b.txt", "/tree/sub", "/tree/sub/c.txt"]
+            .iter()
+            .map(|p| volume.get_metadata(Path::new(p)).unwrap())
+            .collect();
+        entries.push(link);
+        InMemoryVolume::with_entries("Test", entries)
+    };
+    let cancelled = AtomicBool::new(false);
+
+    let outcome = delete_recursive(&volume, Path::new("/tree"), &cancelled, |_| {}).unwrap();
+
+    assert_eq!(outcome.total, 6);
+    assert!(!volume.exists(Path::new("/tree/link")));
+}
+
+#[test]
+fn test_missing_path_fails() {
+    let volume = create_tree();
+    let cancelled = AtomicBool::new(false);
+    let result = delete_recursive(&volume, Path::new("/missing"), &cancelled, |_| {});
+    assert!(matches!(result, Err(VolumeError::NotFound(_))));
+}
+
+#[test]
+fn test_cancel_unknown_operation() {
+    assert!(!cancel_delete("no-such-delete"));
+    let flag = register_delete_operation("test-delete-op");
+    assert!(cancel_delete("test-delete-op"));
+    assert!(flag.load(Ordering::Relaxed));
+    unregister_delete_operation("test-delete-op");
+}
+
+/// A manager with the test tree as its only volume, at "/".
+fn create_manager() -> (VolumeManager, Arc<InMemoryVolume>) {
+    let volume = Arc::new(create_tree());
+    let manager = VolumeManager::new();
+    manager.register("test", volume.clone());
+    (manager, volume)
+}
+
+#[test]
+fn test_delete_paths_goes_on_past_failures() {
+    let (manager, volume) = create_manager();
+    let paths = [
+        PathBuf::from("/tree/a.txt"),
+        PathBuf::from("/missing.txt"),
+        PathBuf::from("/tree/sub"),
+    ];
+    let mut progress = Vec::new();
+
+    let results = delete_paths(&manager, &paths, true, |result, done| {
+        progress.push((result.path.clone(), done))
+    });
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].success);
+    assert_eq!(results[0].error, None);
+    assert!(!results[1].success);
+    assert_eq!(results[1].path, "/missing.txt");
+    assert!(results[1].error.as_deref().unwrap().contains("not found"));
+    assert!(results[2].success, "Folders are deleted with everything in them");
+    assert_eq!(
+        progress,
+        vec![
+            ("/tree/a.txt".to_string(), 1),
+            ("/missing.txt".to_string(), 2),
+            ("/tree/sub".to_string(), 3)
+        ]
+    );
+    assert!(!volume.exists(Path::new("/tree/a.txt")));
+    assert!(!volume.exists(Path::new("/tree/sub/c.txt")));
+    assert!(volume.exists(Path::new("/tree/b.txt")));
+}
+
+#[test]
+fn test_delete_paths_trashes_by_default() {
+    let (manager, volume) = create_manager();
+    let paths = [PathBuf::from("/tree"), PathBuf::from("/tree/a.txt")];
+
+    let results = delete_paths(&manager, &paths, false, |_, _| {});
+
+    assert!(results[0].success);
+    // Already gone with its folder
+    assert!(!results[1].success);
+    assert!(!volume.exists(Path::new("/tree/sub/d.txt")));
+    assert!(volume.exists(Path::new("/keep.txt")));
+}
+
+#[test]
+fn test_delete_paths_reports_paths_outside_volumes() {
+    let manager = VolumeManager::new();
+
+    let results = delete_paths(&manager, &[PathBuf::from("/anywhere.txt")], false, |_, _| {});
+
+    assert_eq!(
+        results,
+        vec![DeleteResult {
+            path: "/anywhere.txt".to_string(),
+            success: false,
+            error: Some("No volume found for /anywhere.txt".to_string()),
+        }]
+    );
+}
@@ -0,0 +1,273 @@
+//! SMB share volume implementation, for browsing a share without mounting it.
+//!
+//! Talks to the server directly with smb-rs. The `Volume` trait is synchronous, so each call blocks on the
+//! async client through Tauri's runtime. Read-only for now: writing, deleting, and watching aren't supported.
+
+use super::{Volume, VolumeError};
+use crate::file_system::FileEntry;
+use crate::file_system::operations::get_icon_id;
+use ::smb::{
+    Client, ClientConfig, Directory, FileAccessMask, FileAttributes, FileCreateArgs, FileDirectoryInformation,
+    Resource, UncPath,
+};
+use futures_util::StreamExt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// How long a single SMB request may take before it's treated as failed.
+const SMB_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A volume backed by a share on an SMB server, accessed through an authenticated tree connection.
+///
+/// Paths are relative to the share root, like "/folder/file.txt". The volume's root is the share's
+/// `smb://server/share` URL, so it never contains local paths.
+pub struct SmbVolume {
+    name: String,
+    root: PathBuf,
+    /// Server name as used for the connection (the IP address, if connected by address)
+    server: String,
+    share: String,
+    client: Client,
+}
+
+impl SmbVolume {
+    /// Connects to a share and returns a volume for it.
+    ///
+    /// # Arguments
+    /// * `server` - Server name, like "nas.local". Also used in the volume ID.
+    /// * `address` - Address to connect to, skipping name resolution (which often fails for mDNS names).
+    ///   None to resolve `server`.
+    /// * `share` - Share name, like "Media"
+    /// * `credentials` - Username and password. Connects as guest if None.
+    pub async fn connect(
+        server: &str,
+        address: Option<SocketAddr>,
+        share: &str,
+        credentials: Option<(&str, &str)>,
+    ) -> Result<Self, VolumeError> {
+        let mut config = ClientConfig::default();
+        config.connection.allow_unsigned_guest_access = credentials.is_none();
+        let client = Client::new(config);
+
+        let connect_name = match address {
+            Some(address) => {
+                let name = address.ip().to_string();
+                with_timeout(client.connect_to_address(&name, address)).await?;
+                name
+            }
+            None => server.strip_suffix(".local").unwrap_or(server).to_string(),
+        };
+
+        let (username, password) = credentials.unwrap_or(("Guest", ""));
+        let share_path = unc_path(&connect_name, share, Path::new(""))?;
+        with_timeout(client.share_connect(&share_path, username, password.to_string())).await?;
+
+        Ok(Self {
+            name: share.to_string(),
+            root: PathBuf::from(format!("smb://{}/{}", server, share)),
+            server: connect_name,
+            share: share.to_string(),
+            client,
+        })
+    }
+
+    /// The ID the volume is registered with in the `VolumeManager`, like "nas.local/Media".
+    pub fn volume_id(server: &str, share: &str) -> String {
+        format!("{}/{}", server, share)
+    }
+
+    /// Opens a directory of the share for listing.
+    async fn open_directory(&self, path: &Path) -> Result<Arc<Directory>, VolumeError> {
+        let target = unc_path(&self.server, &self.share, path)?;
+        let access = FileAccessMask::new().with_generic_read(true);
+        let resource = with_timeout(
+            self.client
+                .create_file(&target, &FileCreateArgs::make_open_existing(access)),
+        )
+        .await
+        .map_err(|e| not_found_or(e, path))?;
+        match resource {
+            Resource::Directory(directory) => Ok(Arc::new(directory)),
+            _ => Err(VolumeError::IoError(format!("Not a directory: {}", path.display()))),
+        }
+    }
+
+    /// Lists the entries of a directory that match `pattern` ("*" for all).
+    async fn query_directory(&self, path: &Path, pattern: &str) -> Result<Vec<SmbEntry>, VolumeError> {
+        let directory = self.open_directory(path).await?;
+        let mut stream = with_timeout(Directory::query::<FileDirectoryInformation>(&directory, pattern)).await?;
+        let mut entries = Vec::new();
+        while let Some(info) = stream.next().await {
+            let info = info.map_err(|e| VolumeError::IoError(e.to_string()))?;
+            let entry = SmbEntry::from_info(&info);
+            if entry.name != "." && entry.name != ".." {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl Volume for SmbVolume {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn list_directory(&self, path: &Path) -> Result<Vec<FileEntry>, VolumeError> {
+        let entries = tauri::async_runtime::block_on(self.query_directory(path, "*"))?;
+        Ok(entries.into_iter().map(|entry| entry.into_file_entry(path)).collect())
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<FileEntry, VolumeError> {
+        let (parent, name) = split_share_path(path);
+        let Some(name) = name else {
+            // The share root has no entry in any directory
+            return Ok(SmbEntry::share_root().into_file_entry(Path::new("/")));
+        };
+        // Listing the parent with the name as the pattern gives the same details as a full listing
+        let entries = tauri::async_runtime::block_on(self.query_directory(&parent, &name))?;
+        entries
+            .into_iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(&name))
+            .map(|entry| entry.into_file_entry(&parent))
+            .ok_or_else(|| VolumeError::NotFound(path.display().to_string()))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.get_metadata(path).is_ok()
+    }
+}
+
+/// The parts of an SMB directory entry that make up a `FileEntry`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SmbEntry {
+    pub name: String,
+    pub is_directory: bool,
+    /// Reparse points are symlinks and junctions, listed like symlinks
+    pub is_reparse_point: bool,
+    pub is_read_only: bool,
+    pub size: u64,
+    pub modified_at: Option<u64>,
+    pub created_at: Option<u64>,
+    pub changed_at: Option<u64>,
+}
+
+impl SmbEntry {
+    fn from_info(info: &FileDirectoryInformation) -> Self {
+        let attributes: &FileAttributes = &info.file_attributes;
+        Self {
+            name: info.file_name.to_string(),
+            is_directory: attributes.directory(),
+            is_reparse_point: attributes.reparse_point(),
+            is_read_only: attributes.readonly(),
+            size: info.end_of_file,
+            modified_at: unix_seconds(info.last_write_time.date_time().assume_utc().unix_timestamp()),
+            created_at: unix_seconds(info.creation_time.date_time().assume_utc().unix_timestamp()),
+            changed_at: unix_seconds(info.change_time.date_time().assume_utc().unix_timestamp()),
+        }
+    }
+
+    fn share_root() -> Self {
+        Self {
+            name: String::new(),
+            is_directory: true,
+            is_reparse_point: false,
+            is_read_only: false,
+            size: 0,
+            modified_at: None,
+            created_at: None,
+            changed_at: None,
+        }
+    }
+
+    /// Converts to a `FileEntry` in the given directory of the share. SMB doesn't expose Unix owners, so the
+    /// owner and group are empty, and permissions only reflect the read-only attribute.
+    pub(crate) fn into_file_entry(self, parent: &Path) -> FileEntry {
+        let path = Path::new("/").join(parent).join(&self.name);
+        let write_bits = if self.is_read_only { 0 } else { 0o200 };
+        let permissions = if self.is_directory { 0o555 } else { 0o444 } | write_bits;
+        FileEntry {
+            icon_id: get_icon_id(self.is_directory, self.is_reparse_point, &self.name),
+            path: path.to_string_lossy().to_string(),
+            is_directory: self.is_directory,
+            is_symlink: self.is_reparse_point,
+            size: if self.is_directory { None } else { Some(self.size) },
+            modified_at: self.modified_at,
+            created_at: self.created_at,
+            changed_at: self.changed_at,
+            added_at: None,
+            opened_at: None,
+            permissions,
+            owner: String::new(),
+            group: String::new(),
+            inode: None,
+            extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
+            name: self.name,
+        }
+    }
+}
+
+/// Converts a Unix timestamp to seconds, or None for times before 1970 (like an unset SMB time).
+fn unix_seconds(timestamp: i64) -> Option<u64> {
+    u64::try_from(timestamp).ok().filter(|&seconds| seconds > 0)
+}
+
+/// Splits a path in the share into its parent directory and name. The share root has no name.
+pub(crate) fn split_share_path(path: &Path) -> (PathBuf, Option<String>) {
+    let relative = path.strip_prefix("/").unwrap_or(path);
+    match relative.file_name() {
+        Some(name) => (
+            relative.parent().map(Path::to_path_buf).unwrap_or_default(),
+            Some(name.to_string_lossy().to_string()),
+        ),
+        None => (PathBuf::new(), None),
+    }
+}
+
+/// Builds the `\\server\share\path` UNC path for a path in the share.
+pub(crate) fn unc_string(server: &str, share: &str, path: &Path) -> String {
+    let relative = path.strip_prefix("/").unwrap_or(path);
+    let mut unc = format!(r"\\{}\{}", server, share);
+    for component in relative.components() {
+        unc.push('\\');
+        unc.push_str(&component.as_os_str().to_string_lossy());
+    }
+    unc
+}
+
+fn unc_path(server: &str, share: &str, path: &Path) -> Result<UncPath, VolumeError> {
+    unc_string(server, share, path)
+        .parse()
+        .map_err(|e| VolumeError::IoError(format!("Invalid SMB path: {}", e)))
+}
+
+/// Runs an SMB request, failing it if it takes longer than `SMB_REQUEST_TIMEOUT`.
+async fn with_timeout<T, E: std::fmt::Display>(
+    request: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, VolumeError> {
+    timeout(SMB_REQUEST_TIMEOUT, request)
+        .await
+        .map_err(|_| VolumeError::IoError(format!("Timeout after {}s", SMB_REQUEST_TIMEOUT.as_secs())))?
+        .map_err(|e| VolumeError::IoError(e.to_string()))
+}
+
+/// Turns the error of opening a path into `NotFound` if the server said so.
+fn not_found_or(error: VolumeError, path: &Path) -> VolumeError {
+    let message = error.to_string().to_lowercase();
+    if message.contains("not found") || message.contains("0xc0000034") || message.contains("0xc000003a") {
+        VolumeError::NotFound(path.display().to_string())
+    } else if message.contains("access denied") || message.contains("0xc0000022") {
+        VolumeError::PermissionDenied(path.display().to_string())
+    } else {
+        error
+    }
+}
@@ -0,0 +1,179 @@
+//! Tests for SmbVolume.
+//!
+//! The tests in `integration` need the Docker SMB servers from test/smb-servers, and only run with the
+//! `integration-tests` feature.
+
+use super::smb_share::{SmbEntry, split_share_path, unc_string};
+use std::path::{Path, PathBuf};
+
+fn smb_entry(name: &str, is_directory: bool) -> SmbEntry {
+    SmbEntry {
+        name: name.to_string(),
+        is_directory,
+        is_reparse_point: false,
+        is_read_only: false,
+        size: 1234,
+        modified_at: Some(1_700_000_000),
+        created_at: Some(1_600_000_000),
+        changed_at: Some(1_600_000_000),
+    }
+}
+
+#[test]
+fn test_unc_string_joins_path_with_backslashes() {
+    assert_eq!(unc_string("nas", "Media", Path::new("")), r"\\nas\Media");
+    assert_eq!(unc_string("nas", "Media", Path::new("/")), r"\\nas\Media");
+    assert_eq!(
+        unc_string("nas", "Media", Path::new("/Movies/2024/clip.mp4")),
+        r"\\nas\Media\Movies\2024\clip.mp4"
+    );
+    assert_eq!(unc_string("nas", "Media", Path::new("Movies")), r"\\nas\Media\Movies");
+}
+
+#[test]
+fn test_split_share_path() {
+    assert_eq!(split_share_path(Path::new("/")), (PathBuf::new(), None));
+    assert_eq!(split_share_path(Path::new("")), (PathBuf::new(), None));
+    assert_eq!(
+        split_share_path(Path::new("/file.txt")),
+        (PathBuf::new(), Some("file.txt".to_string()))
+    );
+    assert_eq!(
+        split_share_path(Path::new("/a/b/file.txt")),
+        (PathBuf::from("a/b"), Some("file.txt".to_string()))
+    );
+}
+
+#[test]
+fn test_file_entry_from_smb_file() {
+    let entry = smb_entry("report.pdf", false).into_file_entry(Path::new("/Documents"));
+
+    assert_eq!(entry.name, "report.pdf");
+    assert_eq!(entry.path, "/Documents/report.pdf");
+    assert!(!entry.is_directory);
+    assert!(!entry.is_symlink);
+    assert_eq!(entry.size, Some(1234));
+    assert_eq!(entry.modified_at, Some(1_700_000_000));
+    assert_eq!(entry.created_at, Some(1_600_000_000));
+    assert_eq!(entry.owner, "");
+    assert_eq!(entry.group, "");
+    assert_eq!(entry.permissions, 0o644);
+    assert_eq!(entry.icon_id, "ext:pdf");
+}
+
+#[test]
+fn test_file_entry_from_smb_directory_has_no_size() {
+    let entry = smb_entry("Photos", true).into_file_entry(Path::new(""));
+
+    assert_eq!(entry.path, "/Photos");
+    assert!(entry.is_directory);
+    assert_eq!(entry.size, None);
+    assert_eq!(entry.permissions, 0o755);
+    assert_eq!(entry.icon_id, "dir");
+}
+
+#[test]
+fn test_file_entry_from_smb_reparse_point_is_a_symlink() {
+    let entry = SmbEntry {
+        is_reparse_point: true,
+        ..smb_entry("link", true)
+    }
+    .into_file_entry(Path::new("/"));
+
+    assert!(entry.is_symlink);
+    assert!(entry.is_directory);
+    assert_eq!(entry.icon_id, "symlink-dir");
+}
+
+#[test]
+fn test_file_entry_from_read_only_smb_file() {
+    let entry = SmbEntry {
+        is_read_only: true,
+        ..smb_entry("locked.txt", false)
+    }
+    .into_file_entry(Path::new("/"));
+
+    assert_eq!(entry.permissions, 0o444);
+}
+
+#[cfg(feature = "integration-tests")]
+mod integration {
+    use super::super::{SmbVolume, Volume, VolumeError};
+    use std::net::SocketAddr;
+    use std::path::Path;
+
+    /// The smb-guest container: guest access to the "public" share
+    const GUEST_PORT: u16 = 9445;
+    /// The smb-auth container: "testuser"/"testpass" for the "private" share
+    const AUTH_PORT: u16 = 9446;
+
+    fn connect(port: u16, share: &str, credentials: Option<(&str, &str)>) -> SmbVolume {
+        let address: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+        tauri::async_runtime::block_on(SmbVolume::connect("localhost", Some(address), share, credentials)).unwrap()
+    }
+
+    #[test]
+    fn test_lists_guest_share() {
+        let volume = connect(GUEST_PORT, "public", None);
+
+        let entries = volume.list_directory(Path::new("/")).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+
+        assert!(names.contains(&"test.txt"));
+        assert!(names.contains(&"hello.txt"));
+        let subfolder = entries.iter().find(|e| e.name == "subfolder").unwrap();
+        assert!(subfolder.is_directory);
+        assert_eq!(subfolder.path, "/subfolder");
+    }
+
+    #[test]
+    fn test_lists_subfolder() {
+        let volume = connect(GUEST_PORT, "public", None);
+
+        let entries = volume.list_directory(Path::new("/subfolder")).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "nested.txt");
+        assert_eq!(entries[0].path, "/subfolder/nested.txt");
+        assert_eq!(entries[0].size, Some("Nested content\n".len() as u64));
+    }
+
+    #[test]
+    fn test_metadata_and_exists() {
+        let volume = connect(GUEST_PORT, "public", None);
+
+        let hello = volume.get_metadata(Path::new("/hello.txt")).unwrap();
+        assert_eq!(hello.size, Some("Hello from SMB\n".len() as u64));
+        assert!(hello.modified_at.is_some());
+        assert!(volume.get_metadata(Path::new("/")).unwrap().is_directory);
+        assert!(volume.exists(Path::new("/subfolder/nested.txt")));
+        assert!(!volume.exists(Path::new("/nope.txt")));
+    }
+
+    #[test]
+    fn test_missing_directory_is_not_found() {
+        let volume = connect(GUEST_PORT, "public", None);
+
+        let result = volume.list_directory(Path::new("/nope"));
+
+        assert!(matches!(result, Err(VolumeError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_lists_share_with_credentials() {
+        let volume = connect(AUTH_PORT, "private", Some(("testuser", "testpass")));
+
+        assert!(!volume.list_directory(Path::new("/")).unwrap().is_empty());
+        assert_eq!(volume.name(), "private");
+        assert_eq!(volume.root(), Path::new("smb://localhost/private"));
+    }
+
+    #[test]
+    fn test_writing_is_not_supported() {
+        let volume = connect(GUEST_PORT, "public", None);
+
+        let result = volume.create_file(Path::new("/new.txt"), b"content");
+
+        assert!(matches!(result, Err(VolumeError::NotSupported)));
+    }
+}
@@ -0,0 +1,111 @@
+//! Free space check that runs before copy and move operations.
+//!
+//! Checking upfront means the user gets a clear error right away instead of a half-finished copy
+//! when the destination fills up.
+
+use super::{Volume, VolumeError};
+use crate::file_system::VolumeManager;
+use std::path::{Path, PathBuf};
+
+/// Computes the total size of a file or, for directories, of everything inside it, recursively.
+///
+/// Symlinks count with their own size and are not followed, like the copy itself does.
+pub fn total_size(volume: &dyn Volume, path: &Path) -> Result<u64, VolumeError> {
+    let entry = volume.get_metadata(path)?;
+    if !entry.is_directory || entry.is_symlink {
+        return Ok(entry.size.unwrap_or(0));
+    }
+
+    let mut total = 0;
+    for child in volume.list_directory(path)? {
+        let child_path = path.join(&child.name);
+        total += if child.is_directory && !child.is_symlink {
+            total_size(volume, &child_path)?
+        } else {
+            child.size.unwrap_or(0)
+        };
+    }
+    Ok(total)
+}
+
+/// Checks that the destination has enough free space for the given sources.
+///
+/// The estimate is conservative: sparse files, compression, and APFS clones can all make the real
+/// usage smaller. That's why callers can skip the check with `ignore_space_check` after the user
+/// confirms. If the destination can't report its free space, the check passes.
+///
+/// # Arguments
+/// * `source` - Volume the sources are on
+/// * `source_paths` - Files and directories to copy, relative to the source volume
+/// * `destination` - Volume to copy to
+/// * `destination_path` - Target directory, relative to the destination volume
+/// * `ignore_space_check` - If true, returns `Ok` without checking
+///
+/// # Returns
+/// The number of bytes needed, or `VolumeError::InsufficientSpace` if they don't fit.
+pub fn check_space_for_copy(
+    source: &dyn Volume,
+    source_paths: &[PathBuf],
+    destination: &dyn Volume,
+    destination_path: &Path,
+    ignore_space_check: bool,
+) -> Result<u64, VolumeError> {
+    let mut needed = 0;
+    for path in source_paths {
+        needed += total_size(source, path)?;
+    }
+    if ignore_space_check {
+        return Ok(needed);
+    }
+    check_fits(destination, destination_path, needed)?;
+    Ok(needed)
+}
+
+/// Like `check_space_for_copy`, for absolute paths, each on whichever volume holds it.
+///
+/// # Arguments
+/// * `volume_manager` - Resolves the absolute paths to volumes
+/// * `source_paths` - Files and directories to copy, as absolute paths
+/// * `destination_dir` - Target directory, as an absolute path. Must exist.
+/// * `ignore_space_check` - If true, returns `Ok` without checking
+///
+/// # Returns
+/// The number of bytes needed, or `VolumeError::InsufficientSpace` if they don't fit.
+pub fn check_space_for_paths(
+    volume_manager: &VolumeManager,
+    source_paths: &[PathBuf],
+    destination_dir: &Path,
+    ignore_space_check: bool,
+) -> Result<u64, VolumeError> {
+    let not_on_a_volume = |path: &Path| VolumeError::NotFound(format!("No volume found for {}", path.display()));
+    let (_, destination, destination_path) = volume_manager
+        .resolve(destination_dir)
+        .ok_or_else(|| not_on_a_volume(destination_dir))?;
+
+    let mut needed = 0;
+    for path in source_paths {
+        let (_, source, relative_path) = volume_manager.resolve(path).ok_or_else(|| not_on_a_volume(path))?;
+        needed += total_size(source.as_ref(), &relative_path)?;
+    }
+    if ignore_space_check {
+        return Ok(needed);
+    }
+    check_fits(destination.as_ref(), &destination_path, needed)?;
+    Ok(needed)
+}
+
+/// Fails with `InsufficientSpace` if `needed` bytes don't fit. Passes if the volume can't report its free space.
+fn check_fits(destination: &dyn Volume, destination_path: &Path, needed: u64) -> Result<(), VolumeError> {
+    let space = match destination.get_space_info(destination_path) {
+        Ok(space) => space,
+        Err(VolumeError::NotSupported) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    if needed > space.available_bytes {
+        return Err(VolumeError::InsufficientSpace {
+            needed,
+            available: space.available_bytes,
+        });
+    }
+    Ok(())
+}
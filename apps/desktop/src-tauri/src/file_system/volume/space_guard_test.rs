@@ -0,0 +1,114 @@
+//! Tests for the free space check.
+
+use super::*;
+use crate::file_system::VolumeManager;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Source volume with a 100-byte file and a directory holding 300 more bytes, one level nested.
+fn create_source() -> InMemoryVolume {
+    let volume = InMemoryVolume::new("Source");
+    volume.create_file(Path::new("/small.txt"), &[0; 100]).unwrap();
+    volume.create_directory(Path::new("/photos")).unwrap();
+    volume.create_file(Path::new("/photos/a.jpg"), &[0; 200]).unwrap();
+    volume.create_directory(Path::new("/photos/raw")).unwrap();
+    volume.create_file(Path::new("/photos/raw/b.raw"), &[0; 100]).unwrap();
+    volume
+}
+
+#[test]
+fn test_total_size_of_file() {
+    let source = create_source();
+    assert_eq!(total_size(&source, Path::new("/small.txt")).unwrap(), 100);
+}
+
+#[test]
+fn test_total_size_of_directory_is_recursive() {
+    let source = create_source();
+    assert_eq!(total_size(&source, Path::new("/photos")).unwrap(), 300);
+}
+
+#[test]
+fn test_check_passes_when_space_is_enough() {
+    let source = create_source();
+    let destination = InMemoryVolume::new("Destination").with_space(10_000, 1_000);
+    let paths = vec![PathBuf::from("/small.txt"), PathBuf::from("/photos")];
+
+    let needed = check_space_for_copy(&source, &paths, &destination, Path::new("/"), false).unwrap();
+    assert_eq!(needed, 400);
+}
+
+#[test]
+fn test_check_fails_when_destination_is_nearly_full() {
+    let source = create_source();
+    let destination = InMemoryVolume::new("Destination").with_space(10_000, 250);
+    let paths = vec![PathBuf::from("/small.txt"), PathBuf::from("/photos")];
+
+    let result = check_space_for_copy(&source, &paths, &destination, Path::new("/"), false);
+    assert!(matches!(
+        result,
+        Err(VolumeError::InsufficientSpace {
+            needed: 400,
+            available: 250
+        })
+    ));
+}
+
+#[test]
+fn test_check_can_be_overridden() {
+    let source = create_source();
+    let destination = InMemoryVolume::new("Destination").with_space(10_000, 250);
+    let paths = vec![PathBuf::from("/photos")];
+
+    let needed = check_space_for_copy(&source, &paths, &destination, Path::new("/"), true).unwrap();
+    assert_eq!(needed, 300);
+}
+
+#[test]
+fn test_check_passes_when_destination_cannot_report_space() {
+    let source = create_source();
+    let destination = InMemoryVolume::new("Destination");
+    let paths = vec![PathBuf::from("/photos")];
+
+    assert!(check_space_for_copy(&source, &paths, &destination, Path::new("/"), false).is_ok());
+}
+
+#[test]
+fn test_check_fails_for_missing_source() {
+    let source = create_source();
+    let destination = InMemoryVolume::new("Destination").with_space(10_000, 1_000);
+    let paths = vec![PathBuf::from("/missing.txt")];
+
+    let result = check_space_for_copy(&source, &paths, &destination, Path::new("/"), false);
+    assert!(matches!(result, Err(VolumeError::NotFound(_))));
+}
+
+#[test]
+fn test_check_for_paths_resolves_volumes() {
+    let manager = VolumeManager::new();
+    let volume = create_source().with_space(10_000, 250);
+    volume.create_directory(Path::new("/backup")).unwrap();
+    manager.register("test", Arc::new(volume));
+    let paths = vec![PathBuf::from("/small.txt"), PathBuf::from("/photos")];
+
+    let result = check_space_for_paths(&manager, &paths, Path::new("/backup"), false);
+    let overridden = check_space_for_paths(&manager, &paths, Path::new("/backup"), true);
+
+    assert!(matches!(
+        result,
+        Err(VolumeError::InsufficientSpace {
+            needed: 400,
+            available: 250
+        })
+    ));
+    assert_eq!(overridden.unwrap(), 400);
+}
+
+#[test]
+fn test_check_for_paths_fails_without_volume() {
+    let manager = VolumeManager::new();
+    let paths = vec![PathBuf::from("/small.txt")];
+
+    let result = check_space_for_paths(&manager, &paths, Path::new("/backup"), false);
+    assert!(matches!(result, Err(VolumeError::NotFound(_))));
+}
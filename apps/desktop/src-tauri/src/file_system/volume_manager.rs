@@ -8,6 +8,7 @@
 
 use super::volume::Volume;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
 /// Manages registered volumes and provides access to them.
@@ -97,6 +98,21 @@ impl VolumeManager {
             .unwrap_or_default()
     }
 
+    /// Finds the volume that holds an absolute path: the one with the longest root that contains it.
+    ///
+    /// # Returns
+    /// The volume ID, the volume, and the path relative to the volume root (with a leading "/"),
+    /// or None if no volume's root contains the path.
+    pub fn resolve(&self, path: &Path) -> Option<(String, Arc<dyn Volume>, PathBuf)> {
+        let volumes = self.volumes.read().ok()?;
+        let (id, volume) = volumes
+            .iter()
+            .filter(|(_, volume)| path.starts_with(volume.root()))
+            .max_by_key(|(_, volume)| volume.root().components().count())?;
+        let relative = path.strip_prefix(volume.root()).ok()?;
+        Some((id.clone(), volume.clone(), Path::new("/").join(relative)))
+    }
+
     /// Returns the number of registered volumes.
     pub fn count(&self) -> usize {
         self.volumes.read().map(|v| v.len()).unwrap_or(0)
@@ -176,6 +192,33 @@ mod tests {
         assert!(manager.default_volume().is_none());
     }
 
+    #[test]
+    fn test_resolve_picks_the_most_specific_volume() {
+        use crate::file_system::volume::LocalPosixVolume;
+
+        let manager = VolumeManager::new();
+        manager.register("root", Arc::new(LocalPosixVolume::new("Macintosh HD", "/")));
+        manager.register("usb", Arc::new(LocalPosixVolume::new("USB", "/Volumes/USB")));
+
+        let (id, _, relative) = manager.resolve(Path::new("/Volumes/USB/photos/a.jpg")).unwrap();
+        assert_eq!(id, "usb");
+        assert_eq!(relative, Path::new("/photos/a.jpg"));
+
+        let (id, _, relative) = manager.resolve(Path::new("/Users/me/a.txt")).unwrap();
+        assert_eq!(id, "root");
+        assert_eq!(relative, Path::new("/Users/me/a.txt"));
+
+        // Only whole path components count, so "/Volumes/USB2" isn't on "/Volumes/USB"
+        let (id, _, _) = manager.resolve(Path::new("/Volumes/USB2/file")).unwrap();
+        assert_eq!(id, "root");
+    }
+
+    #[test]
+    fn test_resolve_without_matching_volume_returns_none() {
+        let manager = VolumeManager::new();
+        assert!(manager.resolve(Path::new("/anything")).is_none());
+    }
+
     #[test]
     fn test_list_volumes() {
         let manager = VolumeManager::new();
@@ -2,22 +2,39 @@
 //!
 //! Watches directories for changes, computes diffs, and emits events to frontend.
 //! Uses the unified LISTING_CACHE from operations.rs (no duplicate cache).
+//!
+//! By default each listing gets its own watcher. Listings that are shown together (the two panes, a tree
+//! sidebar) can instead join a watch session, which watches all their directories with a single debouncer
+//! and routes each event to the listings of the event's parent directory. This saves OS-level watchers,
+//! which are limited (kqueue needs a file descriptor per watched path on macOS).
 
 use notify_debouncer_full::{
     DebounceEventResult, Debouncer, RecommendedCache, new_debouncer,
     notify::{RecommendedWatcher, RecursiveMode},
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{LazyLock, RwLock};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 
 use super::operations::{FileEntry, get_listing_entries, list_directory_core, update_listing_entries};
+use super::volume::{Volume, WatchGuard};
+
+/// Debounce duration in milliseconds, used until `set_debounce_ms` is called
+pub const DEFAULT_DEBOUNCE_MS: u64 = 200;
+
+/// Shortest and longest accepted debounce durations in milliseconds
+const MIN_DEBOUNCE_MS: u64 = 50;
+const MAX_DEBOUNCE_MS: u64 = 2000;
 
-/// Debounce duration in milliseconds
-const DEBOUNCE_MS: u64 = 200;
+/// Debounce duration for new watches, in milliseconds. See `set_debounce_ms`.
+static DEBOUNCE_MS: AtomicU64 = AtomicU64::new(DEFAULT_DEBOUNCE_MS);
+
+/// Most changes sent in a `directory-diff` event. With more, `directory-refresh-needed` is sent instead.
+const MAX_DIFF_CHANGES: usize = 500;
 
 /// Global watcher manager
 static WATCHER_MANAGER: LazyLock<RwLock<WatcherManager>> = LazyLock::new(|| RwLock::new(WatcherManager::new()));
@@ -26,11 +43,14 @@ static WATCHER_MANAGER: LazyLock<RwLock<WatcherManager>> = LazyLock::new(|| RwLo
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DiffChange {
-    /// Type of change: add, remove, or modify
+    /// Type of change: add, remove, modify, or rename
     #[serde(rename = "type")]
     pub change_type: String,
-    /// The file entry
+    /// The file entry. For renames, the entry under its new name.
     pub entry: FileEntry,
+    /// For renames, the path the entry had before
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
 }
 
 /// Diff event sent to frontend
@@ -45,17 +65,68 @@ pub struct DirectoryDiff {
     pub changes: Vec<DiffChange>,
 }
 
+/// Event sent to frontend instead of a diff when there are too many changes, like after extracting a large
+/// archive. The listing cache is already up to date, so the frontend only has to re-fetch what it shows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryRefreshNeeded {
+    /// Listing ID this event belongs to
+    pub listing_id: String,
+    /// Monotonic sequence number, shared with diffs
+    pub sequence: u64,
+}
+
+/// What the frontend is told about a listing's changes.
+#[derive(Debug)]
+enum ChangeEvent {
+    Diff(DirectoryDiff),
+    RefreshNeeded(DirectoryRefreshNeeded),
+}
+
+impl ChangeEvent {
+    /// A diff, or a refresh request if there are more than `MAX_DIFF_CHANGES` changes.
+    fn new(listing_id: &str, sequence: u64, changes: Vec<DiffChange>) -> Self {
+        let listing_id = listing_id.to_string();
+        if changes.len() > MAX_DIFF_CHANGES {
+            Self::RefreshNeeded(DirectoryRefreshNeeded { listing_id, sequence })
+        } else {
+            Self::Diff(DirectoryDiff {
+                listing_id,
+                sequence,
+                changes,
+            })
+        }
+    }
+}
+
 /// State for a watched directory.
 /// NOTE: No `entries` field - we use the unified LISTING_CACHE instead.
 struct WatchedDirectory {
     sequence: u64,
-    #[allow(dead_code)] // Debouncer must be held to keep watching
+    #[allow(dead_code)] // Must be held to keep watching
+    guard: WatchGuard,
+}
+
+/// A listing watched as part of a session.
+struct SessionListing {
+    listing_id: String,
+    /// The listing's directory, as listed
+    path: PathBuf,
+    /// The same directory with symlinks resolved, since events may report paths that way (like /private/tmp)
+    canonical_path: PathBuf,
+    sequence: u64,
+}
+
+/// Several listings watched with a single debouncer.
+struct WatchSession {
+    listings: Vec<SessionListing>,
     debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
 }
 
 /// Manages file watchers for directories
 pub struct WatcherManager {
     watches: HashMap<String, WatchedDirectory>,
+    sessions: HashMap<String, WatchSession>,
     app_handle: Option<AppHandle>,
 }
 
@@ -63,66 +134,257 @@ impl WatcherManager {
     fn new() -> Self {
         Self {
             watches: HashMap::new(),
+            sessions: HashMap::new(),
             app_handle: None,
         }
     }
+
+    /// Increments and returns the diff sequence number of a listing, wherever it's watched.
+    fn next_sequence(&mut self, listing_id: &str) -> Option<u64> {
+        if let Some(watch) = self.watches.get_mut(listing_id) {
+            watch.sequence += 1;
+            return Some(watch.sequence);
+        }
+        let listing = self
+            .sessions
+            .values_mut()
+            .flat_map(|session| session.listings.iter_mut())
+            .find(|listing| listing.listing_id == listing_id)?;
+        listing.sequence += 1;
+        Some(listing.sequence)
+    }
 }
 
-/// Initialize the watcher manager with the app handle.
+/// Initialize the watcher manager with the app handle and the debounce duration from the settings.
 /// Must be called during app setup.
-pub fn init_watcher_manager(app: AppHandle) {
+pub fn init_watcher_manager(app: AppHandle, debounce_ms: u64) {
+    set_debounce_ms(debounce_ms);
     if let Ok(mut manager) = WATCHER_MANAGER.write() {
         manager.app_handle = Some(app);
     }
 }
 
+/// Sets how long to wait for changes to settle before re-reading a directory, clamped to 50–2000 ms.
+///
+/// Longer suits slow network mounts, shorter feels snappier on local disks. Only watches started from now on
+/// use the new value; existing ones keep theirs until their listing is reloaded.
+pub fn set_debounce_ms(debounce_ms: u64) {
+    DEBOUNCE_MS.store(debounce_ms.clamp(MIN_DEBOUNCE_MS, MAX_DEBOUNCE_MS), Ordering::Relaxed);
+}
+
+/// Returns the debounce duration for new watches.
+pub(crate) fn debounce() -> Duration {
+    Duration::from_millis(DEBOUNCE_MS.load(Ordering::Relaxed))
+}
+
 /// Start watching a directory for a given listing.
 ///
 /// # Arguments
 /// * `listing_id` - The listing ID from list_directory_start
-/// * `path` - The directory path to watch
+/// * `volume` - The volume the directory is on. Must support watching.
+/// * `path` - The directory path to watch, relative to the volume root
 ///
 /// Note: Initial entries are read from LISTING_CACHE when needed.
-pub fn start_watching(listing_id: &str, path: &Path) -> Result<(), String> {
-    let listing_id_owned = listing_id.to_string();
-    let listing_for_closure = listing_id_owned.clone();
-
-    // Create the debouncer with a callback that handles changes
-    let mut debouncer = new_debouncer(
-        Duration::from_millis(DEBOUNCE_MS),
-        None, // No tick rate limit
-        move |result: DebounceEventResult| {
-            if let Ok(_events) = result {
-                // Events occurred - re-read directory and compute diff
-                handle_directory_change(&listing_for_closure);
-            }
-        },
-    )
-    .map_err(|e| format!("Failed to create watcher: {}", e))?;
-
-    // Start watching the path (Debouncer implements Watcher trait)
-    debouncer
-        .watch(path, RecursiveMode::NonRecursive)
-        .map_err(|e| format!("Failed to watch path: {}", e))?;
+pub fn start_watching(listing_id: &str, volume: &dyn Volume, path: &Path) -> Result<(), String> {
+    let listing_for_closure = listing_id.to_string();
+    // Re-read the directory and compute the diff when something changes
+    let guard = volume
+        .watch(
+            path,
+            debounce(),
+            Box::new(move || handle_directory_change(&listing_for_closure)),
+        )
+        .map_err(|e| e.to_string())?;
 
     // Store in manager (no entries - we use LISTING_CACHE)
     let mut manager = WATCHER_MANAGER.write().map_err(|_| "Failed to acquire watcher lock")?;
 
     manager
         .watches
-        .insert(listing_id_owned, WatchedDirectory { sequence: 0, debouncer });
+        .insert(listing_id.to_string(), WatchedDirectory { sequence: 0, guard });
 
     Ok(())
 }
 
-/// Stop watching a directory for a given listing.
+/// Stop watching a directory for a given listing, whether it has its own watcher or is part of a session.
 pub fn stop_watching(listing_id: &str) {
     if let Ok(mut manager) = WATCHER_MANAGER.write() {
         // Dropping the WatchedDirectory will drop the debouncer
         manager.watches.remove(listing_id);
+
+        let session_ids: Vec<String> = manager
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.listings.iter().any(|l| l.listing_id == listing_id))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for session_id in session_ids {
+            remove_listing_from_session(&mut manager, &session_id, listing_id);
+        }
+    }
+}
+
+/// Moves a listing's watching into a shared session, creating the session if needed.
+///
+/// The listing's own watcher (if any) is replaced, and its diff sequence numbers carry on where they were.
+/// Directories already watched by another listing of the session aren't watched twice.
+///
+/// # Arguments
+/// * `session_id` - Any ID the caller picks to group listings, for example one per window
+/// * `listing_id` - The listing ID from list_directory_start
+pub fn add_to_watch_session(session_id: &str, listing_id: &str) -> Result<(), String> {
+    let Some((path, _)) = get_listing_entries(listing_id) else {
+        return Err(format!("Listing not found: {}", listing_id));
+    };
+    let canonical_path = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+
+    let mut manager = WATCHER_MANAGER.write().map_err(|_| "Failed to acquire watcher lock")?;
+    if manager
+        .sessions
+        .get(session_id)
+        .is_some_and(|s| s.listings.iter().any(|l| l.listing_id == listing_id))
+    {
+        return Ok(());
+    }
+
+    if !manager.sessions.contains_key(session_id) {
+        let session_for_closure = session_id.to_string();
+        let debouncer = new_debouncer(
+            debounce(),
+            None, // No tick rate limit
+            move |result: DebounceEventResult| {
+                if let Ok(events) = result {
+                    let paths: Vec<PathBuf> = events.iter().flat_map(|e| e.paths.iter().cloned()).collect();
+                    handle_session_change(&session_for_closure, &paths);
+                }
+            },
+        )
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+        manager.sessions.insert(
+            session_id.to_string(),
+            WatchSession {
+                listings: Vec::new(),
+                debouncer,
+            },
+        );
+    }
+
+    let sequence = manager.watches.get(listing_id).map(|w| w.sequence).unwrap_or(0);
+    let Some(session) = manager.sessions.get_mut(session_id) else {
+        return Err(format!("Watch session not found: {}", session_id));
+    };
+    if !session.listings.iter().any(|l| l.path == path)
+        && let Err(e) = session.debouncer.watch(&path, RecursiveMode::NonRecursive)
+    {
+        if session.listings.is_empty() {
+            manager.sessions.remove(session_id);
+        }
+        return Err(format!("Failed to watch path: {}", e));
+    }
+    session.listings.push(SessionListing {
+        listing_id: listing_id.to_string(),
+        path,
+        canonical_path,
+        sequence,
+    });
+
+    // The session watches the directory now, so the listing's own watcher can go
+    manager.watches.remove(listing_id);
+    Ok(())
+}
+
+/// Stops watching a listing as part of a session. Ends the session when its last listing leaves.
+///
+/// The listing isn't watched at all afterward, call `start_watching` to give it its own watcher again.
+pub fn remove_from_watch_session(session_id: &str, listing_id: &str) {
+    if let Ok(mut manager) = WATCHER_MANAGER.write() {
+        remove_listing_from_session(&mut manager, session_id, listing_id);
+    }
+}
+
+/// Stops watching all listings of a session.
+pub fn end_watch_session(session_id: &str) {
+    if let Ok(mut manager) = WATCHER_MANAGER.write() {
+        // Dropping the session will drop its debouncer
+        manager.sessions.remove(session_id);
+    }
+}
+
+/// Returns the diff sequence number of a listing with its own watcher, or None if it doesn't have one.
+#[cfg(test)]
+pub fn watch_sequence(listing_id: &str) -> Option<u64> {
+    let manager = WATCHER_MANAGER.read().ok()?;
+    manager.watches.get(listing_id).map(|w| w.sequence)
+}
+
+/// Returns the directories a session currently watches, or None if there's no such session.
+#[cfg(test)]
+pub fn watched_session_paths(session_id: &str) -> Option<Vec<PathBuf>> {
+    let manager = WATCHER_MANAGER.read().ok()?;
+    let session = manager.sessions.get(session_id)?;
+    let paths: HashSet<&PathBuf> = session.listings.iter().map(|l| &l.path).collect();
+    Some(paths.into_iter().cloned().collect())
+}
+
+fn remove_listing_from_session(manager: &mut WatcherManager, session_id: &str, listing_id: &str) {
+    let Some(session) = manager.sessions.get_mut(session_id) else {
+        return;
+    };
+    let Some(index) = session.listings.iter().position(|l| l.listing_id == listing_id) else {
+        return;
+    };
+    let removed = session.listings.remove(index);
+    if session.listings.is_empty() {
+        manager.sessions.remove(session_id);
+    } else if !session.listings.iter().any(|l| l.path == removed.path) {
+        let _ = session.debouncer.unwatch(&removed.path);
+    }
+}
+
+/// Handles events from a session's debouncer by refreshing the listings whose directory changed.
+fn handle_session_change(session_id: &str, event_paths: &[PathBuf]) {
+    let listing_ids = {
+        let Ok(manager) = WATCHER_MANAGER.read() else {
+            return;
+        };
+        let Some(session) = manager.sessions.get(session_id) else {
+            return;
+        };
+        listings_for_event_paths(&session.listings, event_paths)
+    };
+    for listing_id in listing_ids {
+        handle_directory_change(&listing_id);
     }
 }
 
+/// Finds the listings affected by events on the given paths.
+///
+/// An event on a file belongs to the listing of its parent directory. An event on a watched directory
+/// itself (or one without paths, like a rescan) refreshes the listings of that directory (or all of them).
+fn listings_for_event_paths(listings: &[SessionListing], event_paths: &[PathBuf]) -> Vec<String> {
+    if event_paths.is_empty() {
+        return listings.iter().map(|l| l.listing_id.clone()).collect();
+    }
+    let mut affected: HashSet<&str> = HashSet::new();
+    for event_path in event_paths {
+        let candidates = [Some(event_path.as_path()), event_path.parent()];
+        for listing in listings {
+            if candidates
+                .iter()
+                .flatten()
+                .any(|dir| *dir == listing.path || *dir == listing.canonical_path)
+            {
+                affected.insert(&listing.listing_id);
+            }
+        }
+    }
+    listings
+        .iter()
+        .filter(|l| affected.contains(l.listing_id.as_str()))
+        .map(|l| l.listing_id.clone())
+        .collect()
+}
+
 /// Handle a directory change event.
 /// Re-reads the directory, computes diff, updates LISTING_CACHE, and emits event.
 fn handle_directory_change(listing_id: &str) {
@@ -140,8 +402,10 @@ fn handle_directory_change(listing_id: &str) {
         manager.app_handle.clone()
     };
 
-    // Re-read the directory using core metadata (extended metadata not needed for diffs)
-    let new_entries = match list_directory_core(&path) {
+    // Re-read the directory using core metadata (extended metadata not needed for diffs).
+    // Owner names are only looked up if the listing had them, so changed entries match the rest.
+    let resolve_owners = old_entries.iter().all(|e| e.owner_resolved);
+    let new_entries = match list_directory_core(&path, resolve_owners) {
         Ok(entries) => entries,
         Err(e) => {
             // Silently ignore permission denied - user may have revoked access
@@ -169,24 +433,19 @@ fn handle_directory_change(listing_id: &str) {
             Err(_) => return,
         };
 
-        let watch = match manager.watches.get_mut(listing_id) {
-            Some(w) => w,
+        match manager.next_sequence(listing_id) {
+            Some(sequence) => sequence,
             None => return,
-        };
-
-        watch.sequence += 1;
-        watch.sequence
+        }
     };
 
     // Emit event to frontend
     if let Some(app) = app_handle {
-        let diff = DirectoryDiff {
-            listing_id: listing_id.to_string(),
-            sequence,
-            changes,
+        let result = match ChangeEvent::new(listing_id, sequence, changes) {
+            ChangeEvent::Diff(diff) => app.emit("directory-diff", &diff),
+            ChangeEvent::RefreshNeeded(refresh) => app.emit("directory-refresh-needed", &refresh),
         };
-
-        if let Err(e) = app.emit("directory-diff", &diff) {
+        if let Err(e) = result {
             eprintln!("[WATCHER] Failed to emit event: {}", e);
         }
     }
@@ -201,40 +460,69 @@ pub(crate) fn compute_diff(old: &[FileEntry], new: &[FileEntry]) -> Vec<DiffChan
     let new_map: HashMap<&str, &FileEntry> = new.iter().map(|e| (e.path.as_str(), e)).collect();
 
     // Find additions and modifications
+    let mut added: Vec<&FileEntry> = Vec::new();
     for new_entry in new {
         match old_map.get(new_entry.path.as_str()) {
-            None => {
-                // New entry - addition
-                changes.push(DiffChange {
-                    change_type: "add".to_string(),
-                    entry: new_entry.clone(),
-                });
-            }
+            None => added.push(new_entry),
             Some(old_entry) => {
                 // Exists in both - check if modified
                 if is_entry_modified(old_entry, new_entry) {
                     changes.push(DiffChange {
                         change_type: "modify".to_string(),
                         entry: new_entry.clone(),
+                        old_path: None,
                     });
                 }
             }
         }
     }
 
-    // Find removals
+    // Find removals, and pair them up with additions of the same file as renames
+    let mut removed: Vec<&FileEntry> = Vec::new();
+    let mut renamed: Vec<(&FileEntry, &FileEntry)> = Vec::new();
     for old_entry in old {
-        if !new_map.contains_key(old_entry.path.as_str()) {
-            changes.push(DiffChange {
-                change_type: "remove".to_string(),
-                entry: old_entry.clone(),
-            });
+        if new_map.contains_key(old_entry.path.as_str()) {
+            continue;
+        }
+        match added.iter().position(|new_entry| is_same_file(old_entry, new_entry)) {
+            Some(index) => renamed.push((old_entry, added.remove(index))),
+            None => removed.push(old_entry),
         }
     }
 
+    for new_entry in added {
+        changes.push(DiffChange {
+            change_type: "add".to_string(),
+            entry: new_entry.clone(),
+            old_path: None,
+        });
+    }
+    for old_entry in removed {
+        changes.push(DiffChange {
+            change_type: "remove".to_string(),
+            entry: old_entry.clone(),
+            old_path: None,
+        });
+    }
+    for (old_entry, new_entry) in renamed {
+        changes.push(DiffChange {
+            change_type: "rename".to_string(),
+            entry: new_entry.clone(),
+            old_path: Some(old_entry.path.clone()),
+        });
+    }
+
     changes
 }
 
+/// Check if a removed and an added entry are the same file under a new name.
+///
+/// Renaming keeps the inode, size, and modification time. Entries without an inode are never matched, as size
+/// and modification time alone often match for unrelated files.
+fn is_same_file(old: &FileEntry, new: &FileEntry) -> bool {
+    old.inode.is_some() && old.inode == new.inode && old.size == new.size && old.modified_at == new.modified_at
+}
+
 /// Check if a file entry has been modified.
 fn is_entry_modified(old: &FileEntry, new: &FileEntry) -> bool {
     old.size != new.size
@@ -257,13 +545,17 @@ mod tests {
             size,
             modified_at: None,
             created_at: None,
+            changed_at: None,
             added_at: None,
             opened_at: None,
             permissions: 0o644,
             owner: "user".to_string(),
             group: "group".to_string(),
             icon_id: "ext:txt".to_string(),
+            inode: None,
             extended_metadata_loaded: true,
+            owner_resolved: true,
+            symlink_target: None,
         }
     }
 
@@ -308,4 +600,154 @@ mod tests {
         let diff = compute_diff(&old, &new);
         assert!(diff.is_empty());
     }
+
+    fn make_file(name: &str, inode: u64, size: u64, modified_at: u64) -> FileEntry {
+        FileEntry {
+            inode: Some(inode),
+            modified_at: Some(modified_at),
+            ..make_entry(name, Some(size))
+        }
+    }
+
+    #[test]
+    fn test_large_diff_becomes_refresh_needed() {
+        let new: Vec<FileEntry> = (0..=MAX_DIFF_CHANGES)
+            .map(|i| make_entry(&format!("file{}.txt", i), Some(100)))
+            .collect();
+        let changes = compute_diff(&[], &new);
+        assert_eq!(changes.len(), MAX_DIFF_CHANGES + 1);
+
+        match ChangeEvent::new("listing", 7, changes) {
+            ChangeEvent::RefreshNeeded(refresh) => {
+                assert_eq!(refresh.listing_id, "listing");
+                assert_eq!(refresh.sequence, 7);
+            }
+            ChangeEvent::Diff(_) => panic!("Expected a refresh request for a large diff"),
+        }
+    }
+
+    #[test]
+    fn test_diff_at_the_limit_is_sent_as_is() {
+        let new: Vec<FileEntry> = (0..MAX_DIFF_CHANGES)
+            .map(|i| make_entry(&format!("file{}.txt", i), Some(100)))
+            .collect();
+        let changes = compute_diff(&[], &new);
+
+        match ChangeEvent::new("listing", 7, changes) {
+            ChangeEvent::Diff(diff) => assert_eq!(diff.changes.len(), MAX_DIFF_CHANGES),
+            ChangeEvent::RefreshNeeded(_) => panic!("Expected a diff"),
+        }
+    }
+
+    #[test]
+    fn test_compute_diff_rename() {
+        let old = vec![
+            make_file("a.txt", 1, 100, 1_700_000_000),
+            make_file("b.txt", 2, 200, 1_700_000_000),
+        ];
+        let new = vec![
+            make_file("a.txt", 1, 100, 1_700_000_000),
+            make_file("c.txt", 2, 200, 1_700_000_000),
+        ];
+
+        let diff = compute_diff(&old, &new);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].change_type, "rename");
+        assert_eq!(diff[0].entry.path, "/test/c.txt");
+        assert_eq!(diff[0].old_path.as_deref(), Some("/test/b.txt"));
+    }
+
+    #[test]
+    fn test_compute_diff_unrelated_files_are_not_a_rename() {
+        // Same size and modification time, but a different inode
+        let old = vec![make_file("b.txt", 2, 200, 1_700_000_000)];
+        let new = vec![make_file("c.txt", 3, 200, 1_700_000_000)];
+
+        let diff = compute_diff(&old, &new);
+        let types: Vec<&str> = diff.iter().map(|c| c.change_type.as_str()).collect();
+        assert_eq!(types, vec!["add", "remove"]);
+        assert!(diff.iter().all(|c| c.old_path.is_none()));
+    }
+
+    #[test]
+    fn test_compute_diff_rename_needs_same_size_and_time() {
+        // The inode was reused by a different file
+        let old = vec![
+            make_file("a.txt", 1, 100, 1_700_000_000),
+            make_file("b.txt", 2, 200, 1_700_000_000),
+        ];
+        let new = vec![
+            make_file("c.txt", 1, 101, 1_700_000_000),
+            make_file("d.txt", 2, 200, 1_700_000_001),
+        ];
+
+        let diff = compute_diff(&old, &new);
+        assert!(diff.iter().all(|c| c.change_type != "rename"));
+        assert_eq!(diff.len(), 4);
+    }
+
+    #[test]
+    fn test_compute_diff_without_inodes_is_never_a_rename() {
+        let old = vec![make_entry("b.txt", Some(200))];
+        let new = vec![make_entry("c.txt", Some(200))];
+
+        let diff = compute_diff(&old, &new);
+        assert!(diff.iter().all(|c| c.change_type != "rename"));
+    }
+
+    #[test]
+    fn test_diff_change_serializes_old_path_only_for_renames() {
+        let diff = compute_diff(
+            &[make_file("b.txt", 2, 200, 1_700_000_000)],
+            &[
+                make_file("c.txt", 2, 200, 1_700_000_000),
+                make_file("d.txt", 3, 300, 1_700_000_000),
+            ],
+        );
+        let json: Vec<serde_json::Value> = diff.iter().map(|c| serde_json::to_value(c).unwrap()).collect();
+
+        assert_eq!(json[0]["type"], "add");
+        assert!(json[0].get("oldPath").is_none());
+        assert_eq!(json[1]["type"], "rename");
+        assert_eq!(json[1]["oldPath"], "/test/b.txt");
+    }
+
+    fn make_session_listing(listing_id: &str, path: &str) -> SessionListing {
+        SessionListing {
+            listing_id: listing_id.to_string(),
+            path: PathBuf::from(path),
+            canonical_path: PathBuf::from(format!("/private{}", path)),
+            sequence: 0,
+        }
+    }
+
+    #[test]
+    fn test_session_events_go_to_parent_directory_listing() {
+        let listings = vec![
+            make_session_listing("left", "/tmp/left"),
+            make_session_listing("right", "/tmp/right"),
+            make_session_listing("sidebar", "/tmp"),
+        ];
+
+        let file_in_right = listings_for_event_paths(&listings, &[PathBuf::from("/tmp/right/new.txt")]);
+        let canonical = listings_for_event_paths(&listings, &[PathBuf::from("/private/tmp/left/new.txt")]);
+        // The directory itself changed: its own listing and its parent's listing are affected
+        let dir_itself = listings_for_event_paths(&listings, &[PathBuf::from("/tmp/left")]);
+        let unrelated = listings_for_event_paths(&listings, &[PathBuf::from("/var/log/system.log")]);
+
+        assert_eq!(file_in_right, vec!["right"]);
+        assert_eq!(canonical, vec!["left"]);
+        assert_eq!(dir_itself, vec!["left", "sidebar"]);
+        assert!(unrelated.is_empty());
+    }
+
+    #[test]
+    fn test_session_event_without_paths_refreshes_all_listings() {
+        let listings = vec![
+            make_session_listing("left", "/tmp/left"),
+            make_session_listing("right", "/tmp/right"),
+        ];
+
+        assert_eq!(listings_for_event_paths(&listings, &[]), vec!["left", "right"]);
+    }
 }
@@ -6,11 +6,21 @@
 // 2. Manual testing of file watching in the actual app
 // 3. Integration tests with the full Tauri app
 
-// The start_watching/stop_watching functions require a running app context
-// to emit events, so proper testing requires integration tests.
+// Without an app context, start_watching still updates the listing cache, it just
+// doesn't emit events. Testing the events requires integration tests.
 
-use super::operations::FileEntry;
-use super::watcher::compute_diff;
+use super::operations::{
+    CachedListing, FileEntry, LISTING_CACHE, SortColumn, SortOrder, get_listing_entries, list_directory_core,
+    list_directory_end,
+};
+use super::volume::LocalPosixVolume;
+use super::watcher::{
+    DEFAULT_DEBOUNCE_MS, add_to_watch_session, compute_diff, debounce, remove_from_watch_session, set_debounce_ms,
+    start_watching, watch_sequence, watched_session_paths,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 fn make_entry(name: &str, size: Option<u64>) -> FileEntry {
     FileEntry {
@@ -21,13 +31,17 @@ fn make_entry(name: &str, size: Option<u64>) -> FileEntry {
         size,
         modified_at: None,
         created_at: None,
+        changed_at: None,
         added_at: None,
         opened_at: None,
         permissions: 0o644,
         owner: "user".to_string(),
         group: "group".to_string(),
         icon_id: "ext:txt".to_string(),
+        inode: None,
         extended_metadata_loaded: true,
+        owner_resolved: true,
+        symlink_target: None,
     }
 }
 
@@ -72,3 +86,144 @@ fn test_compute_diff_no_change() {
     let diff = compute_diff(&old, &new);
     assert!(diff.is_empty());
 }
+
+// Watch sessions don't need an app handle to update the listing cache, so they can be tested on real directories
+
+fn create_test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn insert_listing(listing_id: &str, path: &Path) {
+    LISTING_CACHE.write().unwrap().insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: "root".to_string(),
+            path: path.to_path_buf(),
+            entries: list_directory_core(path, true).unwrap(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            case_sensitive: false,
+            ignore_patterns: Default::default(),
+            last_accessed: super::operations::AccessTime::now(),
+        },
+    );
+}
+
+fn entry_names(listing_id: &str) -> Vec<String> {
+    get_listing_entries(listing_id)
+        .map(|(_, entries)| entries.into_iter().map(|e| e.name).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_watching_through_volume_produces_diff() {
+    let dir = create_test_dir("cmdr_watch_volume_diff_test");
+    fs::write(dir.join("existing.txt"), "content").unwrap();
+    insert_listing("test-watch-volume-diff", &dir);
+    let volume = LocalPosixVolume::new("Test", "/");
+    start_watching("test-watch-volume-diff", &volume, &dir).unwrap();
+
+    fs::write(dir.join("touched.txt"), "content").unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while watch_sequence("test-watch-volume-diff") == Some(0) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let sequence = watch_sequence("test-watch-volume-diff");
+    let names = entry_names("test-watch-volume-diff");
+
+    list_directory_end("test-watch-volume-diff");
+    let _ = fs::remove_dir_all(&dir);
+    // The sequence only goes up when a diff has changes
+    assert!(sequence.is_some_and(|s| s >= 1));
+    assert_eq!(names, vec!["existing.txt", "touched.txt"]);
+    assert_eq!(watch_sequence("test-watch-volume-diff"), None);
+}
+
+#[test]
+fn test_existing_watches_keep_their_debounce() {
+    let dir = create_test_dir("cmdr_watch_debounce_test");
+    insert_listing("test-watch-debounce", &dir);
+    set_debounce_ms(DEFAULT_DEBOUNCE_MS);
+    start_watching("test-watch-debounce", &LocalPosixVolume::new("Test", "/"), &dir).unwrap();
+
+    // Too long, so it's clamped to 2 s. The watch above still refreshes after 200 ms.
+    set_debounce_ms(60_000);
+    let clamped_long = debounce();
+    fs::write(dir.join("touched.txt"), "content").unwrap();
+    let deadline = Instant::now() + Duration::from_millis(1500);
+    while watch_sequence("test-watch-debounce") == Some(0) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let sequence = watch_sequence("test-watch-debounce");
+    set_debounce_ms(1);
+    let clamped_short = debounce();
+    set_debounce_ms(DEFAULT_DEBOUNCE_MS);
+
+    list_directory_end("test-watch-debounce");
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(clamped_long, Duration::from_millis(2000));
+    assert_eq!(clamped_short, Duration::from_millis(50));
+    assert!(sequence.is_some_and(|s| s >= 1));
+}
+
+#[test]
+fn test_session_routes_changes_to_the_right_listing() {
+    let dir_a = create_test_dir("cmdr_watch_session_route_a_test");
+    let dir_b = create_test_dir("cmdr_watch_session_route_b_test");
+    insert_listing("test-watch-session-route-a", &dir_a);
+    insert_listing("test-watch-session-route-b", &dir_b);
+    add_to_watch_session("test-session-route", "test-watch-session-route-a").unwrap();
+    add_to_watch_session("test-session-route", "test-watch-session-route-b").unwrap();
+
+    fs::write(dir_b.join("new.txt"), "content").unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while entry_names("test-watch-session-route-b").is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let names_a = entry_names("test-watch-session-route-a");
+    let names_b = entry_names("test-watch-session-route-b");
+    let paths_while_open = watched_session_paths("test-session-route").map(|p| p.len());
+
+    list_directory_end("test-watch-session-route-a");
+    list_directory_end("test-watch-session-route-b");
+    let _ = fs::remove_dir_all(&dir_a);
+    let _ = fs::remove_dir_all(&dir_b);
+    assert!(names_a.is_empty());
+    assert_eq!(names_b, vec!["new.txt"]);
+    assert_eq!(paths_while_open, Some(2));
+    assert!(
+        watched_session_paths("test-session-route").is_none(),
+        "Ending the last listing should end the session"
+    );
+}
+
+#[test]
+fn test_session_watches_shared_directory_once() {
+    let dir = create_test_dir("cmdr_watch_session_shared_test");
+    insert_listing("test-watch-session-shared-left", &dir);
+    insert_listing("test-watch-session-shared-right", &dir);
+    add_to_watch_session("test-session-shared", "test-watch-session-shared-left").unwrap();
+    add_to_watch_session("test-session-shared", "test-watch-session-shared-right").unwrap();
+
+    let shared = watched_session_paths("test-session-shared");
+    remove_from_watch_session("test-session-shared", "test-watch-session-shared-left");
+    let after_one_left = watched_session_paths("test-session-shared");
+    remove_from_watch_session("test-session-shared", "test-watch-session-shared-right");
+    let after_both_left = watched_session_paths("test-session-shared");
+
+    list_directory_end("test-watch-session-shared-left");
+    list_directory_end("test-watch-session-shared-right");
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(shared, Some(vec![dir.clone()]));
+    assert_eq!(after_one_left, Some(vec![dir]));
+    assert_eq!(after_both_left, None);
+}
+
+#[test]
+fn test_session_rejects_unknown_listing() {
+    assert!(add_to_watch_session("test-session-unknown", "no-such-listing").is_err());
+    assert!(watched_session_paths("test-session-unknown").is_none());
+}
@@ -0,0 +1,94 @@
+//! Whether the current user can write to a path, to enable or gray out paste and "new file" actions.
+//!
+//! Asks the kernel via `access(W_OK)` instead of interpreting `FileEntry.permissions`, because mode bits
+//! don't account for ACLs, group memberships, or read-only mounts.
+
+use serde::Serialize;
+use std::ffi::CString;
+use std::path::Path;
+
+/// Whether a path is writable, and if not, why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WriteAccess {
+    Writable,
+    /// The volume is mounted read-only, like a disk image or a locked SD card
+    ReadOnlyMount,
+    /// Permissions or ACLs don't allow writing, or the path doesn't exist
+    NoPermission,
+}
+
+/// Checks whether the current user can write to `path`, and if not, why.
+pub fn get_write_access(path: &Path) -> WriteAccess {
+    let Ok(c_path) = CString::new(path.as_os_str().as_encoded_bytes()) else {
+        return WriteAccess::NoPermission;
+    };
+    if unsafe { libc::access(c_path.as_ptr(), libc::W_OK) } == 0 {
+        return WriteAccess::Writable;
+    }
+    let error = std::io::Error::last_os_error();
+    if error.raw_os_error() == Some(libc::EROFS) || is_read_only_mount(&c_path) {
+        WriteAccess::ReadOnlyMount
+    } else {
+        WriteAccess::NoPermission
+    }
+}
+
+/// Returns true if the current user can write to `path`.
+pub fn is_writable(path: &Path) -> bool {
+    get_write_access(path) == WriteAccess::Writable
+}
+
+/// Returns true if the file system holding `c_path` is mounted read-only.
+fn is_read_only_mount(c_path: &CString) -> bool {
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return false;
+    }
+    stats.f_flag & libc::ST_RDONLY != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_temp_dir_is_writable() {
+        let dir = std::env::temp_dir().join("cmdr_write_access_writable_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let access = get_write_access(&dir);
+
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(access, WriteAccess::Writable);
+    }
+
+    #[test]
+    fn test_read_only_dir_is_not_writable() {
+        // Root can write anywhere, so the permission check can't fail there
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        let dir = std::env::temp_dir().join("cmdr_write_access_read_only_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o555)).unwrap();
+
+        let access = get_write_access(&dir);
+        let writable = is_writable(&dir);
+
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(access, WriteAccess::NoPermission);
+        assert!(!writable);
+    }
+
+    #[test]
+    fn test_missing_path_is_not_writable() {
+        let path = std::env::temp_dir().join("cmdr_write_access_missing_test/nope");
+        assert_eq!(get_write_access(&path), WriteAccess::NoPermission);
+    }
+}
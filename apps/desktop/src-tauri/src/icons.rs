@@ -9,13 +9,66 @@ use base64::Engine;
 use file_icon_provider::get_file_icon;
 use image::{DynamicImage, ImageFormat, imageops::FilterType};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
-use std::sync::RwLock;
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
 
-/// Cache for generated icons (icon_id -> base64 WebP data URL)
-static ICON_CACHE: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+/// System appearance that icons are rendered for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    /// Returns the appearance icons are rendered for under a window theme.
+    pub fn from_theme(theme: tauri::Theme) -> Self {
+        match theme {
+            tauri::Theme::Dark => Self::Dark,
+            _ => Self::Light,
+        }
+    }
+
+    /// Returns the appearance as the string sent to the frontend ("light" or "dark").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+}
+
+/// Cache key for a generated icon. The same icon ID can have different images per size and appearance.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct IconCacheKey {
+    icon_id: String,
+    size: u32,
+    appearance: Appearance,
+}
+
+/// The system appearance, as last reported by the main window. AppKit may only be asked on the main thread,
+/// while icons are fetched from any thread, so it's kept here instead.
+static SYSTEM_APPEARANCE: RwLock<Appearance> = RwLock::new(Appearance::Light);
+
+/// Cache for generated icons (icon_id + size + appearance -> base64 WebP data URL)
+static ICON_CACHE: RwLock<Option<HashMap<IconCacheKey, String>>> = RwLock::new(None);
+
+/// Default app bundle ID last seen for each extension (lowercase ext -> bundle ID, `None` if there's no handler).
+/// Lets a refresh re-extract an extension icon only when its file association actually changed.
+static EXTENSION_HANDLERS: LazyLock<RwLock<HashMap<String, Option<String>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// How long icons of paths, like volumes, are reused before being extracted again.
+const PATH_ICON_TTL: Duration = Duration::from_secs(60);
+
+/// Icons of paths (path + appearance -> data URL, or None if there's no icon) and when they were extracted.
+/// Kept only for `PATH_ICON_TTL`, as a path can get a different icon, like when another disk is mounted there.
+type PathIconCache = RwLock<HashMap<(String, Appearance), (Option<String>, Instant)>>;
+static PATH_ICON_CACHE: LazyLock<PathIconCache> = LazyLock::new(|| RwLock::new(HashMap::new()));
 
 /// Initializes the icon cache if not already done.
 fn ensure_cache() {
@@ -30,26 +83,78 @@ fn ensure_cache() {
     }
 }
 
-/// Gets cached icon data URL for the given icon ID, if available.
-fn get_cached_icon(icon_id: &str) -> Option<String> {
+/// Gets cached icon data URL for the given icon ID, size, and appearance, if available.
+fn get_cached_icon(icon_id: &str, size: u32, appearance: Appearance) -> Option<String> {
     ensure_cache();
     let cache = ICON_CACHE.read().unwrap();
-    cache.as_ref()?.get(icon_id).cloned()
+    let key = IconCacheKey {
+        icon_id: icon_id.to_string(),
+        size,
+        appearance,
+    };
+    cache.as_ref()?.get(&key).cloned()
 }
 
-/// Caches an icon data URL.
-fn cache_icon(icon_id: String, data_url: String) {
+/// Caches an icon data URL for the given size and appearance.
+fn cache_icon(icon_id: String, size: u32, appearance: Appearance, data_url: String) {
     ensure_cache();
     let mut cache = ICON_CACHE.write().unwrap();
     if let Some(ref mut map) = *cache {
-        map.insert(icon_id, data_url);
+        let key = IconCacheKey {
+            icon_id,
+            size,
+            appearance,
+        };
+        map.insert(key, data_url);
     }
 }
 
-/// Converts an image to a base64 WebP data URL.
-fn image_to_data_url(img: &DynamicImage) -> Option<String> {
-    // Resize to configured size
-    let resized = img.resize_exact(ICON_SIZE, ICON_SIZE, FilterType::Lanczos3);
+/// Caches an icon data URL at `ICON_SIZE`, and returns whether it differs from the one cached before.
+fn cache_icon_if_changed(icon_id: &str, appearance: Appearance, data_url: &str) -> bool {
+    if get_cached_icon(icon_id, ICON_SIZE, appearance).as_deref() == Some(data_url) {
+        return false;
+    }
+    cache_icon(icon_id.to_string(), ICON_SIZE, appearance, data_url.to_string());
+    true
+}
+
+/// Removes all cached variants (every size and appearance) of the given icon ID.
+fn invalidate_icon(icon_id: &str) {
+    let mut cache = ICON_CACHE.write().unwrap();
+    if let Some(ref mut map) = *cache {
+        map.retain(|key, _| key.icon_id != icon_id);
+    }
+}
+
+/// Returns the bundle ID of the default app for an extension. Always `None` outside macOS.
+fn get_extension_handler(ext: &str) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos_icons::get_default_app_bundle_id_for_extension(ext)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = ext;
+        None
+    }
+}
+
+/// Returns the current system appearance, as last set with `set_system_appearance`. Light until then.
+pub fn system_appearance() -> Appearance {
+    SYSTEM_APPEARANCE.read().map(|a| *a).unwrap_or(Appearance::Light)
+}
+
+/// Remembers the system appearance. Called at startup and whenever the window theme changes.
+pub fn set_system_appearance(appearance: Appearance) {
+    if let Ok(mut current) = SYSTEM_APPEARANCE.write() {
+        *current = appearance;
+    }
+}
+
+/// Converts an image to a base64 WebP data URL of `size`×`size` pixels.
+fn image_to_data_url(img: &DynamicImage, size: u32) -> Option<String> {
+    // Resize to the requested size
+    let resized = img.resize_exact(size, size, FilterType::Lanczos3);
 
     // Encode as WebP
     let mut buffer = Cursor::new(Vec::new());
@@ -60,22 +165,67 @@ fn image_to_data_url(img: &DynamicImage) -> Option<String> {
     Some(format!("data:image/webp;base64,{}", base64))
 }
 
-/// Fetches icon for a specific file path.
-fn fetch_icon_for_path(path: &Path) -> Option<String> {
-    // Get icon from OS (size is u16)
-    let icon = get_file_icon(path, ICON_SIZE as u16).ok()?;
+/// Fetches icon for a specific file path at the given pixel size and appearance.
+fn fetch_icon_for_path(path: &Path, size: u32, appearance: Appearance) -> Option<String> {
+    // Get icon from OS (size is u16), drawn with the requested appearance where the OS supports it
+    #[cfg(target_os = "macos")]
+    let icon = crate::macos_icons::with_drawing_appearance(appearance == Appearance::Dark, || {
+        get_file_icon(path, size as u16)
+    })
+    .ok()?;
+    #[cfg(not(target_os = "macos"))]
+    let icon = {
+        let _ = appearance;
+        get_file_icon(path, size as u16).ok()?
+    };
 
     // file_icon_provider returns Icon with width, height, and RGBA pixels
     let img = image::RgbaImage::from_raw(icon.width, icon.height, icon.pixels)?;
     let dynamic_img = DynamicImage::ImageRgba8(img);
 
-    image_to_data_url(&dynamic_img)
+    image_to_data_url(&dynamic_img, size)
 }
 
 /// Gets icon for a path as base64 data URL.
 /// Public API for use by volumes module.
-pub fn get_icon_for_path(path: &str) -> Option<String> {
-    fetch_icon_for_path(Path::new(path))
+/// Reuses the icon extracted in the last `PATH_ICON_TTL`, as the volume list is refreshed often.
+///
+/// # Arguments
+/// * `path` - Path to get the icon for
+/// * `appearance` - Appearance to render for; `None` uses the current system appearance
+pub fn get_icon_for_path(path: &str, appearance: Option<Appearance>) -> Option<String> {
+    let appearance = appearance.unwrap_or_else(system_appearance);
+    cached_or_extract(&PATH_ICON_CACHE, (path.to_string(), appearance), Instant::now(), || {
+        fetch_icon_for_path(Path::new(path), ICON_SIZE, appearance)
+    })
+}
+
+/// Forgets the cached icons of a path, like when the volume mounted there is unmounted.
+pub fn invalidate_path_icon(path: &str) {
+    if let Ok(mut cache) = PATH_ICON_CACHE.write() {
+        cache.retain(|(cached_path, _), _| cached_path != path);
+    }
+}
+
+/// Returns the icon cached for `key` if it's younger than `PATH_ICON_TTL`, or else extracts and caches it.
+fn cached_or_extract(
+    cache: &PathIconCache,
+    key: (String, Appearance),
+    now: Instant,
+    extract: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    if let Ok(cache) = cache.read()
+        && let Some((icon, extracted_at)) = cache.get(&key)
+        && now.duration_since(*extracted_at) < PATH_ICON_TTL
+    {
+        return icon.clone();
+    }
+
+    let icon = extract();
+    if let Ok(mut cache) = cache.write() {
+        cache.insert(key, (icon.clone(), now));
+    }
+    icon
 }
 
 /// Gets the sample file path to use for fetching an icon by ID.
@@ -85,10 +235,26 @@ fn get_sample_path_for_icon_id(icon_id: &str) -> Option<PathBuf> {
         // Use home directory as sample directory (symlinks to dirs get folder icon)
         return dirs::home_dir();
     }
-    if icon_id == "symlink-file" || icon_id == "symlink" || icon_id == "file" {
+    if icon_id == "symlink-file" || icon_id == "symlink" || icon_id == "alias-file" || icon_id == "file" {
         // Generic file icon - use /etc/hosts which exists on all macOS systems
         return Some(PathBuf::from("/etc/hosts"));
     }
+    if let Some(path) = icon_id.strip_prefix("path:") {
+        // Icon of a specific file, folder, or app bundle
+        return Some(PathBuf::from(path));
+    }
+    if let Some(kind) = icon_id.strip_prefix("kind:") {
+        // Content-sniffed kinds of extensionless files: use a representative sample
+        let ext = match kind {
+            "executable" => return Some(PathBuf::from("/bin/ls")),
+            "script" => "sh",
+            "image" => "png",
+            "archive" => "zip",
+            "pdf" => "pdf",
+            _ => return None,
+        };
+        return get_sample_path_for_icon_id(&format!("ext:{}", ext));
+    }
     if let Some(ext) = icon_id.strip_prefix("ext:") {
         // Create an actual temp file with the extension
         // macOS Launch Services needs the file to exist to get the correct icon
@@ -104,21 +270,28 @@ fn get_sample_path_for_icon_id(icon_id: &str) -> Option<PathBuf> {
 
 /// Fetches icons for the given icon IDs that are not already cached.
 /// Returns a map of icon_id -> data URL.
-pub fn get_icons(icon_ids: Vec<String>) -> HashMap<String, String> {
+/// `appearance` defaults to the current system appearance.
+pub fn get_icons(icon_ids: Vec<String>, appearance: Option<Appearance>) -> HashMap<String, String> {
+    get_icons_for_size(icon_ids, ICON_SIZE, appearance.unwrap_or_else(system_appearance))
+}
+
+/// Fetches icons for the given icon IDs at a single pixel size.
+/// Returns a map of icon_id -> data URL.
+fn get_icons_for_size(icon_ids: Vec<String>, size: u32, appearance: Appearance) -> HashMap<String, String> {
     let mut result = HashMap::new();
 
     for icon_id in icon_ids {
         // Check cache first
-        if let Some(cached) = get_cached_icon(&icon_id) {
+        if let Some(cached) = get_cached_icon(&icon_id, size, appearance) {
             result.insert(icon_id, cached);
             continue;
         }
 
         // Not cached, fetch it
         if let Some(sample_path) = get_sample_path_for_icon_id(&icon_id)
-            && let Some(data_url) = fetch_icon_for_path(&sample_path)
+            && let Some(data_url) = fetch_icon_for_path(&sample_path, size, appearance)
         {
-            cache_icon(icon_id.clone(), data_url.clone());
+            cache_icon(icon_id.clone(), size, appearance, data_url.clone());
             result.insert(icon_id, data_url);
         }
     }
@@ -126,15 +299,35 @@ pub fn get_icons(icon_ids: Vec<String>) -> HashMap<String, String> {
     result
 }
 
+/// Fetches icons for the given icon IDs at each of the requested pixel sizes (for example, 32 and 64 for @1x/@2x).
+/// Returns a map of icon_id -> (size -> data URL). Icons that couldn't be fetched at some size are omitted for it.
+/// `appearance` defaults to the current system appearance.
+pub fn get_icons_with_sizes(
+    icon_ids: Vec<String>,
+    sizes: Vec<u32>,
+    appearance: Option<Appearance>,
+) -> HashMap<String, HashMap<u32, String>> {
+    let appearance = appearance.unwrap_or_else(system_appearance);
+    let mut result: HashMap<String, HashMap<u32, String>> = HashMap::new();
+
+    for size in sizes {
+        for (icon_id, data_url) in get_icons_for_size(icon_ids.clone(), size, appearance) {
+            result.entry(icon_id).or_default().insert(size, data_url);
+        }
+    }
+
+    result
+}
+
 /// Fetches a fresh icon for an extension, bypassing any OS cache.
 /// On macOS, this goes directly to the app bundle. On other platforms, falls back to temp files.
-fn fetch_fresh_extension_icon(ext: &str) -> Option<String> {
+fn fetch_fresh_extension_icon(ext: &str, appearance: Appearance) -> Option<String> {
     // On macOS, try to get the icon directly from the default app's bundle
     // This bypasses the Launch Services icon cache
     #[cfg(target_os = "macos")]
     {
-        if let Some(img) = crate::macos_icons::fetch_fresh_icon_for_extension(ext) {
-            return image_to_data_url(&img);
+        if let Some(img) = crate::macos_icons::fetch_fresh_icon_for_extension(ext, ICON_SIZE) {
+            return image_to_data_url(&img, ICON_SIZE);
         }
     }
 
@@ -143,37 +336,58 @@ fn fetch_fresh_extension_icon(ext: &str) -> Option<String> {
     if !sample_path.exists() {
         let _ = std::fs::File::create(&sample_path);
     }
-    fetch_icon_for_path(&sample_path)
+    fetch_icon_for_path(&sample_path, ICON_SIZE, appearance)
 }
 
 /// Refreshes icons for a directory listing.
 /// Fetches icons in parallel for:
-/// 1. All unique extensions (checking for file association changes)
-/// 2. All directory paths (for custom folder icons)
+/// 1. Extensions whose default app changed since we last saw them (or that we haven't seen yet this session)
+/// 2. All directory paths (for custom folder icons), of which only the ones that differ from the cache are returned
 ///
 /// On macOS, extension icons are fetched directly from app bundles to bypass
 /// the Launch Services icon cache, ensuring we always show the current association.
+/// When an extension's default app changed, all cached variants of its icon are invalidated.
 ///
-/// Returns only the icons that were successfully fetched, regardless of cache state.
-/// This allows the frontend to detect changes by comparing with its cached icons.
+/// Returns only the icons that were (re-)extracted, so the keys are the set of icon IDs the frontend should update.
+/// Icons are rendered for the current system appearance.
 pub fn refresh_icons_for_directory(directory_paths: Vec<String>, extensions: Vec<String>) -> HashMap<String, String> {
+    let appearance = system_appearance();
     let mut result = HashMap::new();
 
-    // Fetch extension icons in parallel (uses rayon's global pool)
-    if !extensions.is_empty() {
-        let ext_results: Vec<(String, Option<String>)> = extensions
-            .par_iter()
+    // Find extensions whose backing app changed (uses rayon's global pool, Launch Services lookups aren't free)
+    let mut unique_extensions: Vec<String> = extensions.iter().map(|ext| ext.to_lowercase()).collect();
+    unique_extensions.sort();
+    unique_extensions.dedup();
+    let changed_extensions: Vec<(String, Option<String>)> = {
+        let known_handlers = EXTENSION_HANDLERS.read().unwrap();
+        unique_extensions
+            .into_par_iter()
             .map(|ext| {
-                let icon_id = format!("ext:{}", ext.to_lowercase());
-                let data_url = fetch_fresh_extension_icon(ext);
-                (icon_id, data_url)
+                let handler = get_extension_handler(&ext);
+                (ext, handler)
+            })
+            .filter(|(ext, handler)| known_handlers.get(ext) != Some(handler))
+            .collect()
+    };
+
+    // Re-extract icons for changed extensions in parallel
+    if !changed_extensions.is_empty() {
+        let ext_results: Vec<(String, Option<String>, Option<String>)> = changed_extensions
+            .into_par_iter()
+            .map(|(ext, handler)| {
+                let data_url = fetch_fresh_extension_icon(&ext, appearance);
+                (ext, handler, data_url)
             })
             .collect();
 
-        for (icon_id, data_url) in ext_results {
+        let mut known_handlers = EXTENSION_HANDLERS.write().unwrap();
+        for (ext, handler, data_url) in ext_results {
             if let Some(url) = data_url {
-                cache_icon(icon_id.clone(), url.clone());
+                let icon_id = format!("ext:{}", ext);
+                invalidate_icon(&icon_id);
+                cache_icon(icon_id.clone(), ICON_SIZE, appearance, url.clone());
                 result.insert(icon_id, url);
+                known_handlers.insert(ext, handler);
             }
         }
     }
@@ -184,16 +398,16 @@ pub fn refresh_icons_for_directory(directory_paths: Vec<String>, extensions: Vec
             .par_iter()
             .map(|path| {
                 let path_buf = PathBuf::from(path);
-                let data_url = fetch_icon_for_path(&path_buf);
+                let data_url = fetch_icon_for_path(&path_buf, ICON_SIZE, appearance);
                 // Use path as the icon ID for directories
                 (format!("path:{}", path), data_url)
             })
             .collect();
 
         for (icon_id, data_url) in dir_results {
-            if let Some(url) = data_url {
-                // Update cache
-                cache_icon(icon_id.clone(), url.clone());
+            if let Some(url) = data_url
+                && cache_icon_if_changed(&icon_id, appearance, &url)
+            {
                 result.insert(icon_id, url);
             }
         }
@@ -201,3 +415,111 @@ pub fn refresh_icons_for_directory(directory_paths: Vec<String>, extensions: Vec
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const ICON: &str = "data:image/webp;base64,AAAA";
+
+    fn key(path: &str) -> (String, Appearance) {
+        (path.to_string(), Appearance::Light)
+    }
+
+    #[test]
+    fn test_path_icon_is_reused_within_ttl() {
+        let cache = PathIconCache::default();
+        let extractions = Cell::new(0);
+        let extract = || {
+            extractions.set(extractions.get() + 1);
+            Some(ICON.to_string())
+        };
+        let start = Instant::now();
+
+        let first = cached_or_extract(&cache, key("/Volumes/Backup"), start, extract);
+        let second = cached_or_extract(&cache, key("/Volumes/Backup"), start + Duration::from_secs(30), extract);
+
+        assert_eq!(first.as_deref(), Some(ICON));
+        assert_eq!(second.as_deref(), Some(ICON));
+        assert_eq!(extractions.get(), 1);
+    }
+
+    #[test]
+    fn test_path_icon_is_extracted_again_after_ttl() {
+        let cache = PathIconCache::default();
+        let extractions = Cell::new(0);
+        let extract = || {
+            extractions.set(extractions.get() + 1);
+            Some(ICON.to_string())
+        };
+        let start = Instant::now();
+
+        cached_or_extract(&cache, key("/Volumes/Backup"), start, extract);
+        cached_or_extract(&cache, key("/Volumes/Backup"), start + PATH_ICON_TTL, extract);
+        // Other paths and appearances are cached separately
+        cached_or_extract(&cache, key("/Volumes/Photos"), start, extract);
+        cached_or_extract(
+            &cache,
+            ("/Volumes/Photos".to_string(), Appearance::Dark),
+            start,
+            extract,
+        );
+
+        assert_eq!(extractions.get(), 4);
+    }
+
+    #[test]
+    fn test_unchanged_directory_icon_is_not_reported() {
+        let icon_id = "path:/cmdr/refresh-changed-test";
+
+        let first = cache_icon_if_changed(icon_id, Appearance::Light, "folder-icon");
+        let unchanged = cache_icon_if_changed(icon_id, Appearance::Light, "folder-icon");
+        let custom = cache_icon_if_changed(icon_id, Appearance::Light, "custom-folder-icon");
+        let other_appearance = cache_icon_if_changed(icon_id, Appearance::Dark, "custom-folder-icon");
+
+        invalidate_icon(icon_id);
+        assert!(first);
+        assert!(!unchanged);
+        assert!(custom);
+        assert!(other_appearance);
+    }
+
+    #[test]
+    fn test_icon_cache_keys_differ_by_appearance() {
+        let icon_id = "ext:appearance-cache-test";
+        cache_icon(icon_id.to_string(), 32, Appearance::Light, "light-icon".to_string());
+        cache_icon(icon_id.to_string(), 32, Appearance::Dark, "dark-icon".to_string());
+
+        let light = get_cached_icon(icon_id, 32, Appearance::Light);
+        let dark = get_cached_icon(icon_id, 32, Appearance::Dark);
+        invalidate_icon(icon_id);
+
+        assert_eq!(light.as_deref(), Some("light-icon"));
+        assert_eq!(dark.as_deref(), Some("dark-icon"));
+        assert_eq!(get_cached_icon(icon_id, 32, Appearance::Light), None);
+        assert_eq!(get_cached_icon(icon_id, 32, Appearance::Dark), None);
+    }
+
+    #[test]
+    fn test_appearance_follows_window_theme() {
+        assert_eq!(Appearance::from_theme(tauri::Theme::Dark), Appearance::Dark);
+        assert_eq!(Appearance::from_theme(tauri::Theme::Light), Appearance::Light);
+    }
+
+    #[test]
+    fn test_invalidated_path_icon_is_extracted_again() {
+        let path = "/Volumes/IconCacheTestDrive";
+        let extractions = Cell::new(0);
+        let extract = || {
+            extractions.set(extractions.get() + 1);
+            None
+        };
+
+        cached_or_extract(&PATH_ICON_CACHE, key(path), Instant::now(), extract);
+        invalidate_path_icon(path);
+        cached_or_extract(&PATH_ICON_CACHE, key(path), Instant::now(), extract);
+
+        assert_eq!(extractions.get(), 2);
+    }
+}
@@ -18,10 +18,6 @@ use smb as _;
 #[cfg(target_os = "macos")]
 use smb_rpc as _;
 
-//noinspection ALL
-// chrono is used in network/known_shares.rs for timestamps
-#[cfg(target_os = "macos")]
-use chrono as _;
 //noinspection ALL
 // MCP Bridge is only used in debug builds, so silence the warning in release builds
 #[cfg(not(debug_assertions))]
@@ -32,8 +28,13 @@ use tauri_plugin_mcp_bridge as _;
 use security_framework as _;
 
 pub mod benchmark;
+mod command_error;
 mod commands;
 pub mod config;
+#[cfg(target_os = "macos")]
+mod config_transfer;
+#[cfg(target_os = "macos")]
+mod file_handlers;
 mod file_system;
 mod font_metrics;
 pub mod icons;
@@ -43,9 +44,13 @@ mod macos_icons;
 mod menu;
 #[cfg(target_os = "macos")]
 mod network;
+pub mod owner_cache;
+mod path_format;
 #[cfg(target_os = "macos")]
 mod permissions;
+mod session_state;
 mod settings;
+mod time_format;
 #[cfg(target_os = "macos")]
 mod volumes;
 
@@ -88,8 +93,11 @@ pub fn run() {
             // Initialize benchmarking (enabled by RUSTY_COMMANDER_BENCHMARK=1)
             benchmark::init_benchmarking();
 
+            // Load persisted settings, to initialize the watcher and the menu with the correct state
+            let saved_settings = settings::load_settings(app.handle());
+
             // Initialize the file watcher manager with app handle for events
-            file_system::init_watcher_manager(app.handle().clone());
+            file_system::init_watcher_manager(app.handle().clone(), saved_settings.watcher_debounce_ms);
 
             // Initialize the volume manager with the root volume
             file_system::init_volume_manager();
@@ -98,10 +106,6 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             network::start_discovery(app.handle().clone());
 
-            // Start volume mount/unmount watcher
-            #[cfg(target_os = "macos")]
-            volumes::watcher::start_volume_watcher(app.handle());
-
             // Inject Docker SMB test hosts if enabled (dev mode only)
             // Enable with: RUSTY_INJECT_TEST_SMB=1 pnpm tauri dev
             #[cfg(target_os = "macos")]
@@ -111,11 +115,26 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             network::known_shares::load_known_shares(app.handle());
 
+            // Load which location categories the sidebar shows
+            #[cfg(target_os = "macos")]
+            volumes::sidebar_config::load_sidebar_config(app.handle());
+
+            // Start volume mount/unmount watcher, after the sidebar config, as it lists the mounted volumes
+            #[cfg(target_os = "macos")]
+            volumes::watcher::start_volume_watching(app.handle().clone());
+
+            // Load the sorts remembered per directory. Manual orders load themselves when first needed.
+            settings::load_directory_sorts(app.handle());
+            file_system::init_manual_orders(app.handle());
+
             // Initialize font metrics for default font (system font at 12px)
             font_metrics::init_font_metrics(app.handle(), "system-400-12");
 
-            // Load persisted settings to initialize menu with correct state
-            let saved_settings = settings::load_settings(app.handle());
+            // Apply the settings the backend keeps its own copy of
+            file_system::set_ignore_patterns(&saved_settings.ignore_patterns);
+            file_system::set_follow_symlinks_for_type(saved_settings.follow_symlinks_for_type);
+            #[cfg(target_os = "macos")]
+            network::smb_client::set_cache_ttl_secs(saved_settings.share_cache_ttl_secs);
 
             // Build and set the application menu with persisted showHiddenFiles
             // Note: view mode is per-pane and managed by frontend, so we default to Brief here
@@ -134,10 +153,25 @@ pub fn run() {
             let title = licensing::get_window_title(&license_status);
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.set_title(&title);
+                // Icons are rendered for this; `ThemeChanged` keeps it up to date
+                if let Ok(theme) = window.theme() {
+                    icons::set_system_appearance(icons::Appearance::from_theme(theme));
+                }
             }
 
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // Let the frontend re-request icons when the OS switches between light and dark mode
+            if let tauri::WindowEvent::ThemeChanged(theme) = event {
+                let appearance = icons::Appearance::from_theme(*theme);
+                icons::set_system_appearance(appearance);
+                let _ = window.app_handle().emit(
+                    "appearance-changed",
+                    serde_json::json!({ "appearance": appearance.as_str() }),
+                );
+            }
+        })
         .on_menu_event(|app, event| {
             let id = event.id().as_ref();
             if id == SHOW_HIDDEN_FILES_ID {
@@ -193,41 +227,117 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             greet,
             commands::file_system::list_directory_start,
+            commands::file_system::list_directory_start_streaming,
+            commands::file_system::cancel_listing_stream,
+            commands::file_system::compute_directory_size,
+            commands::file_system::cancel_directory_size,
+            commands::file_system::find_in_directory,
+            commands::file_system::search_file_contents,
+            commands::file_system::cancel_find,
             commands::file_system::list_directory_end,
+            commands::file_system::add_to_watch_session,
+            commands::file_system::remove_from_watch_session,
+            commands::file_system::end_watch_session,
             commands::file_system::get_file_range,
+            commands::file_system::get_file_range_or_recreate,
             commands::file_system::get_file_at,
+            commands::file_system::get_extended_metadata_batch,
+            commands::file_system::resolve_owners_batch,
             commands::file_system::get_total_count,
             commands::file_system::get_max_filename_width,
             commands::file_system::find_file_index,
+            commands::file_system::typeahead_find,
+            commands::file_system::filter_listing,
             commands::file_system::resort_listing,
+            commands::file_system::clear_saved_sorts,
+            commands::file_system::set_manual_order,
+            commands::file_system::set_ignore_patterns,
+            commands::file_system::set_follow_symlinks_for_type,
+            commands::file_system::set_watcher_debounce_ms,
             commands::file_system::path_exists,
+            commands::file_system::path_info,
+            commands::file_system::get_path_metadata,
+            commands::file_system::is_remote_volume,
+            commands::file_system::is_case_sensitive_volume,
+            commands::file_system::is_writable,
+            commands::file_system::get_write_access,
+            commands::file_system::check_name_collision,
+            commands::file_system::rename_path,
+            commands::file_system::make_directory,
+            commands::file_system::create_link,
+            commands::file_system::set_file_permissions,
+            commands::file_system::set_file_owner,
+            commands::file_system::resolve_alias,
+            commands::file_system::check_copy_space,
+            commands::file_system::copy_file,
+            commands::file_system::copy_with_progress,
+            commands::file_system::cancel_copy,
+            commands::file_system::find_hardlinks,
+            commands::file_system::delete_recursive,
+            commands::file_system::cancel_delete,
+            commands::file_system::delete_path,
+            commands::file_system::delete_paths,
+            commands::file_system::copy_paths,
+            commands::file_system::move_paths,
             commands::file_system::benchmark_log,
             commands::font_metrics::store_font_metrics,
             commands::font_metrics::has_font_metrics,
             commands::icons::get_icons,
+            commands::icons::get_icons_with_sizes,
             commands::icons::refresh_directory_icons,
+            commands::session_state::load_session_state,
+            commands::session_state::save_session_state,
+            commands::time_format::format_relative_time,
+            commands::time_format::format_absolute_time,
+            commands::ui::copy_path,
             commands::ui::show_file_context_menu,
             commands::ui::show_main_window,
             commands::ui::update_menu_context,
             #[cfg(target_os = "macos")]
             commands::sync_status::get_sync_status,
             #[cfg(target_os = "macos")]
+            commands::file_system::list_xattrs,
+            #[cfg(target_os = "macos")]
+            commands::file_system::get_xattr,
+            #[cfg(target_os = "macos")]
+            commands::file_system::get_finder_tags_batch,
+            #[cfg(target_os = "macos")]
+            commands::file_system::set_finder_tags,
+            #[cfg(target_os = "macos")]
+            commands::file_handlers::get_handlers_for_path,
+            #[cfg(target_os = "macos")]
             commands::volumes::list_volumes,
             #[cfg(target_os = "macos")]
             commands::volumes::get_default_volume_id,
             #[cfg(target_os = "macos")]
             commands::volumes::find_containing_volume,
             #[cfg(target_os = "macos")]
+            commands::volumes::get_sidebar_config,
+            #[cfg(target_os = "macos")]
+            commands::volumes::set_sidebar_config,
+            #[cfg(target_os = "macos")]
+            commands::config_transfer::export_config,
+            #[cfg(target_os = "macos")]
+            commands::config_transfer::import_config,
+            #[cfg(target_os = "macos")]
             commands::network::list_network_hosts,
             #[cfg(target_os = "macos")]
             commands::network::get_network_discovery_state,
             #[cfg(target_os = "macos")]
+            commands::network::stop_network_discovery,
+            #[cfg(target_os = "macos")]
+            commands::network::restart_network_discovery,
+            #[cfg(target_os = "macos")]
             commands::network::resolve_host,
             #[cfg(target_os = "macos")]
             commands::network::list_shares_on_host,
             #[cfg(target_os = "macos")]
             commands::network::prefetch_shares,
             #[cfg(target_os = "macos")]
+            commands::network::list_shares_for_hosts,
+            #[cfg(target_os = "macos")]
+            commands::network::set_share_cache_ttl_secs,
+            #[cfg(target_os = "macos")]
             commands::network::get_host_auth_mode,
             #[cfg(target_os = "macos")]
             commands::network::fe_log,
@@ -238,6 +348,10 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             commands::network::update_known_share,
             #[cfg(target_os = "macos")]
+            commands::network::forget_known_share,
+            #[cfg(target_os = "macos")]
+            commands::network::forget_all_shares_for_server,
+            #[cfg(target_os = "macos")]
             commands::network::get_username_hints,
             #[cfg(target_os = "macos")]
             commands::network::save_smb_credentials,
@@ -250,8 +364,14 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             commands::network::list_shares_with_credentials,
             #[cfg(target_os = "macos")]
+            commands::network::test_smb_connection,
+            #[cfg(target_os = "macos")]
+            commands::network::connect_share_volume,
+            #[cfg(target_os = "macos")]
             commands::network::mount_network_share,
             #[cfg(target_os = "macos")]
+            commands::network::unmount_network_share,
+            #[cfg(target_os = "macos")]
             permissions::check_full_disk_access,
             #[cfg(target_os = "macos")]
             permissions::open_privacy_settings,
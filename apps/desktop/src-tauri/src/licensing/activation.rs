@@ -0,0 +1,212 @@
+//! Machine activations, for licenses that allow a limited number of machines.
+//!
+//! Each machine gets a stable id: a salted hash of its hardware UUID, so it survives reinstalls and doesn't reveal
+//! the UUID itself. The id is saved in the license store, and activating a license records it there too.
+//!
+//! We can't enforce the limit offline, since each install only sees its own store. But the store travels with the
+//! app data: when it's carried to another machine (Migration Assistant, a restored backup, or a synced app data
+//! folder), that machine's id no longer matches the saved one, and its activation is recorded next to the earlier
+//! ones. So the count lets the app warn when a license's store has been on more machines than the license allows.
+
+use sha2::{Digest, Sha256};
+use tauri_plugin_store::StoreExt;
+
+/// The id of the machine the store was last used on.
+const STORE_KEY_MACHINE_ID: &str = "machine_id";
+const STORE_KEY_ACTIVATED_MACHINES: &str = "activated_machines";
+
+/// Get this machine's id, and save it in the license store. Stable for the same machine, see the module docs.
+fn machine_id(app: &tauri::AppHandle) -> Option<String> {
+    let store = app.store("license.json").ok()?;
+    let stored_id = store
+        .get(STORE_KEY_MACHINE_ID)
+        .and_then(|v| v.as_str().map(str::to_string));
+    let id = current_machine_id(stored_id.as_deref(), hardware_uuid().as_deref());
+    if stored_id.as_deref() != Some(id.as_str()) {
+        store.set(STORE_KEY_MACHINE_ID, serde_json::json!(id));
+    }
+    Some(id)
+}
+
+/// Picks this machine's id: the hashed hardware UUID if it can be read. Otherwise, the saved id, or a new random
+/// one if there's none yet.
+fn current_machine_id(stored_id: Option<&str>, hardware_uuid: Option<&str>) -> String {
+    match (hardware_uuid, stored_id) {
+        (Some(uuid), _) => hash_machine_id(uuid),
+        (None, Some(id)) => id.to_string(),
+        (None, None) => hash_machine_id(&uuid::Uuid::new_v4().to_string()),
+    }
+}
+
+/// Record that the license was activated on this machine.
+pub fn record_activation(app: &tauri::AppHandle) {
+    let Some(id) = machine_id(app) else {
+        return;
+    };
+    if let Ok(store) = app.store("license.json") {
+        let mut machines = stored_machines(&store);
+        if add_machine(&mut machines, &id) {
+            store.set(STORE_KEY_ACTIVATED_MACHINES, serde_json::json!(machines));
+        }
+    }
+}
+
+/// Get the number of distinct machines the license was activated on, as seen by this install.
+pub fn activation_count(app: &tauri::AppHandle) -> u32 {
+    app.store("license.json")
+        .map(|store| u32::try_from(stored_machines(&store).len()).unwrap_or(u32::MAX))
+        .unwrap_or(0)
+}
+
+/// Forget the recorded activations (for testing only).
+#[cfg(debug_assertions)]
+pub fn reset_activations(app: &tauri::AppHandle) {
+    if let Ok(store) = app.store("license.json") {
+        store.delete(STORE_KEY_ACTIVATED_MACHINES);
+    }
+}
+
+fn stored_machines<R: tauri::Runtime>(store: &tauri_plugin_store::Store<R>) -> Vec<String> {
+    store
+        .get(STORE_KEY_ACTIVATED_MACHINES)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Adds the machine id to the list unless it's already there. Returns whether it was new.
+fn add_machine(machines: &mut Vec<String>, machine_id: &str) -> bool {
+    if machines.iter().any(|id| id == machine_id) {
+        return false;
+    }
+    machines.push(machine_id.to_string());
+    true
+}
+
+/// Hashes a hardware UUID into a machine id. Case and surrounding whitespace don't matter.
+fn hash_machine_id(hardware_uuid: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(b"cmdr-machine-id:")
+        .chain_update(hardware_uuid.trim().to_uppercase())
+        .finalize();
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Reads the hardware UUID, `IOPlatformUUID` from the I/O Registry.
+#[cfg(target_os = "macos")]
+fn hardware_uuid() -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+    parse_platform_uuid(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Reads the machine id systemd generates on install.
+#[cfg(target_os = "linux")]
+fn hardware_uuid() -> Option<String> {
+    let id = std::fs::read_to_string("/etc/machine-id").ok()?;
+    (!id.trim().is_empty()).then(|| id.trim().to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn hardware_uuid() -> Option<String> {
+    None
+}
+
+/// Finds the value of the `"IOPlatformUUID" = "…"` line in `ioreg` output.
+#[cfg(any(target_os = "macos", test))]
+fn parse_platform_uuid(ioreg_output: &str) -> Option<String> {
+    ioreg_output.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "\"IOPlatformUUID\"").then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_machine_id_is_stable() {
+        let uuid = "6A1B2C3D-1111-2222-3333-444455556666";
+        assert_eq!(hash_machine_id(uuid), hash_machine_id(uuid));
+        assert_eq!(
+            hash_machine_id(uuid),
+            hash_machine_id(&format!(" {}\n", uuid.to_lowercase()))
+        );
+        assert_eq!(hash_machine_id(uuid).len(), 64);
+    }
+
+    #[test]
+    fn test_machine_id_differs_per_machine_and_hides_uuid() {
+        let first = hash_machine_id("6A1B2C3D-1111-2222-3333-444455556666");
+        let second = hash_machine_id("6A1B2C3D-1111-2222-3333-444455556667");
+        assert_ne!(first, second);
+        assert!(!first.contains("6a1b2c3d"));
+    }
+
+    #[test]
+    fn test_hardware_uuid_is_stable_between_calls() {
+        assert_eq!(hardware_uuid(), hardware_uuid());
+    }
+
+    #[test]
+    fn test_parse_platform_uuid() {
+        let output = r#"+-o MacBookPro18,3  <class IOPlatformExpertDevice, id 0x100000110, registered, matched, active>
+    {
+      "IOPlatformSerialNumber" = "C02XXXXXXXXX"
+      "IOPlatformUUID" = "6A1B2C3D-1111-2222-3333-444455556666"
+      "model" = <"MacBookPro18,3">
+    }"#;
+        assert_eq!(
+            parse_platform_uuid(output),
+            Some("6A1B2C3D-1111-2222-3333-444455556666".to_string())
+        );
+        assert_eq!(parse_platform_uuid("no uuid here"), None);
+    }
+
+    #[test]
+    fn test_machine_id_prefers_hardware_uuid_over_stored_id() {
+        let hardware_id = hash_machine_id("6A1B2C3D-1111-2222-3333-444455556666");
+
+        assert_eq!(
+            current_machine_id(
+                Some("id-of-another-machine"),
+                Some("6A1B2C3D-1111-2222-3333-444455556666")
+            ),
+            hardware_id
+        );
+        assert_eq!(current_machine_id(Some("saved-id"), None), "saved-id");
+        assert_ne!(current_machine_id(None, None), current_machine_id(None, None));
+    }
+
+    #[test]
+    fn test_activation_count_grows_when_store_moves_to_another_machine() {
+        // Activated on the first machine
+        let first = current_machine_id(None, Some("first-machine"));
+        let mut machines = Vec::new();
+        add_machine(&mut machines, &first);
+
+        // The store, with the first machine's id, was migrated to a second machine
+        let second = current_machine_id(Some(&first), Some("second-machine"));
+        add_machine(&mut machines, &second);
+
+        assert_ne!(first, second);
+        assert_eq!(machines.len(), 2);
+    }
+
+    #[test]
+    fn test_activation_count_tracks_distinct_machines() {
+        let mut machines = Vec::new();
+        let first = hash_machine_id("first-machine");
+        let second = hash_machine_id("second-machine");
+
+        assert!(add_machine(&mut machines, &first));
+        assert!(!add_machine(&mut machines, &first));
+        assert_eq!(machines.len(), 1);
+
+        assert!(add_machine(&mut machines, &second));
+        assert!(!add_machine(&mut machines, &second));
+        assert_eq!(machines, vec![first, second]);
+    }
+}
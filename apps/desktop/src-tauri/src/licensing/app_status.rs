@@ -6,6 +6,7 @@
 //! - Caching for offline use (30-day grace period)
 //! - Mock mode for local testing
 
+use crate::licensing::activation::activation_count;
 use crate::licensing::verification::{LicenseInfo, get_license_info};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -45,6 +46,11 @@ pub enum AppStatus {
         license_type: LicenseType,
         organization_name: Option<String>,
         expires_at: Option<String>,
+        /// Distinct machines the license was activated on with this install's license store, which can be carried
+        /// to other machines with the app data. Only a warning sign, as each install only knows about its own store.
+        activation_count: u32,
+        /// From the license key. None means no limit.
+        max_activations: Option<u32>,
     },
     /// Expired commercial license - reverted to personal.
     #[serde(rename_all = "camelCase")]
@@ -144,7 +150,7 @@ pub async fn validate_license_async(app: &tauri::AppHandle) -> AppStatus {
             );
 
             // Return the new status based on the response
-            response_to_app_status(app, &resp)
+            response_to_app_status(app, &resp, &license_info)
         }
         None => {
             // Network error - fall back to cached status
@@ -158,6 +164,7 @@ pub async fn validate_license_async(app: &tauri::AppHandle) -> AppStatus {
 fn response_to_app_status(
     app: &tauri::AppHandle,
     resp: &crate::licensing::validation_client::ValidationResponse,
+    license_info: &LicenseInfo,
 ) -> AppStatus {
     let license_type = resp.license_type.as_deref().and_then(string_to_license_type);
 
@@ -168,6 +175,8 @@ fn response_to_app_status(
                 license_type: lt,
                 organization_name: resp.organization_name.clone(),
                 expires_at: resp.expires_at.clone(),
+                activation_count: activation_count(app),
+                max_activations: license_info.max_activations,
             },
             None => AppStatus::Personal,
         },
@@ -212,7 +221,7 @@ fn get_cached_or_validate(app: &tauri::AppHandle, license_info: &LicenseInfo) ->
         let cache_age = now.saturating_sub(cached.cached_at);
 
         if cache_age <= OFFLINE_GRACE_PERIOD_SECS {
-            return cached_to_app_status(app, &cached);
+            return cached_to_app_status(app, &cached, license_info);
         }
     }
 
@@ -227,7 +236,7 @@ fn get_cached_or_validate(app: &tauri::AppHandle, license_info: &LicenseInfo) ->
 }
 
 /// Convert cached status to AppStatus.
-fn cached_to_app_status(app: &tauri::AppHandle, cached: &CachedLicenseStatus) -> AppStatus {
+fn cached_to_app_status(app: &tauri::AppHandle, cached: &CachedLicenseStatus, license_info: &LicenseInfo) -> AppStatus {
     match cached.status.as_str() {
         "active" => match cached.license_type {
             Some(LicenseType::Supporter) => AppStatus::Supporter,
@@ -235,6 +244,8 @@ fn cached_to_app_status(app: &tauri::AppHandle, cached: &CachedLicenseStatus) ->
                 license_type: lt,
                 organization_name: cached.organization_name.clone(),
                 expires_at: cached.expires_at.clone(),
+                activation_count: activation_count(app),
+                max_activations: license_info.max_activations,
             },
             None => AppStatus::Personal,
         },
@@ -314,6 +325,7 @@ pub fn reset_license(app: &tauri::AppHandle) {
         store.delete(STORE_KEY_LAST_VALIDATION);
         store.delete(STORE_KEY_EXPIRATION_SHOWN);
     }
+    crate::licensing::activation::reset_activations(app);
 }
 
 #[cfg(not(debug_assertions))]
@@ -352,11 +364,15 @@ fn get_mock_status() -> Option<AppStatus> {
             license_type: LicenseType::CommercialSubscription,
             organization_name: Some("Test Corporation".to_string()),
             expires_at: Some("2027-01-10T00:00:00Z".to_string()),
+            activation_count: 1,
+            max_activations: Some(3),
         }),
         "perpetual" => Some(AppStatus::Commercial {
             license_type: LicenseType::CommercialPerpetual,
             organization_name: Some("Perpetual Inc.".to_string()),
             expires_at: None,
+            activation_count: 1,
+            max_activations: None,
         }),
         "expired" => Some(AppStatus::Expired {
             organization_name: Some("Expired Corp".to_string()),
@@ -394,6 +410,8 @@ mod tests {
             license_type: LicenseType::CommercialSubscription,
             organization_name: Some("Test Corp".to_string()),
             expires_at: Some("2027-01-01".to_string()),
+            activation_count: 1,
+            max_activations: None,
         };
         assert_eq!(get_window_title(&status), "Cmdr");
     }
@@ -404,6 +422,8 @@ mod tests {
             license_type: LicenseType::CommercialPerpetual,
             organization_name: None,
             expires_at: None,
+            activation_count: 1,
+            max_activations: None,
         };
         assert_eq!(get_window_title(&status), "Cmdr");
     }
@@ -444,12 +464,16 @@ mod tests {
             license_type: LicenseType::CommercialSubscription,
             organization_name: Some("Acme".to_string()),
             expires_at: Some("2027-01-01".to_string()),
+            activation_count: 4,
+            max_activations: Some(3),
         };
         let json = serde_json::to_string(&status).unwrap();
         assert!(json.contains("\"type\":\"commercial\""));
         // Fields are camelCase due to serde rename_all
         assert!(json.contains("Acme"));
         assert!(json.contains("commercial_subscription")); // LicenseType is snake_case
+        assert!(json.contains("\"activationCount\":4"));
+        assert!(json.contains("\"maxActivations\":3"));
     }
 
     #[test]
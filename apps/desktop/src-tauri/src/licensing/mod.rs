@@ -3,6 +3,7 @@
 //! Uses Ed25519 signatures for offline license validation.
 //! The public key is embedded at compile time.
 
+mod activation;
 mod app_status;
 mod validation_client;
 mod verification;
@@ -23,4 +24,10 @@ pub struct LicenseData {
     pub issued_at: String,
     #[serde(rename = "type")]
     pub license_type: Option<String>,
+    /// RFC 3339 date after which the license is no longer valid. Licenses without it are perpetual.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// How many machines the license may be used on. None means no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_activations: Option<u32>,
 }
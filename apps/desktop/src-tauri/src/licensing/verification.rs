@@ -1,9 +1,13 @@
 //! License key verification using Ed25519 signatures.
 
 use crate::licensing::LicenseData;
+use crate::licensing::activation::record_activation;
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use tauri_plugin_store::StoreExt;
 
 // Ed25519 public key (32 bytes, hex-encoded).
@@ -14,6 +18,13 @@ const PUBLIC_KEY_HEX: &str = "c3b18e765fc5c74f9fb7f3a9869d14c6bdeda1f28ec85aa618
 
 const STORE_KEY_LICENSE: &str = "license_key";
 
+/// How long the last verified license keeps the app activated while the stored key can't be read (24 hours).
+const UNREADABLE_KEY_GRACE_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The last license that passed signature verification. Bridges transient failures to open the store, like lock
+/// contention, so the app doesn't flip to unlicensed for a moment.
+static LAST_VERIFIED_LICENSE: LazyLock<Mutex<Option<VerifiedLicense>>> = LazyLock::new(|| Mutex::new(None));
+
 /// Information about the current license.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +32,34 @@ pub struct LicenseInfo {
     pub email: String,
     pub transaction_id: String,
     pub issued_at: String,
+    /// How many machines the license may be used on, see `activation_count`. None means no limit.
+    pub max_activations: Option<u32>,
+}
+
+impl From<LicenseData> for LicenseInfo {
+    fn from(data: LicenseData) -> Self {
+        Self {
+            email: data.email,
+            transaction_id: data.transaction_id,
+            issued_at: data.issued_at,
+            max_activations: data.max_activations,
+        }
+    }
+}
+
+/// A license that passed verification, and when. Uses `Instant` so clock changes don't stretch the grace period.
+#[derive(Debug, Clone)]
+struct VerifiedLicense {
+    info: LicenseInfo,
+    verified_at: Instant,
+}
+
+/// The result of reading the license key from the store.
+enum StoredKey {
+    Key(String),
+    Missing,
+    /// The store couldn't be opened, which may be temporary
+    Unreadable,
 }
 
 /// Activate a license key. Returns the license info if valid.
@@ -34,24 +73,64 @@ pub fn activate_license(app: &tauri::AppHandle, license_key: &str) -> Result<Lic
         .map_err(|e| format!("Failed to open store: {}", e))?;
 
     store.set(STORE_KEY_LICENSE, serde_json::json!(license_key));
-
-    Ok(LicenseInfo {
-        email: data.email,
-        transaction_id: data.transaction_id,
-        issued_at: data.issued_at,
-    })
+    record_activation(app);
+
+    let info = LicenseInfo::from(data);
+    if let Ok(mut last_verified) = LAST_VERIFIED_LICENSE.lock() {
+        *last_verified = Some(VerifiedLicense {
+            info: info.clone(),
+            verified_at: Instant::now(),
+        });
+    }
+    Ok(info)
 }
 
 /// Get stored license info, if any.
+///
+/// The signature is checked on every call whenever the key can be read. Only if the store can't be opened does
+/// this fall back to the last verified license, for up to `UNREADABLE_KEY_GRACE_PERIOD`.
 pub fn get_license_info(app: &tauri::AppHandle) -> Option<LicenseInfo> {
-    let store = app.store("license.json").ok()?;
-    let license_key = store.get(STORE_KEY_LICENSE)?.as_str()?.to_string();
-
-    validate_license_key(&license_key).ok().map(|data| LicenseInfo {
-        email: data.email,
-        transaction_id: data.transaction_id,
-        issued_at: data.issued_at,
-    })
+    let stored = read_stored_key(app);
+    let mut last_verified = LAST_VERIFIED_LICENSE.lock().ok()?;
+    resolve_license_info(stored, PUBLIC_KEY_HEX, &mut last_verified, Instant::now())
+}
+
+/// Reads the license key from the store. A value that isn't a string counts as an (invalid) key.
+fn read_stored_key(app: &tauri::AppHandle) -> StoredKey {
+    let Ok(store) = app.store("license.json") else {
+        return StoredKey::Unreadable;
+    };
+    match store.get(STORE_KEY_LICENSE) {
+        Some(value) => StoredKey::Key(value.as_str().unwrap_or_default().to_string()),
+        None => StoredKey::Missing,
+    }
+}
+
+/// Decides the license info from what the store gave, updating `last_verified`.
+/// A readable key is always verified, and a missing or invalid one forgets the last verified license.
+fn resolve_license_info(
+    stored: StoredKey,
+    public_key_hex: &str,
+    last_verified: &mut Option<VerifiedLicense>,
+    now: Instant,
+) -> Option<LicenseInfo> {
+    match stored {
+        StoredKey::Key(license_key) => {
+            let info = validate_license_key_with_public_key(&license_key, public_key_hex)
+                .ok()
+                .map(LicenseInfo::from);
+            *last_verified = info.clone().map(|info| VerifiedLicense { info, verified_at: now });
+            info
+        }
+        StoredKey::Missing => {
+            *last_verified = None;
+            None
+        }
+        StoredKey::Unreadable => last_verified
+            .as_ref()
+            .filter(|verified| now.saturating_duration_since(verified.verified_at) < UNREADABLE_KEY_GRACE_PERIOD)
+            .map(|verified| verified.info.clone()),
+    }
 }
 
 /// Validate a license key and extract the data.
@@ -98,9 +177,24 @@ fn validate_license_key_with_public_key(license_key: &str, public_key_hex: &str)
     let data: LicenseData =
         serde_json::from_slice(&payload_bytes).map_err(|_| "Invalid license key: bad payload data")?;
 
+    check_expiry(data.expires_at.as_deref(), Utc::now())?;
+
     Ok(data)
 }
 
+/// Fails with "License expired" if `expires_at` is in the past at `now`. No expiry means a perpetual license.
+fn check_expiry(expires_at: Option<&str>, now: DateTime<Utc>) -> Result<(), String> {
+    let Some(expires_at) = expires_at else {
+        return Ok(());
+    };
+    let expires_at =
+        DateTime::parse_from_rfc3339(expires_at).map_err(|_| "Invalid license key: bad expiry date".to_string())?;
+    if expires_at <= now {
+        return Err("License expired".to_string());
+    }
+    Ok(())
+}
+
 fn hex_decode(hex: &str) -> Result<Vec<u8>, ()> {
     if !hex.len().is_multiple_of(2) {
         return Err(());
@@ -188,6 +282,8 @@ mod tests {
             transaction_id: "txn_test_123".to_string(),
             issued_at: "2026-01-08T12:00:00Z".to_string(),
             license_type: None,
+            expires_at: None,
+            max_activations: None,
         };
 
         // Serialize payload (same as server)
@@ -228,6 +324,8 @@ mod tests {
             transaction_id: "txn_original".to_string(),
             issued_at: "2026-01-08T12:00:00Z".to_string(),
             license_type: None,
+            expires_at: None,
+            max_activations: None,
         };
         let original_json = serde_json::to_string(&original_data).unwrap();
         let signature = signing_key.sign(original_json.as_bytes());
@@ -239,6 +337,8 @@ mod tests {
             transaction_id: "txn_original".to_string(),
             issued_at: "2026-01-08T12:00:00Z".to_string(),
             license_type: None,
+            expires_at: None,
+            max_activations: None,
         };
         let tampered_json = serde_json::to_string(&tampered_data).unwrap();
         let tampered_payload_base64 = BASE64.encode(tampered_json.as_bytes());
@@ -251,6 +351,149 @@ mod tests {
         assert!(result.unwrap_err().contains("signature verification failed"));
     }
 
+    /// Creates a key pair and a license key signed with it. Returns the license key and the public key in hex.
+    fn make_signed_license(email: &str) -> (String, String) {
+        make_signed_license_expiring(email, None)
+    }
+
+    /// Like `make_signed_license`, with the given expiry date.
+    fn make_signed_license_expiring(email: &str, expires_at: Option<&str>) -> (String, String) {
+        use ed25519_dalek::{Signer, SigningKey};
+        use rand_core::OsRng;
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let public_key_hex: String = signing_key
+            .verifying_key()
+            .as_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let license_data = crate::licensing::LicenseData {
+            email: email.to_string(),
+            transaction_id: "txn_test".to_string(),
+            issued_at: "2026-01-08T12:00:00Z".to_string(),
+            license_type: None,
+            expires_at: expires_at.map(str::to_string),
+            max_activations: None,
+        };
+        let payload_json = serde_json::to_string(&license_data).unwrap();
+        let signature = signing_key.sign(payload_json.as_bytes());
+        let license_key = format!(
+            "{}.{}",
+            BASE64.encode(payload_json.as_bytes()),
+            BASE64.encode(signature.to_bytes())
+        );
+        (license_key, public_key_hex)
+    }
+
+    #[test]
+    fn test_unreadable_store_uses_last_verified_license() {
+        let (license_key, public_key_hex) = make_signed_license("cached@example.com");
+        let mut last_verified = None;
+        let start = Instant::now();
+
+        let info = resolve_license_info(StoredKey::Key(license_key), &public_key_hex, &mut last_verified, start);
+        assert_eq!(info.unwrap().email, "cached@example.com");
+
+        let later = start + Duration::from_secs(60 * 60);
+        let info = resolve_license_info(StoredKey::Unreadable, &public_key_hex, &mut last_verified, later);
+        assert_eq!(info.unwrap().email, "cached@example.com");
+    }
+
+    #[test]
+    fn test_unreadable_store_after_grace_period_is_unlicensed() {
+        let (license_key, public_key_hex) = make_signed_license("cached@example.com");
+        let mut last_verified = None;
+        let start = Instant::now();
+        resolve_license_info(StoredKey::Key(license_key), &public_key_hex, &mut last_verified, start);
+
+        let later = start + UNREADABLE_KEY_GRACE_PERIOD;
+        let info = resolve_license_info(StoredKey::Unreadable, &public_key_hex, &mut last_verified, later);
+        assert!(info.is_none());
+    }
+
+    #[test]
+    fn test_unreadable_store_without_verified_license_is_unlicensed() {
+        let mut last_verified = None;
+        let info = resolve_license_info(
+            StoredKey::Unreadable,
+            PUBLIC_KEY_HEX,
+            &mut last_verified,
+            Instant::now(),
+        );
+        assert!(info.is_none());
+    }
+
+    #[test]
+    fn test_invalid_key_forgets_last_verified_license() {
+        let (license_key, public_key_hex) = make_signed_license("cached@example.com");
+        let mut last_verified = None;
+        let now = Instant::now();
+        resolve_license_info(StoredKey::Key(license_key), &public_key_hex, &mut last_verified, now);
+
+        // A readable key is authoritative, even right after a successful verification
+        let tampered = StoredKey::Key("YWJj.YWJj".to_string());
+        assert!(resolve_license_info(tampered, &public_key_hex, &mut last_verified, now).is_none());
+        assert!(last_verified.is_none());
+        assert!(resolve_license_info(StoredKey::Unreadable, &public_key_hex, &mut last_verified, now).is_none());
+    }
+
+    #[test]
+    fn test_removed_key_forgets_last_verified_license() {
+        let (license_key, public_key_hex) = make_signed_license("cached@example.com");
+        let mut last_verified = None;
+        let now = Instant::now();
+        resolve_license_info(StoredKey::Key(license_key), &public_key_hex, &mut last_verified, now);
+
+        assert!(resolve_license_info(StoredKey::Missing, &public_key_hex, &mut last_verified, now).is_none());
+        assert!(resolve_license_info(StoredKey::Unreadable, &public_key_hex, &mut last_verified, now).is_none());
+    }
+
+    #[test]
+    fn test_license_with_future_expiry_is_valid() {
+        let (license_key, public_key_hex) =
+            make_signed_license_expiring("sub@example.com", Some("2999-01-01T00:00:00Z"));
+
+        let data = validate_license_key_with_public_key(&license_key, &public_key_hex).unwrap();
+        assert_eq!(data.expires_at.as_deref(), Some("2999-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_license_with_past_expiry_is_rejected() {
+        let (license_key, public_key_hex) =
+            make_signed_license_expiring("sub@example.com", Some("2020-01-01T00:00:00Z"));
+
+        let result = validate_license_key_with_public_key(&license_key, &public_key_hex);
+        assert_eq!(result.unwrap_err(), "License expired");
+    }
+
+    #[test]
+    fn test_license_without_expiry_is_perpetual() {
+        let (license_key, public_key_hex) = make_signed_license("perpetual@example.com");
+
+        let data = validate_license_key_with_public_key(&license_key, &public_key_hex).unwrap();
+        assert!(data.expires_at.is_none());
+        assert!(check_expiry(None, DateTime::<Utc>::MAX_UTC).is_ok());
+    }
+
+    #[test]
+    fn test_check_expiry_boundary_and_time_zones() {
+        let now = DateTime::parse_from_rfc3339("2026-06-01T12:00:00Z").unwrap().to_utc();
+
+        assert_eq!(
+            check_expiry(Some("2026-06-01T12:00:00Z"), now).unwrap_err(),
+            "License expired"
+        );
+        // Same instant as 11:00 UTC, so already past
+        assert!(check_expiry(Some("2026-06-01T13:00:00+02:00"), now).is_err());
+        assert!(check_expiry(Some("2026-06-01T13:00:00Z"), now).is_ok());
+        assert!(
+            check_expiry(Some("next tuesday"), now)
+                .unwrap_err()
+                .contains("bad expiry date")
+        );
+    }
+
     /// Test that wrong public key rejects valid license
     #[test]
     fn test_wrong_public_key_rejects_license() {
@@ -273,6 +516,8 @@ mod tests {
             transaction_id: "txn_test".to_string(),
             issued_at: "2026-01-08T12:00:00Z".to_string(),
             license_type: None,
+            expires_at: None,
+            max_activations: None,
         };
         let payload_json = serde_json::to_string(&license_data).unwrap();
         let signature = signing_key.sign(payload_json.as_bytes());
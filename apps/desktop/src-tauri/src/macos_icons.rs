@@ -17,11 +17,15 @@ use core_services::{
     UTTypeCreatePreferredIdentifierForTag, kLSRolesAll, kUTTagClassFilenameExtension,
 };
 use image::DynamicImage;
+use objc2::msg_send;
+use objc2::rc::Retained;
+use objc2::runtime::{AnyClass, AnyObject};
+use objc2_foundation::NSString;
 use plist::Value;
 use std::path::{Path, PathBuf};
 
 /// Gets the UTI (Uniform Type Identifier) for a file extension.
-fn get_uti_for_extension(ext: &str) -> Option<CFString> {
+pub(crate) fn get_uti_for_extension(ext: &str) -> Option<CFString> {
     let tag = CFString::new(ext);
 
     unsafe {
@@ -40,7 +44,7 @@ fn get_uti_for_extension(ext: &str) -> Option<CFString> {
 }
 
 /// Gets the default application bundle ID for a content type (UTI).
-fn get_default_app_bundle_id(uti: &CFString) -> Option<CFString> {
+pub(crate) fn get_default_app_bundle_id(uti: &CFString) -> Option<CFString> {
     unsafe {
         let bundle_id_ref = LSCopyDefaultRoleHandlerForContentType(uti.as_concrete_TypeRef(), kLSRolesAll);
 
@@ -52,8 +56,15 @@ fn get_default_app_bundle_id(uti: &CFString) -> Option<CFString> {
     }
 }
 
+/// Gets the bundle ID of the app that currently opens files with the given extension by default.
+/// Returns `None` if the extension has no UTI or no registered handler.
+pub fn get_default_app_bundle_id_for_extension(ext: &str) -> Option<String> {
+    let uti = get_uti_for_extension(ext)?;
+    get_default_app_bundle_id(&uti).map(|bundle_id| bundle_id.to_string())
+}
+
 /// Gets the application URL for a bundle identifier.
-fn get_app_url_for_bundle_id(bundle_id: &CFString) -> Option<PathBuf> {
+pub(crate) fn get_app_url_for_bundle_id(bundle_id: &CFString) -> Option<PathBuf> {
     unsafe {
         let urls_ref = LSCopyApplicationURLsForBundleIdentifier(bundle_id.as_concrete_TypeRef(), std::ptr::null_mut());
 
@@ -130,38 +141,46 @@ fn get_document_icon_name_from_bundle(app_path: &Path, uti: &str) -> Option<Stri
 
 /// Loads an ICNS icon file and converts it to a DynamicImage.
 /// Uses the `icns` crate which properly parses macOS icon format.
-fn load_icns_icon(icon_path: &Path) -> Option<DynamicImage> {
+/// Picks the smallest representation that is at least `size` pixels wide, so that the later resize only ever
+/// scales down. Falls back to the largest available representation if none is big enough.
+fn load_icns_icon(icon_path: &Path, size: u32) -> Option<DynamicImage> {
     let file = std::fs::File::open(icon_path).ok()?;
     let icon_family = icns::IconFamily::read(file).ok()?;
 
-    // Get the largest available icon (prefer 256x256 or 512x512)
+    // Ordered from smallest to largest
     let icon_types = [
-        icns::IconType::RGBA32_512x512,
-        icns::IconType::RGBA32_256x256,
-        icns::IconType::RGBA32_128x128,
-        icns::IconType::RGBA32_64x64,
+        icns::IconType::RGBA32_16x16,
         icns::IconType::RGBA32_32x32,
+        icns::IconType::RGBA32_64x64,
+        icns::IconType::RGBA32_128x128,
+        icns::IconType::RGBA32_256x256,
+        icns::IconType::RGBA32_512x512,
     ];
-
-    for icon_type in icon_types {
-        if let Ok(icon_image) = icon_family.get_icon_with_type(icon_type) {
-            // Convert icns::Image to image::DynamicImage
-            let width = icon_image.width();
-            let height = icon_image.height();
-            let pixels = icon_image.into_data();
-
-            if let Some(img) = image::RgbaImage::from_raw(width, height, pixels.to_vec()) {
-                return Some(DynamicImage::ImageRgba8(img));
-            }
-        }
-    }
-
-    None
+    let available: Vec<icns::IconType> = icon_types
+        .into_iter()
+        .filter(|t| icon_family.has_icon_with_type(*t))
+        .collect();
+    let icon_type = available
+        .iter()
+        .find(|t| t.pixel_width() >= size)
+        .or_else(|| available.last())?;
+
+    let icon_image = icon_family.get_icon_with_type(*icon_type).ok()?;
+    // Convert icns::Image to image::DynamicImage
+    let width = icon_image.width();
+    let height = icon_image.height();
+    let pixels = icon_image.into_data();
+
+    image::RgbaImage::from_raw(width, height, pixels.to_vec()).map(DynamicImage::ImageRgba8)
 }
 
 /// Fetches the icon for a file extension directly from the default app's bundle.
 /// This bypasses the Launch Services icon cache.
-pub fn fetch_fresh_icon_for_extension(ext: &str) -> Option<DynamicImage> {
+///
+/// # Arguments
+/// * `ext` - File extension without the dot, for example "pdf"
+/// * `size` - Target pixel size, used to pick the closest representation in the ICNS file
+pub fn fetch_fresh_icon_for_extension(ext: &str, size: u32) -> Option<DynamicImage> {
     // 1. Get UTI for extension
     let uti = get_uti_for_extension(ext)?;
     let uti_str = uti.to_string();
@@ -185,7 +204,32 @@ pub fn fetch_fresh_icon_for_extension(ext: &str) -> Option<DynamicImage> {
     let icon_path = app_path.join("Contents/Resources").join(&icon_filename);
 
     // 6. Load and return the icon
-    load_icns_icon(&icon_path)
+    load_icns_icon(&icon_path, size)
+}
+
+/// Runs `f` with `NSAppearance.currentAppearance` set to Aqua or Dark Aqua on the current thread.
+/// Icons drawn by AppKit inside `f` (for example, folder icons) are rendered for that appearance.
+/// The previous appearance is restored afterward.
+pub fn with_drawing_appearance<T>(dark: bool, f: impl FnOnce() -> T) -> T {
+    let Some(appearance_class) = AnyClass::get(c"NSAppearance") else {
+        return f();
+    };
+    // The values of the NSAppearanceName constants are the same as their symbol names
+    let name = NSString::from_str(if dark {
+        "NSAppearanceNameDarkAqua"
+    } else {
+        "NSAppearanceNameAqua"
+    });
+    let appearance: Option<Retained<AnyObject>> = unsafe { msg_send![appearance_class, appearanceNamed: &*name] };
+    let Some(appearance) = appearance else {
+        return f();
+    };
+
+    let previous: Option<Retained<AnyObject>> = unsafe { msg_send![appearance_class, currentAppearance] };
+    let _: () = unsafe { msg_send![appearance_class, setCurrentAppearance: &*appearance] };
+    let result = f();
+    let _: () = unsafe { msg_send![appearance_class, setCurrentAppearance: previous.as_deref()] };
+    result
 }
 
 #[cfg(test)]
@@ -214,7 +258,7 @@ mod tests {
     #[test]
     fn test_fetch_fresh_icon() {
         // Try a common extension
-        let icon = fetch_fresh_icon_for_extension("pdf");
+        let icon = fetch_fresh_icon_for_extension("pdf", crate::config::ICON_SIZE);
         // This might fail if no PDF reader is installed, which is fine
         if let Some(img) = icon {
             println!("Got PDF icon: {}x{}", img.width(), img.height());
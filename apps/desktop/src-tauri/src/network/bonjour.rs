@@ -59,7 +59,7 @@ unsafe impl Send for BonjourManager {}
 /// Global app handle for sending events.
 static APP_HANDLE: OnceLock<Mutex<Option<AppHandle>>> = OnceLock::new();
 
-fn get_app_handle() -> Option<AppHandle> {
+pub(super) fn get_app_handle() -> Option<AppHandle> {
     APP_HANDLE
         .get()
         .and_then(|m| m.lock().ok())
@@ -462,8 +462,7 @@ pub fn start_discovery(app_handle: AppHandle) {
     });
 }
 
-/// Stops Bonjour discovery.
-#[allow(dead_code)]
+/// Stops Bonjour discovery. Does nothing if it's not running.
 pub fn stop_discovery() {
     let mut manager_guard = get_bonjour_manager().lock().unwrap();
 
@@ -10,6 +10,9 @@ use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::Manager;
 
+/// How many connection times are kept per share in `recent_connections`.
+const MAX_RECENT_CONNECTIONS: usize = 10;
+
 /// Connection mode used for the last successful connection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -50,6 +53,12 @@ pub struct KnownNetworkShare {
     pub last_known_auth_options: AuthOptions,
     /// Username used (None for guest).
     pub username: Option<String>,
+    /// How many times we've successfully connected.
+    #[serde(default)]
+    pub connect_count: u32,
+    /// When we last connected (ISO 8601), oldest first, at most `MAX_RECENT_CONNECTIONS`.
+    #[serde(default)]
+    pub recent_connections: Vec<String>,
 }
 
 /// The known shares store, persisted to disk.
@@ -154,40 +163,93 @@ pub fn get_known_shares_for_server(server_name: &str) -> Vec<KnownNetworkShare>
 }
 
 /// Updates or adds a known network share.
-/// Called after a successful connection.
+/// Called after a successful connection, at `share.last_connected_at`, which is added to the connection history.
 pub fn update_known_share<R: tauri::Runtime>(app: &tauri::AppHandle<R>, share: KnownNetworkShare) {
-    let key = share_key(&share.server_name, &share.share_name);
+    if let Ok(mut cache) = get_known_shares_mutex().lock() {
+        record_connection(&mut cache, share);
+    }
 
+    save_known_shares(app);
+}
+
+/// Adds a known network share as is, with its connection history, replacing any existing one.
+/// For restoring shares from a config export, which aren't new connections.
+pub fn restore_known_share<R: tauri::Runtime>(app: &tauri::AppHandle<R>, share: KnownNetworkShare) {
     if let Ok(mut cache) = get_known_shares_mutex().lock() {
-        // Find and update, or add new
-        if let Some(existing) = cache
-            .known_network_shares
-            .iter_mut()
-            .find(|s| share_key(&s.server_name, &s.share_name) == key)
-        {
-            *existing = share;
-        } else {
-            cache.known_network_shares.push(share);
-        }
+        upsert(&mut cache, share);
     }
 
     save_known_shares(app);
 }
 
+/// Updates or adds `share`, counting the connection and keeping the history of the one it replaces.
+fn record_connection(store: &mut KnownSharesStore, mut share: KnownNetworkShare) {
+    let key = share_key(&share.server_name, &share.share_name);
+    let previous = store
+        .known_network_shares
+        .iter()
+        .find(|s| share_key(&s.server_name, &s.share_name) == key);
+    let (connect_count, mut recent_connections) = match previous {
+        Some(previous) => (previous.connect_count, previous.recent_connections.clone()),
+        None => (0, Vec::new()),
+    };
+
+    recent_connections.push(share.last_connected_at.clone());
+    let excess = recent_connections.len().saturating_sub(MAX_RECENT_CONNECTIONS);
+    recent_connections.drain(..excess);
+    share.connect_count = connect_count.saturating_add(1);
+    share.recent_connections = recent_connections;
+
+    upsert(store, share);
+}
+
+/// Replaces the share with the same server and share name, or adds it.
+fn upsert(store: &mut KnownSharesStore, share: KnownNetworkShare) {
+    let key = share_key(&share.server_name, &share.share_name);
+    // Find and update, or add new
+    if let Some(existing) = store
+        .known_network_shares
+        .iter_mut()
+        .find(|s| share_key(&s.server_name, &s.share_name) == key)
+    {
+        *existing = share;
+    } else {
+        store.known_network_shares.push(share);
+    }
+}
+
 /// Removes a known network share.
-#[allow(dead_code)] // Will be used when implementing share removal UI
 pub fn remove_known_share<R: tauri::Runtime>(app: &tauri::AppHandle<R>, server_name: &str, share_name: &str) {
-    let key = share_key(server_name, share_name);
+    if let Ok(mut cache) = get_known_shares_mutex().lock() {
+        remove_share(&mut cache, server_name, share_name);
+    }
 
+    save_known_shares(app);
+}
+
+/// Removes all known shares of a server, including the server-level one.
+pub fn remove_known_shares_for_server<R: tauri::Runtime>(app: &tauri::AppHandle<R>, server_name: &str) {
     if let Ok(mut cache) = get_known_shares_mutex().lock() {
-        cache
-            .known_network_shares
-            .retain(|s| share_key(&s.server_name, &s.share_name) != key);
+        remove_server(&mut cache, server_name);
     }
 
     save_known_shares(app);
 }
 
+fn remove_share(store: &mut KnownSharesStore, server_name: &str, share_name: &str) {
+    let key = share_key(server_name, share_name);
+    store
+        .known_network_shares
+        .retain(|s| share_key(&s.server_name, &s.share_name) != key);
+}
+
+fn remove_server(store: &mut KnownSharesStore, server_name: &str) {
+    let server_lower = server_name.to_lowercase();
+    store
+        .known_network_shares
+        .retain(|s| s.server_name.to_lowercase() != server_lower);
+}
+
 /// Builds a map of server names to their last known usernames.
 /// Useful for pre-filling login forms.
 pub fn get_username_hints() -> HashMap<String, String> {
@@ -211,6 +273,20 @@ pub fn get_username_hints() -> HashMap<String, String> {
 mod tests {
     use super::*;
 
+    fn sample_share(server_name: &str, share_name: &str, connected_at: &str) -> KnownNetworkShare {
+        KnownNetworkShare {
+            server_name: server_name.to_string(),
+            share_name: share_name.to_string(),
+            protocol: "smb".to_string(),
+            last_connected_at: connected_at.to_string(),
+            last_connection_mode: ConnectionMode::Guest,
+            last_known_auth_options: AuthOptions::GuestOnly,
+            username: None,
+            connect_count: 0,
+            recent_connections: Vec::new(),
+        }
+    }
+
     #[test]
     fn test_share_key() {
         assert_eq!(share_key("MyNAS", "Documents"), "mynas/documents");
@@ -250,6 +326,8 @@ mod tests {
             last_connection_mode: ConnectionMode::Credentials,
             last_known_auth_options: AuthOptions::GuestOrCredentials,
             username: Some("david".to_string()),
+            connect_count: 0,
+            recent_connections: Vec::new(),
         };
 
         let json = serde_json::to_string_pretty(&share).unwrap();
@@ -266,6 +344,74 @@ mod tests {
         assert_eq!(parsed.last_connection_mode, ConnectionMode::Credentials);
     }
 
+    #[test]
+    fn test_connection_history_round_trip() {
+        let share = KnownNetworkShare {
+            connect_count: 23,
+            recent_connections: vec!["2026-01-05T09:00:00Z".to_string(), "2026-01-06T10:00:00Z".to_string()],
+            ..sample_share("Alpha", "Documents", "2026-01-06T10:00:00Z")
+        };
+
+        let json = serde_json::to_string(&share).unwrap();
+        assert!(json.contains(r#""connectCount":23"#));
+        assert!(json.contains(r#""recentConnections":["2026-01-05T09:00:00Z","2026-01-06T10:00:00Z"]"#));
+
+        let parsed: KnownNetworkShare = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.connect_count, 23);
+        assert_eq!(parsed.recent_connections, share.recent_connections);
+    }
+
+    #[test]
+    fn test_share_without_history_loads() {
+        // Stores saved before connection history was tracked
+        let json = r#"{
+            "serverName": "Alpha",
+            "shareName": "Documents",
+            "protocol": "smb",
+            "lastConnectedAt": "2026-01-03T21:00:00Z",
+            "lastConnectionMode": "guest",
+            "lastKnownAuthOptions": "guest_only",
+            "username": null
+        }"#;
+
+        let parsed: KnownNetworkShare = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.connect_count, 0);
+        assert!(parsed.recent_connections.is_empty());
+    }
+
+    #[test]
+    fn test_recording_connections_counts_them() {
+        let mut store = KnownSharesStore::default();
+        record_connection(&mut store, sample_share("Alpha", "Documents", "2026-01-05T09:00:00Z"));
+        record_connection(&mut store, sample_share("alpha", "documents", "2026-01-06T10:00:00Z"));
+
+        assert_eq!(store.known_network_shares.len(), 1);
+        let share = &store.known_network_shares[0];
+        assert_eq!(share.connect_count, 2);
+        assert_eq!(share.last_connected_at, "2026-01-06T10:00:00Z");
+        assert_eq!(
+            share.recent_connections,
+            vec!["2026-01-05T09:00:00Z", "2026-01-06T10:00:00Z"]
+        );
+    }
+
+    #[test]
+    fn test_recent_connections_are_capped() {
+        let mut store = KnownSharesStore::default();
+        for day in 1..=25 {
+            let connected_at = format!("2026-01-{:02}T12:00:00Z", day);
+            record_connection(&mut store, sample_share("Alpha", "Documents", &connected_at));
+            assert!(store.known_network_shares[0].recent_connections.len() <= MAX_RECENT_CONNECTIONS);
+        }
+
+        let share = &store.known_network_shares[0];
+        assert_eq!(share.connect_count, 25);
+        assert_eq!(share.recent_connections.len(), MAX_RECENT_CONNECTIONS);
+        // The oldest ones are dropped
+        assert_eq!(share.recent_connections[0], "2026-01-16T12:00:00Z");
+        assert_eq!(share.recent_connections[9], "2026-01-25T12:00:00Z");
+    }
+
     #[test]
     fn test_store_serialization() {
         let store = KnownSharesStore {
@@ -278,6 +424,8 @@ mod tests {
                     last_connection_mode: ConnectionMode::Credentials,
                     last_known_auth_options: AuthOptions::GuestOrCredentials,
                     username: Some("david".to_string()),
+                    connect_count: 0,
+                    recent_connections: Vec::new(),
                 },
                 KnownNetworkShare {
                     server_name: "Bravo".to_string(),
@@ -287,6 +435,8 @@ mod tests {
                     last_connection_mode: ConnectionMode::Guest,
                     last_known_auth_options: AuthOptions::GuestOnly,
                     username: None,
+                    connect_count: 0,
+                    recent_connections: Vec::new(),
                 },
             ],
         };
@@ -323,6 +473,8 @@ mod tests {
                 last_connection_mode: ConnectionMode::Guest,
                 last_known_auth_options: AuthOptions::GuestOnly,
                 username: None,
+                connect_count: 0,
+                recent_connections: Vec::new(),
             });
         }
 
@@ -345,6 +497,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_removing_shares() {
+        let mut store = KnownSharesStore {
+            known_network_shares: vec![
+                sample_share("Alpha", "", "2026-01-06T12:00:00Z"),
+                sample_share("Alpha", "Documents", "2026-01-06T12:00:00Z"),
+                sample_share("Alpha", "Media", "2026-01-06T12:00:00Z"),
+                sample_share("Bravo", "Documents", "2026-01-06T12:00:00Z"),
+            ],
+        };
+        let names = |store: &KnownSharesStore| -> Vec<String> {
+            store
+                .known_network_shares
+                .iter()
+                .map(|s| share_key(&s.server_name, &s.share_name))
+                .collect()
+        };
+
+        // Only the one share goes, matched case-insensitively
+        remove_share(&mut store, "alpha", "DOCUMENTS");
+        assert_eq!(names(&store), vec!["alpha/", "alpha/media", "bravo/documents"]);
+
+        // Removing an unknown share changes nothing
+        remove_share(&mut store, "Charlie", "Documents");
+        assert_eq!(store.known_network_shares.len(), 3);
+
+        // All of the server's shares go, but not other servers'
+        remove_server(&mut store, "ALPHA");
+        assert_eq!(names(&store), vec!["bravo/documents"]);
+    }
+
     #[test]
     fn test_username_hints() {
         let cache = get_known_shares_mutex();
@@ -360,6 +543,8 @@ mod tests {
                 last_connection_mode: ConnectionMode::Credentials,
                 last_known_auth_options: AuthOptions::CredentialsOnly,
                 username: Some("alice".to_string()),
+                connect_count: 0,
+                recent_connections: Vec::new(),
             });
             c.known_network_shares.push(KnownNetworkShare {
                 server_name: "Server2".to_string(),
@@ -369,6 +554,8 @@ mod tests {
                 last_connection_mode: ConnectionMode::Guest,
                 last_known_auth_options: AuthOptions::GuestOnly,
                 username: None,
+                connect_count: 0,
+                recent_connections: Vec::new(),
             });
         }
 
@@ -16,7 +16,8 @@ use std::sync::{Mutex, OnceLock};
 use tauri::{AppHandle, Emitter};
 
 pub use bonjour::start_discovery;
-pub use smb_client::{AuthMode, ShareListError, ShareListResult};
+use bonjour::stop_discovery;
+pub use smb_client::{AuthMode, ConnectionTestResult, ShareListError, ShareListResult};
 
 /// Injects Docker SMB test hosts for QA testing if enabled.
 /// Call this after `start_discovery()` in dev mode.
@@ -79,6 +80,19 @@ impl Default for NetworkDiscoveryState {
     }
 }
 
+impl NetworkDiscoveryState {
+    /// Forgets all hosts and goes idle. Returns the IDs of the hosts it forgot.
+    fn reset(&mut self) -> Vec<String> {
+        self.state = DiscoveryState::Idle;
+        self.hosts.drain().map(|(id, _)| id).collect()
+    }
+
+    /// Adds a host, or updates it if it's already known. Returns whether it's new.
+    fn add_host(&mut self, host: NetworkHost) -> bool {
+        self.hosts.insert(host.id.clone(), host).is_none()
+    }
+}
+
 /// Global discovery state, protected by a mutex.
 static DISCOVERY_STATE: OnceLock<Mutex<NetworkDiscoveryState>> = OnceLock::new();
 
@@ -98,11 +112,32 @@ pub fn get_discovery_state_value() -> DiscoveryState {
     state.state
 }
 
+/// Stops discovery and forgets the discovered hosts, so stale ones disappear from the sidebar.
+/// Must be called on the main thread. Does nothing harmful if discovery isn't running.
+pub fn stop_network_discovery(app_handle: &AppHandle) {
+    stop_discovery();
+
+    let lost_ids = get_discovery_state().lock().unwrap().reset();
+    for host_id in lost_ids {
+        let _ = app_handle.emit("network-host-lost", serde_json::json!({ "id": host_id }));
+    }
+    on_discovery_state_changed(DiscoveryState::Idle, app_handle);
+    info!("Network discovery stopped");
+}
+
+/// Restarts discovery from scratch, with an empty host list. Safe to call repeatedly.
+/// Must be called on the main thread.
+pub fn restart_network_discovery(app_handle: AppHandle) {
+    stop_network_discovery(&app_handle);
+    start_discovery(app_handle.clone());
+    inject_test_hosts_if_enabled(&app_handle);
+}
+
 /// Called by the Bonjour module when a host is discovered.
 pub(crate) fn on_host_found(host: NetworkHost, app_handle: &AppHandle) {
     let mut state = get_discovery_state().lock().unwrap();
 
-    let is_new = !state.hosts.contains_key(&host.id);
+    let is_new = state.add_host(host.clone());
     info!(
         "Host {}: id={}, name={}, ip={:?}, hostname={:?}",
         if is_new { "ADDED" } else { "UPDATED" },
@@ -112,9 +147,6 @@ pub(crate) fn on_host_found(host: NetworkHost, app_handle: &AppHandle) {
         host.hostname
     );
 
-    // Insert or update the host
-    state.hosts.insert(host.id.clone(), host.clone());
-
     // Emit event to frontend
     let _ = app_handle.emit("network-host-found", &host);
 }
@@ -393,6 +425,74 @@ mod tests {
         assert!(!json.contains("ipAddress"));
     }
 
+    #[test]
+    fn test_reset_forgets_hosts_and_goes_idle() {
+        let mut state = NetworkDiscoveryState::default();
+        for id in ["nas", "macbook"] {
+            state.hosts.insert(
+                id.to_string(),
+                NetworkHost {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    hostname: None,
+                    ip_address: None,
+                    port: 445,
+                },
+            );
+        }
+        state.state = DiscoveryState::Active;
+
+        let mut lost_ids = state.reset();
+        lost_ids.sort();
+        assert_eq!(lost_ids, vec!["macbook", "nas"]);
+        assert_eq!(state.state, DiscoveryState::Idle);
+        assert!(state.hosts.is_empty());
+    }
+
+    #[test]
+    fn test_reset_is_safe_to_repeat() {
+        let mut state = NetworkDiscoveryState {
+            state: DiscoveryState::Searching,
+            ..Default::default()
+        };
+
+        assert!(state.reset().is_empty());
+        assert!(state.reset().is_empty());
+        assert_eq!(state.state, DiscoveryState::Idle);
+    }
+
+    #[test]
+    fn test_stop_and_repeated_restart_leave_no_duplicate_hosts() {
+        let host = |id: &str| NetworkHost {
+            id: id.to_string(),
+            name: id.to_string(),
+            hostname: None,
+            ip_address: None,
+            port: 445,
+        };
+        let mut state = NetworkDiscoveryState::default();
+
+        for _ in 0..3 {
+            // A restart stops first, then Bonjour finds the same hosts again
+            state.reset();
+            assert_eq!(state.state, DiscoveryState::Idle);
+            assert!(state.hosts.is_empty());
+
+            assert!(state.add_host(host("nas")));
+            assert!(state.add_host(host("macbook")));
+            assert!(!state.add_host(host("nas")));
+            state.state = DiscoveryState::Active;
+            assert_eq!(state.hosts.len(), 2);
+        }
+
+        // Stopping forgets them all
+        let mut lost_ids = state.reset();
+        lost_ids.sort();
+        assert_eq!(lost_ids, vec!["macbook", "nas"]);
+        assert_eq!(state.state, DiscoveryState::Idle);
+        assert!(state.hosts.is_empty());
+    }
+
     #[test]
     fn test_service_name_to_hostname() {
         // Basic conversion
@@ -1,6 +1,9 @@
 //! SMB share mounting using macOS NetFS.framework.
 //!
 //! Provides async mount operations with proper error handling and credential support.
+//!
+//! Mounting reports its progress in `smb-mount-progress` events. The phases are best-effort: NetFS mounts in one
+//! opaque call, so we can only tell when it started and ended, plus a heartbeat while it's still going.
 
 use core_foundation::base::TCFType;
 use core_foundation::string::CFString;
@@ -8,6 +11,8 @@ use core_foundation::url::CFURL;
 use serde::{Deserialize, Serialize};
 use std::ffi::c_void;
 use std::ptr;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 
 /// Result of a successful mount operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +46,33 @@ pub enum MountError {
     ProtocolError { message: String },
     /// Mount path already exists but isn't a mountpoint
     MountPathConflict { message: String },
+    /// Can't unmount because files on the share are still open
+    InUse { message: String },
+}
+
+/// Phase of a mount, as reported in `smb-mount-progress` events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MountPhase {
+    /// The mount started
+    Connecting,
+    /// Heartbeat, sent every `MOUNT_HEARTBEAT_INTERVAL` while the mount is still going
+    StillConnecting,
+    /// The share is mounted (or was already)
+    Mounted,
+    /// The mount failed or timed out
+    Failed,
+}
+
+/// Payload of `smb-mount-progress` events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountProgress {
+    pub server: String,
+    pub share: String,
+    pub phase: MountPhase,
+    /// Seconds since the mount started
+    pub elapsed_secs: u64,
 }
 
 // NetFS.framework FFI declarations
@@ -64,9 +96,12 @@ const ENETFSNOSHARESAVAIL: i32 = -5998;
 const ENETFSNOAUTHMECHSUPP: i32 = -5997;
 const ENETFSNOPROTOVERSSUPP: i32 = -5996;
 const USER_CANCELLED_ERR: i32 = -128;
+const EPERM: i32 = 1;
 const ENOENT: i32 = 2;
 const EEXIST: i32 = 17; // Share already mounted
 const EACCES: i32 = 13;
+const EBUSY: i32 = 16; // Files on the volume are still open
+const EINVAL: i32 = 22; // Not a mount point
 const ETIMEDOUT: i32 = 60;
 const ECONNREFUSED: i32 = 61;
 const EHOSTUNREACH: i32 = 65;
@@ -115,6 +150,7 @@ fn error_from_code(code: i32, share_name: &str, server_name: &str) -> MountError
 /// # Arguments
 /// * `server` - Server hostname or IP address
 /// * `share` - Name of the share to mount
+/// * `subpath` - Optional folder in the share, like "projects/2024", to mount instead of the share root
 /// * `username` - Optional username for authentication
 /// * `password` - Optional password for authentication
 ///
@@ -124,11 +160,19 @@ fn error_from_code(code: i32, share_name: &str, server_name: &str) -> MountError
 pub fn mount_share_sync(
     server: &str,
     share: &str,
+    subpath: Option<&str>,
     username: Option<&str>,
     password: Option<&str>,
 ) -> Result<MountResult, MountError> {
-    // Build SMB URL: smb://server/share
-    let url_string = format!("smb://{}/{}", server, share);
+    // Build SMB URL: smb://server/share or smb://server/share/sub/path
+    let url_string = smb_url(server, share, subpath);
+    let segments = subpath_segments(subpath);
+    // The folder that gets mounted, which is also what macOS names the mount point after
+    let mounted_name = segments.last().copied().unwrap_or(share);
+    let share_path = std::iter::once(share)
+        .chain(segments.iter().copied())
+        .collect::<Vec<_>>()
+        .join("/");
 
     // Create URL from string using CFURLCreateWithString
     let cf_url_string = CFString::new(&url_string);
@@ -172,9 +216,10 @@ pub fn mount_share_sync(
     // Check result
     // EEXIST (17) means the share is already mounted - this is not an error
     if result == EEXIST {
-        // Share is already mounted, return success with expected path
+        // Share is already mounted, possibly at "/Volumes/{share}-1" if the name was taken, so look it up
         return Ok(MountResult {
-            mount_path: format!("/Volumes/{}", share),
+            mount_path: find_mounted_smb_path(server, &share_path)
+                .unwrap_or_else(|| format!("/Volumes/{}", mounted_name)),
             already_mounted: true,
         });
     }
@@ -199,12 +244,12 @@ pub fn mount_share_sync(
                 // Release the array even if empty
                 core_foundation::base::CFRelease(mountpoints);
                 // Fall back to expected path
-                format!("/Volumes/{}", share)
+                format!("/Volumes/{}", mounted_name)
             }
         }
     } else {
         // No mount points returned, use expected path
-        format!("/Volumes/{}", share)
+        format!("/Volumes/{}", mounted_name)
     };
 
     Ok(MountResult {
@@ -213,13 +258,159 @@ pub fn mount_share_sync(
     })
 }
 
+/// Builds the URL to mount, like "smb://server/share/My%20Folder". The subpath is URL-encoded per segment.
+fn smb_url(server: &str, share: &str, subpath: Option<&str>) -> String {
+    let mut url = format!("smb://{}/{}", server, share);
+    for segment in subpath_segments(subpath) {
+        url.push('/');
+        url.push_str(&urlencoding::encode(segment));
+    }
+    url
+}
+
+/// The folder names in a subpath, ignoring extra slashes.
+fn subpath_segments(subpath: Option<&str>) -> Vec<&str> {
+    subpath
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// A mounted SMB share in the mount table.
+#[derive(Debug, Clone)]
+struct SmbMount {
+    /// Where the share came from (`statfs.f_mntfromname`), like "//user@server/share"
+    source: String,
+    /// Where the share is mounted, like "/Volumes/share-1"
+    mount_point: String,
+}
+
+/// Returns where the share is mounted, or None if it isn't. `share` may include a subpath, like "share/projects".
+fn find_mounted_smb_path(server: &str, share: &str) -> Option<String> {
+    find_smb_mount(&load_smb_mounts(), server, share).map(str::to_string)
+}
+
+/// Finds the mount point of `server/share` among the mounts.
+fn find_smb_mount<'a>(mounts: &'a [SmbMount], server: &str, share: &str) -> Option<&'a str> {
+    mounts
+        .iter()
+        .find(|mount| smb_source_matches(&mount.source, server, share))
+        .map(|mount| mount.mount_point.as_str())
+}
+
+/// Whether a mount source like "//user@nas.local/My%20Share" is `server/share`. Names are compared ignoring case
+/// and a ".local" suffix, as macOS may have mounted the share by either name.
+fn smb_source_matches(source: &str, server: &str, share: &str) -> bool {
+    let Some((host, source_share)) = source.trim_start_matches('/').split_once('/') else {
+        return false;
+    };
+    // Drop the user name and any port
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    let host = host.split_once(':').map_or(host, |(host, _)| host);
+    let source_share = urlencoding::decode(source_share.trim_end_matches('/')).unwrap_or_default();
+    let without_local = |name: &str| name.to_lowercase().trim_end_matches(".local").to_string();
+
+    without_local(host) == without_local(server) && source_share.to_lowercase() == share.to_lowercase()
+}
+
+/// Lists the mounted SMB shares. Uses `MNT_NOWAIT`, so it doesn't touch the servers.
+fn load_smb_mounts() -> Vec<SmbMount> {
+    use std::ffi::CStr;
+
+    let mut stats: *mut libc::statfs = ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut stats, libc::MNT_NOWAIT) };
+    if count <= 0 || stats.is_null() {
+        return Vec::new();
+    }
+
+    // The buffer is owned by libc and stays valid until the next getmntinfo call on this thread
+    let stats = unsafe { std::slice::from_raw_parts(stats, count as usize) };
+    stats
+        .iter()
+        .filter(|stat| unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) }.to_bytes() == b"smbfs")
+        .map(|stat| SmbMount {
+            source: unsafe { CStr::from_ptr(stat.f_mntfromname.as_ptr()) }
+                .to_string_lossy()
+                .to_string(),
+            mount_point: unsafe { CStr::from_ptr(stat.f_mntonname.as_ptr()) }
+                .to_string_lossy()
+                .to_string(),
+        })
+        .collect()
+}
+
 /// Mount timeout in seconds
 const MOUNT_TIMEOUT_SECS: u64 = 20;
 
+/// How often a mount in progress sends a "still connecting" event.
+const MOUNT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(3);
+
 /// Async wrapper for mount_share_sync that runs in a blocking task with timeout.
+/// Emits `smb-mount-progress` events while mounting.
 pub async fn mount_share(
+    app: AppHandle,
+    server: String,
+    share: String,
+    subpath: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<MountResult, MountError> {
+    let emit = {
+        let (server, share) = (server.clone(), share.clone());
+        move |phase: MountPhase, elapsed: Duration| {
+            let progress = MountProgress {
+                server: server.clone(),
+                share: share.clone(),
+                phase,
+                elapsed_secs: elapsed.as_secs(),
+            };
+            let _ = app.emit("smb-mount-progress", progress);
+        }
+    };
+    let started = Instant::now();
+    emit(MountPhase::Connecting, Duration::ZERO);
+
+    let heartbeat_emit = emit.clone();
+    let result = with_heartbeat(
+        MOUNT_HEARTBEAT_INTERVAL,
+        move |elapsed| heartbeat_emit(MountPhase::StillConnecting, elapsed),
+        mount_share_with_timeout(server, share, subpath, username, password),
+    )
+    .await;
+
+    let phase = if result.is_ok() {
+        MountPhase::Mounted
+    } else {
+        MountPhase::Failed
+    };
+    emit(phase, started.elapsed());
+    result
+}
+
+/// Runs `work`, calling `on_beat` with the elapsed time every `interval` until it's done.
+async fn with_heartbeat<T>(
+    interval: Duration,
+    on_beat: impl Fn(Duration) + Send + 'static,
+    work: impl std::future::Future<Output = T>,
+) -> T {
+    let started = Instant::now();
+    let heartbeat = tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            on_beat(started.elapsed());
+        }
+    });
+    let result = work.await;
+    heartbeat.abort();
+    result
+}
+
+/// Runs `mount_share_sync` in a blocking task, failing it after `MOUNT_TIMEOUT_SECS`.
+async fn mount_share_with_timeout(
     server: String,
     share: String,
+    subpath: Option<String>,
     username: Option<String>,
     password: Option<String>,
 ) -> Result<MountResult, MountError> {
@@ -227,10 +418,16 @@ pub async fn mount_share(
 
     // Use timeout to prevent hanging indefinitely
     let mount_future = tokio::task::spawn_blocking(move || {
-        mount_share_sync(&server, &share, username.as_deref(), password.as_deref())
+        mount_share_sync(
+            &server,
+            &share,
+            subpath.as_deref(),
+            username.as_deref(),
+            password.as_deref(),
+        )
     });
 
-    match tokio::time::timeout(std::time::Duration::from_secs(MOUNT_TIMEOUT_SECS), mount_future).await {
+    match tokio::time::timeout(Duration::from_secs(MOUNT_TIMEOUT_SECS), mount_future).await {
         Ok(Ok(result)) => result,
         Ok(Err(join_error)) => Err(MountError::ProtocolError {
             message: format!("Mount task failed: {}", join_error),
@@ -244,6 +441,64 @@ pub async fn mount_share(
     }
 }
 
+/// Map the errno of a failed unmount(2) call to the result of unmounting.
+/// A path that isn't mounted (anymore) counts as unmounted.
+fn unmount_result_from_code(code: i32, mount_path: &str) -> Result<(), MountError> {
+    match code {
+        EINVAL | ENOENT => Ok(()),
+        EBUSY => Err(MountError::InUse {
+            message: format!(
+                "\"{}\" is in use. Close the files open on it, then try again.",
+                mount_path
+            ),
+        }),
+        EPERM | EACCES => Err(MountError::PermissionDenied {
+            message: format!("Not allowed to unmount \"{}\"", mount_path),
+        }),
+        _ => Err(MountError::ProtocolError {
+            message: format!("Unmount of \"{}\" failed with error code {}", mount_path, code),
+        }),
+    }
+}
+
+/// Unmount a share from the local filesystem.
+///
+/// This is a synchronous function that should be called from a spawn_blocking context.
+/// Doesn't force the unmount, so it fails with `InUse` while files on the share are open.
+pub fn unmount_share_sync(mount_path: &str) -> Result<(), MountError> {
+    let c_path = std::ffi::CString::new(mount_path).map_err(|_| MountError::ProtocolError {
+        message: format!("Invalid mount path: {}", mount_path),
+    })?;
+
+    if unsafe { libc::unmount(c_path.as_ptr(), 0) } == 0 {
+        return Ok(());
+    }
+    let code = std::io::Error::last_os_error().raw_os_error().unwrap_or(0);
+    unmount_result_from_code(code, mount_path)
+}
+
+/// Async wrapper for unmount_share_sync that runs in a blocking task with timeout.
+/// Succeeds if the path isn't mounted.
+pub async fn unmount_share(mount_path: String) -> Result<(), MountError> {
+    let mount_path_clone = mount_path.clone();
+
+    // Use timeout, as unmounting a share of an unreachable server can hang too
+    let unmount_future = tokio::task::spawn_blocking(move || unmount_share_sync(&mount_path));
+
+    match tokio::time::timeout(Duration::from_secs(MOUNT_TIMEOUT_SECS), unmount_future).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(join_error)) => Err(MountError::ProtocolError {
+            message: format!("Unmount task failed: {}", join_error),
+        }),
+        Err(_timeout) => Err(MountError::Timeout {
+            message: format!(
+                "Unmounting \"{}\" timed out after {} seconds",
+                mount_path_clone, MOUNT_TIMEOUT_SECS
+            ),
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,6 +533,167 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unmount_result_from_code() {
+        // Not mounted counts as unmounted
+        assert!(unmount_result_from_code(EINVAL, "/Volumes/Media").is_ok());
+        assert!(unmount_result_from_code(ENOENT, "/Volumes/Media").is_ok());
+
+        match unmount_result_from_code(EBUSY, "/Volumes/Media") {
+            Err(MountError::InUse { message }) => assert!(message.contains("/Volumes/Media")),
+            other => panic!("Expected InUse error, got {:?}", other),
+        }
+
+        match unmount_result_from_code(EPERM, "/Volumes/Media") {
+            Err(MountError::PermissionDenied { .. }) => (),
+            other => panic!("Expected PermissionDenied error, got {:?}", other),
+        }
+
+        match unmount_result_from_code(ETIMEDOUT, "/Volumes/Media") {
+            Err(MountError::ProtocolError { message }) => assert!(message.contains("60")),
+            other => panic!("Expected ProtocolError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_smb_url_without_subpath() {
+        assert_eq!(smb_url("nas.local", "Media", None), "smb://nas.local/Media");
+        assert_eq!(smb_url("nas.local", "Media", Some("")), "smb://nas.local/Media");
+        assert_eq!(smb_url("nas.local", "Media", Some("/")), "smb://nas.local/Media");
+    }
+
+    #[test]
+    fn test_smb_url_with_subpath() {
+        assert_eq!(
+            smb_url("nas.local", "Media", Some("projects/2024")),
+            "smb://nas.local/Media/projects/2024"
+        );
+        // Extra slashes are ignored
+        assert_eq!(
+            smb_url("nas.local", "Media", Some("/projects//2024/")),
+            "smb://nas.local/Media/projects/2024"
+        );
+    }
+
+    #[test]
+    fn test_smb_url_encodes_subpath_segments() {
+        assert_eq!(
+            smb_url("nas.local", "Media", Some("My Projects/Q1 plan")),
+            "smb://nas.local/Media/My%20Projects/Q1%20plan"
+        );
+        assert_eq!(
+            smb_url("nas.local", "Media", Some("Fotók/café")),
+            "smb://nas.local/Media/Fot%C3%B3k/caf%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_find_smb_mount_with_subpath() {
+        let mounts = vec![
+            smb_mount("//david@nas.local/Media", "/Volumes/Media"),
+            smb_mount("//david@nas.local/Media/My%20Projects", "/Volumes/My Projects"),
+        ];
+
+        assert_eq!(
+            find_smb_mount(&mounts, "nas.local", "Media/My Projects"),
+            Some("/Volumes/My Projects")
+        );
+        assert_eq!(find_smb_mount(&mounts, "nas.local", "Media"), Some("/Volumes/Media"));
+    }
+
+    fn smb_mount(source: &str, mount_point: &str) -> SmbMount {
+        SmbMount {
+            source: source.to_string(),
+            mount_point: mount_point.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_smb_mount_returns_the_real_path() {
+        let mounts = vec![
+            smb_mount("//david@otherserver/Media", "/Volumes/Media"),
+            smb_mount("//GUEST:@nas.local/Media", "/Volumes/Media-1"),
+            smb_mount("//david@nas.local/My%20Files", "/Volumes/My Files"),
+        ];
+
+        assert_eq!(find_smb_mount(&mounts, "nas.local", "Media"), Some("/Volumes/Media-1"));
+        assert_eq!(find_smb_mount(&mounts, "NAS", "media"), Some("/Volumes/Media-1"));
+        assert_eq!(
+            find_smb_mount(&mounts, "nas.local", "My Files"),
+            Some("/Volumes/My Files")
+        );
+        assert_eq!(find_smb_mount(&mounts, "otherserver", "Media"), Some("/Volumes/Media"));
+    }
+
+    #[test]
+    fn test_find_smb_mount_without_match() {
+        let mounts = vec![smb_mount("//david@nas.local/Media", "/Volumes/Media")];
+
+        assert_eq!(find_smb_mount(&mounts, "nas.local", "Backups"), None);
+        assert_eq!(find_smb_mount(&mounts, "other.local", "Media"), None);
+        assert_eq!(find_smb_mount(&[], "nas.local", "Media"), None);
+    }
+
+    #[test]
+    fn test_smb_source_matches_with_port_and_ip() {
+        assert!(smb_source_matches(
+            "//david@192.168.1.10:445/Media",
+            "192.168.1.10",
+            "Media"
+        ));
+        assert!(smb_source_matches("//192.168.1.10/Media/", "192.168.1.10", "Media"));
+        assert!(!smb_source_matches("//192.168.1.10", "192.168.1.10", "Media"));
+    }
+
+    #[test]
+    fn test_heartbeat_stops_when_work_returns_quickly() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let beats = Arc::new(AtomicUsize::new(0));
+        let counter = beats.clone();
+        tauri::async_runtime::block_on(async move {
+            let result = with_heartbeat(
+                Duration::from_millis(20),
+                move |_| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                },
+                async { 42 },
+            )
+            .await;
+            assert_eq!(result, 42);
+            // Several intervals pass after the work is done
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        });
+
+        assert_eq!(beats.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_heartbeat_beats_while_work_is_running() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let beats = Arc::new(AtomicUsize::new(0));
+        let counter = beats.clone();
+        let beats_when_done = tauri::async_runtime::block_on(async move {
+            with_heartbeat(
+                Duration::from_millis(20),
+                move |_| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                },
+                tokio::time::sleep(Duration::from_millis(150)),
+            )
+            .await;
+            let beats_when_done = beats.load(Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            assert_eq!(beats.load(Ordering::SeqCst), beats_when_done);
+            beats_when_done
+        });
+
+        assert!(beats_when_done >= 2);
+    }
+
     #[test]
     fn test_timeout_constant() {
         // Verify timeout is reasonable (10-60 seconds)
@@ -3,14 +3,18 @@
 //! Uses the `smb` crate (smb-rs) to list shares on network hosts.
 //! Implements connection pooling, caching, and authentication handling.
 
+use super::known_shares::{self, AuthOptions, ConnectionMode, KnownNetworkShare};
+use futures_util::future::join_all;
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
-use smb::{Client, ClientConfig};
+use smb::{Client, ClientConfig, FileAccessMask, FileCreateArgs, FileFsFullSizeInformation, Resource, UncPath};
 use smb_rpc::interface::ShareInfo1;
 use std::collections::HashMap;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 use tokio::time::timeout;
 
 /// Information about a discovered share.
@@ -23,6 +27,10 @@ pub struct ShareInfo {
     pub is_disk: bool,
     /// Optional description/comment for the share.
     pub comment: Option<String>,
+    /// Size of the disk the share is on, in bytes. Only filled when listing with `with_capacity`.
+    pub total_bytes: Option<u64>,
+    /// Free space available to the user on the share, in bytes. Only filled when listing with `with_capacity`.
+    pub free_bytes: Option<u64>,
 }
 
 /// Authentication mode detected for a host.
@@ -47,6 +55,24 @@ pub struct ShareListResult {
     pub auth_mode: AuthMode,
     /// Whether this result came from cache.
     pub from_cache: bool,
+    /// Whether the detected auth mode differs from the auth options saved for the host last time, like when
+    /// a server that allowed guests now requires credentials.
+    #[serde(default)]
+    pub auth_changed: bool,
+}
+
+/// Result of testing the connection to a host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionTestResult {
+    /// Whether the SMB port accepted a TCP connection.
+    pub reachable: bool,
+    /// Whether the server accepted the credentials (or guest access, if none were given).
+    pub auth_ok: bool,
+    /// Negotiated SMB dialect (for example, "3.1.1"), if known.
+    pub dialect: Option<String>,
+    /// Time it took to open the TCP connection, in milliseconds.
+    pub latency_ms: u64,
 }
 
 /// Error types for share listing operations.
@@ -83,30 +109,82 @@ impl std::fmt::Display for ShareListError {
     }
 }
 
+impl ShareListError {
+    /// Whether trying again might help, like when the server dropped the connection.
+    fn is_transient(&self) -> bool {
+        matches!(self, Self::Timeout(_) | Self::HostUnreachable(_))
+    }
+}
+
 // --- Cache ---
 
-/// Cached share list with expiration.
+/// Cached share list, valid until it's older than the cache TTL.
 struct CachedShares {
     result: ShareListResult,
-    expires_at: Instant,
+    cached_at: Instant,
+}
+
+impl CachedShares {
+    fn is_fresh(&self) -> bool {
+        self.cached_at.elapsed() < cache_ttl()
+    }
 }
 
-/// Share cache with 30-second TTL.
+/// Share cache, see `set_cache_ttl_secs` for how long entries are kept.
 static SHARE_CACHE: std::sync::OnceLock<Mutex<HashMap<String, CachedShares>>> = std::sync::OnceLock::new();
 
-const CACHE_TTL: Duration = Duration::from_secs(30);
+/// How long share lists are cached, in seconds, used until `set_cache_ttl_secs` is called
+const DEFAULT_CACHE_TTL_SECS: u64 = 30;
+
+/// Longest accepted cache TTL in seconds
+const MAX_CACHE_TTL_SECS: u64 = 3600;
+
+/// How long share lists are cached, in seconds. See `set_cache_ttl_secs`.
+static CACHE_TTL_SECS: AtomicU64 = AtomicU64::new(DEFAULT_CACHE_TTL_SECS);
+
 const LIST_SHARES_TIMEOUT: Duration = Duration::from_secs(15);
+const TEST_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+const SHARE_CAPACITY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Most hosts `list_shares_for_hosts` lists at the same time
+const MAX_CONCURRENT_HOST_LISTINGS: usize = 8;
+/// Waits between attempts to list shares after a transient error. Some servers drop the first connection.
+const LIST_SHARES_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+];
 
 fn get_share_cache() -> &'static Mutex<HashMap<String, CachedShares>> {
     SHARE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
+/// Sets how long share lists are cached, clamped to at most an hour. 0 turns the cache off.
+///
+/// Longer suits stable home networks, where shares rarely change. Applies to already cached lists too.
+pub fn set_cache_ttl_secs(ttl_secs: u64) {
+    CACHE_TTL_SECS.store(ttl_secs.min(MAX_CACHE_TTL_SECS), Ordering::Relaxed);
+}
+
+/// Returns how long share lists are cached.
+fn cache_ttl() -> Duration {
+    Duration::from_secs(CACHE_TTL_SECS.load(Ordering::Relaxed))
+}
+
+/// Key of a host's share list in the cache. Lists with and without capacity are cached separately.
+fn cache_key(host_id: &str, with_capacity: bool) -> String {
+    if with_capacity {
+        format!("{}#capacity", host_id)
+    } else {
+        host_id.to_string()
+    }
+}
+
 /// Gets cached shares for a host if still valid.
-fn get_cached_shares(host_id: &str) -> Option<ShareListResult> {
+fn get_cached_shares(host_id: &str, with_capacity: bool) -> Option<ShareListResult> {
     let cache = get_share_cache().lock().ok()?;
-    let entry = cache.get(host_id)?;
+    let entry = cache.get(&cache_key(host_id, with_capacity))?;
 
-    if Instant::now() < entry.expires_at {
+    if entry.is_fresh() {
         let mut result = entry.result.clone();
         result.from_cache = true;
         Some(result)
@@ -116,17 +194,16 @@ fn get_cached_shares(host_id: &str) -> Option<ShareListResult> {
 }
 
 /// Caches share list for a host.
-fn cache_shares(host_id: &str, result: &ShareListResult) {
+fn cache_shares(host_id: &str, with_capacity: bool, result: &ShareListResult) {
     if let Ok(mut cache) = get_share_cache().lock() {
         // Clean up expired entries while we're here
-        let now = Instant::now();
-        cache.retain(|_, v| v.expires_at > now);
+        cache.retain(|_, v| v.is_fresh());
 
         cache.insert(
-            host_id.to_string(),
+            cache_key(host_id, with_capacity),
             CachedShares {
                 result: result.clone(),
-                expires_at: now + CACHE_TTL,
+                cached_at: Instant::now(),
             },
         );
     }
@@ -136,20 +213,19 @@ fn cache_shares(host_id: &str, result: &ShareListResult) {
 #[allow(dead_code)] // Will be used when implementing cache invalidation on host disconnect
 pub fn invalidate_cache(host_id: &str) {
     if let Ok(mut cache) = get_share_cache().lock() {
-        cache.remove(host_id);
+        cache.remove(&cache_key(host_id, false));
+        cache.remove(&cache_key(host_id, true));
     }
 }
 
-/// Gets the cached auth mode for a host, if available.
+/// Gets the cached auth mode for a host, if available, from either cached share list.
 pub fn get_cached_shares_auth_mode(host_id: &str) -> Option<AuthMode> {
     let cache = get_share_cache().lock().ok()?;
-    let entry = cache.get(host_id)?;
-
-    if Instant::now() < entry.expires_at {
-        Some(entry.result.auth_mode)
-    } else {
-        None
-    }
+    [false, true]
+        .iter()
+        .filter_map(|&with_capacity| cache.get(&cache_key(host_id, with_capacity)))
+        .find(|entry| entry.is_fresh())
+        .map(|entry| entry.result.auth_mode)
 }
 
 // --- Share Listing ---
@@ -157,46 +233,309 @@ pub fn get_cached_shares_auth_mode(host_id: &str) -> Option<AuthMode> {
 /// Lists shares on a network host.
 ///
 /// Attempts guest access first, then uses provided credentials if guest fails.
-/// Results are cached for 30 seconds.
+/// Results are cached, for 30 seconds by default (see `set_cache_ttl_secs`).
 ///
 /// # Arguments
 /// * `host_id` - Unique identifier for the host (used for caching)
 /// * `hostname` - Hostname to connect to (for example, "TEST_SERVER.local")
 /// * `ip_address` - Optional resolved IP address (preferred over hostname)
 /// * `credentials` - Optional (username, password) tuple for authenticated access
+/// * `with_capacity` - Whether to also get the total and free bytes of each share. Slower, as it connects to
+///   each share. Not available when falling back to smbutil.
+/// * `refresh` - Whether to skip the cache, like when the user asks to refresh. The fresh result is still cached.
 pub async fn list_shares(
     host_id: &str,
     hostname: &str,
     ip_address: Option<&str>,
     port: u16,
     credentials: Option<(&str, &str)>,
+    with_capacity: bool,
+    refresh: bool,
 ) -> Result<ShareListResult, ShareListError> {
     // Only use cache for non-authenticated requests.
     // When credentials are provided, the user is explicitly authenticating
     // and expects fresh results (not cached guest attempt results).
-    if credentials.is_none()
-        && let Some(cached) = get_cached_shares(host_id)
-    {
+    let read_cache = credentials.is_none() && !refresh;
+    let known_share = known_server_share(host_id);
+    let credentials_first = known_share
+        .clone()
+        .filter(|share| tries_credentials_first(share, credentials));
+    let fetch = list_shares_uncached(
+        hostname,
+        ip_address,
+        port,
+        credentials,
+        with_capacity,
+        credentials_first,
+    );
+    let mut result = cached_or_fetch(host_id, with_capacity, read_cache, fetch).await?;
+    result.auth_changed =
+        known_share.is_some_and(|share| auth_options_changed(share.last_known_auth_options, result.auth_mode));
+    Ok(result)
+}
+
+/// Whether a freshly detected auth mode contradicts the auth options saved for the host.
+/// An unknown auth mode never counts as a change.
+fn auth_options_changed(known: AuthOptions, detected: AuthMode) -> bool {
+    match detected {
+        AuthMode::GuestAllowed => known == AuthOptions::CredentialsOnly,
+        AuthMode::CredsRequired => known != AuthOptions::CredentialsOnly,
+        AuthMode::Unknown => false,
+    }
+}
+
+/// The server-level known share of a discovered host, saved when the user last listed its shares.
+fn known_server_share(host_id: &str) -> Option<KnownNetworkShare> {
+    let host = super::get_host_for_resolution(host_id)?;
+    known_shares::get_known_share(&host.name, "")
+}
+
+/// Whether to skip the guest attempt and use the credentials right away, because they were needed last time.
+fn tries_credentials_first(known_share: &KnownNetworkShare, credentials: Option<(&str, &str)>) -> bool {
+    credentials.is_some() && known_share.last_connection_mode == ConnectionMode::Credentials
+}
+
+/// Updates the known share after listing shares with its credentials.
+fn remember_credentials_connection(known_share: &KnownNetworkShare, username: &str) {
+    let Some(app) = super::bonjour::get_app_handle() else {
+        return;
+    };
+    let share = KnownNetworkShare {
+        last_connected_at: chrono::Utc::now().to_rfc3339(),
+        last_connection_mode: ConnectionMode::Credentials,
+        username: Some(username.to_string()),
+        ..known_share.clone()
+    };
+    known_shares::update_known_share(&app, share);
+}
+
+/// Returns the cached shares if `read_cache` is set and there are any, or else awaits `fetch` and caches its result.
+async fn cached_or_fetch(
+    host_id: &str,
+    with_capacity: bool,
+    read_cache: bool,
+    fetch: impl std::future::Future<Output = Result<ShareListResult, ShareListError>>,
+) -> Result<ShareListResult, ShareListError> {
+    if read_cache && let Some(cached) = get_cached_shares(host_id, with_capacity) {
         return Ok(cached);
     }
 
     // Try to list shares
-    let result = list_shares_uncached(hostname, ip_address, port, credentials).await?;
+    let result = fetch.await?;
 
     // Cache successful result
-    cache_shares(host_id, &result);
+    cache_shares(host_id, with_capacity, &result);
 
     Ok(result)
 }
 
+/// Lists shares on several discovered hosts at once, as guest, like when the network view opens.
+///
+/// Hosts with cached shares are answered from the cache. The others are listed concurrently, at most
+/// `MAX_CONCURRENT_HOST_LISTINGS` at a time, so a large network doesn't open dozens of connections at once.
+/// Returns the result for each host ID. Unknown host IDs get a `ResolutionFailed` error.
+pub async fn list_shares_for_hosts(host_ids: Vec<String>) -> HashMap<String, Result<ShareListResult, ShareListError>> {
+    list_bounded(host_ids, MAX_CONCURRENT_HOST_LISTINGS, |host_id| async move {
+        let info = super::get_host_for_resolution(&host_id)
+            .ok_or_else(|| ShareListError::ResolutionFailed(format!("Unknown host: {}", host_id)))?;
+        let hostname = info
+            .hostname
+            .unwrap_or_else(|| super::service_name_to_hostname(&info.name));
+        list_shares(
+            &host_id,
+            &hostname,
+            info.ip_address.as_deref(),
+            info.port,
+            None,
+            false,
+            false,
+        )
+        .await
+    })
+    .await
+}
+
+/// Calls `list` for each host that has no cached shares, running at most `max_concurrent` calls at a time.
+async fn list_bounded<F, Fut>(
+    host_ids: Vec<String>,
+    max_concurrent: usize,
+    list: F,
+) -> HashMap<String, Result<ShareListResult, ShareListError>>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<ShareListResult, ShareListError>>,
+{
+    let mut results = HashMap::new();
+    let mut uncached = Vec::new();
+    for host_id in host_ids {
+        match get_cached_shares(&host_id, false) {
+            Some(cached) => {
+                results.insert(host_id, Ok(cached));
+            }
+            None => uncached.push(host_id),
+        }
+    }
+
+    let semaphore = Semaphore::new(max_concurrent);
+    let listings = uncached.into_iter().map(|host_id| {
+        let semaphore = &semaphore;
+        let list = &list;
+        async move {
+            // The semaphore is never closed, so acquiring only fails if it's dropped, which it isn't while we wait
+            let _permit = semaphore.acquire().await;
+            let result = list(host_id.clone()).await;
+            (host_id, result)
+        }
+    });
+    results.extend(join_all(listings).await);
+    results
+}
+
+/// Tests whether a host accepts SMB connections and the given credentials, without listing shares.
+///
+/// Opens a TCP connection to the SMB port first, so unreachable hosts fail fast. Then negotiates a session and
+/// connects to IPC$ as guest or with the credentials, and tears the connection down. Never uses the share cache.
+///
+/// # Arguments
+/// * `hostname` - Hostname to connect to (for example, "TEST_SERVER.local")
+/// * `ip_address` - Optional resolved IP address (preferred over hostname)
+/// * `port` - SMB port
+/// * `credentials` - Optional (username, password) tuple. Guest access is tested if None.
+pub async fn test_connection(
+    hostname: &str,
+    ip_address: Option<&str>,
+    port: u16,
+    credentials: Option<(&str, &str)>,
+) -> Result<ConnectionTestResult, ShareListError> {
+    let addresses = resolve_socket_addresses(hostname, ip_address, port)?;
+    let latency = tokio::task::spawn_blocking(move || check_reachable(&addresses, TEST_CONNECTION_TIMEOUT))
+        .await
+        .map_err(|e| ShareListError::ProtocolError(format!("Failed to spawn reachability check: {}", e)))?;
+    let latency_ms = match latency {
+        Ok(latency) => latency.as_millis() as u64,
+        Err(e) => {
+            debug!("test_connection: {}:{} not reachable: {}", hostname, port, e);
+            return Ok(ConnectionTestResult {
+                reachable: false,
+                auth_ok: false,
+                dialect: None,
+                latency_ms: 0,
+            });
+        }
+    };
+
+    let mut config = ClientConfig::default();
+    config.connection.allow_unsigned_guest_access = credentials.is_none();
+    let client = Client::new(config);
+    let server_name = if let Some(ip) = ip_address {
+        ip
+    } else {
+        hostname.strip_suffix(".local").unwrap_or(hostname)
+    };
+    let (username, password) = credentials.unwrap_or(("Guest", ""));
+
+    let handshake = timeout(TEST_CONNECTION_TIMEOUT, async {
+        let connect_name = if let Some(ip) = ip_address {
+            let socket_addr = socket_address(ip, port)?;
+            client
+                .connect_to_address(server_name, socket_addr)
+                .await
+                .map_err(|e| format!("Connect to {} failed: {}", ip, e))?;
+            server_name
+        } else {
+            hostname
+        };
+        client
+            .ipc_connect(connect_name, username, password.to_string())
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("IPC connect failed: {}", e))
+    })
+    .await
+    .map_err(|_| format!("Timeout after {}s", TEST_CONNECTION_TIMEOUT.as_secs()))
+    .and_then(|result| result);
+    // Dropping the client closes the connection
+    drop(client);
+
+    match handshake {
+        Ok(()) => Ok(ConnectionTestResult {
+            reachable: true,
+            auth_ok: true,
+            dialect: None,
+            latency_ms,
+        }),
+        Err(e) if is_auth_error(&e) => {
+            debug!("test_connection: auth rejected: {}", e);
+            Ok(ConnectionTestResult {
+                reachable: true,
+                auth_ok: false,
+                dialect: None,
+                latency_ms,
+            })
+        }
+        Err(e) => Err(classify_error(&e)),
+    }
+}
+
+/// Builds the address to connect to from an IP address string and a port.
+///
+/// Parses the IP on its own rather than as "ip:port", because IPv6 addresses like the ones Bonjour reports
+/// would need brackets around them.
+pub(crate) fn socket_address(ip: &str, port: u16) -> Result<SocketAddr, String> {
+    let ip: IpAddr = ip.parse().map_err(|e| format!("Invalid IP {}: {}", ip, e))?;
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// Resolves the addresses to try for a host, preferring the IP address if known.
+fn resolve_socket_addresses(
+    hostname: &str,
+    ip_address: Option<&str>,
+    port: u16,
+) -> Result<Vec<SocketAddr>, ShareListError> {
+    if let Some(ip) = ip_address {
+        let ip: IpAddr = ip
+            .parse()
+            .map_err(|e| ShareListError::ResolutionFailed(format!("Invalid IP {}: {}", ip, e)))?;
+        return Ok(vec![SocketAddr::new(ip, port)]);
+    }
+    let addresses: Vec<SocketAddr> = (hostname, port)
+        .to_socket_addrs()
+        .map_err(|e| ShareListError::ResolutionFailed(format!("Couldn't resolve {}: {}", hostname, e)))?
+        .collect();
+    if addresses.is_empty() {
+        return Err(ShareListError::ResolutionFailed(format!(
+            "No addresses found for {}",
+            hostname
+        )));
+    }
+    Ok(addresses)
+}
+
+/// Opens and closes a TCP connection to the first address that accepts one.
+/// Returns how long the successful connection took.
+fn check_reachable(addresses: &[SocketAddr], connect_timeout: Duration) -> std::io::Result<Duration> {
+    let mut last_error = std::io::Error::new(std::io::ErrorKind::NotFound, "No addresses to connect to");
+    for address in addresses {
+        let started = Instant::now();
+        match TcpStream::connect_timeout(address, connect_timeout) {
+            Ok(_stream) => return Ok(started.elapsed()),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
 /// Lists shares without checking cache.
 /// Uses IP address when available to bypass mDNS resolution issues with smb-rs.
 /// Falls back to smbutil on macOS when smb-rs fails with protocol errors.
+/// Retries with backoff on timeouts and unreachable hosts, see `LIST_SHARES_RETRY_DELAYS`.
 async fn list_shares_uncached(
     hostname: &str,
     ip_address: Option<&str>,
     port: u16,
     credentials: Option<(&str, &str)>,
+    with_capacity: bool,
+    credentials_first: Option<KnownNetworkShare>,
 ) -> Result<ShareListResult, ShareListError> {
     // Debug log the incoming params
     debug!(
@@ -207,25 +546,56 @@ async fn list_shares_uncached(
         credentials.is_some()
     );
 
-    // Try smb-rs first
-    match list_shares_smb_rs(hostname, ip_address, port, credentials).await {
-        Ok(result) => Ok(result),
-        Err(ShareListError::ProtocolError(ref msg)) => {
-            // Protocol error (likely RPC incompatibility with Samba)
-            // Try smbutil fallback on macOS
-            debug!("smb-rs failed with protocol error: {}, trying smbutil fallback", msg);
-            list_shares_smbutil(hostname, ip_address, port).await
+    with_retries(&LIST_SHARES_RETRY_DELAYS, || async {
+        // Try smb-rs first
+        let known_share = credentials_first.as_ref();
+        match list_shares_smb_rs(hostname, ip_address, port, credentials, with_capacity, known_share).await {
+            Ok(result) => Ok(result),
+            Err(ShareListError::ProtocolError(ref msg)) => {
+                // Protocol error (likely RPC incompatibility with Samba)
+                // Try smbutil fallback on macOS
+                debug!("smb-rs failed with protocol error: {}, trying smbutil fallback", msg);
+                list_shares_smbutil(hostname, ip_address, port).await
+            }
+            Err(e) => Err(e),
+        }
+    })
+    .await
+}
+
+/// Runs `attempt`, and again after each delay for as long as it fails with a transient error.
+/// Other errors, like auth errors, are returned right away.
+async fn with_retries<T, F, Fut>(delays: &[Duration], mut attempt: F) -> Result<T, ShareListError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ShareListError>>,
+{
+    let mut delays = delays.iter();
+    loop {
+        match attempt().await {
+            Err(e) if e.is_transient() => match delays.next() {
+                Some(delay) => {
+                    debug!("Listing shares failed with {}, retrying in {}ms", e, delay.as_millis());
+                    tokio::time::sleep(*delay).await;
+                }
+                None => return Err(e),
+            },
+            result => return result,
         }
-        Err(e) => Err(e),
     }
 }
 
 /// Lists shares using smb-rs (pure Rust implementation).
+///
+/// Tries guest access first, then the credentials. With `credentials_first`, the known share of a host the user
+/// connected to with credentials last time, it tries the credentials first instead, and falls back to guest.
 async fn list_shares_smb_rs(
     hostname: &str,
     ip_address: Option<&str>,
     port: u16,
     credentials: Option<(&str, &str)>,
+    with_capacity: bool,
+    credentials_first: Option<&KnownNetworkShare>,
 ) -> Result<ShareListResult, ShareListError> {
     // Create SMB client with unsigned guest access allowed
     // (some servers like Samba don't require signing for anonymous access)
@@ -247,12 +617,46 @@ async fn list_shares_smb_rs(
         server_name,
         credentials.is_some()
     );
+    // Same name the shares are listed with, see try_list_shares_as_guest
+    let connect_name = if ip_address.is_some() { server_name } else { hostname };
+
+    if let (Some(known_share), Some((user, pass))) = (credentials_first, credentials) {
+        debug!(
+            "Connected with credentials last time, trying them first with user: {}",
+            user
+        );
+        let auth_client = new_authenticated_client();
+        match try_list_shares_authenticated(&auth_client, server_name, hostname, ip_address, port, user, pass).await {
+            Ok(shares) if !shares.is_empty() => {
+                debug!("Authenticated access succeeded, got {} raw shares", shares.len());
+                remember_credentials_connection(known_share, user);
+                // Guest access wasn't tried, so go by what was detected last time
+                let auth_mode = match known_share.last_known_auth_options {
+                    AuthOptions::CredentialsOnly => AuthMode::CredsRequired,
+                    AuthOptions::GuestOnly | AuthOptions::GuestOrCredentials => AuthMode::GuestAllowed,
+                };
+                let credentials = (user, pass);
+                return Ok(share_list_result(
+                    shares,
+                    auth_mode,
+                    with_capacity,
+                    &auth_client,
+                    connect_name,
+                    credentials,
+                )
+                .await);
+            }
+            Ok(_) => debug!("Credentials gave no shares, trying guest access"),
+            Err(e) => debug!("Credentials failed: {}, trying guest access", e),
+        }
+    }
 
     // Try guest access first, then authenticated
-    let (shares, auth_mode) = match try_list_shares_as_guest(&client, server_name, hostname, ip_address, port).await {
+    let guest_result = try_list_shares_as_guest(&client, server_name, hostname, ip_address, port).await;
+    let (shares, auth_mode, auth_client) = match guest_result {
         Ok(shares) => {
             debug!("Guest access succeeded, got {} raw shares", shares.len());
-            (shares, AuthMode::GuestAllowed)
+            (shares, AuthMode::GuestAllowed, None)
         }
         Err(e) if is_auth_error(&e) => {
             debug!("Guest failed with auth error: {}", e);
@@ -263,9 +667,7 @@ async fn list_shares_smb_rs(
                 // IMPORTANT: Create a fresh client for authenticated attempt.
                 // smb-rs reuses connections internally, so if we use the same client,
                 // the failed guest connection can interfere with the auth attempt.
-                let mut auth_config = ClientConfig::default();
-                auth_config.connection.allow_unsigned_guest_access = false; // Require proper auth
-                let auth_client = Client::new(auth_config);
+                let auth_client = new_authenticated_client();
 
                 match try_list_shares_authenticated(&auth_client, server_name, hostname, ip_address, port, user, pass)
                     .await
@@ -273,7 +675,7 @@ async fn list_shares_smb_rs(
                     Ok(shares) if !shares.is_empty() => {
                         // smb-rs auth worked and returned shares
                         debug!("Authenticated access succeeded, got {} raw shares", shares.len());
-                        (shares, AuthMode::CredsRequired)
+                        (shares, AuthMode::CredsRequired, Some(auth_client))
                     }
                     Ok(_) | Err(_) => {
                         // smb-rs returned 0 shares or failed - fall back to smbutil with auth
@@ -315,19 +717,57 @@ async fn list_shares_smb_rs(
         }
     };
 
+    let (listing_client, listing_credentials) = match &auth_client {
+        Some(auth_client) => (auth_client, credentials.unwrap_or(("Guest", ""))),
+        None => (&client, ("Guest", "")),
+    };
+    let result = share_list_result(
+        shares,
+        auth_mode,
+        with_capacity,
+        listing_client,
+        connect_name,
+        listing_credentials,
+    )
+    .await;
+    Ok(result)
+}
+
+/// Creates a client for authenticated access, which requires signing unlike the guest one.
+fn new_authenticated_client() -> Client {
+    let mut auth_config = ClientConfig::default();
+    auth_config.connection.allow_unsigned_guest_access = false; // Require proper auth
+    Client::new(auth_config)
+}
+
+/// Builds the result from the raw share list: keeps the disk shares, and adds their capacity if asked, connecting
+/// to each share with the client and credentials the list was got with.
+async fn share_list_result(
+    shares: Vec<ShareInfo1>,
+    auth_mode: AuthMode,
+    with_capacity: bool,
+    client: &Client,
+    connect_name: &str,
+    (username, password): (&str, &str),
+) -> ShareListResult {
     // Filter to disk shares only
-    let filtered_shares = filter_disk_shares(shares);
+    let mut filtered_shares = filter_disk_shares(shares);
     debug!(
         "After filtering: {} disk shares (from {} raw)",
         filtered_shares.len(),
         filtered_shares.len()
     );
 
-    Ok(ShareListResult {
+    if with_capacity {
+        add_share_capacities(client, connect_name, username, password, &mut filtered_shares).await;
+    }
+
+    ShareListResult {
         shares: filtered_shares,
         auth_mode,
         from_cache: false,
-    })
+        auth_changed: false,
+    }
 }
 
 /// Lists shares using macOS smbutil command as fallback.
@@ -385,6 +825,7 @@ async fn list_shares_smbutil(
         shares,
         auth_mode: AuthMode::GuestAllowed,
         from_cache: false,
+        auth_changed: false,
     })
 }
 
@@ -454,6 +895,7 @@ async fn list_shares_smbutil_authenticated_from_keychain(
         shares,
         auth_mode: AuthMode::CredsRequired, // User is authenticated via Keychain
         from_cache: false,
+        auth_changed: false,
     })
 }
 
@@ -537,6 +979,7 @@ async fn list_shares_smbutil_with_auth(
         shares,
         auth_mode: AuthMode::CredsRequired,
         from_cache: false,
+        auth_changed: false,
     })
 }
 
@@ -629,6 +1072,9 @@ fn parse_smbutil_output(output: &str) -> Vec<ShareInfo> {
             name,
             is_disk: true,
             comment,
+            // smbutil doesn't report capacity
+            total_bytes: None,
+            free_bytes: None,
         });
     }
 
@@ -648,9 +1094,7 @@ async fn try_list_shares_as_guest(
         // Determine how to connect: by IP (preferred) or by hostname
         let connect_name = if let Some(ip) = ip_address {
             // Use IP address for connection to bypass mDNS resolution issues
-            let socket_addr: SocketAddr = format!("{}:{}", ip, port)
-                .parse()
-                .map_err(|e| format!("Invalid IP {}: {}", ip, e))?;
+            let socket_addr = socket_address(ip, port)?;
 
             debug!(
                 "Connecting to server_name='{}' at socket_addr='{}'",
@@ -707,9 +1151,7 @@ async fn try_list_shares_authenticated(
         // Determine how to connect: by IP (preferred) or by hostname
         let connect_name = if let Some(ip) = ip_address {
             // Use IP address for connection to bypass mDNS resolution issues
-            let socket_addr: SocketAddr = format!("{}:{}", ip, port)
-                .parse()
-                .map_err(|e| format!("Invalid IP {}: {}", ip, e))?;
+            let socket_addr = socket_address(ip, port)?;
 
             client
                 .connect_to_address(server_name, socket_addr)
@@ -739,6 +1181,79 @@ async fn try_list_shares_authenticated(
     .map_err(|_| format!("Timeout after {}s", LIST_SHARES_TIMEOUT.as_secs()))?
 }
 
+/// Fills in the total and free bytes of each share, connecting to each one in turn.
+/// Shares that can't be queried are left without capacity.
+async fn add_share_capacities(
+    client: &Client,
+    connect_name: &str,
+    username: &str,
+    password: &str,
+    shares: &mut [ShareInfo],
+) {
+    for share in shares.iter_mut() {
+        let query = query_share_capacity(client, connect_name, &share.name, username, password);
+        match timeout(SHARE_CAPACITY_TIMEOUT, query).await {
+            Ok(Ok((total_bytes, free_bytes))) => {
+                share.total_bytes = Some(total_bytes);
+                share.free_bytes = Some(free_bytes);
+            }
+            Ok(Err(e)) => debug!("Couldn't get capacity of share {}: {}", share.name, e),
+            Err(_) => debug!("Timeout getting capacity of share {}", share.name),
+        }
+    }
+}
+
+/// Connects to a share and asks for the size of the disk it's on. Returns (total bytes, free bytes).
+async fn query_share_capacity(
+    client: &Client,
+    connect_name: &str,
+    share_name: &str,
+    username: &str,
+    password: &str,
+) -> Result<(u64, u64), String> {
+    let share_path: UncPath = format!(r"\\{}\{}", connect_name, share_name)
+        .parse()
+        .map_err(|e| format!("Invalid share path: {}", e))?;
+    client
+        .share_connect(&share_path, username, password.to_string())
+        .await
+        .map_err(|e| format!("Tree connect failed: {}", e))?;
+    let access = FileAccessMask::new().with_generic_read(true);
+    let Resource::Directory(root) = client
+        .create_file(&share_path, &FileCreateArgs::make_open_existing(access))
+        .await
+        .map_err(|e| format!("Opening share root failed: {}", e))?
+    else {
+        return Err("Share root isn't a directory".to_string());
+    };
+    let info = root
+        .query_fs_info::<FileFsFullSizeInformation>()
+        .await
+        .map_err(|e| format!("Query info failed: {}", e))?;
+    Ok(capacity_from_allocation_units(
+        info.total_allocation_units,
+        info.caller_available_allocation_units,
+        info.sectors_per_allocation_unit,
+        info.bytes_per_sector,
+    ))
+}
+
+/// Converts the allocation unit counts of `FileFsFullSizeInformation` to (total bytes, free bytes).
+///
+/// Uses the units available to the caller for free space, which accounts for quotas.
+fn capacity_from_allocation_units(
+    total_units: u64,
+    available_units: u64,
+    sectors_per_unit: u32,
+    bytes_per_sector: u32,
+) -> (u64, u64) {
+    let unit_size = u64::from(sectors_per_unit) * u64::from(bytes_per_sector);
+    (
+        total_units.saturating_mul(unit_size),
+        available_units.saturating_mul(unit_size),
+    )
+}
+
 /// Checks if an error is an authentication error (including signing requirement).
 fn is_auth_error(err: &str) -> bool {
     let lower = err.to_lowercase();
@@ -797,6 +1312,8 @@ fn filter_disk_shares(shares: Vec<ShareInfo1>) -> Vec<ShareInfo> {
                 name,
                 is_disk: true,
                 comment,
+                total_bytes: None,
+                free_bytes: None,
             })
         })
         .collect()
@@ -876,12 +1393,126 @@ mod tests {
         }
     }
 
+    fn known_share(mode: ConnectionMode) -> KnownNetworkShare {
+        KnownNetworkShare {
+            server_name: "Office NAS".to_string(),
+            share_name: String::new(),
+            protocol: "smb".to_string(),
+            last_connected_at: "2026-01-01T00:00:00Z".to_string(),
+            last_connection_mode: mode,
+            last_known_auth_options: AuthOptions::CredentialsOnly,
+            username: Some("david".to_string()),
+            connect_count: 1,
+            recent_connections: vec!["2026-01-01T00:00:00Z".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_known_credentials_host_skips_guest_attempt() {
+        let known = known_share(ConnectionMode::Credentials);
+        assert!(tries_credentials_first(&known, Some(("david", "secret"))));
+    }
+
+    #[test]
+    fn test_guest_is_tried_first_otherwise() {
+        // Connected as guest last time
+        assert!(!tries_credentials_first(
+            &known_share(ConnectionMode::Guest),
+            Some(("david", "secret"))
+        ));
+        // No credentials to try
+        assert!(!tries_credentials_first(
+            &known_share(ConnectionMode::Credentials),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_auth_change_from_guest_to_credentials() {
+        assert!(auth_options_changed(AuthOptions::GuestOnly, AuthMode::CredsRequired));
+        assert!(auth_options_changed(
+            AuthOptions::GuestOrCredentials,
+            AuthMode::CredsRequired
+        ));
+    }
+
+    #[test]
+    fn test_auth_change_from_credentials_to_guest() {
+        assert!(auth_options_changed(
+            AuthOptions::CredentialsOnly,
+            AuthMode::GuestAllowed
+        ));
+    }
+
+    #[test]
+    fn test_unchanged_or_unknown_auth_is_not_a_change() {
+        assert!(!auth_options_changed(AuthOptions::GuestOnly, AuthMode::GuestAllowed));
+        assert!(!auth_options_changed(
+            AuthOptions::GuestOrCredentials,
+            AuthMode::GuestAllowed
+        ));
+        assert!(!auth_options_changed(
+            AuthOptions::CredentialsOnly,
+            AuthMode::CredsRequired
+        ));
+        assert!(!auth_options_changed(AuthOptions::GuestOnly, AuthMode::Unknown));
+    }
+
+    #[test]
+    fn test_socket_address_from_ipv4() {
+        let address = socket_address("192.168.1.20", 445).unwrap();
+        assert_eq!(address, "192.168.1.20:445".parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_socket_address_from_ipv6() {
+        let address = socket_address("fe80::1c2a:3bff:fe4d:5e6f", 445).unwrap();
+        assert!(address.is_ipv6());
+        assert_eq!(address.port(), 445);
+        assert_eq!(address.to_string(), "[fe80::1c2a:3bff:fe4d:5e6f]:445");
+
+        let loopback = socket_address("::1", 9445).unwrap();
+        assert_eq!(loopback, "[::1]:9445".parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_socket_address_rejects_non_ips() {
+        assert!(socket_address("nas.local", 445).is_err());
+        // Already bracketed or with a port isn't an IP address
+        assert!(socket_address("[::1]", 445).is_err());
+        assert!(socket_address("192.168.1.20:445", 445).is_err());
+    }
+
+    #[test]
+    fn test_resolve_socket_addresses_prefers_ip() {
+        let addresses = resolve_socket_addresses("nas.local", Some("192.168.1.20"), 4450).unwrap();
+        assert_eq!(addresses, vec!["192.168.1.20:4450".parse::<SocketAddr>().unwrap()]);
+
+        match resolve_socket_addresses("nas.local", Some("not-an-ip"), 445) {
+            Err(ShareListError::ResolutionFailed(_)) => {}
+            other => panic!("Expected ResolutionFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_reachable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        assert!(check_reachable(&[address], Duration::from_secs(1)).is_ok());
+
+        // Nothing listens on the port anymore, so the connection is refused
+        drop(listener);
+        assert!(check_reachable(&[address], Duration::from_secs(1)).is_err());
+        assert!(check_reachable(&[], Duration::from_secs(1)).is_err());
+    }
+
     #[test]
     fn test_cache_operations() {
         let host_id = "test-host-cache";
 
         // Initially no cache
-        assert!(get_cached_shares(host_id).is_none());
+        assert!(get_cached_shares(host_id, false).is_none());
 
         // Cache something
         let result = ShareListResult {
@@ -889,14 +1520,17 @@ mod tests {
                 name: "TestShare".to_string(),
                 is_disk: true,
                 comment: None,
+                total_bytes: None,
+                free_bytes: None,
             }],
             auth_mode: AuthMode::GuestAllowed,
             from_cache: false,
+            auth_changed: false,
         };
-        cache_shares(host_id, &result);
+        cache_shares(host_id, false, &result);
 
         // Should be cached now
-        let cached = get_cached_shares(host_id);
+        let cached = get_cached_shares(host_id, false);
         assert!(cached.is_some());
         let cached = cached.unwrap();
         assert!(cached.from_cache);
@@ -905,7 +1539,196 @@ mod tests {
 
         // Invalidate
         invalidate_cache(host_id);
-        assert!(get_cached_shares(host_id).is_none());
+        assert!(get_cached_shares(host_id, false).is_none());
+    }
+
+    fn share_list(share_name: &str) -> ShareListResult {
+        ShareListResult {
+            shares: vec![ShareInfo {
+                name: share_name.to_string(),
+                is_disk: true,
+                comment: None,
+                total_bytes: None,
+                free_bytes: None,
+            }],
+            auth_mode: AuthMode::GuestAllowed,
+            from_cache: false,
+            auth_changed: false,
+        }
+    }
+
+    #[test]
+    fn test_refresh_skips_cache_but_updates_it() {
+        let host_id = "test-host-cache-refresh";
+        cache_shares(host_id, false, &share_list("Old"));
+
+        // Without refresh, the cached list is returned
+        let cached = tauri::async_runtime::block_on(cached_or_fetch(host_id, false, true, async {
+            Ok(share_list("Fetched"))
+        }))
+        .unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(cached.shares[0].name, "Old");
+
+        // With refresh, the fresh list is returned even though the cached one hasn't expired
+        let refreshed =
+            tauri::async_runtime::block_on(cached_or_fetch(host_id, false, false, async { Ok(share_list("New")) }))
+                .unwrap();
+        assert!(!refreshed.from_cache);
+        assert_eq!(refreshed.shares[0].name, "New");
+
+        // And it replaced the cached one
+        let cached = tauri::async_runtime::block_on(cached_or_fetch(host_id, false, true, async {
+            Ok(share_list("Fetched"))
+        }))
+        .unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(cached.shares[0].name, "New");
+
+        invalidate_cache(host_id);
+    }
+
+    #[test]
+    fn test_listing_many_hosts_is_bounded_and_complete() {
+        use std::sync::atomic::AtomicUsize;
+
+        let host_ids: Vec<String> = (0..20).map(|i| format!("test-host-bounded-{}", i)).collect();
+        let running = AtomicUsize::new(0);
+        let most_running = AtomicUsize::new(0);
+
+        let results = tauri::async_runtime::block_on(list_bounded(host_ids.clone(), 3, |host_id| {
+            let running = &running;
+            let most_running = &most_running;
+            async move {
+                let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                most_running.fetch_max(now_running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(share_list(&host_id))
+            }
+        }));
+
+        assert_eq!(results.len(), 20);
+        for host_id in &host_ids {
+            assert_eq!(results[host_id].as_ref().unwrap().shares[0].name, *host_id);
+        }
+        assert!(most_running.load(Ordering::SeqCst) <= 3);
+        assert!(
+            most_running.load(Ordering::SeqCst) > 1,
+            "Expected hosts to be listed concurrently"
+        );
+    }
+
+    #[test]
+    fn test_listing_many_hosts_skips_cached_ones() {
+        let cached_host = "test-host-bounded-cached";
+        cache_shares(cached_host, false, &share_list("Cached"));
+        let calls = std::sync::Mutex::new(Vec::new());
+
+        let results = tauri::async_runtime::block_on(list_bounded(
+            vec![cached_host.to_string(), "test-host-bounded-fresh".to_string()],
+            8,
+            |host_id| {
+                calls.lock().unwrap().push(host_id);
+                async { Err(ShareListError::Timeout("dropped".to_string())) }
+            },
+        ));
+
+        assert_eq!(*calls.lock().unwrap(), vec!["test-host-bounded-fresh".to_string()]);
+        assert_eq!(results[cached_host].as_ref().unwrap().shares[0].name, "Cached");
+        assert!(results[cached_host].as_ref().unwrap().from_cache);
+        assert!(matches!(
+            results["test-host-bounded-fresh"],
+            Err(ShareListError::Timeout(_))
+        ));
+        invalidate_cache(cached_host);
+    }
+
+    #[test]
+    fn test_cache_keeps_lists_with_capacity_apart() {
+        let host_id = "test-host-cache-capacity";
+        let result = ShareListResult {
+            shares: vec![ShareInfo {
+                name: "TestShare".to_string(),
+                is_disk: true,
+                comment: Some("Team files".to_string()),
+                total_bytes: Some(2_000_000),
+                free_bytes: Some(500_000),
+            }],
+            auth_mode: AuthMode::CredsRequired,
+            from_cache: false,
+            auth_changed: false,
+        };
+        cache_shares(host_id, true, &result);
+
+        // A list without capacity doesn't satisfy a request for one, and the other way around
+        assert!(get_cached_shares(host_id, false).is_none());
+        let cached = get_cached_shares(host_id, true).unwrap();
+        assert_eq!(cached.shares[0].total_bytes, Some(2_000_000));
+        assert_eq!(cached.shares[0].free_bytes, Some(500_000));
+        assert_eq!(get_cached_shares_auth_mode(host_id), Some(AuthMode::CredsRequired));
+
+        invalidate_cache(host_id);
+        assert!(get_cached_shares(host_id, true).is_none());
+        assert_eq!(get_cached_shares_auth_mode(host_id), None);
+    }
+
+    #[test]
+    fn test_retries_transient_errors_until_success() {
+        let attempts = std::cell::Cell::new(0);
+        let result = tauri::async_runtime::block_on(with_retries(&[Duration::ZERO; 3], || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                match attempt {
+                    1 => Err(ShareListError::Timeout("dropped".to_string())),
+                    2 => Err(ShareListError::HostUnreachable("refused".to_string())),
+                    _ => Ok(attempt),
+                }
+            }
+        }));
+
+        assert!(matches!(result, Ok(3)));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn test_gives_up_after_last_retry() {
+        let attempts = std::cell::Cell::new(0);
+        let result: Result<(), _> = tauri::async_runtime::block_on(with_retries(&[Duration::ZERO; 3], || {
+            attempts.set(attempts.get() + 1);
+            async { Err(ShareListError::Timeout("dropped".to_string())) }
+        }));
+
+        assert!(matches!(result, Err(ShareListError::Timeout(_))));
+        assert_eq!(attempts.get(), 4);
+    }
+
+    #[test]
+    fn test_does_not_retry_auth_errors() {
+        let attempts = std::cell::Cell::new(0);
+        let result: Result<(), _> = tauri::async_runtime::block_on(with_retries(&[Duration::ZERO; 3], || {
+            attempts.set(attempts.get() + 1);
+            async { Err(ShareListError::AuthRequired("log in".to_string())) }
+        }));
+
+        assert!(matches!(result, Err(ShareListError::AuthRequired(_))));
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn test_capacity_from_allocation_units() {
+        // 8 sectors of 512 bytes per unit = 4 KiB units
+        assert_eq!(
+            capacity_from_allocation_units(1000, 250, 8, 512),
+            (4_096_000, 1_024_000)
+        );
+        assert_eq!(capacity_from_allocation_units(0, 0, 8, 512), (0, 0));
+        // Doesn't overflow on absurd values
+        assert_eq!(
+            capacity_from_allocation_units(u64::MAX, u64::MAX, u32::MAX, u32::MAX),
+            (u64::MAX, u64::MAX)
+        );
     }
 
     #[test]
@@ -946,4 +1769,27 @@ ADMIN$                                          Disk    Admin share
         let web = shares.iter().find(|s| s.name == "Web").unwrap();
         assert!(web.comment.is_none());
     }
+
+    #[test]
+    fn test_smbutil_shares_have_no_capacity() {
+        // smbutil doesn't report sizes, so they're unknown rather than zero
+        let output = r#"Share                                           Type    Comments
+-------------------------------
+Public                                          Disk    System default share
+Backups                                         Disk    1 TB of backups
+
+2 shares listed
+"#;
+
+        let shares = parse_smbutil_output(output);
+
+        assert_eq!(shares.len(), 2);
+        for share in &shares {
+            assert_eq!(share.total_bytes, None, "{} shouldn't have a total size", share.name);
+            assert_eq!(share.free_bytes, None, "{} shouldn't have a free size", share.name);
+        }
+        let json = serde_json::to_string(&shares[0]).unwrap();
+        assert!(json.contains(r#""totalBytes":null"#));
+        assert!(json.contains(r#""freeBytes":null"#));
+    }
 }
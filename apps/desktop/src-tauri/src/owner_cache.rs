@@ -0,0 +1,297 @@
+//! Cached uid → user name and gid → group name resolution for directory listings.
+//!
+//! Each cache miss costs a `getpwuid`/`getgrgid` call, and there's no batch version of those.
+//! So listings call `prewarm_owners` with the deduplicated ids of all entries once, before the
+//! per-entry loop, which then always hits the cache.
+//!
+//! Both caches are bounded LRUs so that browsing many directories owned by many different
+//! users doesn't grow them forever. Public so benchmarks can use it.
+//!
+//! The other way around, `resolve_owner_ids` turns names into ids for changing owners. That's rare, so it's not
+//! cached.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, RwLock};
+use uzers::{Groups, Users, get_group_by_gid, get_user_by_uid};
+
+/// Maximum number of names kept per cache. Way more than the distinct owners on a typical Mac.
+const CACHE_CAPACITY: usize = 1024;
+
+static OWNER_CACHE: LazyLock<BoundedNameCache> = LazyLock::new(|| BoundedNameCache::new(CACHE_CAPACITY));
+static GROUP_CACHE: LazyLock<BoundedNameCache> = LazyLock::new(|| BoundedNameCache::new(CACHE_CAPACITY));
+
+/// A cached name plus when it was last used, for LRU eviction.
+struct CachedName {
+    name: String,
+    /// Atomic so that cache hits can bump it while only holding the read lock
+    last_used: AtomicU64,
+}
+
+/// Bounded id → name cache that evicts the least recently used name when full.
+struct BoundedNameCache {
+    entries: RwLock<HashMap<u32, CachedName>>,
+    capacity: usize,
+    /// Monotonic counter used as the "time" of each access
+    clock: AtomicU64,
+}
+
+impl BoundedNameCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            capacity,
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn get(&self, id: u32) -> Option<String> {
+        let entries = self.entries.read().ok()?;
+        let cached = entries.get(&id)?;
+        cached.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(cached.name.clone())
+    }
+
+    /// Inserts several names under a single write lock.
+    fn insert_many(&self, names: Vec<(u32, String)>) {
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+        for (id, name) in names {
+            if !entries.contains_key(&id) && entries.len() >= self.capacity {
+                Self::evict_least_recently_used(&mut entries);
+            }
+            let last_used = AtomicU64::new(self.tick());
+            entries.insert(id, CachedName { name, last_used });
+        }
+    }
+
+    fn evict_least_recently_used(entries: &mut HashMap<u32, CachedName>) {
+        let oldest = entries
+            .iter()
+            .min_by_key(|(_, cached)| cached.last_used.load(Ordering::Relaxed))
+            .map(|(id, _)| *id);
+        if let Some(id) = oldest {
+            entries.remove(&id);
+        }
+    }
+
+    fn contains(&self, id: u32) -> bool {
+        self.entries.read().map(|e| e.contains_key(&id)).unwrap_or(false)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.read().map(|e| e.len()).unwrap_or(0)
+    }
+
+    fn clear(&self) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.clear();
+        }
+    }
+}
+
+fn resolve_user_name(uid: u32) -> String {
+    get_user_by_uid(uid)
+        .map(|u| u.name().to_string_lossy().into_owned())
+        .unwrap_or_else(|| uid.to_string())
+}
+
+fn resolve_group_name(gid: u32) -> String {
+    get_group_by_gid(gid)
+        .map(|g| g.name().to_string_lossy().into_owned())
+        .unwrap_or_else(|| gid.to_string())
+}
+
+/// Resolves a uid to a user name, with caching. Falls back to the uid as a string for unknown users.
+pub fn get_owner_name(uid: u32) -> String {
+    if let Some(name) = OWNER_CACHE.get(uid) {
+        return name;
+    }
+    let name = resolve_user_name(uid);
+    OWNER_CACHE.insert_many(vec![(uid, name.clone())]);
+    name
+}
+
+/// Resolves a gid to a group name, with caching. Falls back to the gid as a string for unknown groups.
+pub fn get_group_name(gid: u32) -> String {
+    if let Some(name) = GROUP_CACHE.get(gid) {
+        return name;
+    }
+    let name = resolve_group_name(gid);
+    GROUP_CACHE.insert_many(vec![(gid, name.clone())]);
+    name
+}
+
+/// Resolves a user and a group name to ids, like `chown` does: names that aren't found but are numbers are taken
+/// as ids. None stays None, to leave that part unchanged.
+///
+/// # Arguments
+/// * `lookup` - Where to look names up, usually a `uzers::UsersCache`
+/// * `owner_name` - User name, or None
+/// * `group_name` - Group name, or None
+///
+/// # Returns
+/// The uid and gid, or an error naming the user or group that doesn't exist.
+pub fn resolve_owner_ids(
+    lookup: &(impl Users + Groups),
+    owner_name: Option<&str>,
+    group_name: Option<&str>,
+) -> Result<(Option<u32>, Option<u32>), String> {
+    let uid = owner_name
+        .map(|name| {
+            lookup
+                .get_user_by_name(name)
+                .map(|user| user.uid())
+                .or_else(|| name.parse().ok())
+                .ok_or_else(|| format!("Unknown user: {}", name))
+        })
+        .transpose()?;
+    let gid = group_name
+        .map(|name| {
+            lookup
+                .get_group_by_name(name)
+                .map(|group| group.gid())
+                .or_else(|| name.parse().ok())
+                .ok_or_else(|| format!("Unknown group: {}", name))
+        })
+        .transpose()?;
+    Ok((uid, gid))
+}
+
+/// Resolves all not-yet-cached uids and gids in one go, so later lookups for them are cache hits.
+/// Duplicates are fine, each id is resolved at most once.
+///
+/// # Arguments
+/// * `uids` - User ids of the entries about to be listed
+/// * `gids` - Group ids of the entries about to be listed
+pub fn prewarm_owners(uids: impl IntoIterator<Item = u32>, gids: impl IntoIterator<Item = u32>) {
+    let missing_uids: HashSet<u32> = uids.into_iter().filter(|uid| !OWNER_CACHE.contains(*uid)).collect();
+    let missing_gids: HashSet<u32> = gids.into_iter().filter(|gid| !GROUP_CACHE.contains(*gid)).collect();
+
+    if !missing_uids.is_empty() {
+        OWNER_CACHE.insert_many(
+            missing_uids
+                .into_iter()
+                .map(|uid| (uid, resolve_user_name(uid)))
+                .collect(),
+        );
+    }
+    if !missing_gids.is_empty() {
+        GROUP_CACHE.insert_many(
+            missing_gids
+                .into_iter()
+                .map(|gid| (gid, resolve_group_name(gid)))
+                .collect(),
+        );
+    }
+}
+
+/// Empties both caches. Used by benchmarks to measure cold lookups.
+pub fn clear_owner_caches() {
+    OWNER_CACHE.clear();
+    GROUP_CACHE.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_cache_evicts_when_full() {
+        let cache = BoundedNameCache::new(3);
+        cache.insert_many(vec![(1, "one".into()), (2, "two".into()), (3, "three".into())]);
+        cache.insert_many(vec![(4, "four".into())]);
+
+        assert_eq!(cache.len(), 3);
+        assert!(!cache.contains(1), "Oldest entry should be evicted");
+        assert_eq!(cache.get(4), Some("four".to_string()));
+    }
+
+    #[test]
+    fn test_bounded_cache_evicts_least_recently_used() {
+        let cache = BoundedNameCache::new(3);
+        cache.insert_many(vec![(1, "one".into()), (2, "two".into()), (3, "three".into())]);
+
+        // Touch 1 so 2 becomes the least recently used
+        assert_eq!(cache.get(1), Some("one".to_string()));
+        cache.insert_many(vec![(4, "four".into())]);
+
+        assert!(cache.contains(1));
+        assert!(!cache.contains(2));
+        assert!(cache.contains(3));
+        assert!(cache.contains(4));
+    }
+
+    #[test]
+    fn test_bounded_cache_reinsert_does_not_evict() {
+        let cache = BoundedNameCache::new(2);
+        cache.insert_many(vec![(1, "one".into()), (2, "two".into())]);
+        cache.insert_many(vec![(2, "two again".into())]);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(2), Some("two again".to_string()));
+    }
+
+    #[test]
+    fn test_prewarm_then_lookup_hits_cache() {
+        // uid/gid 0 exists on every Unix system
+        prewarm_owners([0, 0, 0], [0]);
+        assert!(OWNER_CACHE.contains(0));
+        assert!(GROUP_CACHE.contains(0));
+        assert!(!get_owner_name(0).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_uid_falls_back_to_number() {
+        assert_eq!(get_owner_name(4_000_000_123), "4000000123");
+    }
+
+    fn create_mock_users() -> uzers::mock::MockUsers {
+        let mut users = uzers::mock::MockUsers::with_current_uid(501);
+        users.add_user(uzers::User::new(501, "alice", 20));
+        users.add_group(uzers::Group::new(20, "staff"));
+        users
+    }
+
+    #[test]
+    fn test_resolve_owner_ids_looks_up_names() {
+        let users = create_mock_users();
+
+        assert_eq!(
+            resolve_owner_ids(&users, Some("alice"), Some("staff")),
+            Ok((Some(501), Some(20)))
+        );
+        assert_eq!(resolve_owner_ids(&users, None, Some("staff")), Ok((None, Some(20))));
+        assert_eq!(resolve_owner_ids(&users, None, None), Ok((None, None)));
+    }
+
+    #[test]
+    fn test_resolve_owner_ids_takes_numbers_as_ids() {
+        let users = create_mock_users();
+
+        assert_eq!(
+            resolve_owner_ids(&users, Some("502"), Some("80")),
+            Ok((Some(502), Some(80)))
+        );
+    }
+
+    #[test]
+    fn test_resolve_owner_ids_names_unknown_user_or_group() {
+        let users = create_mock_users();
+
+        assert_eq!(
+            resolve_owner_ids(&users, Some("mallory"), Some("staff")),
+            Err("Unknown user: mallory".to_string())
+        );
+        assert_eq!(
+            resolve_owner_ids(&users, Some("alice"), Some("wheel")),
+            Err("Unknown group: wheel".to_string())
+        );
+    }
+}
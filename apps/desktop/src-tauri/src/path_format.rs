@@ -0,0 +1,122 @@
+//! Formatting paths for the clipboard: as is, shell-escaped, or as `file://` URLs.
+
+use serde::{Deserialize, Serialize};
+
+/// How to format paths when copying them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PathFormat {
+    /// The path as is, for example `/Users/me/My file.txt`
+    Plain,
+    /// Quoted for POSIX shells when needed, for example `'/Users/me/My file.txt'`
+    PosixEscaped,
+    /// A percent-encoded file URL, for example `file:///Users/me/My%20file.txt`
+    FileUrl,
+}
+
+/// Formats each path and joins them with newlines.
+pub fn format_paths(paths: &[String], format: PathFormat) -> String {
+    paths
+        .iter()
+        .map(|path| format_path(path, format))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats a single path.
+pub fn format_path(path: &str, format: PathFormat) -> String {
+    match format {
+        PathFormat::Plain => path.to_string(),
+        PathFormat::PosixEscaped => shell_escape(path),
+        PathFormat::FileUrl => format!("file://{}", percent_encode_path(path)),
+    }
+}
+
+/// Quotes a string for POSIX shells. Strings made only of safe characters are returned unchanged.
+/// Everything else is wrapped in single quotes, with embedded single quotes written as `'\''`.
+fn shell_escape(s: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+    if !s.is_empty() && s.chars().all(is_safe) {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Percent-encodes a path for use in a URL, keeping `/` separators and RFC 3986 unreserved characters.
+/// Non-ASCII characters are encoded as their UTF-8 bytes.
+fn percent_encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACES: &str = "/Users/test/My Documents/report final.pdf";
+    const UNICODE: &str = "/Users/test/Fotók/café ☕.jpg";
+
+    #[test]
+    fn test_plain_keeps_path_unchanged() {
+        assert_eq!(format_path(SPACES, PathFormat::Plain), SPACES);
+        assert_eq!(format_path(UNICODE, PathFormat::Plain), UNICODE);
+    }
+
+    #[test]
+    fn test_posix_escaped_quotes_spaces() {
+        assert_eq!(
+            format_path(SPACES, PathFormat::PosixEscaped),
+            "'/Users/test/My Documents/report final.pdf'"
+        );
+    }
+
+    #[test]
+    fn test_posix_escaped_quotes_unicode() {
+        assert_eq!(
+            format_path(UNICODE, PathFormat::PosixEscaped),
+            "'/Users/test/Fotók/café ☕.jpg'"
+        );
+    }
+
+    #[test]
+    fn test_posix_escaped_leaves_safe_paths_alone() {
+        assert_eq!(
+            format_path("/usr/local/bin/cmdr-1.0", PathFormat::PosixEscaped),
+            "/usr/local/bin/cmdr-1.0"
+        );
+    }
+
+    #[test]
+    fn test_posix_escaped_handles_single_quotes() {
+        assert_eq!(format_path("/tmp/it's", PathFormat::PosixEscaped), r"'/tmp/it'\''s'");
+    }
+
+    #[test]
+    fn test_file_url_encodes_spaces() {
+        assert_eq!(
+            format_path(SPACES, PathFormat::FileUrl),
+            "file:///Users/test/My%20Documents/report%20final.pdf"
+        );
+    }
+
+    #[test]
+    fn test_file_url_encodes_unicode() {
+        assert_eq!(
+            format_path(UNICODE, PathFormat::FileUrl),
+            "file:///Users/test/Fot%C3%B3k/caf%C3%A9%20%E2%98%95.jpg"
+        );
+    }
+
+    #[test]
+    fn test_multiple_paths_joined_with_newlines() {
+        let paths = vec!["/a b".to_string(), "/c".to_string()];
+        assert_eq!(format_paths(&paths, PathFormat::PosixEscaped), "'/a b'\n/c");
+    }
+}
@@ -0,0 +1,188 @@
+//! Session state persistence for crash recovery.
+//!
+//! Keeps the most recent directory, sort, and selected index of both panes in `session-state.json`,
+//! so the next launch (even after a crash) can put the user back where they were.
+//! Saves are debounced, since the frontend reports every cursor move, and written atomically
+//! (temp file + rename) so a crash mid-write never leaves a corrupt file behind.
+
+use crate::file_system::{SortColumn, SortOrder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::Manager;
+
+const SESSION_STATE_FILE_NAME: &str = "session-state.json";
+
+/// How long to wait for more updates before writing to disk.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// State of a single pane.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaneSessionState {
+    /// Directory shown in the pane
+    pub path: String,
+    pub sort_by: SortColumn,
+    pub sort_order: SortOrder,
+    /// Index of the entry under the cursor
+    pub selected_index: usize,
+}
+
+/// State of both panes. A pane is `None` if it has nothing (valid) to restore.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionState {
+    pub left: Option<PaneSessionState>,
+    pub right: Option<PaneSessionState>,
+}
+
+/// The latest state waiting to be written, with the file to write it to.
+static PENDING_SAVE: Mutex<Option<(PathBuf, SessionState)>> = Mutex::new(None);
+/// Whether a writer thread is already waiting out the debounce.
+static SAVE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the path to the session state file.
+fn get_session_state_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(SESSION_STATE_FILE_NAME))
+}
+
+/// Loads the saved session, or `None` if restoring is turned off or there's nothing to restore.
+/// Panes whose directory no longer exists are dropped.
+pub fn load_session_state<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<SessionState> {
+    if !crate::settings::load_settings(app).restore_session {
+        return None;
+    }
+    let state = read_session_state(&get_session_state_path(app)?)?;
+    let state = validate_session_state(state, |path| Path::new(path).is_dir());
+    (state.left.is_some() || state.right.is_some()).then_some(state)
+}
+
+/// Saves the session, debounced. Does nothing if restoring is turned off.
+pub fn save_session_state<R: tauri::Runtime>(app: &tauri::AppHandle<R>, state: SessionState) {
+    if !crate::settings::load_settings(app).restore_session {
+        return;
+    }
+    let Some(path) = get_session_state_path(app) else {
+        return;
+    };
+    if let Ok(mut pending) = PENDING_SAVE.lock() {
+        *pending = Some((path, state));
+    }
+    if SAVE_SCHEDULED.swap(true, Ordering::AcqRel) {
+        return; // The waiting writer will pick up the new state
+    }
+
+    std::thread::spawn(|| {
+        std::thread::sleep(SAVE_DEBOUNCE);
+        SAVE_SCHEDULED.store(false, Ordering::Release);
+        let pending = PENDING_SAVE.lock().ok().and_then(|mut pending| pending.take());
+        if let Some((path, state)) = pending
+            && let Err(e) = write_session_state(&path, &state)
+        {
+            log::warn!("Failed to save session state: {}", e);
+        }
+    });
+}
+
+/// Reads and parses a session state file. Returns `None` if it's missing or corrupt.
+fn read_session_state(path: &Path) -> Option<SessionState> {
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Writes the session state atomically: to a temp file first, then renamed over the real one.
+fn write_session_state(path: &Path, state: &SessionState) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Drops panes whose directory doesn't pass `dir_exists`.
+fn validate_session_state(state: SessionState, dir_exists: impl Fn(&str) -> bool) -> SessionState {
+    let validate = |pane: Option<PaneSessionState>| pane.filter(|p| dir_exists(&p.path));
+    SessionState {
+        left: validate(state.left),
+        right: validate(state.right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(path: &str, selected_index: usize) -> PaneSessionState {
+        PaneSessionState {
+            path: path.to_string(),
+            sort_by: SortColumn::Name,
+            sort_order: SortOrder::Ascending,
+            selected_index,
+        }
+    }
+
+    #[test]
+    fn test_write_then_read_round_trips() {
+        let temp_dir = std::env::temp_dir().join("cmdr_session_state_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let path = temp_dir.join(SESSION_STATE_FILE_NAME);
+        let state = SessionState {
+            left: Some(pane("/Users/test", 3)),
+            right: Some(pane("/tmp", 0)),
+        };
+
+        write_session_state(&path, &state).unwrap();
+        let read = read_session_state(&path);
+        let temp_file_left_behind = path.with_extension("json.tmp").exists();
+
+        let _ = fs::remove_dir_all(&temp_dir);
+        assert_eq!(read, Some(state));
+        assert!(!temp_file_left_behind);
+    }
+
+    #[test]
+    fn test_corrupt_file_reads_as_none() {
+        let temp_dir = std::env::temp_dir().join("cmdr_session_state_corrupt_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let path = temp_dir.join(SESSION_STATE_FILE_NAME);
+        fs::write(&path, "{ \"left\": ").unwrap();
+
+        let read = read_session_state(&path);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+        assert_eq!(read, None);
+    }
+
+    #[test]
+    fn test_validate_drops_missing_directories() {
+        let state = SessionState {
+            left: Some(pane("/exists", 5)),
+            right: Some(pane("/gone", 2)),
+        };
+
+        let validated = validate_session_state(state, |path| path == "/exists");
+
+        assert_eq!(validated.left, Some(pane("/exists", 5)));
+        assert_eq!(validated.right, None);
+    }
+
+    #[test]
+    fn test_serializes_camel_case() {
+        let state = SessionState {
+            left: Some(pane("/a", 1)),
+            right: None,
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.contains("\"selectedIndex\":1"));
+        assert!(json.contains("\"sortBy\":\"name\""));
+    }
+}
@@ -2,10 +2,15 @@
 //!
 //! Reads settings from the tauri-plugin-store JSON file.
 //! Used to initialize the menu with the correct checked state on startup.
+//!
+//! Also remembers the sort chosen per directory, in `directory-sorts.json`. Only the most recently used
+//! `MAX_DIRECTORY_SORTS` directories are kept.
 
-use serde::Deserialize;
+use crate::file_system::{SortColumn, SortOrder};
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
 use tauri::Manager;
 
 /// User's choice regarding full disk access permission.
@@ -29,6 +34,42 @@ pub struct Settings {
     #[serde(default)]
     #[allow(dead_code)] // Only used by frontend, backend just persists it
     pub full_disk_access_choice: FullDiskAccessChoice,
+    /// Whether to restore both panes' directories and cursors on the next launch
+    #[serde(default = "default_restore_session")]
+    pub restore_session: bool,
+    /// Whether to compute the size of each folder in the background when listing a directory
+    #[serde(default)]
+    pub compute_folder_sizes: bool,
+    /// Glob patterns (like `.DS_Store` or `__pycache__/`) of names to leave out of listings
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+    /// Whether symlinks to folders are listed as folders. If false, all symlinks are listed as files.
+    #[serde(default = "default_follow_symlinks_for_type")]
+    pub follow_symlinks_for_type: bool,
+    /// How long the file watcher waits for changes to settle before refreshing a listing, in milliseconds
+    #[serde(default = "default_watcher_debounce_ms")]
+    pub watcher_debounce_ms: u64,
+    /// How long lists of network shares are cached, in seconds
+    #[serde(default = "default_share_cache_ttl_secs")]
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))] // Network shares are macOS only
+    pub share_cache_ttl_secs: u64,
+}
+
+fn default_restore_session() -> bool {
+    true
+}
+
+fn default_follow_symlinks_for_type() -> bool {
+    true
+}
+
+fn default_watcher_debounce_ms() -> u64 {
+    crate::file_system::DEFAULT_DEBOUNCE_MS
+}
+
+/// Same as `smb_client::DEFAULT_CACHE_TTL_SECS`, which only exists on macOS
+fn default_share_cache_ttl_secs() -> u64 {
+    30
 }
 
 impl Default for Settings {
@@ -36,6 +77,12 @@ impl Default for Settings {
         Self {
             show_hidden_files: true,
             full_disk_access_choice: FullDiskAccessChoice::NotAskedYet,
+            restore_session: true,
+            compute_folder_sizes: false,
+            ignore_patterns: Vec::new(),
+            follow_symlinks_for_type: true,
+            watcher_debounce_ms: default_watcher_debounce_ms(),
+            share_cache_ttl_secs: default_share_cache_ttl_secs(),
         }
     }
 }
@@ -57,3 +104,183 @@ pub fn load_settings<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Settings {
 
     serde_json::from_str(&contents).unwrap_or_default()
 }
+
+// ============================================================================
+// Per-directory sort preferences
+// ============================================================================
+
+/// How many directories' sorts are remembered. The least recently used ones are forgotten first.
+const MAX_DIRECTORY_SORTS: usize = 500;
+
+/// The sort chosen for a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectorySort {
+    pub sort_by: SortColumn,
+    pub sort_order: SortOrder,
+}
+
+/// A remembered sort, as stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DirectorySortEntry {
+    path: PathBuf,
+    #[serde(flatten)]
+    sort: DirectorySort,
+}
+
+/// Remembered sorts, least recently used first.
+#[derive(Debug, Default)]
+struct DirectorySorts {
+    entries: Vec<DirectorySortEntry>,
+}
+
+impl DirectorySorts {
+    /// Returns the sort saved for `path`, and marks it as recently used.
+    fn get(&mut self, path: &Path) -> Option<DirectorySort> {
+        let index = self.entries.iter().position(|e| e.path == path)?;
+        let entry = self.entries.remove(index);
+        let sort = entry.sort;
+        self.entries.push(entry);
+        Some(sort)
+    }
+
+    /// Saves the sort for `path`, forgetting the least recently used ones if there are more than `max`.
+    fn set(&mut self, path: &Path, sort: DirectorySort, max: usize) {
+        self.entries.retain(|e| e.path != path);
+        self.entries.push(DirectorySortEntry {
+            path: path.to_path_buf(),
+            sort,
+        });
+        let excess = self.entries.len().saturating_sub(max);
+        self.entries.drain(..excess);
+    }
+}
+
+/// In-memory copy of the remembered sorts, synchronized with disk.
+static DIRECTORY_SORTS: LazyLock<RwLock<DirectorySorts>> = LazyLock::new(|| RwLock::new(DirectorySorts::default()));
+
+/// Returns the path to the file the remembered sorts are stored in.
+fn get_directory_sorts_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("directory-sorts.json"))
+}
+
+/// Loads the remembered sorts from disk into memory.
+pub fn load_directory_sorts<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(path) = get_directory_sorts_path(app) else {
+        return;
+    };
+    let entries: Vec<DirectorySortEntry> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    if let Ok(mut sorts) = DIRECTORY_SORTS.write() {
+        sorts.entries = entries;
+        let excess = sorts.entries.len().saturating_sub(MAX_DIRECTORY_SORTS);
+        sorts.entries.drain(..excess);
+    }
+}
+
+/// Returns the sort saved for a directory, if any.
+pub fn get_directory_sort(path: &Path) -> Option<DirectorySort> {
+    DIRECTORY_SORTS.write().ok()?.get(path)
+}
+
+/// Saves the sort for a directory, in memory and to disk.
+pub fn set_directory_sort<R: tauri::Runtime>(app: &tauri::AppHandle<R>, path: &Path, sort: DirectorySort) {
+    if let Ok(mut sorts) = DIRECTORY_SORTS.write() {
+        sorts.set(path, sort, MAX_DIRECTORY_SORTS);
+    }
+    save_directory_sorts(app);
+}
+
+/// Forgets all saved sorts.
+pub fn clear_directory_sorts<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if let Ok(mut sorts) = DIRECTORY_SORTS.write() {
+        sorts.entries.clear();
+    }
+    save_directory_sorts(app);
+}
+
+/// Writes the remembered sorts to disk.
+fn save_directory_sorts<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(path) = get_directory_sorts_path(app) else {
+        return;
+    };
+    let Ok(sorts) = DIRECTORY_SORTS.read() else {
+        return;
+    };
+    let Ok(json) = serde_json::to_string_pretty(&sorts.entries) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(&path, json);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BY_SIZE: DirectorySort = DirectorySort {
+        sort_by: SortColumn::Size,
+        sort_order: SortOrder::Descending,
+    };
+    const BY_NAME: DirectorySort = DirectorySort {
+        sort_by: SortColumn::Name,
+        sort_order: SortOrder::Ascending,
+    };
+
+    #[test]
+    fn test_saved_sort_is_returned() {
+        let mut sorts = DirectorySorts::default();
+        sorts.set(Path::new("/Users/test/Downloads"), BY_SIZE, 10);
+
+        assert_eq!(sorts.get(Path::new("/Users/test/Downloads")), Some(BY_SIZE));
+        assert_eq!(sorts.get(Path::new("/Users/test/Documents")), None);
+    }
+
+    #[test]
+    fn test_saving_again_replaces_the_sort() {
+        let mut sorts = DirectorySorts::default();
+        sorts.set(Path::new("/a"), BY_SIZE, 10);
+        sorts.set(Path::new("/a"), BY_NAME, 10);
+
+        assert_eq!(sorts.entries.len(), 1);
+        assert_eq!(sorts.get(Path::new("/a")), Some(BY_NAME));
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut sorts = DirectorySorts::default();
+        sorts.set(Path::new("/a"), BY_SIZE, 3);
+        sorts.set(Path::new("/b"), BY_SIZE, 3);
+        sorts.set(Path::new("/c"), BY_SIZE, 3);
+        // Using "/a" makes "/b" the least recently used
+        sorts.get(Path::new("/a"));
+        sorts.set(Path::new("/d"), BY_SIZE, 3);
+
+        assert_eq!(sorts.entries.len(), 3);
+        assert_eq!(sorts.get(Path::new("/b")), None);
+        assert_eq!(sorts.get(Path::new("/a")), Some(BY_SIZE));
+        assert_eq!(sorts.get(Path::new("/c")), Some(BY_SIZE));
+        assert_eq!(sorts.get(Path::new("/d")), Some(BY_SIZE));
+    }
+
+    #[test]
+    fn test_entries_serialize_flat() {
+        let entry = DirectorySortEntry {
+            path: PathBuf::from("/a"),
+            sort: BY_SIZE,
+        };
+        assert_eq!(
+            serde_json::to_value(&entry).unwrap(),
+            serde_json::json!({ "path": "/a", "sortBy": "size", "sortOrder": "descending" })
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Formatting of file timestamps.
+//!
+//! Centralizes how `modified_at`, `added_at`, and similar timestamps are shown, so every view
+//! renders them the same way. Timestamps are signed Unix seconds, so pre-1970 dates work too.
+//!
+//! Relative times are worded in English, German, French, or Spanish, and fall back to English for other
+//! languages. Absolute dates use the given locale's date format.
+
+use chrono::{DateTime, Datelike, Local, Locale, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// How to format an absolute date.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AbsoluteTimeStyle {
+    /// Date only, for example "Jan 3, 2026", or the locale's date format
+    #[default]
+    Date,
+    /// Date and time, for example "Jan 3, 2026 at 14:05", or the locale's date format and the time
+    DateTime,
+    /// Sortable, fixed-width, for example "2026-01-03 14:05". The same in every locale.
+    Iso,
+}
+
+/// The words for relative times in one language. Counts are `(singular, plural)` patterns, with `{}` for the number.
+struct RelativeWords {
+    just_now: &'static str,
+    yesterday: &'static str,
+    minutes: (&'static str, &'static str),
+    hours: (&'static str, &'static str),
+    days: (&'static str, &'static str),
+    /// Format of dates earlier this year, without the year
+    month_day: &'static str,
+}
+
+const ENGLISH: RelativeWords = RelativeWords {
+    just_now: "Just now",
+    yesterday: "Yesterday",
+    minutes: ("{} minute ago", "{} minutes ago"),
+    hours: ("{} hour ago", "{} hours ago"),
+    days: ("{} day ago", "{} days ago"),
+    month_day: "%b %-d",
+};
+
+const GERMAN: RelativeWords = RelativeWords {
+    just_now: "Gerade eben",
+    yesterday: "Gestern",
+    minutes: ("vor {} Minute", "vor {} Minuten"),
+    hours: ("vor {} Stunde", "vor {} Stunden"),
+    days: ("vor {} Tag", "vor {} Tagen"),
+    month_day: "%-d. %b",
+};
+
+const FRENCH: RelativeWords = RelativeWords {
+    just_now: "À l'instant",
+    yesterday: "Hier",
+    minutes: ("il y a {} minute", "il y a {} minutes"),
+    hours: ("il y a {} heure", "il y a {} heures"),
+    days: ("il y a {} jour", "il y a {} jours"),
+    month_day: "%-d %b",
+};
+
+const SPANISH: RelativeWords = RelativeWords {
+    just_now: "Ahora mismo",
+    yesterday: "Ayer",
+    minutes: ("hace {} minuto", "hace {} minutos"),
+    hours: ("hace {} hora", "hace {} horas"),
+    days: ("hace {} día", "hace {} días"),
+    month_day: "%-d %b",
+};
+
+/// Formats a timestamp relative to now, for example "2 hours ago", "Yesterday", or "Jan 3, 2026".
+///
+/// # Arguments
+/// * `timestamp` - Unix timestamp in seconds (can be negative for pre-1970 dates)
+/// * `locale` - BCP 47 or POSIX locale like "de-DE" or "de_DE". Unknown or None gives English.
+pub fn format_relative_time(timestamp: i64, locale: Option<&str>) -> String {
+    format_relative_time_at(timestamp, &Local::now(), locale)
+}
+
+/// Formats a timestamp as an absolute date in the local time zone.
+///
+/// # Arguments
+/// * `timestamp` - Unix timestamp in seconds (can be negative for pre-1970 dates)
+/// * `style` - How much detail to include
+/// * `locale` - BCP 47 or POSIX locale like "de-DE" or "de_DE". Unknown or None gives the English format.
+pub fn format_absolute_time(timestamp: i64, style: AbsoluteTimeStyle, locale: Option<&str>) -> String {
+    match Local.timestamp_opt(timestamp, 0).single() {
+        Some(time) => format_absolute(&time, style, locale.and_then(parse_locale)),
+        None => String::new(),
+    }
+}
+
+/// Formats `timestamp` relative to `now`. Split out from `format_relative_time` so tests can fix "now" and the zone.
+///
+/// Rules, in order:
+/// - Less than a minute ago (or up to a minute in the future, for clock skew): "Just now"
+/// - Less than an hour ago: "N minutes ago"
+/// - Earlier today: "N hours ago"
+/// - The previous calendar day: "Yesterday"
+/// - Within the last week: "N days ago"
+/// - Earlier this year: "Jan 3"
+/// - Anything else (prior years, or further in the future): the full date, like `AbsoluteTimeStyle::Date`
+fn format_relative_time_at<Tz: TimeZone>(timestamp: i64, now: &DateTime<Tz>, locale: Option<&str>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let Some(time) = now.timezone().timestamp_opt(timestamp, 0).single() else {
+        return String::new();
+    };
+    let chrono_locale = locale.and_then(parse_locale);
+    // Languages without words here get English words, but dates in their own format
+    let own_words = locale.and_then(relative_words);
+    let words = own_words.unwrap_or(&ENGLISH);
+    let seconds_ago = now.timestamp() - timestamp;
+
+    if (-60..60).contains(&seconds_ago) {
+        return words.just_now.to_string();
+    }
+    if seconds_ago < 0 {
+        return format_absolute(&time, AbsoluteTimeStyle::Date, chrono_locale);
+    }
+    if seconds_ago < 3600 {
+        return count(seconds_ago / 60, words.minutes);
+    }
+
+    let days_ago = now.date_naive().signed_duration_since(time.date_naive()).num_days();
+    match days_ago {
+        0 => count(seconds_ago / 3600, words.hours),
+        1 => words.yesterday.to_string(),
+        2..7 => count(days_ago, words.days),
+        _ if time.year() == now.year() => match chrono_locale {
+            None => time.format(words.month_day).to_string(),
+            Some(chrono_locale) if own_words.is_some() => {
+                time.format_localized(words.month_day, chrono_locale).to_string()
+            }
+            // The English month-day order may be wrong in this language, so it gets the full date
+            Some(_) => format_absolute(&time, AbsoluteTimeStyle::Date, chrono_locale),
+        },
+        _ => format_absolute(&time, AbsoluteTimeStyle::Date, chrono_locale),
+    }
+}
+
+/// Formats a date-time in the given style, in `locale` if given.
+fn format_absolute<Tz: TimeZone>(time: &DateTime<Tz>, style: AbsoluteTimeStyle, locale: Option<Locale>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match (style, locale) {
+        (AbsoluteTimeStyle::Iso, _) => time.format("%Y-%m-%d %H:%M").to_string(),
+        (AbsoluteTimeStyle::Date, None) => time.format("%b %-d, %Y").to_string(),
+        (AbsoluteTimeStyle::DateTime, None) => time.format("%b %-d, %Y at %H:%M").to_string(),
+        (AbsoluteTimeStyle::Date, Some(locale)) => time.format_localized("%x", locale).to_string(),
+        (AbsoluteTimeStyle::DateTime, Some(locale)) => time.format_localized("%x %H:%M", locale).to_string(),
+    }
+}
+
+/// Parses a locale like "de-DE" or "de_DE". Returns None for unknown ones.
+fn parse_locale(locale: &str) -> Option<Locale> {
+    Locale::try_from(locale.replace('-', "_").as_str()).ok()
+}
+
+/// Returns the relative time words for the language of `locale`, or None if there are none for it.
+fn relative_words(locale: &str) -> Option<&'static RelativeWords> {
+    let language = locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+    match language.as_str() {
+        "en" => Some(&ENGLISH),
+        "de" => Some(&GERMAN),
+        "fr" => Some(&FRENCH),
+        "es" => Some(&SPANISH),
+        _ => None,
+    }
+}
+
+/// Fills in `n` in the singular or the plural pattern.
+fn count(n: i64, (singular, plural): (&str, &str)) -> String {
+    let pattern = if n == 1 { singular } else { plural };
+    pattern.replace("{}", &n.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    /// Wednesday, Jan 14, 2026, 15:00:00 UTC
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 14, 15, 0, 0).unwrap()
+    }
+
+    fn ts(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+            .timestamp()
+    }
+
+    fn relative(timestamp: i64) -> String {
+        format_relative_time_at(timestamp, &now(), None)
+    }
+
+    #[test]
+    fn test_just_now() {
+        let now = now().timestamp();
+        assert_eq!(relative(now), "Just now");
+        assert_eq!(relative(now - 59), "Just now");
+        // Small clock skew into the future
+        assert_eq!(relative(now + 30), "Just now");
+    }
+
+    #[test]
+    fn test_minutes_boundary() {
+        let now = now().timestamp();
+        assert_eq!(relative(now - 60), "1 minute ago");
+        assert_eq!(relative(now - 3599), "59 minutes ago");
+    }
+
+    #[test]
+    fn test_hours_same_day() {
+        assert_eq!(relative(now().timestamp() - 3600), "1 hour ago");
+        assert_eq!(relative(ts(2026, 1, 14, 0, 0)), "15 hours ago");
+    }
+
+    #[test]
+    fn test_yesterday_boundary() {
+        // One minute before midnight is yesterday, even though it's only ~15 hours ago
+        assert_eq!(relative(ts(2026, 1, 13, 23, 59)), "Yesterday");
+        assert_eq!(relative(ts(2026, 1, 13, 0, 0)), "Yesterday");
+        assert_eq!(relative(ts(2026, 1, 12, 23, 59)), "2 days ago");
+    }
+
+    #[test]
+    fn test_days_within_week() {
+        assert_eq!(relative(ts(2026, 1, 8, 12, 0)), "6 days ago");
+        assert_eq!(relative(ts(2026, 1, 7, 12, 0)), "Jan 7");
+    }
+
+    #[test]
+    fn test_prior_year() {
+        assert_eq!(relative(ts(2025, 12, 31, 10, 0)), "Dec 31, 2025");
+    }
+
+    #[test]
+    fn test_pre_epoch() {
+        assert_eq!(relative(ts(1969, 7, 20, 20, 17)), "Jul 20, 1969");
+    }
+
+    #[test]
+    fn test_future_beyond_skew() {
+        assert_eq!(relative(ts(2026, 2, 1, 0, 0)), "Feb 1, 2026");
+    }
+
+    #[test]
+    fn test_relative_in_locale() {
+        let now = now();
+        let german = |timestamp| format_relative_time_at(timestamp, &now, Some("de-DE"));
+        assert_eq!(german(now.timestamp()), "Gerade eben");
+        assert_eq!(german(now.timestamp() - 60), "vor 1 Minute");
+        assert_eq!(german(now.timestamp() - 7200), "vor 2 Stunden");
+        assert_eq!(german(ts(2026, 1, 13, 12, 0)), "Gestern");
+        assert_eq!(german(ts(2026, 1, 10, 12, 0)), "vor 4 Tagen");
+        assert_eq!(german(ts(2026, 1, 3, 12, 0)), "3. Jan");
+        assert_eq!(german(ts(2025, 12, 31, 10, 0)), "31.12.2025");
+        let french = format_relative_time_at(ts(2026, 1, 14, 12, 0), &now, Some("fr_FR"));
+        assert_eq!(french, "il y a 3 heures");
+    }
+
+    #[test]
+    fn test_relative_in_language_without_words() {
+        let now = now();
+        let italian = |timestamp| format_relative_time_at(timestamp, &now, Some("it-IT"));
+        // English words, but the locale's date format
+        assert_eq!(italian(ts(2026, 1, 13, 12, 0)), "Yesterday");
+        assert_eq!(italian(ts(2026, 1, 3, 12, 0)), "03/01/2026");
+        assert_eq!(
+            format_relative_time_at(now.timestamp(), &now, Some("xx-YY")),
+            "Just now"
+        );
+    }
+
+    #[test]
+    fn test_absolute_styles() {
+        let time = Utc.with_ymd_and_hms(2026, 1, 3, 14, 5, 0).unwrap();
+        assert_eq!(format_absolute(&time, AbsoluteTimeStyle::Date, None), "Jan 3, 2026");
+        assert_eq!(
+            format_absolute(&time, AbsoluteTimeStyle::DateTime, None),
+            "Jan 3, 2026 at 14:05"
+        );
+        assert_eq!(format_absolute(&time, AbsoluteTimeStyle::Iso, None), "2026-01-03 14:05");
+    }
+
+    #[test]
+    fn test_absolute_styles_in_locale() {
+        let time = Utc.with_ymd_and_hms(2026, 1, 3, 14, 5, 0).unwrap();
+        let german = parse_locale("de-DE");
+        assert_eq!(format_absolute(&time, AbsoluteTimeStyle::Date, german), "03.01.2026");
+        assert_eq!(
+            format_absolute(&time, AbsoluteTimeStyle::DateTime, german),
+            "03.01.2026 14:05"
+        );
+        assert_eq!(
+            format_absolute(&time, AbsoluteTimeStyle::Iso, german),
+            "2026-01-03 14:05"
+        );
+    }
+
+    #[test]
+    fn test_unknown_locale_is_ignored() {
+        assert!(parse_locale("xx-YY").is_none());
+        assert!(parse_locale("de_DE").is_some());
+    }
+}
@@ -7,11 +7,13 @@
 //! - Cloud drives (Dropbox, iCloud, Google Drive, etc.)
 //! - Network locations
 
+pub mod sidebar_config;
 pub mod watcher;
 
 use serde::{Deserialize, Serialize};
+use sidebar_config::get_sidebar_config;
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Category of a location item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -46,52 +48,53 @@ pub struct LocationInfo {
     pub icon: Option<String>,
     /// Whether this can be ejected.
     pub is_ejectable: bool,
+    /// Size of the volume in bytes. Only set for volumes, not for favorites and cloud drives.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<u64>,
+    /// Free space on the volume in bytes. Only set for volumes, not for favorites and cloud drives.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
 }
 
 /// Default volume ID for the root filesystem.
 pub const DEFAULT_VOLUME_ID: &str = "root";
 
 /// Get all locations organized by category, deduplicated.
+///
+/// Categories are ordered and hidden according to the sidebar config. When a path appears in several
+/// categories, it's listed under the first one.
 pub fn list_locations() -> Vec<LocationInfo> {
-    let mut locations = Vec::new();
-    let mut seen_paths: HashSet<String> = HashSet::new();
-
-    // 1. Favorites
-    for loc in get_favorites() {
-        if seen_paths.insert(loc.path.clone()) {
-            locations.push(loc);
-        }
-    }
+    let locations = get_sidebar_config()
+        .visible_categories()
+        .into_iter()
+        .flat_map(get_locations_in_category);
+    dedupe_locations(locations)
+}
 
-    // 2. Main volume
-    if let Some(loc) = get_main_volume()
-        && seen_paths.insert(loc.path.clone())
-    {
-        locations.push(loc);
-    }
+/// Keeps the first location of each path. Paths are compared after resolving symlinks, so iCloud Drive listed
+/// both as a favorite and as a cloud drive, by different paths, only shows up once.
+fn dedupe_locations(locations: impl IntoIterator<Item = LocationInfo>) -> Vec<LocationInfo> {
+    let mut seen_paths: HashSet<PathBuf> = HashSet::new();
+    locations
+        .into_iter()
+        .filter(|loc| seen_paths.insert(dedupe_key(&loc.path)))
+        .collect()
+}
 
-    // 3. Attached volumes
-    for loc in get_attached_volumes() {
-        if seen_paths.insert(loc.path.clone()) {
-            locations.push(loc);
-        }
-    }
+/// The canonical form of a path, or the path without trailing slashes if it can't be resolved.
+fn dedupe_key(path: &str) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| Path::new(path).components().collect())
+}
 
-    // 4. Cloud drives (skip if already in favorites)
-    for loc in get_cloud_drives() {
-        if seen_paths.insert(loc.path.clone()) {
-            locations.push(loc);
-        }
+/// Get the locations of one category.
+fn get_locations_in_category(category: LocationCategory) -> Vec<LocationInfo> {
+    match category {
+        LocationCategory::Favorite => get_favorites(),
+        LocationCategory::MainVolume => get_main_volume().into_iter().collect(),
+        LocationCategory::AttachedVolume => get_attached_volumes(),
+        LocationCategory::CloudDrive => get_cloud_drives(),
+        LocationCategory::Network => get_network_locations(),
     }
-
-    // 5. Network - commented out for now as /Network requires special handling
-    // for loc in get_network_locations() {
-    //     if seen_paths.insert(loc.path.clone()) {
-    //         locations.push(loc);
-    //     }
-    // }
-
-    locations
 }
 
 /// Get Finder favorites (common user folders).
@@ -120,6 +123,8 @@ fn get_favorites() -> Vec<LocationInfo> {
             category: LocationCategory::Favorite,
             icon: get_icon_for_path(path),
             is_ejectable: false,
+            total_bytes: None,
+            available_bytes: None,
         })
         .collect()
 }
@@ -150,6 +155,8 @@ fn get_main_volume() -> Option<LocationInfo> {
                 category: LocationCategory::MainVolume,
                 icon: get_icon_for_path("/"),
                 is_ejectable: false,
+                total_bytes: get_u64_resource(&url, "NSURLVolumeTotalCapacityKey"),
+                available_bytes: get_u64_resource(&url, "NSURLVolumeAvailableCapacityKey"),
             });
         }
     }
@@ -206,6 +213,8 @@ fn get_attached_volumes() -> Vec<LocationInfo> {
             category: LocationCategory::AttachedVolume,
             icon: get_icon_for_path(&path),
             is_ejectable,
+            total_bytes: get_u64_resource(&url, "NSURLVolumeTotalCapacityKey"),
+            available_bytes: get_u64_resource(&url, "NSURLVolumeAvailableCapacityKey"),
         });
     }
 
@@ -229,6 +238,8 @@ fn get_cloud_drives() -> Vec<LocationInfo> {
             category: LocationCategory::CloudDrive,
             icon: get_icon_for_path(&icloud_path.to_string_lossy()),
             is_ejectable: false,
+            total_bytes: None,
+            available_bytes: None,
         });
     }
 
@@ -250,6 +261,8 @@ fn get_cloud_drives() -> Vec<LocationInfo> {
                         category: LocationCategory::CloudDrive,
                         icon: get_icon_for_path(&path.to_string_lossy()),
                         is_ejectable: false,
+                        total_bytes: None,
+                        available_bytes: None,
                     });
                 }
             }
@@ -286,32 +299,56 @@ fn parse_cloud_provider_name(dir_name: &str) -> (String, String) {
     if dir_name.starts_with("pCloud") {
         return ("pCloud".to_string(), "cloud-pcloud".to_string());
     }
+    if dir_name.starts_with("ProtonDrive") {
+        return ("Proton Drive".to_string(), "cloud-proton-drive".to_string());
+    }
+    if dir_name.starts_with("MEGA") {
+        return ("MEGA".to_string(), "cloud-mega".to_string());
+    }
+    if dir_name.starts_with("Sync.com") || dir_name.starts_with("SyncCom") {
+        return ("Sync.com".to_string(), "cloud-sync-com".to_string());
+    }
+    if dir_name.starts_with("CreativeCloud") || dir_name.starts_with("Creative Cloud Files") {
+        return ("Creative Cloud Files".to_string(), "cloud-creative-cloud".to_string());
+    }
     // Generic cloud provider
     if !dir_name.is_empty() {
-        let clean_name = dir_name.split('-').next().unwrap_or(dir_name);
+        let clean_name = strip_account_suffix(dir_name);
         return (clean_name.to_string(), format!("cloud-{}", clean_name.to_lowercase()));
     }
     (String::new(), String::new())
 }
 
+/// Strips the account from a CloudStorage directory name, like "Foo-Bar-user@example.com" -> "Foo-Bar".
+/// Names with an email lose everything from the dash before it, so dashes in the provider name are kept.
+/// Others lose everything from the first dash, like "Provider-Personal" -> "Provider".
+fn strip_account_suffix(dir_name: &str) -> &str {
+    let cut = match dir_name.split_once('@') {
+        Some((before_at, _)) => before_at.rfind('-'),
+        None => dir_name.find('-'),
+    };
+    match cut {
+        Some(index) if index > 0 => &dir_name[..index],
+        _ => dir_name,
+    }
+}
+
 /// Get network locations.
-#[allow(dead_code)]
+///
+/// Only a placeholder "Network" entry, like Finder has. It doesn't touch `/Network`, as listing it can hang.
+/// Opening it shows the hosts found by Bonjour discovery instead.
 fn get_network_locations() -> Vec<LocationInfo> {
-    let mut locations = Vec::new();
-
-    // Always include Network like Finder does
-    // Even if /Network doesn't exist as a directory, it's a browseable location in Finder
-    let network_path = "/Network";
-    locations.push(LocationInfo {
+    vec![LocationInfo {
         id: "network".to_string(),
         name: "Network".to_string(),
-        path: network_path.to_string(),
+        // The same virtual path the frontend uses for the network view
+        path: "smb://".to_string(),
         category: LocationCategory::Network,
         icon: None, // Will use placeholder in frontend
         is_ejectable: false,
-    });
-
-    locations
+        total_bytes: None,
+        available_bytes: None,
+    }]
 }
 
 /// Get the display name for a volume.
@@ -348,7 +385,7 @@ fn path_to_id(path: &str) -> String {
 
 /// Get icon for a path as base64-encoded WebP.
 fn get_icon_for_path(path: &str) -> Option<String> {
-    crate::icons::get_icon_for_path(path)
+    crate::icons::get_icon_for_path(path, None)
 }
 
 /// Get a boolean resource value from an NSURL.
@@ -367,6 +404,29 @@ fn get_bool_resource(url: &objc2_foundation::NSURL, key: &str) -> Option<bool> {
     }
 }
 
+/// Get an integer resource value, like a capacity, from an NSURL.
+fn get_u64_resource(url: &objc2_foundation::NSURL, key: &str) -> Option<u64> {
+    use objc2::rc::Retained;
+    use objc2_foundation::NSString;
+
+    let key = NSString::from_str(key);
+    let mut value: Option<Retained<objc2::runtime::AnyObject>> = None;
+    let success = unsafe { url.getResourceValue_forKey_error(&mut value, &key) };
+
+    if success.is_ok() {
+        value.and_then(|obj| number_to_u64(&obj))
+    } else {
+        None
+    }
+}
+
+/// Reads a resource value as an integer, or None if it's not an NSNumber.
+fn number_to_u64(value: &objc2::runtime::AnyObject) -> Option<u64> {
+    value
+        .downcast_ref::<objc2_foundation::NSNumber>()
+        .map(|n| n.unsignedLongLongValue())
+}
+
 /// Get a string resource value from an NSURL.
 fn get_string_resource(url: &objc2_foundation::NSURL, key: &str) -> Option<String> {
     use objc2::rc::Retained;
@@ -432,6 +492,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_list_locations_includes_network() {
+        let locations = list_locations();
+        let network = locations
+            .iter()
+            .find(|l| l.category == LocationCategory::Network)
+            .expect("Should include Network");
+        assert_eq!(network.id, "network");
+        assert!(!network.is_ejectable);
+        assert!(network.icon.is_none());
+    }
+
     #[test]
     fn test_locations_are_deduplicated() {
         let locations = list_locations();
@@ -441,6 +513,77 @@ mod tests {
         }
     }
 
+    fn location(id: &str, path: &str, category: LocationCategory) -> LocationInfo {
+        LocationInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            category,
+            icon: None,
+            is_ejectable: false,
+            total_bytes: None,
+            available_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_ignores_trailing_slashes() {
+        let temp_dir = std::env::temp_dir();
+        let temp_path = temp_dir.to_string_lossy().trim_end_matches('/').to_string();
+        let locations = vec![
+            location("fav-temp", &temp_path, LocationCategory::Favorite),
+            location("cloud-temp", &format!("{}/", temp_path), LocationCategory::CloudDrive),
+            // Also for paths that don't exist, so can't be canonicalized
+            location("fav-missing", "/nonexistent/cloud", LocationCategory::Favorite),
+            location("cloud-missing", "/nonexistent/cloud/", LocationCategory::CloudDrive),
+        ];
+
+        let ids: Vec<String> = dedupe_locations(locations).into_iter().map(|l| l.id).collect();
+
+        assert_eq!(ids, vec!["fav-temp", "fav-missing"]);
+    }
+
+    #[test]
+    fn test_dedupe_keeps_distinct_paths() {
+        let locations = vec![
+            location("root", "/", LocationCategory::MainVolume),
+            location("volumes-a", "/nonexistent/Volumes/A", LocationCategory::AttachedVolume),
+            location(
+                "volumes-a-copy",
+                "/nonexistent/Volumes/A copy",
+                LocationCategory::AttachedVolume,
+            ),
+        ];
+
+        assert_eq!(dedupe_locations(locations).len(), 3);
+    }
+
+    #[test]
+    fn test_number_resource_as_u64() {
+        use objc2_foundation::{NSNumber, NSString};
+
+        assert_eq!(
+            number_to_u64(&NSNumber::new_u64(994_662_584_320)),
+            Some(994_662_584_320)
+        );
+        assert_eq!(number_to_u64(&NSNumber::new_u64(0)), Some(0));
+        assert_eq!(number_to_u64(&NSString::from_str("994662584320")), None);
+    }
+
+    #[test]
+    fn test_volumes_have_capacity_but_favorites_dont() {
+        let locations = list_locations();
+        let main = locations
+            .iter()
+            .find(|l| l.category == LocationCategory::MainVolume)
+            .unwrap();
+        assert!(main.total_bytes.is_some_and(|total| total > 0));
+        assert!(main.available_bytes <= main.total_bytes);
+        for favorite in locations.iter().filter(|l| l.category == LocationCategory::Favorite) {
+            assert_eq!(favorite.total_bytes, None);
+        }
+    }
+
     #[test]
     fn test_parse_cloud_provider_name() {
         assert_eq!(
@@ -455,6 +598,43 @@ mod tests {
             parse_cloud_provider_name("OneDrive-Personal"),
             ("OneDrive".to_string(), "cloud-onedrive".to_string())
         );
+        assert_eq!(
+            parse_cloud_provider_name("ProtonDrive-user@proton.me-folder"),
+            ("Proton Drive".to_string(), "cloud-proton-drive".to_string())
+        );
+        assert_eq!(
+            parse_cloud_provider_name("MEGA-user@example.com"),
+            ("MEGA".to_string(), "cloud-mega".to_string())
+        );
+        assert_eq!(
+            parse_cloud_provider_name("Sync.com-user"),
+            ("Sync.com".to_string(), "cloud-sync-com".to_string())
+        );
+        assert_eq!(
+            parse_cloud_provider_name("CreativeCloudFiles-user@example.com"),
+            ("Creative Cloud Files".to_string(), "cloud-creative-cloud".to_string())
+        );
+        assert_eq!(
+            parse_cloud_provider_name("Creative Cloud Files"),
+            ("Creative Cloud Files".to_string(), "cloud-creative-cloud".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_generic_cloud_provider_name() {
+        assert_eq!(
+            parse_cloud_provider_name("Nextcloud"),
+            ("Nextcloud".to_string(), "cloud-nextcloud".to_string())
+        );
+        assert_eq!(
+            parse_cloud_provider_name("Nextcloud-Personal"),
+            ("Nextcloud".to_string(), "cloud-nextcloud".to_string())
+        );
+        // Dashes in the provider name are kept when the account is an email
+        assert_eq!(
+            parse_cloud_provider_name("Some-Drive-user@example.com"),
+            ("Some-Drive".to_string(), "cloud-some-drive".to_string())
+        );
     }
 
     #[test]
@@ -0,0 +1,172 @@
+//! Which location categories the sidebar shows, and in what order.
+//!
+//! Persisted to `sidebar-config.json` in the app data directory. The main volume is always shown, even if the
+//! config hides it, so there's always somewhere to navigate to.
+
+use super::LocationCategory;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+use tauri::Manager;
+
+/// The order used when there's no config, and for categories missing from `category_order`.
+const DEFAULT_ORDER: [LocationCategory; 5] = [
+    LocationCategory::Favorite,
+    LocationCategory::MainVolume,
+    LocationCategory::AttachedVolume,
+    LocationCategory::CloudDrive,
+    LocationCategory::Network,
+];
+
+/// Sidebar category order and visibility.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidebarConfig {
+    /// Categories in the order they appear. Missing ones go after these, in the default order.
+    #[serde(default)]
+    pub category_order: Vec<LocationCategory>,
+    /// Categories to leave out. `main_volume` is ignored here.
+    #[serde(default)]
+    pub hidden_categories: Vec<LocationCategory>,
+}
+
+impl Default for SidebarConfig {
+    fn default() -> Self {
+        Self {
+            category_order: DEFAULT_ORDER.to_vec(),
+            hidden_categories: Vec::new(),
+        }
+    }
+}
+
+impl SidebarConfig {
+    /// The categories to show, in order. Always contains the main volume, and each category only once.
+    pub fn visible_categories(&self) -> Vec<LocationCategory> {
+        let mut categories: Vec<LocationCategory> = Vec::new();
+        for category in self.category_order.iter().chain(DEFAULT_ORDER.iter()) {
+            if categories.contains(category) {
+                continue;
+            }
+            if *category != LocationCategory::MainVolume && self.hidden_categories.contains(category) {
+                continue;
+            }
+            categories.push(*category);
+        }
+        categories
+    }
+}
+
+/// In-memory copy of the config, synchronized with disk.
+static SIDEBAR_CONFIG: LazyLock<RwLock<SidebarConfig>> = LazyLock::new(|| RwLock::new(SidebarConfig::default()));
+
+/// Returns the path to the sidebar config file.
+fn get_config_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("sidebar-config.json"))
+}
+
+/// Loads the sidebar config from disk into memory.
+pub fn load_sidebar_config<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(path) = get_config_path(app) else {
+        return;
+    };
+
+    let config = if let Ok(contents) = fs::read_to_string(&path) {
+        serde_json::from_str(&contents).unwrap_or_default()
+    } else {
+        SidebarConfig::default()
+    };
+
+    if let Ok(mut current) = SIDEBAR_CONFIG.write() {
+        *current = config;
+    }
+}
+
+/// Returns the current sidebar config.
+pub fn get_sidebar_config() -> SidebarConfig {
+    SIDEBAR_CONFIG.read().map(|config| config.clone()).unwrap_or_default()
+}
+
+/// Replaces the sidebar config in memory and saves it to disk.
+pub fn set_sidebar_config<R: tauri::Runtime>(app: &tauri::AppHandle<R>, config: SidebarConfig) {
+    if let Ok(mut current) = SIDEBAR_CONFIG.write() {
+        *current = config.clone();
+    }
+
+    let Some(path) = get_config_path(app) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&config) {
+        let _ = fs::write(&path, json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_shows_all_categories_in_default_order() {
+        assert_eq!(SidebarConfig::default().visible_categories(), DEFAULT_ORDER.to_vec());
+    }
+
+    #[test]
+    fn test_hidden_categories_are_excluded() {
+        let config = SidebarConfig {
+            category_order: DEFAULT_ORDER.to_vec(),
+            hidden_categories: vec![LocationCategory::Network, LocationCategory::CloudDrive],
+        };
+
+        assert_eq!(
+            config.visible_categories(),
+            vec![
+                LocationCategory::Favorite,
+                LocationCategory::MainVolume,
+                LocationCategory::AttachedVolume,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_order_is_respected_and_missing_categories_are_appended() {
+        let config = SidebarConfig {
+            category_order: vec![LocationCategory::CloudDrive, LocationCategory::MainVolume],
+            hidden_categories: vec![],
+        };
+
+        assert_eq!(
+            config.visible_categories(),
+            vec![
+                LocationCategory::CloudDrive,
+                LocationCategory::MainVolume,
+                LocationCategory::Favorite,
+                LocationCategory::AttachedVolume,
+                LocationCategory::Network,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_main_volume_cant_be_hidden() {
+        let config = SidebarConfig {
+            category_order: vec![],
+            hidden_categories: DEFAULT_ORDER.to_vec(),
+        };
+
+        assert_eq!(config.visible_categories(), vec![LocationCategory::MainVolume]);
+    }
+
+    #[test]
+    fn test_deserializes_partial_config() {
+        let config: SidebarConfig = serde_json::from_str(r#"{ "hiddenCategories": ["network"] }"#).unwrap();
+
+        assert_eq!(config.category_order, Vec::<LocationCategory>::new());
+        assert_eq!(config.hidden_categories, vec![LocationCategory::Network]);
+    }
+}
@@ -1,171 +1,288 @@
 //! Volume mount/unmount watcher for macOS.
 //!
-//! Watches the /Volumes directory for changes using FSEvents, detecting when
-//! volumes are mounted or unmounted, and emits Tauri events to the frontend.
+//! Observes `NSWorkspace`'s `didMountNotification` and `didUnmountNotification`, and emits
+//! `volume-added` and `volume-removed` Tauri events to the frontend.
+//!
+//! Notifications are debounced, as a single disk can mount several volumes at once. Each burst is
+//! coalesced into one set of changes, so each volume is reported once, and a volume that came and
+//! went within the burst isn't reported at all.
+
+// Suppress snake_case warnings for ObjC observer methods that must use camelCase
+#![allow(non_snake_case)]
 
+use super::{LocationInfo, list_locations, path_to_id};
 use log::{debug, error, info};
-use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use std::collections::HashSet;
-use std::path::Path;
-use std::sync::{Mutex, OnceLock};
+use objc2::rc::Retained;
+use objc2::runtime::{AnyClass, AnyObject};
+use objc2::{define_class, msg_send, sel};
+use objc2_foundation::{NSObject, NSObjectProtocol, NSString};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
-/// Global app handle for emitting events from the watcher
+/// How long the mount notifications must be quiet before the changes are reported
+const VOLUME_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// The values of the NSWorkspace notification name constants are the same as their symbol names
+const DID_MOUNT_NOTIFICATION: &str = "NSWorkspaceDidMountNotification";
+const DID_UNMOUNT_NOTIFICATION: &str = "NSWorkspaceDidUnmountNotification";
+/// Key of the volume's path in the notifications' `userInfo`
+const DEVICE_PATH_KEY: &str = "NSDevicePath";
+
+/// Global app handle for emitting events from the observer
 static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
 
-/// The watcher instance (kept alive for the duration of the app)
-static WATCHER: OnceLock<Mutex<Option<RecommendedWatcher>>> = OnceLock::new();
+/// The registered observer (kept alive for the duration of the app)
+static OBSERVER: OnceLock<Mutex<Option<ObserverRegistration>>> = OnceLock::new();
+
+/// Changes seen since the last report
+static PENDING_CHANGES: LazyLock<Mutex<VolumeChanges>> = LazyLock::new(|| Mutex::new(VolumeChanges::default()));
 
-/// Track known volume paths for comparison
-static KNOWN_VOLUMES: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+/// Locations of the mounted volumes, by path, so removals can carry the location that went away
+static KNOWN_LOCATIONS: LazyLock<Mutex<HashMap<String, LocationInfo>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
 
-/// Payload for volume mount/unmount events
+/// Payload for `volume-added` and `volume-removed` events
 #[derive(Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VolumeEventPayload {
     /// The volume path (e.g., "/Volumes/MyDrive")
     pub volume_path: String,
+    /// The volume ID, like in `list_volumes`
+    pub volume_id: String,
+    /// The volume, as `list_volumes` lists it. Only missing if it's not listed, for example, when hidden.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<LocationInfo>,
 }
 
-/// Get the current set of volumes in /Volumes
-fn get_current_volumes() -> HashSet<String> {
-    let mut volumes = HashSet::new();
-    if let Ok(entries) = std::fs::read_dir("/Volumes") {
-        for entry in entries.flatten() {
-            if let Some(name) = entry.path().to_str() {
-                volumes.insert(name.to_string());
-            }
-        }
-    }
-    volumes
+/// Mount and unmount notifications collected during a burst.
+#[derive(Default)]
+struct VolumeChanges {
+    /// Whether each volume was mounted before the burst, and whether it's mounted now, by path
+    states: HashMap<String, (bool, bool)>,
+    /// When the last notification arrived, or `None` if there's nothing to report
+    last_change: Option<Instant>,
 }
 
-/// Start watching for volume mount/unmount events.
-/// Call this once at app initialization.
-pub fn start_volume_watcher(app: &AppHandle) {
-    // Store app handle for event emission
-    if APP_HANDLE.set(app.clone()).is_err() {
-        debug!("Volume watcher already initialized");
-        return;
+impl VolumeChanges {
+    /// Records a mount or unmount notification for the volume at `path`.
+    fn record(&mut self, path: &str, mounted: bool, at: Instant) {
+        // The first notification tells what the state was before the burst
+        let state = self.states.entry(path.to_string()).or_insert((!mounted, mounted));
+        state.1 = mounted;
+        self.last_change = Some(at);
     }
 
-    // Initialize known volumes
-    let initial_volumes = get_current_volumes();
-    let known = KNOWN_VOLUMES.get_or_init(|| Mutex::new(HashSet::new()));
-    if let Ok(mut known_guard) = known.lock() {
-        *known_guard = initial_volumes.clone();
-        debug!("Initial volumes: {:?}", known_guard);
+    /// Whether no notification arrived for `VOLUME_DEBOUNCE` before `now`.
+    fn is_quiet(&self, now: Instant) -> bool {
+        self.last_change
+            .is_none_or(|last| now.saturating_duration_since(last) >= VOLUME_DEBOUNCE)
     }
 
-    info!("Starting volume mount/unmount watcher on /Volumes");
+    /// Takes the mounted and the unmounted volumes, sorted, and starts a new burst.
+    fn take(&mut self) -> (Vec<String>, Vec<String>) {
+        self.last_change = None;
+        let mut mounted = Vec::new();
+        let mut unmounted = Vec::new();
+        for (path, (was_mounted, is_mounted)) in self.states.drain() {
+            match (was_mounted, is_mounted) {
+                (false, true) => mounted.push(path),
+                (true, false) => unmounted.push(path),
+                _ => {}
+            }
+        }
+        mounted.sort();
+        unmounted.sort();
+        (mounted, unmounted)
+    }
+}
 
-    // Create a watcher for /Volumes directory
-    let watcher_result = notify::recommended_watcher(move |result: Result<Event, notify::Error>| match result {
-        Ok(event) => handle_fs_event(event),
-        Err(e) => error!("Volume watcher error: {}", e),
-    });
+define_class!(
+    // SAFETY:
+    // - NSObject has no special subclassing requirements.
+    // - VolumeObserver doesn't implement Drop.
+    #[unsafe(super(NSObject))]
+    #[name = "RCVolumeObserver"]
+    struct VolumeObserver;
 
-    match watcher_result {
-        Ok(mut watcher) => {
-            // Watch /Volumes with non-recursive mode (we only care about direct children)
-            let volumes_path = Path::new("/Volumes");
-            if let Err(e) = watcher.watch(volumes_path, RecursiveMode::NonRecursive) {
-                error!("Failed to watch /Volumes: {}", e);
-                return;
-            }
+    unsafe impl NSObjectProtocol for VolumeObserver {}
 
-            // Store the watcher to keep it alive
-            let watcher_storage = WATCHER.get_or_init(|| Mutex::new(None));
-            if let Ok(mut guard) = watcher_storage.lock() {
-                *guard = Some(watcher);
+    impl VolumeObserver {
+        #[unsafe(method(volumeDidMount:))]
+        fn volumeDidMount(&self, notification: &AnyObject) {
+            if let Some(path) = device_path(notification) {
+                record_change(&path, true);
             }
-
-            info!("Volume watcher started successfully");
         }
-        Err(e) => {
-            error!("Failed to create volume watcher: {}", e);
+
+        #[unsafe(method(volumeDidUnmount:))]
+        fn volumeDidUnmount(&self, notification: &AnyObject) {
+            if let Some(path) = device_path(notification) {
+                record_change(&path, false);
+            }
         }
     }
-}
+);
 
-/// Handle filesystem events on /Volumes
-fn handle_fs_event(event: Event) {
-    // We're interested in Create and Remove events
-    match event.kind {
-        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_) => {
-            // Debounce: compare current state with known state
-            check_for_volume_changes();
-        }
-        _ => {}
+impl VolumeObserver {
+    fn new() -> Retained<Self> {
+        let this = Self::alloc().set_ivars(());
+        unsafe { msg_send![super(this), init] }
     }
 }
 
-/// Check for volume changes by comparing current state with known state
-fn check_for_volume_changes() {
-    let current_volumes = get_current_volumes();
+/// The observer and the notification center it's registered with.
+struct ObserverRegistration {
+    // Keep the observer alive - the notification center doesn't retain it
+    observer: Retained<VolumeObserver>,
+    center: Retained<AnyObject>,
+}
 
-    let known = match KNOWN_VOLUMES.get() {
-        Some(k) => k,
-        None => return,
-    };
+// SAFETY: The registration is only created and removed on the main thread.
+// We need Send to store it in a static Mutex, but actual access is synchronized.
+unsafe impl Send for ObserverRegistration {}
 
-    let mut known_guard = match known.lock() {
-        Ok(g) => g,
-        Err(_) => return,
-    };
+/// Gets `NSWorkspace.sharedWorkspace.notificationCenter`.
+fn workspace_notification_center() -> Option<Retained<AnyObject>> {
+    let workspace_class = AnyClass::get(c"NSWorkspace")?;
+    let workspace: Option<Retained<AnyObject>> = unsafe { msg_send![workspace_class, sharedWorkspace] };
+    let workspace = workspace?;
+    unsafe { msg_send![&*workspace, notificationCenter] }
+}
 
-    // Find newly mounted volumes
-    for path in current_volumes.difference(&known_guard) {
-        info!("Volume mounted: {}", path);
-        emit_volume_mounted(path);
+/// Reads the volume's path from a mount or unmount notification.
+fn device_path(notification: &AnyObject) -> Option<String> {
+    let user_info: Option<Retained<AnyObject>> = unsafe { msg_send![notification, userInfo] };
+    let user_info = user_info?;
+    let key = NSString::from_str(DEVICE_PATH_KEY);
+    let path: Option<Retained<NSString>> = unsafe { msg_send![&*user_info, objectForKey: &*key] };
+    path.map(|p| p.to_string())
+}
+
+/// Start watching for volume mount/unmount events.
+/// Call this once at app initialization, on the main thread, after the sidebar config is loaded.
+pub fn start_volume_watching(app: AppHandle) {
+    // Store app handle for event emission
+    if APP_HANDLE.set(app).is_err() {
+        debug!("Volume watcher already initialized");
+        return;
     }
 
-    // Find unmounted volumes
-    for path in known_guard.difference(&current_volumes) {
-        info!("Volume unmounted: {}", path);
-        emit_volume_unmounted(path);
+    // Remember the mounted volumes, so the ones unmounted later can be reported with their location
+    if let Ok(mut known) = KNOWN_LOCATIONS.lock() {
+        *known = list_locations().into_iter().map(|l| (l.path.clone(), l)).collect();
+    }
+
+    let Some(center) = workspace_notification_center() else {
+        error!("Failed to get the NSWorkspace notification center");
+        return;
+    };
+    let observer = VolumeObserver::new();
+    for (selector, name) in [
+        (sel!(volumeDidMount:), DID_MOUNT_NOTIFICATION),
+        (sel!(volumeDidUnmount:), DID_UNMOUNT_NOTIFICATION),
+    ] {
+        let name = NSString::from_str(name);
+        let object: Option<&AnyObject> = None;
+        let _: () =
+            unsafe { msg_send![&*center, addObserver: &*observer, selector: selector, name: &*name, object: object] };
+    }
+
+    // Store the observer to keep it alive
+    let storage = OBSERVER.get_or_init(|| Mutex::new(None));
+    if let Ok(mut guard) = storage.lock() {
+        *guard = Some(ObserverRegistration { observer, center });
     }
 
-    // Update known volumes
-    *known_guard = current_volumes;
+    info!("Volume watcher started successfully");
 }
 
 /// Stop watching for volume events.
 /// Call this on app shutdown.
 #[allow(dead_code)]
-pub fn stop_volume_watcher() {
-    if let Some(watcher_storage) = WATCHER.get()
-        && let Ok(mut guard) = watcher_storage.lock()
+pub fn stop_volume_watching() {
+    if let Some(storage) = OBSERVER.get()
+        && let Ok(mut guard) = storage.lock()
+        && let Some(registration) = guard.take()
     {
-        *guard = None;
+        let _: () = unsafe { msg_send![&*registration.center, removeObserver: &*registration.observer] };
     }
     info!("Volume watcher stopped");
 }
 
-/// Emit a volume mounted event to the frontend.
-fn emit_volume_mounted(volume_path: &str) {
-    if let Some(app) = APP_HANDLE.get() {
-        let payload = VolumeEventPayload {
-            volume_path: volume_path.to_string(),
+/// Records a notification, and schedules a report for when the burst is over.
+fn record_change(path: &str, mounted: bool) {
+    let Ok(mut changes) = PENDING_CHANGES.lock() else {
+        return;
+    };
+    // A report is already scheduled while there are changes
+    let report_scheduled = changes.last_change.is_some();
+    changes.record(path, mounted, Instant::now());
+    drop(changes);
+
+    if !report_scheduled {
+        std::thread::spawn(report_when_quiet);
+    }
+}
+
+/// Waits until no notification arrived for `VOLUME_DEBOUNCE`, then reports the changes.
+fn report_when_quiet() {
+    loop {
+        std::thread::sleep(VOLUME_DEBOUNCE);
+        let Ok(mut changes) = PENDING_CHANGES.lock() else {
+            return;
         };
-        if let Err(e) = app.emit("volume-mounted", payload) {
-            error!("Failed to emit volume-mounted event: {}", e);
-        } else {
-            debug!("Emitted volume-mounted event for {}", volume_path);
+        if changes.is_quiet(Instant::now()) {
+            let (mounted, unmounted) = changes.take();
+            drop(changes);
+            report_changes(&mounted, &unmounted);
+            return;
         }
     }
 }
 
-/// Emit a volume unmounted event to the frontend.
-fn emit_volume_unmounted(volume_path: &str) {
+/// Emits an event for each mounted and unmounted volume.
+fn report_changes(mounted: &[String], unmounted: &[String]) {
+    if mounted.is_empty() && unmounted.is_empty() {
+        return;
+    }
+    crate::file_system::invalidate_mount_cache();
+
+    // Describe newly mounted volumes like the volume list does
+    let locations = if mounted.is_empty() {
+        Vec::new()
+    } else {
+        list_locations()
+    };
+    for path in mounted {
+        info!("Volume mounted: {}", path);
+        let location = locations.iter().find(|l| &l.path == path).cloned();
+        if let (Some(location), Ok(mut known)) = (&location, KNOWN_LOCATIONS.lock()) {
+            known.insert(path.clone(), location.clone());
+        }
+        emit_volume_event("volume-added", path, location);
+    }
+
+    for path in unmounted {
+        info!("Volume unmounted: {}", path);
+        crate::icons::invalidate_path_icon(path);
+        let location = KNOWN_LOCATIONS.lock().ok().and_then(|mut known| known.remove(path));
+        emit_volume_event("volume-removed", path, location);
+    }
+}
+
+/// Emit a `volume-added` or `volume-removed` event to the frontend.
+fn emit_volume_event(event: &str, volume_path: &str, location: Option<LocationInfo>) {
     if let Some(app) = APP_HANDLE.get() {
         let payload = VolumeEventPayload {
             volume_path: volume_path.to_string(),
+            volume_id: path_to_id(volume_path),
+            location,
         };
-        if let Err(e) = app.emit("volume-unmounted", payload) {
-            error!("Failed to emit volume-unmounted event: {}", e);
+        if let Err(e) = app.emit(event, payload) {
+            error!("Failed to emit {} event: {}", event, e);
         } else {
-            debug!("Emitted volume-unmounted event for {}", volume_path);
+            debug!("Emitted {} event for {}", event, volume_path);
         }
     }
 }
@@ -178,17 +295,59 @@ mod tests {
     fn test_volume_event_payload_serialization() {
         let payload = VolumeEventPayload {
             volume_path: "/Volumes/MyDrive".to_string(),
+            volume_id: path_to_id("/Volumes/MyDrive"),
+            location: None,
         };
         let json = serde_json::to_string(&payload).unwrap();
         assert!(json.contains("volumePath"));
         assert!(json.contains("/Volumes/MyDrive"));
+        assert!(json.contains(r#""volumeId":"volumesmydrive""#));
+        assert!(!json.contains("location"));
+    }
+
+    #[test]
+    fn test_burst_of_mounts_is_reported_once_per_volume() {
+        // A disk with two partitions mounted during one debounce window, one of them notified twice
+        let mut changes = VolumeChanges::default();
+        let start = Instant::now();
+        changes.record("/Volumes/Photos", true, start);
+        changes.record("/Volumes/Backup", true, start);
+        changes.record("/Volumes/Photos", true, start);
+
+        let (mounted, unmounted) = changes.take();
+
+        assert_eq!(mounted, vec!["/Volumes/Backup", "/Volumes/Photos"]);
+        assert!(unmounted.is_empty());
     }
 
     #[test]
-    fn test_get_current_volumes() {
-        let volumes = get_current_volumes();
-        // /Volumes should always have at least "Macintosh HD" or similar
-        // This test just ensures the function doesn't panic
-        assert!(volumes.is_empty() || !volumes.is_empty());
+    fn test_volume_that_came_and_went_in_a_burst_is_not_reported() {
+        let mut changes = VolumeChanges::default();
+        let start = Instant::now();
+        changes.record("/Volumes/Flaky", true, start);
+        changes.record("/Volumes/Flaky", false, start);
+
+        let (mounted, unmounted) = changes.take();
+
+        assert!(mounted.is_empty());
+        assert!(unmounted.is_empty());
+    }
+
+    #[test]
+    fn test_burst_is_reported_once_quiet() {
+        let mut changes = VolumeChanges::default();
+        let start = Instant::now();
+        assert!(changes.is_quiet(start));
+
+        changes.record("/Volumes/Backup", true, start);
+        changes.record("/Volumes/Old", false, start + VOLUME_DEBOUNCE / 2);
+
+        assert!(!changes.is_quiet(start + VOLUME_DEBOUNCE));
+        assert!(changes.is_quiet(start + VOLUME_DEBOUNCE * 2));
+        assert_eq!(
+            changes.take(),
+            (vec!["/Volumes/Backup".to_string()], vec!["/Volumes/Old".to_string()])
+        );
+        assert!(changes.is_quiet(start));
     }
 }
@@ -79,7 +79,18 @@ function parseViewMode(raw: unknown): ViewMode {
 }
 
 function parseSortColumn(raw: unknown): SortColumn {
-    const validColumns: SortColumn[] = ['name', 'extension', 'size', 'modified', 'created']
+    const validColumns: SortColumn[] = [
+        'name',
+        'extension',
+        'size',
+        'modified',
+        'created',
+        'changed',
+        'added',
+        'opened',
+        'manual',
+        'kind',
+    ]
     if (typeof raw === 'string' && validColumns.includes(raw as SortColumn)) {
         return raw as SortColumn
     }
@@ -167,7 +178,18 @@ type ColumnSortOrders = Partial<Record<SortColumn, SortOrder>>
 
 function isValidSortOrders(value: unknown): value is ColumnSortOrders {
     if (typeof value !== 'object' || value === null) return false
-    const validColumns: string[] = ['name', 'extension', 'size', 'modified', 'created']
+    const validColumns: string[] = [
+        'name',
+        'extension',
+        'size',
+        'modified',
+        'created',
+        'changed',
+        'added',
+        'opened',
+        'manual',
+        'kind',
+    ]
     const validOrders: string[] = ['ascending', 'descending']
     return Object.entries(value).every(([k, v]) => validColumns.includes(k) && validOrders.includes(v as string))
 }
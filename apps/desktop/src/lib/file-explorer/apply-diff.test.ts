@@ -236,6 +236,35 @@ describe('applyDiff', () => {
         })
     })
 
+    describe('renames', () => {
+        it('moves the renamed file to its new sorted position', () => {
+            const files = [file('a.txt'), file('b.txt'), file('c.txt')]
+            const changes: DiffChange[] = [{ type: 'rename', entry: file('d.txt'), oldPath: '/test/a.txt' }]
+
+            applyDiff(files, 0, changes)
+
+            expect(files.map((f) => f.name)).toEqual(['b.txt', 'c.txt', 'd.txt'])
+        })
+
+        it('keeps the cursor on the renamed file', () => {
+            const files = [file('a.txt'), file('b.txt'), file('c.txt')]
+            const changes: DiffChange[] = [{ type: 'rename', entry: file('z.txt'), oldPath: '/test/b.txt' }]
+
+            const newIndex = applyDiff(files, 1, changes)
+
+            expect(files[newIndex].name).toBe('z.txt')
+        })
+
+        it('keeps the cursor on another file when a different one is renamed', () => {
+            const files = [file('a.txt'), file('b.txt'), file('c.txt')]
+            const changes: DiffChange[] = [{ type: 'rename', entry: file('0.txt'), oldPath: '/test/c.txt' }]
+
+            const newIndex = applyDiff(files, 1, changes)
+
+            expect(files[newIndex].name).toBe('b.txt')
+        })
+    })
+
     describe('hidden files interaction', () => {
         // Note: Hidden file filtering happens AFTER applyDiff in the component
         // This test verifies the raw list is correct before filtering
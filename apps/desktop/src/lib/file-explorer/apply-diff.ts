@@ -15,30 +15,27 @@ import type { FileEntry, DiffChange } from './types'
  */
 export function applyDiff(files: FileEntry[], selectedIndex: number, changes: DiffChange[]): number {
     // Capture the currently selected file's path before any changes
-    const selectedPath = files[selectedIndex]?.path
+    let selectedPath = files[selectedIndex]?.path
 
     // Apply all changes
     for (const change of changes) {
         if (change.type === 'add') {
-            // Insert in sorted position
-            const entry = change.entry
-            let insertIndex = files.findIndex((f) => {
-                // Keep ".." at the top
-                if (f.name === '..') return false
-                if (entry.name === '..') return true
-                // Directories come before files
-                if (entry.isDirectory && !f.isDirectory) return true
-                if (!entry.isDirectory && f.isDirectory) return false
-                // Alphabetical within same type
-                return f.name.toLowerCase() > entry.name.toLowerCase()
-            })
-            if (insertIndex === -1) insertIndex = files.length
-            files.splice(insertIndex, 0, entry)
+            insertSorted(files, change.entry)
         } else if (change.type === 'remove') {
             const idx = files.findIndex((f) => f.path === change.entry.path)
             if (idx >= 0) {
                 files.splice(idx, 1)
             }
+        } else if (change.type === 'rename') {
+            const idx = files.findIndex((f) => f.path === change.oldPath)
+            if (idx >= 0) {
+                files.splice(idx, 1)
+            }
+            insertSorted(files, change.entry)
+            // Keep the cursor on the renamed file
+            if (selectedPath === change.oldPath) {
+                selectedPath = change.entry.path
+            }
         } else {
             // change.type === 'modify'
             const idx = files.findIndex((f) => f.path === change.entry.path)
@@ -64,3 +61,19 @@ export function applyDiff(files: FileEntry[], selectedIndex: number, changes: Di
 
     return 0
 }
+
+/** Inserts an entry in sorted position: ".." first, then directories, then files, alphabetically. */
+function insertSorted(files: FileEntry[], entry: FileEntry): void {
+    let insertIndex = files.findIndex((f) => {
+        // Keep ".." at the top
+        if (f.name === '..') return false
+        if (entry.name === '..') return true
+        // Directories come before files
+        if (entry.isDirectory && !f.isDirectory) return true
+        if (!entry.isDirectory && f.isDirectory) return false
+        // Alphabetical within same type
+        return f.name.toLowerCase() > entry.name.toLowerCase()
+    })
+    if (insertIndex === -1) insertIndex = files.length
+    files.splice(insertIndex, 0, entry)
+}
@@ -36,6 +36,7 @@ vi.mock('$lib/tauri-commands', () => ({
                 group: 'staff',
                 iconId: 'dir',
                 extendedMetadataLoaded: true,
+                ownerResolved: true,
             }
         } else {
             mockSelectedEntry = {
@@ -48,6 +49,7 @@ vi.mock('$lib/tauri-commands', () => ({
                 group: 'staff',
                 iconId: 'file',
                 extendedMetadataLoaded: true,
+                ownerResolved: true,
             }
         }
         return Promise.resolve(mockSelectedEntry)
@@ -18,6 +18,7 @@ vi.mock('$lib/tauri-commands', () => {
             shares: [{ name: 'TestShare', isDisk: true }],
             authMode: 'guest_allowed',
             fromCache: false,
+            authChanged: false,
         }),
         isKeychainError: (error: unknown): boolean => {
             return (
@@ -38,6 +39,7 @@ vi.mock('$lib/network-store.svelte', () => {
             shares: [{ name: 'TestShare', isDisk: true }],
             authMode: 'guest_allowed',
             fromCache: false,
+            authChanged: false,
         }),
         clearShareState: vi.fn(),
     }
@@ -81,6 +83,8 @@ describe('Network authentication types', () => {
                 lastConnectionMode: 'credentials',
                 lastKnownAuthOptions: 'guest_or_credentials',
                 username: 'testuser',
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             }
 
             expect(share.serverName).toBe('TestServer')
@@ -98,6 +102,8 @@ describe('Network authentication types', () => {
                 lastConnectionMode: 'guest',
                 lastKnownAuthOptions: 'guest_only',
                 username: null,
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             }
 
             expect(share.username).toBeNull()
@@ -177,6 +183,8 @@ describe('Authentication flow logic', () => {
                 lastConnectionMode: 'credentials',
                 lastKnownAuthOptions: 'guest_or_credentials',
                 username: 'storeduser',
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             })
 
             const known = await getKnownShareByName('TestServer', 'Documents')
@@ -194,6 +202,8 @@ describe('Authentication flow logic', () => {
                 lastConnectionMode: 'guest',
                 lastKnownAuthOptions: 'guest_only',
                 username: null,
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             }
             const currentAuthMode: AuthMode = 'guest_allowed'
 
@@ -211,6 +221,8 @@ describe('Authentication flow logic', () => {
                 lastConnectionMode: 'credentials',
                 lastKnownAuthOptions: 'credentials_only',
                 username: 'testuser',
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             }
 
             // Still credentials required, no change
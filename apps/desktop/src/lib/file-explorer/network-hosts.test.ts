@@ -278,6 +278,7 @@ describe('Share listing types', () => {
             shares: [],
             authMode: 'guest_allowed',
             fromCache: false,
+            authChanged: false,
             ...overrides,
         }
     }
@@ -368,6 +369,7 @@ describe('Share caching behavior', () => {
                 shares: [{ name: 'Documents', isDisk: true }],
                 authMode: 'guest_allowed',
                 fromCache: false,
+                authChanged: false,
             }
             cache.set(hostId, { result, expiresAt: now + ttl })
             return result
@@ -404,6 +406,7 @@ describe('Share caching behavior', () => {
             shares: [],
             authMode: 'creds_required',
             fromCache: false,
+            authChanged: false,
         })
 
         expect(cachedAuthMode).toBe('creds_required')
@@ -463,6 +466,8 @@ describe('Known shares store types', () => {
             lastConnectionMode: 'credentials',
             lastKnownAuthOptions: 'guest_or_credentials',
             username: 'david',
+            connectCount: 1,
+            recentConnections: ['2026-01-06T12:00:00Z'],
             ...overrides,
         }
     }
@@ -524,6 +529,8 @@ describe('Known shares username pre-fill logic', () => {
                 lastConnectionMode: 'credentials',
                 lastKnownAuthOptions: 'credentials_only',
                 username: 'admin',
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             },
         ]
 
@@ -546,6 +553,8 @@ describe('Known shares username pre-fill logic', () => {
                 lastConnectionMode: 'guest',
                 lastKnownAuthOptions: 'guest_only',
                 username: null,
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             },
         ]
 
@@ -634,6 +643,8 @@ describe('Known shares store operations', () => {
             lastConnectionMode: 'guest',
             lastKnownAuthOptions: 'guest_only',
             username: null,
+            connectCount: 1,
+            recentConnections: ['2026-01-06T12:00:00Z'],
         })
 
         expect(shares).toHaveLength(1)
@@ -650,6 +661,8 @@ describe('Known shares store operations', () => {
                 lastConnectionMode: 'guest',
                 lastKnownAuthOptions: 'guest_only',
                 username: null,
+                connectCount: 1,
+                recentConnections: ['2026-01-05T12:00:00Z'],
             },
         ]
 
@@ -671,6 +684,8 @@ describe('Known shares store operations', () => {
             lastConnectionMode: 'credentials',
             lastKnownAuthOptions: 'guest_or_credentials',
             username: 'admin',
+            connectCount: 1,
+            recentConnections: ['2026-01-06T12:00:00Z'],
         })
 
         expect(shares).toHaveLength(1)
@@ -688,6 +703,8 @@ describe('Known shares store operations', () => {
                 lastConnectionMode: 'credentials',
                 lastKnownAuthOptions: 'credentials_only',
                 username: 'admin',
+                connectCount: 1,
+                recentConnections: ['2026-01-06T12:00:00Z'],
             },
         ]
 
@@ -19,6 +19,7 @@ export function createFileEntry(partial: {
     group?: string
     iconId?: string
     extendedMetadataLoaded?: boolean
+    ownerResolved?: boolean
 }): FileEntry {
     const isDir = partial.isDirectory
     return {
@@ -34,6 +35,7 @@ export function createFileEntry(partial: {
         group: partial.group ?? 'staff',
         iconId: partial.iconId ?? (isDir ? 'dir' : 'file'),
         extendedMetadataLoaded: partial.extendedMetadataLoaded ?? true,
+        ownerResolved: partial.ownerResolved ?? true,
     }
 }
 
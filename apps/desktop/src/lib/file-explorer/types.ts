@@ -6,6 +6,8 @@ export interface FileEntry {
     size?: number
     modifiedAt?: number
     createdAt?: number
+    /** When the file's metadata (inode) last changed. Not the same as createdAt. */
+    changedAt?: number
     /** When the file was added to its current directory (macOS only) */
     addedAt?: number
     /** When the file was last opened (macOS only) */
@@ -14,10 +16,63 @@ export interface FileEntry {
     owner: string
     group: string
     iconId: string
+    /** Inode number, to tell a rename apart from a delete and an add */
+    inode?: number
     /** Whether extended metadata (addedAt, openedAt) has been loaded */
     extendedMetadataLoaded: boolean
+    /** Whether owner and group are names. If false, they're the numeric uid and gid, see resolveOwnersBatch. */
+    ownerResolved: boolean
+    /** Where a symlink points, as stored in the link (so possibly relative). Also set for broken symlinks. */
+    symlinkTarget?: string
 }
 
+/** Owner and group names for a file listed with resolveOwners off. Null if the file couldn't be read. */
+export interface OwnerNames {
+    path: string
+    owner: string | null
+    group: string | null
+}
+
+/** Result of deleting one of the paths passed to deletePaths. */
+export interface DeleteResult {
+    path: string
+    success: boolean
+    /** Why the delete failed. Null if it succeeded. */
+    error: string | null
+}
+
+/** What to do when a copy or move finds its destination name taken. Rename picks the next free "name copy". */
+export type ConflictPolicy = 'skip' | 'overwrite' | 'rename' | 'error'
+
+/** Result of copying or moving one of the paths passed to copyPaths or movePaths. */
+export interface TransferResult {
+    path: string
+    /** Where the entry ended up. Null if it was skipped or failed. */
+    destination: string | null
+    success: boolean
+    /** The policy that was applied because the destination existed. Null if it didn't. */
+    conflict: ConflictPolicy | null
+    /** Why the copy or move failed. Null if it succeeded. */
+    error: string | null
+}
+
+/** A Finder tag, like "Important" in red. */
+export interface FinderTag {
+    name: string
+    /** Finder's label color: 0 none, 1 gray, 2 green, 3 purple, 4 blue, 5 yellow, 6 red, 7 orange */
+    color: number
+}
+
+/** Finder tags of one path, from getFinderTagsBatch. */
+export interface FinderTags {
+    /** File path (key for merging) */
+    path: string
+    tags: FinderTag[]
+}
+
+/** Symlinks point to a path, which may not exist. Hard links are another name for an existing file. */
+export type LinkKind = 'symbolic' | 'hard'
+
 /** Cloud sync status for files in Dropbox/iCloud/etc. folders */
 export type SyncStatus = 'synced' | 'online_only' | 'uploading' | 'downloading' | 'unknown'
 
@@ -32,15 +87,22 @@ export interface ListingStartResult {
     totalCount: number
     /** Maximum filename width in pixels (for Brief mode columns). None if font metrics not available. */
     maxFilenameWidth?: number
+    /** The sort the listing uses. Differs from the requested one if a saved sort was applied. */
+    sortBy: SortColumn
+    sortOrder: SortOrder
+    /** True if the directory can't be read for lack of permissions. The listing is then empty. */
+    accessDenied: boolean
 }
 
 /**
  * A single change in a directory diff.
  */
 export interface DiffChange {
-    type: 'add' | 'remove' | 'modify'
-    /** The affected file entry */
+    type: 'add' | 'remove' | 'modify' | 'rename'
+    /** The affected file entry. For renames, the entry under its new name. */
     entry: FileEntry
+    /** For renames, the path the entry had before */
+    oldPath?: string
 }
 
 /**
@@ -56,6 +118,17 @@ export interface DirectoryDiff {
     changes: DiffChange[]
 }
 
+/**
+ * Sent by the backend watcher instead of a diff when too many files changed at once.
+ * The listing is up to date on the backend; re-fetch what's shown.
+ */
+export interface DirectoryRefreshNeeded {
+    /** Listing ID this event belongs to */
+    listingId: string
+    /** Monotonic sequence number, shared with diffs */
+    sequence: number
+}
+
 /**
  * Category of a location item.
  */
@@ -77,6 +150,17 @@ export interface VolumeInfo {
     icon?: string
     /** Whether this can be ejected */
     isEjectable: boolean
+    /** Size of the volume in bytes. Not set for favorites and cloud drives. */
+    totalBytes?: number
+    /** Free space on the volume in bytes. Not set for favorites and cloud drives. */
+    availableBytes?: number
+}
+
+/** Which location categories the sidebar shows, and in what order. The main volume is always shown. */
+export interface SidebarConfig {
+    /** Categories in display order. Missing ones go after these, in the default order. */
+    categoryOrder: LocationCategory[]
+    hiddenCategories: LocationCategory[]
 }
 
 // ============================================================================
@@ -84,7 +168,17 @@ export interface VolumeInfo {
 // ============================================================================
 
 /** Column to sort files by. Must match Rust enum. */
-export type SortColumn = 'name' | 'extension' | 'size' | 'modified' | 'created'
+export type SortColumn =
+    | 'name'
+    | 'extension'
+    | 'size'
+    | 'modified'
+    | 'created'
+    | 'changed'
+    | 'added'
+    | 'opened'
+    | 'manual'
+    | 'kind'
 
 /** Sort order. Must match Rust enum. */
 export type SortOrder = 'ascending' | 'descending'
@@ -96,6 +190,11 @@ export const defaultSortOrders: Record<SortColumn, SortOrder> = {
     size: 'descending',
     modified: 'descending',
     created: 'descending',
+    changed: 'descending',
+    added: 'descending',
+    opened: 'descending',
+    manual: 'ascending',
+    kind: 'ascending',
 }
 
 /** Default sort column when opening a new directory. */
@@ -107,6 +206,13 @@ export interface ResortResult {
     newCursorIndex?: number
 }
 
+/** Result of getting a range with getFileRangeOrRecreate. */
+export interface FileRangeResult {
+    entries: FileEntry[]
+    /** ID of the re-created listing if the original one had expired. Use it from now on. */
+    newListingId?: string
+}
+
 // ============================================================================
 // Network discovery types
 // ============================================================================
@@ -140,6 +246,10 @@ export interface ShareInfo {
     isDisk: boolean
     /** Optional description/comment for the share */
     comment?: string
+    /** Size of the disk the share is on, in bytes. Only set when listed with capacity. */
+    totalBytes?: number
+    /** Free space available to the user on the share, in bytes. Only set when listed with capacity. */
+    freeBytes?: number
 }
 
 /** Authentication mode detected for a host. */
@@ -153,6 +263,20 @@ export interface ShareListResult {
     authMode: AuthMode
     /** Whether this result came from cache */
     fromCache: boolean
+    /** Whether the auth mode differs from the one saved for the host last time, like when guests are no longer allowed */
+    authChanged: boolean
+}
+
+/** Result of testing the connection to a host. */
+export interface ConnectionTestResult {
+    /** Whether the SMB port accepted a TCP connection */
+    reachable: boolean
+    /** Whether the server accepted the credentials (or guest access, if none were given) */
+    authOk: boolean
+    /** Negotiated SMB dialect (for example, "3.1.1"), if known */
+    dialect: string | null
+    /** Time it took to open the TCP connection, in milliseconds */
+    latencyMs: number
 }
 
 /** Error types for share listing operations. */
@@ -191,6 +315,10 @@ export interface KnownNetworkShare {
     lastKnownAuthOptions: AuthOptions
     /** Username used (null for guest) */
     username: string | null
+    /** How many times we've successfully connected */
+    connectCount: number
+    /** When we last connected (ISO 8601), oldest first, at most 10 */
+    recentConnections: string[]
 }
 
 // ============================================================================
@@ -223,6 +351,18 @@ export interface MountResult {
     alreadyMounted: boolean
 }
 
+/** Phase of a mount, best-effort. */
+export type MountPhase = 'connecting' | 'still_connecting' | 'mounted' | 'failed'
+
+/** Payload of `smb-mount-progress` events. */
+export interface MountProgress {
+    server: string
+    share: string
+    phase: MountPhase
+    /** Seconds since the mount started */
+    elapsedSecs: number
+}
+
 /** Error types for mount operations. */
 export type MountError =
     | { type: 'host_unreachable'; message: string }
@@ -234,3 +374,13 @@ export type MountError =
     | { type: 'cancelled'; message: string }
     | { type: 'protocol_error'; message: string }
     | { type: 'mount_path_conflict'; message: string }
+    | { type: 'in_use'; message: string }
+
+/** Error returned by file system commands. Branch on `type`; `message` is for logs and as a fallback. */
+export type CommandError =
+    | { type: 'not_found'; message: string }
+    | { type: 'permission_denied'; message: string }
+    | { type: 'listing_expired'; message: string }
+    | { type: 'invalid_argument'; message: string }
+    | { type: 'cross_volume_move'; message: string }
+    | { type: 'io'; message: string }
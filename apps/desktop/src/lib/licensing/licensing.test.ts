@@ -58,6 +58,8 @@ describe('License status types', () => {
             licenseType: 'commercial_subscription',
             organizationName: 'Test Corp',
             expiresAt: '2027-01-10',
+            activationCount: 1,
+            maxActivations: null,
         }
         vi.mocked(getLicenseStatus).mockResolvedValue(mockStatus)
 
@@ -75,6 +77,8 @@ describe('License status types', () => {
             licenseType: 'commercial_perpetual',
             organizationName: 'Perpetual LLC',
             expiresAt: null,
+            activationCount: 1,
+            maxActivations: null,
         }
         vi.mocked(getLicenseStatus).mockResolvedValue(mockStatus)
 
@@ -155,6 +159,7 @@ describe('License activation', () => {
             email: 'test@example.com',
             transactionId: 'txn_123',
             issuedAt: '2026-01-10',
+            maxActivations: null,
         }
         vi.mocked(activateLicense).mockResolvedValue(mockInfo)
 
@@ -186,6 +191,7 @@ describe('License info retrieval', () => {
             email: 'user@company.com',
             transactionId: 'txn_456',
             issuedAt: '2025-06-15',
+            maxActivations: null,
         }
         vi.mocked(getLicenseInfo).mockResolvedValue(mockInfo)
 
@@ -240,6 +246,8 @@ describe('Server validation', () => {
             licenseType: 'commercial_subscription',
             organizationName: 'Validated Corp',
             expiresAt: '2027-06-15',
+            activationCount: 1,
+            maxActivations: null,
         }
         vi.mocked(validateLicenseWithServer).mockResolvedValue(mockStatus)
 
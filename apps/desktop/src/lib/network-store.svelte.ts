@@ -257,8 +257,9 @@ export function isShareDataStale(hostId: string): boolean {
 /**
  * Fetch shares for a host. Updates the share state reactively.
  * Returns the result or throws an error.
+ * @param refresh Skip the backend's cache, for when the user asks to refresh
  */
-export async function fetchShares(host: NetworkHost): Promise<ShareListResult> {
+export async function fetchShares(host: NetworkHost, refresh = false): Promise<ShareListResult> {
     if (!host.hostname) {
         throw new Error('Host hostname not resolved')
     }
@@ -267,7 +268,7 @@ export async function fetchShares(host: NetworkHost): Promise<ShareListResult> {
     shareStates.set(host.id, { status: 'loading' })
 
     try {
-        const result = await listSharesOnHost(host.id, host.hostname, host.ipAddress, host.port)
+        const result = await listSharesOnHost(host.id, host.hostname, host.ipAddress, host.port, false, refresh)
         shareStates.set(host.id, { status: 'loaded', result, fetchedAt: Date.now() })
         return result
     } catch (error) {
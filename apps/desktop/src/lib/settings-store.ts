@@ -11,11 +11,29 @@ export type FullDiskAccessChoice = 'allow' | 'deny' | 'notAskedYet'
 export interface Settings {
     showHiddenFiles: boolean
     fullDiskAccessChoice: FullDiskAccessChoice
+    /** Restore both panes' directories and cursors on the next launch, even after a crash */
+    restoreSession: boolean
+    /** Compute folder sizes in the background when listing a directory */
+    computeFolderSizes: boolean
+    /** Glob patterns of names to leave out of listings, like `.DS_Store` or `__pycache__/` */
+    ignorePatterns: string[]
+    /** List symlinks to folders as folders. If false, all symlinks are listed as files. */
+    followSymlinksForType: boolean
+    /** How long to wait for file changes to settle before refreshing a listing, in ms. Longer suits network mounts. */
+    watcherDebounceMs: number
+    /** How long lists of network shares are cached, in seconds. Longer suits stable home networks. */
+    shareCacheTtlSecs: number
 }
 
 const DEFAULT_SETTINGS: Settings = {
     showHiddenFiles: true,
     fullDiskAccessChoice: 'notAskedYet',
+    restoreSession: true,
+    computeFolderSizes: false,
+    ignorePatterns: [],
+    followSymlinksForType: true,
+    watcherDebounceMs: 200,
+    shareCacheTtlSecs: 30,
 }
 
 let storeInstance: Store | null = null
@@ -36,6 +54,12 @@ export async function loadSettings(): Promise<Settings> {
         const store = await getStore()
         const showHiddenFiles = await store.get('showHiddenFiles')
         const fullDiskAccessChoice = await store.get('fullDiskAccessChoice')
+        const restoreSession = await store.get('restoreSession')
+        const computeFolderSizes = await store.get('computeFolderSizes')
+        const ignorePatterns = await store.get('ignorePatterns')
+        const followSymlinksForType = await store.get('followSymlinksForType')
+        const watcherDebounceMs = await store.get('watcherDebounceMs')
+        const shareCacheTtlSecs = await store.get('shareCacheTtlSecs')
 
         const validChoices: FullDiskAccessChoice[] = ['allow', 'deny', 'notAskedYet']
         return {
@@ -43,6 +67,21 @@ export async function loadSettings(): Promise<Settings> {
             fullDiskAccessChoice: validChoices.includes(fullDiskAccessChoice as FullDiskAccessChoice)
                 ? (fullDiskAccessChoice as FullDiskAccessChoice)
                 : DEFAULT_SETTINGS.fullDiskAccessChoice,
+            restoreSession: typeof restoreSession === 'boolean' ? restoreSession : DEFAULT_SETTINGS.restoreSession,
+            computeFolderSizes:
+                typeof computeFolderSizes === 'boolean' ? computeFolderSizes : DEFAULT_SETTINGS.computeFolderSizes,
+            ignorePatterns:
+                Array.isArray(ignorePatterns) && ignorePatterns.every((p) => typeof p === 'string')
+                    ? (ignorePatterns as string[])
+                    : DEFAULT_SETTINGS.ignorePatterns,
+            followSymlinksForType:
+                typeof followSymlinksForType === 'boolean'
+                    ? followSymlinksForType
+                    : DEFAULT_SETTINGS.followSymlinksForType,
+            watcherDebounceMs:
+                typeof watcherDebounceMs === 'number' ? watcherDebounceMs : DEFAULT_SETTINGS.watcherDebounceMs,
+            shareCacheTtlSecs:
+                typeof shareCacheTtlSecs === 'number' ? shareCacheTtlSecs : DEFAULT_SETTINGS.shareCacheTtlSecs,
         }
     } catch {
         // If store fails, return defaults
@@ -62,6 +101,24 @@ export async function saveSettings(settings: Partial<Settings>): Promise<void> {
         if (settings.fullDiskAccessChoice !== undefined) {
             await store.set('fullDiskAccessChoice', settings.fullDiskAccessChoice)
         }
+        if (settings.restoreSession !== undefined) {
+            await store.set('restoreSession', settings.restoreSession)
+        }
+        if (settings.computeFolderSizes !== undefined) {
+            await store.set('computeFolderSizes', settings.computeFolderSizes)
+        }
+        if (settings.ignorePatterns !== undefined) {
+            await store.set('ignorePatterns', settings.ignorePatterns)
+        }
+        if (settings.followSymlinksForType !== undefined) {
+            await store.set('followSymlinksForType', settings.followSymlinksForType)
+        }
+        if (settings.watcherDebounceMs !== undefined) {
+            await store.set('watcherDebounceMs', settings.watcherDebounceMs)
+        }
+        if (settings.shareCacheTtlSecs !== undefined) {
+            await store.set('shareCacheTtlSecs', settings.shareCacheTtlSecs)
+        }
         await store.save()
     } catch {
         // Silently fail - persistence is nice-to-have
@@ -6,21 +6,33 @@ import { type Event, listen, type UnlistenFn } from '@tauri-apps/api/event'
 import type {
     AuthMode,
     AuthOptions,
+    CommandError,
+    ConflictPolicy,
+    ConnectionTestResult,
     ConnectionMode,
+    DeleteResult,
     DiscoveryState,
     FileEntry,
+    FileRangeResult,
+    FinderTag,
+    FinderTags,
     KeychainError,
     KnownNetworkShare,
+    LinkKind,
     ListingStartResult,
     MountError,
     MountResult,
     NetworkHost,
+    OwnerNames,
     ResortResult,
+    ShareListError,
     ShareListResult,
+    SidebarConfig,
     SmbCredentials,
     SortColumn,
     SortOrder,
     SyncStatus,
+    TransferResult,
     VolumeInfo,
 } from './file-explorer/types'
 
@@ -39,14 +51,38 @@ export { listen }
  * @param includeHidden - Whether to include hidden files in total count.
  * @param sortBy - Column to sort by.
  * @param sortOrder - Ascending or descending.
+ * @param caseSensitive - Whether to compare names as raw bytes, so uppercase comes first. Defaults to false.
+ * @param useSavedSort - Whether to use the sort last chosen for this directory instead of sortBy and sortOrder,
+ *   if there is one. The result's sortBy and sortOrder tell which sort was used. Defaults to false.
+ * @param resolveOwners - Whether to look up owner and group names. If false, they're left as numeric IDs with
+ *   ownerResolved: false, to fill in with resolveOwnersBatch when needed. Defaults to true.
  */
 export async function listDirectoryStart(
     path: string,
     includeHidden: boolean,
     sortBy: SortColumn,
     sortOrder: SortOrder,
+    caseSensitive = false,
+    useSavedSort = false,
+    resolveOwners = true,
 ): Promise<ListingStartResult> {
-    return invoke<ListingStartResult>('list_directory_start', { path, includeHidden, sortBy, sortOrder })
+    return invoke<ListingStartResult>('list_directory_start', {
+        path,
+        includeHidden,
+        sortBy,
+        sortOrder,
+        caseSensitive,
+        useSavedSort,
+        resolveOwners,
+    })
+}
+
+/**
+ * Looks up owner and group names for entries listed with resolveOwners off.
+ * @param paths - File paths to resolve owners for.
+ */
+export async function resolveOwnersBatch(paths: string[]): Promise<OwnerNames[]> {
+    return invoke<OwnerNames[]>('resolve_owners_batch', { paths })
 }
 
 /**
@@ -69,6 +105,22 @@ export async function resortListing(
     return invoke<ResortResult>('resort_listing', { listingId, sortBy, sortOrder, cursorFilename, includeHidden })
 }
 
+/**
+ * Forgets the sorts saved for all directories. Sorts are saved by resortListing.
+ */
+export async function clearSavedSorts(): Promise<void> {
+    await invoke('clear_saved_sorts')
+}
+
+/**
+ * Sets how long the file watcher waits for changes to settle before refreshing a listing.
+ * Open listings keep their interval; listings opened afterward use the new one.
+ * @param debounceMs - Milliseconds, clamped to 50–2000.
+ */
+export async function setWatcherDebounceMs(debounceMs: number): Promise<void> {
+    await invoke('set_watcher_debounce_ms', { debounceMs })
+}
+
 /**
  * Gets a range of entries from a cached listing.
  * @param listingId - The listing ID from listDirectoryStart.
@@ -85,6 +137,36 @@ export async function getFileRange(
     return invoke<FileEntry[]>('get_file_range', { listingId, start, count, includeHidden })
 }
 
+/**
+ * Gets a range of entries, re-creating the listing if it has expired.
+ * @param listingId - The listing ID from listDirectoryStart.
+ * @param path - The directory the listing was started for.
+ * @param sortBy - The sort column the listing was started with.
+ * @param sortOrder - The sort order the listing was started with.
+ * @param start - Start index (0-based).
+ * @param count - Number of entries to return.
+ * @param includeHidden - Whether to include hidden files.
+ * @param caseSensitive - Whether the listing was started with case-sensitive sorting.
+ */
+export async function getFileRangeOrRecreate(
+    listingId: string,
+    path: string,
+    sortBy: SortColumn,
+    sortOrder: SortOrder,
+    start: number,
+    count: number,
+    includeHidden: boolean,
+    caseSensitive = false,
+): Promise<FileRangeResult> {
+    return invoke<FileRangeResult>('get_file_range_or_recreate', {
+        listingId,
+        origin: { path, sortBy, sortOrder, caseSensitive },
+        start,
+        count,
+        includeHidden,
+    })
+}
+
 /**
  * Gets total count of entries in a cached listing.
  * @param listingId - The listing ID from listDirectoryStart.
@@ -132,6 +214,81 @@ export async function listDirectoryEnd(listingId: string): Promise<void> {
     await invoke('list_directory_end', { listingId })
 }
 
+/**
+ * Computes the total size of a folder, recursively. Symlinks aren't followed and hardlinked files count once.
+ * Emits directory-size-progress events ({ token, path, size }) with the size so far while walking.
+ * Rejects if the path isn't a folder or the computation was cancelled.
+ * @param path - Folder path. Supports tilde expansion (~).
+ * @param token - Chosen by the caller, to cancel with cancelDirectorySize.
+ */
+export async function computeDirectorySize(path: string, token: string): Promise<number> {
+    return invoke<number>('compute_directory_size', { path, token })
+}
+
+/**
+ * Cancels a running computeDirectorySize. Returns true if it was still running.
+ * @param token - The token passed to computeDirectorySize.
+ */
+export async function cancelDirectorySize(token: string): Promise<boolean> {
+    return invoke<boolean>('cancel_directory_size', { token })
+}
+
+/**
+ * Searches a folder and its subfolders for entries whose names contain the query (case-insensitive).
+ * Emits find-result events ({ token, entries }) with batches of matches while walking.
+ * Resolves to the number of entries scanned. Rejects if the root isn't a folder.
+ * @param root - Folder path. Supports tilde expansion (~).
+ * @param query - Text the names must contain.
+ * @param maxResults - The search stops after this many matches.
+ * @param token - Chosen by the caller, to cancel with cancelFind.
+ */
+export async function findInDirectory(
+    root: string,
+    query: string,
+    maxResults: number,
+    token: string,
+): Promise<number> {
+    return invoke<number>('find_in_directory', { root, query, maxResults, token })
+}
+
+/**
+ * Searches the text files in a folder and its subfolders for lines matching a pattern.
+ * Binary files and files larger than maxFileSize are skipped.
+ * Emits a content-match event ({ token, path, lineNumber, line }) for every matching line.
+ * Resolves to the number of files searched. Rejects if the root isn't a folder or the regex is invalid.
+ * @param root - Folder path. Supports tilde expansion (~).
+ * @param pattern - Text the lines must contain (case-insensitive), or a regex if isRegex is true.
+ * @param token - Chosen by the caller, to cancel with cancelFind.
+ * @param isRegex - Whether the pattern is a regex. Defaults to false.
+ * @param maxFileSize - Larger files are skipped, in bytes. Defaults to 10 MB.
+ */
+export async function searchFileContents(
+    root: string,
+    pattern: string,
+    token: string,
+    isRegex?: boolean,
+    maxFileSize?: number,
+): Promise<number> {
+    return invoke<number>('search_file_contents', { root, pattern, isRegex, maxFileSize, token })
+}
+
+/**
+ * Cancels a running findInDirectory or searchFileContents. Returns true if it was still running.
+ * @param token - The token passed to findInDirectory or searchFileContents.
+ */
+export async function cancelFind(token: string): Promise<boolean> {
+    return invoke<boolean>('cancel_find', { token })
+}
+
+/**
+ * Gets the full metadata of a single path, without listing its parent. Symlinks are described themselves.
+ * @param path - The file or directory.
+ * @returns The entry, with the extended fields (like addedAt) already loaded.
+ */
+export async function getPathMetadata(path: string): Promise<FileEntry> {
+    return invoke<FileEntry>('get_path_metadata', { path })
+}
+
 /**
  * Checks if a path exists.
  * @param path - Path to check.
@@ -141,6 +298,153 @@ export async function pathExists(path: string): Promise<boolean> {
     return invoke<boolean>('path_exists', { path })
 }
 
+/**
+ * Moves or renames a file or directory within a volume.
+ * Fails with a `cross_volume_move` CommandError if the paths are on different volumes.
+ * @param from - Path to move.
+ * @param to - New path. Must not exist yet, also not with a different case on case-insensitive volumes.
+ * @returns The entry at its new path.
+ */
+export async function renamePath(from: string, to: string): Promise<FileEntry> {
+    return invoke<FileEntry>('rename_path', { from, to })
+}
+
+/**
+ * Sets the permission bits of a file or directory, like chmod. Symlinks are followed.
+ * @param path - The file or directory.
+ * @param mode - The new mode, like 0o644. Includes the setuid, setgid, and sticky bits, so at most 0o7777.
+ * @returns The updated entry.
+ */
+export async function setFilePermissions(path: string, mode: number): Promise<FileEntry> {
+    return invoke<FileEntry>('set_file_permissions', { path, mode })
+}
+
+/**
+ * Changes the owner and group of a file or directory, like chown. Usually needs admin rights.
+ * @param path - The file or directory.
+ * @param ownerName - New owner's user name, or a uid. Omit to keep the owner.
+ * @param groupName - New group's name, or a gid. Omit to keep the group.
+ * @returns The updated entry.
+ */
+export async function setFileOwner(path: string, ownerName?: string, groupName?: string): Promise<FileEntry> {
+    return invoke<FileEntry>('set_file_owner', { path, ownerName, groupName })
+}
+
+/**
+ * Lists the names of a file's extended attributes, like com.apple.quarantine. macOS only.
+ * @param path - The file or directory.
+ */
+export async function listXattrs(path: string): Promise<string[]> {
+    return invoke<string[]>('list_xattrs', { path })
+}
+
+/**
+ * Reads an extended attribute of a file, for example to tell whether a download is quarantined. macOS only.
+ * @param path - The file or directory.
+ * @param name - The attribute, like com.apple.quarantine.
+ * @returns The raw bytes, or null if the file doesn't have the attribute.
+ */
+export async function getXattr(path: string, name: string): Promise<number[] | null> {
+    return invoke<number[] | null>('get_xattr', { path, name })
+}
+
+/**
+ * Reads the Finder tags of a batch of paths, so listings can show colored dots. macOS only.
+ * @param paths - File paths to read tags for.
+ * @returns The tags of each path, empty for untagged files.
+ */
+export async function getFinderTagsBatch(paths: string[]): Promise<FinderTags[]> {
+    return invoke<FinderTags[]>('get_finder_tags_batch', { paths })
+}
+
+/**
+ * Sets the Finder tags of a file, replacing the ones it had. macOS only.
+ * @param path - The file or directory.
+ * @param tags - The new tags. Empty to remove all tags.
+ */
+export async function setFinderTags(path: string, tags: FinderTag[]): Promise<void> {
+    await invoke('set_finder_tags', { path, tags })
+}
+
+/**
+ * Creates a directory.
+ * @param path - The directory to create.
+ * @param createParents - Whether to create missing parents too. Then an existing directory is not an error either.
+ * @returns The new directory's entry.
+ */
+export async function makeDirectory(path: string, createParents: boolean): Promise<FileEntry> {
+    return invoke<FileEntry>('make_directory', { path, createParents })
+}
+
+/**
+ * Creates a symbolic or hard link.
+ * Fails with an `invalid_argument` CommandError for a hard link to another volume.
+ * @param kind - Symlinks point to a path, hard links are another name for an existing file.
+ * @param linkPath - Where to create the link. Must not exist yet.
+ * @param targetPath - What the link points to. Symlinks store it as given, so it may be relative and may not exist.
+ * @returns The new link's entry.
+ */
+export async function createLink(kind: LinkKind, linkPath: string, targetPath: string): Promise<FileEntry> {
+    return invoke<FileEntry>('create_link', { kind, linkPath, targetPath })
+}
+
+/**
+ * Moves a file or directory to the Trash, or deletes it permanently.
+ * @param path - What to delete.
+ * @param permanent - Whether to delete permanently instead. Only removes files and empty directories.
+ * @returns Where the entry ended up in the Trash, if known. Always null for permanent deletes.
+ */
+export async function deletePath(path: string, permanent: boolean): Promise<string | null> {
+    return invoke<string | null>('delete_path', { path, permanent })
+}
+
+/**
+ * Moves several files and directories to the Trash, or deletes them permanently with everything in them.
+ * A path that fails doesn't stop the others. For batches of more than 20 items, emits a delete-paths-progress event
+ * ({ path, success, done, total }) after each item.
+ * @param paths - What to delete.
+ * @param permanent - Whether to delete permanently instead of trashing.
+ * @returns One result per path, in the same order.
+ */
+export async function deletePaths(paths: string[], permanent: boolean): Promise<DeleteResult[]> {
+    return invoke<DeleteResult[]>('delete_paths', { paths, permanent })
+}
+
+/**
+ * Copies several files and directories into a directory, keeping their names. Each path must be on the same volume
+ * as the destination. A path that fails doesn't stop the others. For batches of more than 20 items, emits a
+ * transfer-progress event ({ path, success, done, total }) after each item.
+ * @param paths - What to copy.
+ * @param destination - Directory to copy into.
+ * @param conflict - What to do for items whose name is taken in the destination. Defaults to 'error'.
+ * @param ignoreSpaceCheck - Skips the free space check, for when the user confirms to go ahead anyway.
+ * @returns One result per path, in the same order. Rejects with "Not enough free space: …" before copying anything
+ * if the paths don't fit.
+ */
+export async function copyPaths(
+    paths: string[],
+    destination: string,
+    conflict?: ConflictPolicy,
+    ignoreSpaceCheck?: boolean,
+): Promise<TransferResult[]> {
+    return invoke<TransferResult[]>('copy_paths', { paths, destination, conflict, ignoreSpaceCheck })
+}
+
+/**
+ * Moves several files and directories into a directory, keeping their names. Works like copyPaths.
+ * @param paths - What to move.
+ * @param destination - Directory to move into.
+ * @param conflict - What to do for items whose name is taken in the destination. Defaults to 'error'.
+ * @returns One result per path, in the same order.
+ */
+export async function movePaths(
+    paths: string[],
+    destination: string,
+    conflict?: ConflictPolicy,
+): Promise<TransferResult[]> {
+    return invoke<TransferResult[]>('move_paths', { paths, destination, conflict })
+}
+
 /**
  * Opens a file with the system's default application.
  * @param path - Path to the file to open.
@@ -160,18 +464,34 @@ export async function openExternalUrl(url: string): Promise<void> {
 /**
  * Gets icon data URLs for the requested icon IDs.
  * @param iconIds - Array of icon IDs like "ext:jpg", "dir", "symlink"
+ * @param appearance - "light" or "dark"; defaults to the current system appearance
  * @returns Map of icon_id → base64 WebP data URL
  */
-export async function getIcons(iconIds: string[]): Promise<Record<string, string>> {
-    return invoke<Record<string, string>>('get_icons', { iconIds })
+export async function getIcons(iconIds: string[], appearance?: 'light' | 'dark'): Promise<Record<string, string>> {
+    return invoke<Record<string, string>>('get_icons', { iconIds, appearance })
+}
+
+/**
+ * Gets icon data URLs for the requested icon IDs at several pixel sizes.
+ * @param iconIds - Array of icon IDs like "ext:jpg", "dir", "symlink"
+ * @param sizes - Pixel sizes to fetch, for example [32, 64] for @1x/@2x
+ * @param appearance - "light" or "dark"; defaults to the current system appearance
+ * @returns Map of icon_id → (size → base64 WebP data URL)
+ */
+export async function getIconsWithSizes(
+    iconIds: string[],
+    sizes: number[],
+    appearance?: 'light' | 'dark',
+): Promise<Record<string, Record<number, string>>> {
+    return invoke<Record<string, Record<number, string>>>('get_icons_with_sizes', { iconIds, sizes, appearance })
 }
 
 /**
  * Refreshes icons for a directory listing.
- * Fetches icons in parallel for directories (by path) and extensions.
+ * Fetches icons in parallel for directories (by path) and for extensions whose default app changed.
  * @param directoryPaths - Array of directory paths to fetch icons for
  * @param extensions - Array of file extensions (without dot)
- * @returns Map of icon_id → base64 WebP data URL
+ * @returns Map of icon_id → base64 WebP data URL, only for icons that changed
  */
 export async function refreshDirectoryIcons(
     directoryPaths: string[],
@@ -291,6 +611,52 @@ export async function findContainingVolume(path: string): Promise<VolumeInfo | n
     }
 }
 
+/**
+ * Exports settings, known shares, and the sidebar config as a JSON string, for backups and migration.
+ * Keychain passwords are never included.
+ * Only available on macOS.
+ */
+export async function exportConfig(): Promise<string> {
+    return invoke<string>('export_config')
+}
+
+/**
+ * Imports a JSON string from exportConfig. Nothing is applied if it's invalid.
+ * Emits settings-changed and locations-changed when done.
+ * Only available on macOS.
+ * @throws Error message if the JSON isn't a valid export
+ */
+export async function importConfig(json: string): Promise<void> {
+    await invoke('import_config', { json })
+}
+
+/**
+ * Gets which location categories the sidebar shows, and in what order.
+ * Only available on macOS.
+ * @returns The config, or null if not available
+ */
+export async function getSidebarConfig(): Promise<SidebarConfig | null> {
+    try {
+        return await invoke<SidebarConfig>('get_sidebar_config')
+    } catch {
+        // Command not available (non-macOS) - return null
+        return null
+    }
+}
+
+/**
+ * Sets which location categories the sidebar shows, and in what order, and saves it.
+ * Emits a locations-changed event so the sidebar reloads.
+ * Only available on macOS.
+ */
+export async function setSidebarConfig(config: SidebarConfig): Promise<void> {
+    try {
+        await invoke('set_sidebar_config', { config })
+    } catch {
+        // Command not available (non-macOS) - silently fail
+    }
+}
+
 // ============================================================================
 // Permission checking (macOS only)
 // ============================================================================
@@ -353,6 +719,30 @@ export async function getNetworkDiscoveryState(): Promise<DiscoveryState> {
     }
 }
 
+/**
+ * Stops network discovery and clears the discovered hosts, for example on metered or VPN networks.
+ * Only available on macOS.
+ */
+export async function stopNetworkDiscovery(): Promise<void> {
+    try {
+        await invoke('stop_network_discovery')
+    } catch {
+        // Command not available (non-macOS) - nothing to stop
+    }
+}
+
+/**
+ * Restarts network discovery with an empty host list. Safe to call repeatedly.
+ * Only available on macOS.
+ */
+export async function restartNetworkDiscovery(): Promise<void> {
+    try {
+        await invoke('restart_network_discovery')
+    } catch {
+        // Command not available (non-macOS) - nothing to restart
+    }
+}
+
 /**
  * Resolves a network host's hostname and IP address.
  * This performs lazy resolution - only called on hover or when connecting.
@@ -375,12 +765,14 @@ export async function resolveNetworkHost(hostId: string): Promise<NetworkHost |
 
 /**
  * Lists shares available on a network host.
- * Returns cached results if available (30 second TTL), otherwise queries the host.
+ * Returns cached results if available (30 second TTL by default), otherwise queries the host.
  * Attempts guest access first; returns an error if authentication is required.
  * @param hostId Unique identifier for the host (used for caching)
  * @param hostname Hostname to connect to (for example, "TEST_SERVER.local")
  * @param ipAddress Optional resolved IP address (preferred over hostname for reliability)
  * @param port SMB port (default 445, but Docker containers may use different ports)
+ * @param withCapacity Whether to also get each share's total and free bytes. Slower, and cached separately.
+ * @param refresh Whether to query the host even if its shares are cached. The fresh result is cached.
  * @returns Result with shares and auth mode, or error
  */
 export async function listSharesOnHost(
@@ -388,10 +780,41 @@ export async function listSharesOnHost(
     hostname: string,
     ipAddress: string | undefined,
     port: number,
+    withCapacity = false,
+    refresh = false,
 ): Promise<ShareListResult> {
     // The Rust command returns Result<ShareListResult, ShareListError>
     // Tauri auto-converts Ok to value and Err to thrown error
-    return invoke<ShareListResult>('list_shares_on_host', { hostId, hostname, ipAddress, port })
+    return invoke<ShareListResult>('list_shares_on_host', {
+        hostId,
+        hostname,
+        ipAddress,
+        port,
+        withCapacity,
+        refresh,
+    })
+}
+
+/**
+ * Lists shares on several discovered hosts at once, as guest. Hosts with cached shares are answered from the cache,
+ * the others are queried concurrently, a few at a time. Much faster than listing hosts one by one.
+ * @param hostIds IDs of the hosts to list
+ * @returns Each host's shares, or the error listing them, by host ID
+ */
+export async function listSharesForHosts(
+    hostIds: string[],
+): Promise<Record<string, { Ok: ShareListResult } | { Err: ShareListError }>> {
+    return invoke<Record<string, { Ok: ShareListResult } | { Err: ShareListError }>>('list_shares_for_hosts', {
+        hostIds,
+    })
+}
+
+/**
+ * Sets how long share lists are cached.
+ * @param ttlSecs Seconds, at most 3600. 0 turns the cache off.
+ */
+export async function setShareCacheTtlSecs(ttlSecs: number): Promise<void> {
+    await invoke('set_share_cache_ttl_secs', { ttlSecs })
 }
 
 /**
@@ -477,6 +900,37 @@ export async function getKnownShareByName(serverName: string, shareName: string)
     }
 }
 
+/**
+ * Forgets a known network share, like when the user removes it from the list.
+ * Only available on macOS.
+ * @param serverName Server hostname or IP
+ * @param shareName Share name ('' for the server-level entry)
+ * @returns The remaining known shares
+ */
+export async function forgetKnownShare(serverName: string, shareName: string): Promise<KnownNetworkShare[]> {
+    try {
+        return await invoke<KnownNetworkShare[]>('forget_known_share', { serverName, shareName })
+    } catch {
+        // Command not available (non-macOS) - return empty array
+        return []
+    }
+}
+
+/**
+ * Forgets all known shares of a server, including the server-level entry.
+ * Only available on macOS.
+ * @param serverName Server hostname or IP
+ * @returns The remaining known shares
+ */
+export async function forgetAllSharesForServer(serverName: string): Promise<KnownNetworkShare[]> {
+    try {
+        return await invoke<KnownNetworkShare[]>('forget_all_shares_for_server', { serverName })
+    } catch {
+        // Command not available (non-macOS) - return empty array
+        return []
+    }
+}
+
 /**
  * Updates or adds a known network share after successful connection.
  * Only available on macOS.
@@ -585,6 +1039,7 @@ export async function deleteSmbCredentials(server: string, share: string | null)
  * @param port SMB port
  * @param username Username for authentication (null for guest)
  * @param password Password for authentication (null for guest)
+ * @param withCapacity Whether to also get each share's total and free bytes. Slower.
  */
 export async function listSharesWithCredentials(
     hostId: string,
@@ -593,6 +1048,7 @@ export async function listSharesWithCredentials(
     port: number,
     username: string | null,
     password: string | null,
+    withCapacity = false,
 ): Promise<ShareListResult> {
     return invoke<ShareListResult>('list_shares_with_credentials', {
         hostId,
@@ -601,9 +1057,55 @@ export async function listSharesWithCredentials(
         port,
         username,
         password,
+        withCapacity,
+    })
+}
+
+/**
+ * Tests the connection to a host without listing shares.
+ * @param hostname Hostname to connect to
+ * @param ipAddress Optional resolved IP address
+ * @param port SMB port
+ * @param username Username for authentication (null for guest)
+ * @param password Password for authentication (null for guest)
+ */
+export async function testSmbConnection(
+    hostname: string,
+    ipAddress: string | undefined,
+    port: number,
+    username: string | null,
+    password: string | null,
+): Promise<ConnectionTestResult> {
+    return invoke<ConnectionTestResult>('test_smb_connection', {
+        hostname,
+        ipAddress,
+        port,
+        username,
+        password,
     })
 }
 
+/**
+ * Connects to a share and registers it as a volume, to browse it without mounting. Read-only for now.
+ * @param server Server hostname
+ * @param ipAddress Optional resolved IP address
+ * @param port SMB port
+ * @param share Name of the share
+ * @param username Username for authentication (null for guest)
+ * @param password Password for authentication (null for guest)
+ * @returns The volume ID, like "nas.local/Media"
+ */
+export async function connectShareVolume(
+    server: string,
+    ipAddress: string | undefined,
+    port: number,
+    share: string,
+    username: string | null,
+    password: string | null,
+): Promise<string> {
+    return invoke<string>('connect_share_volume', { server, ipAddress, port, share, username, password })
+}
+
 /**
  * Helper to check if an error is a KeychainError
  */
@@ -628,6 +1130,7 @@ export function isKeychainError(error: unknown): error is KeychainError {
  * @param share Name of the share to mount
  * @param username Optional username for authentication
  * @param password Optional password for authentication
+ * @param subpath Optional folder in the share to mount instead of the share root, like "projects/2024"
  * @returns MountResult with mount path on success
  * @throws MountError on failure
  */
@@ -636,15 +1139,27 @@ export async function mountNetworkShare(
     share: string,
     username: string | null,
     password: string | null,
+    subpath: string | null = null,
 ): Promise<MountResult> {
     return invoke<MountResult>('mount_network_share', {
         server,
         share,
+        subpath,
         username,
         password,
     })
 }
 
+/**
+ * Unmounts a mounted share. Succeeds if the path isn't mounted.
+ *
+ * @param mountPath Path the share is mounted at, like "/Volumes/Documents"
+ * @throws MountError on failure, `in_use` if files on the share are still open
+ */
+export async function unmountNetworkShare(mountPath: string): Promise<void> {
+    await invoke('unmount_network_share', { mountPath })
+}
+
 /**
  * Helper to check if an error is a MountError
  */
@@ -663,10 +1178,31 @@ export function isMountError(error: unknown): error is MountError {
             'cancelled',
             'protocol_error',
             'mount_path_conflict',
+            'in_use',
         ].includes((error as MountError).type)
     )
 }
 
+/**
+ * Helper to check if an error is a CommandError
+ */
+export function isCommandError(error: unknown): error is CommandError {
+    return (
+        typeof error === 'object' &&
+        error !== null &&
+        'type' in error &&
+        'message' in error &&
+        [
+            'not_found',
+            'permission_denied',
+            'listing_expired',
+            'invalid_argument',
+            'cross_volume_move',
+            'io',
+        ].includes((error as CommandError).type)
+    )
+}
+
 // ============================================================================
 // Licensing
 // ============================================================================
@@ -678,7 +1214,16 @@ export type LicenseType = 'supporter' | 'commercial_subscription' | 'commercial_
 export type LicenseStatus =
     | { type: 'personal' }
     | { type: 'supporter' }
-    | { type: 'commercial'; licenseType: LicenseType; organizationName: string | null; expiresAt: string | null }
+    | {
+          type: 'commercial'
+          licenseType: LicenseType
+          organizationName: string | null
+          expiresAt: string | null
+          /** Distinct machines this install has seen the license activated on */
+          activationCount: number
+          /** How many machines the license allows, null for no limit */
+          maxActivations: number | null
+      }
     | { type: 'expired'; organizationName: string | null; expiredAt: string; showModal: boolean }
 
 /** License information from activation */
@@ -686,6 +1231,7 @@ export interface LicenseInfo {
     email: string
     transactionId: string
     issuedAt: string
+    maxActivations: number | null
 }
 
 /**